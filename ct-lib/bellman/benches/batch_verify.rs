@@ -0,0 +1,131 @@
+#![feature(test)]
+
+extern crate bellman;
+extern crate ff;
+extern crate pairing;
+extern crate rand;
+extern crate test;
+
+use bellman::{Circuit, ConstraintSystem, SynthesisError};
+use bellman::groth16::{
+    BatchVerifier,
+    Parameters,
+    PreparedVerifyingKey,
+    Proof,
+    create_random_proof,
+    generate_random_parameters,
+    prepare_verifying_key,
+    verify_proof,
+};
+use ff::Field;
+use pairing::Engine;
+use pairing::bls12_381::{Bls12, Fr};
+use rand::{Rand, XorShiftRng, SeedableRng};
+
+/// Proves knowledge of `a` and `b` such that `a * b = c` for a public `c`.
+struct MulCircuit<E: Engine> {
+    a: Option<E::Fr>,
+    b: Option<E::Fr>
+}
+
+impl<E: Engine> Circuit<E> for MulCircuit<E> {
+    fn synthesize<CS: ConstraintSystem<E>>(
+        self,
+        cs: &mut CS
+    ) -> Result<(), SynthesisError>
+    {
+        let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+        let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+        let c = cs.alloc_input(|| "c", || {
+            let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+            let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+            a.mul_assign(&b);
+            Ok(a)
+        })?;
+
+        cs.enforce(
+            || "a*b=c",
+            |lc| lc + a,
+            |lc| lc + b,
+            |lc| lc + c
+        );
+
+        Ok(())
+    }
+}
+
+fn setup(count: usize) -> (PreparedVerifyingKey<Bls12>, Vec<(Proof<Bls12>, Vec<Fr>)>) {
+    let rng = &mut XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    let params: Parameters<Bls12> = generate_random_parameters(
+        MulCircuit::<Bls12> { a: None, b: None },
+        rng
+    ).unwrap();
+    let pvk = prepare_verifying_key(&params.vk);
+
+    let proofs = (0..count).map(|_| {
+        let a = Fr::rand(rng);
+        let b = Fr::rand(rng);
+        let mut c = a;
+        c.mul_assign(&b);
+
+        let proof = create_random_proof(MulCircuit { a: Some(a), b: Some(b) }, &params, rng).unwrap();
+        (proof, vec![c])
+    }).collect();
+
+    (pvk, proofs)
+}
+
+fn bench_sequential(b: &mut test::Bencher, count: usize) {
+    let (pvk, proofs) = setup(count);
+
+    b.iter(|| {
+        for &(ref proof, ref inputs) in &proofs {
+            assert!(verify_proof(&pvk, proof, inputs).unwrap());
+        }
+    });
+}
+
+fn bench_batched(b: &mut test::Bencher, count: usize) {
+    let (pvk, proofs) = setup(count);
+    let rng = &mut XorShiftRng::from_seed([0x3dbe6258, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+
+    b.iter(|| {
+        let mut batch = BatchVerifier::new();
+        for &(ref proof, ref inputs) in &proofs {
+            batch.queue(proof.clone(), inputs.clone());
+        }
+        assert!(batch.verify(&pvk, rng).unwrap());
+    });
+}
+
+#[bench]
+fn bench_verify_sequential_1(b: &mut test::Bencher) {
+    bench_sequential(b, 1);
+}
+
+#[bench]
+fn bench_verify_batched_1(b: &mut test::Bencher) {
+    bench_batched(b, 1);
+}
+
+#[bench]
+fn bench_verify_sequential_10(b: &mut test::Bencher) {
+    bench_sequential(b, 10);
+}
+
+#[bench]
+fn bench_verify_batched_10(b: &mut test::Bencher) {
+    bench_batched(b, 10);
+}
+
+#[bench]
+fn bench_verify_sequential_100(b: &mut test::Bencher) {
+    bench_sequential(b, 100);
+}
+
+#[bench]
+fn bench_verify_batched_100(b: &mut test::Bencher) {
+    bench_batched(b, 100);
+}
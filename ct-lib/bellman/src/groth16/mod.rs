@@ -490,40 +490,40 @@ mod test_with_bls12_381 {
     use rand::{Rand, thread_rng};
     use pairing::bls12_381::{Bls12, Fr};
 
-    #[test]
-    fn serialization() {
-        struct MySillyCircuit<E: Engine> {
-            a: Option<E::Fr>,
-            b: Option<E::Fr>
-        }
-
-        impl<E: Engine> Circuit<E> for MySillyCircuit<E> {
-            fn synthesize<CS: ConstraintSystem<E>>(
-                self,
-                cs: &mut CS
-            ) -> Result<(), SynthesisError>
-            {
-                let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
-                let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
-                let c = cs.alloc_input(|| "c", || {
-                    let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
-                    let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
-
-                    a.mul_assign(&b);
-                    Ok(a)
-                })?;
-
-                cs.enforce(
-                    || "a*b=c",
-                    |lc| lc + a,
-                    |lc| lc + b,
-                    |lc| lc + c
-                );
+    struct MySillyCircuit<E: Engine> {
+        a: Option<E::Fr>,
+        b: Option<E::Fr>
+    }
 
-                Ok(())
-            }
+    impl<E: Engine> Circuit<E> for MySillyCircuit<E> {
+        fn synthesize<CS: ConstraintSystem<E>>(
+            self,
+            cs: &mut CS
+        ) -> Result<(), SynthesisError>
+        {
+            let a = cs.alloc(|| "a", || self.a.ok_or(SynthesisError::AssignmentMissing))?;
+            let b = cs.alloc(|| "b", || self.b.ok_or(SynthesisError::AssignmentMissing))?;
+            let c = cs.alloc_input(|| "c", || {
+                let mut a = self.a.ok_or(SynthesisError::AssignmentMissing)?;
+                let b = self.b.ok_or(SynthesisError::AssignmentMissing)?;
+
+                a.mul_assign(&b);
+                Ok(a)
+            })?;
+
+            cs.enforce(
+                || "a*b=c",
+                |lc| lc + a,
+                |lc| lc + b,
+                |lc| lc + c
+            );
+
+            Ok(())
         }
+    }
 
+    #[test]
+    fn serialization() {
         let rng = &mut thread_rng();
 
         let params = generate_random_parameters::<Bls12, _, _>(
@@ -573,4 +573,50 @@ mod test_with_bls12_381 {
             assert!(!verify_proof(&pvk, &proof, &[a]).unwrap());
         }
     }
+
+    #[test]
+    fn batch_verification() {
+        let rng = &mut thread_rng();
+
+        let params = generate_random_parameters::<Bls12, _, _>(
+            MySillyCircuit { a: None, b: None },
+            rng
+        ).unwrap();
+        let pvk = prepare_verifying_key::<Bls12>(&params.vk);
+
+        let mut batch = BatchVerifier::new();
+        assert!(batch.verify(&pvk, rng).unwrap());
+
+        let mut proofs = vec![];
+        for _ in 0..10 {
+            let a = Fr::rand(rng);
+            let b = Fr::rand(rng);
+            let mut c = a;
+            c.mul_assign(&b);
+
+            let proof = create_random_proof(
+                MySillyCircuit {
+                    a: Some(a),
+                    b: Some(b)
+                },
+                &params,
+                rng
+            ).unwrap();
+
+            batch.queue(proof.clone(), vec![c]);
+            proofs.push((proof, a));
+        }
+
+        assert_eq!(batch.len(), 10);
+        assert!(batch.verify(&pvk, rng).unwrap());
+        assert!(batch.verify_each(&pvk).unwrap().into_iter().all(|valid| valid));
+
+        // A single proof with wrong public inputs invalidates the whole batch.
+        let (proof, a) = proofs[3].clone();
+        batch.queue(proof, vec![a]);
+        assert!(!batch.verify(&pvk, rng).unwrap());
+
+        let results = batch.verify_each(&pvk).unwrap();
+        assert_eq!(results.iter().position(|valid| !valid), Some(10));
+    }
 }
@@ -1,6 +1,7 @@
-use ff::PrimeField;
+use ff::{Field, PrimeField};
 use group::{CurveAffine, CurveProjective};
 use pairing::{Engine, PairingCurveAffine};
+use rand::{Rand, Rng};
 
 use super::{
     Proof,
@@ -29,11 +30,12 @@ pub fn prepare_verifying_key<E: Engine>(
     }
 }
 
-pub fn verify_proof<'a, E: Engine>(
-    pvk: &'a PreparedVerifyingKey<E>,
-    proof: &Proof<E>,
+/// Computes the linear combination of the IC points of `pvk` with the
+/// public inputs of a proof.
+fn prepare_inputs<E: Engine>(
+    pvk: &PreparedVerifyingKey<E>,
     public_inputs: &[E::Fr]
-) -> Result<bool, SynthesisError>
+) -> Result<E::G1, SynthesisError>
 {
     if (public_inputs.len() + 1) != pvk.ic.len() {
         return Err(SynthesisError::MalformedVerifyingKey);
@@ -45,6 +47,17 @@ pub fn verify_proof<'a, E: Engine>(
         acc.add_assign(&b.mul(i.into_repr()));
     }
 
+    Ok(acc)
+}
+
+pub fn verify_proof<'a, E: Engine>(
+    pvk: &'a PreparedVerifyingKey<E>,
+    proof: &Proof<E>,
+    public_inputs: &[E::Fr]
+) -> Result<bool, SynthesisError>
+{
+    let acc = prepare_inputs(pvk, public_inputs)?;
+
     // The original verification equation is:
     // A * B = alpha * beta + inputs * gamma + C * delta
     // ... however, we rearrange it so that it is:
@@ -61,3 +74,95 @@ pub fn verify_proof<'a, E: Engine>(
         ].into_iter())
     ).unwrap() == pvk.alpha_g1_beta_g2)
 }
+
+/// Accumulates Groth16 proofs sharing one verifying key so that they can be
+/// checked together with a single final exponentiation.
+///
+/// Each queued proof is weighted with a fresh random scalar `r_i`, and the
+/// verification equations are combined into
+///
+/// prod(e(r_i * A_i, B_i)) * e(sum(r_i * inputs_i), -gamma) * e(sum(r_i * C_i), -delta)
+///     = (alpha * beta)^sum(r_i)
+///
+/// A batch that contains an invalid proof passes only with negligible
+/// probability. A failing batch does not tell which proof is invalid; use
+/// `verify_each` to locate it.
+pub struct BatchVerifier<E: Engine> {
+    items: Vec<(Proof<E>, Vec<E::Fr>)>
+}
+
+impl<E: Engine> BatchVerifier<E> {
+    pub fn new() -> Self {
+        BatchVerifier {
+            items: vec![]
+        }
+    }
+
+    /// Queue a proof together with its public inputs.
+    pub fn queue(&mut self, proof: Proof<E>, public_inputs: Vec<E::Fr>) {
+        self.items.push((proof, public_inputs));
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Verify all queued proofs at once. An empty batch is valid.
+    pub fn verify<R: Rng>(
+        &self,
+        pvk: &PreparedVerifyingKey<E>,
+        rng: &mut R
+    ) -> Result<bool, SynthesisError>
+    {
+        if self.items.is_empty() {
+            return Ok(true);
+        }
+
+        let mut acc_inputs = E::G1::zero();
+        let mut acc_c = E::G1::zero();
+        let mut r_sum = E::Fr::zero();
+        let mut prepared_ab = Vec::with_capacity(self.items.len());
+
+        for &(ref proof, ref public_inputs) in &self.items {
+            let r = E::Fr::rand(rng);
+            r_sum.add_assign(&r);
+
+            let mut inputs = prepare_inputs(pvk, public_inputs)?;
+            inputs.mul_assign(r.into_repr());
+            acc_inputs.add_assign(&inputs);
+
+            acc_c.add_assign(&proof.c.mul(r.into_repr()));
+
+            prepared_ab.push((
+                proof.a.mul(r.into_repr()).into_affine().prepare(),
+                proof.b.prepare()
+            ));
+        }
+
+        let acc_inputs = acc_inputs.into_affine().prepare();
+        let acc_c = acc_c.into_affine().prepare();
+
+        let mut terms = prepared_ab.iter().map(|&(ref a, ref b)| (a, b)).collect::<Vec<_>>();
+        terms.push((&acc_inputs, &pvk.neg_gamma_g2));
+        terms.push((&acc_c, &pvk.neg_delta_g2));
+
+        Ok(E::final_exponentiation(&E::miller_loop(terms.iter())).unwrap()
+            == pvk.alpha_g1_beta_g2.pow(r_sum.into_repr()))
+    }
+
+    /// Verify every queued proof on its own, returning the result for each
+    /// one in queueing order.
+    pub fn verify_each(
+        &self,
+        pvk: &PreparedVerifyingKey<E>
+    ) -> Result<Vec<bool>, SynthesisError>
+    {
+        self.items.iter()
+            .map(|&(ref proof, ref public_inputs)| verify_proof(pvk, proof, public_inputs))
+            .collect()
+    }
+}
@@ -19,7 +19,6 @@ use sapling_crypto::jubjub::JubjubBls12;
 pub mod keys;
 pub mod merkle_tree;
 pub mod note_encryption;
-pub mod prover;
 pub mod sapling;
 mod serialize;
 pub mod transaction;
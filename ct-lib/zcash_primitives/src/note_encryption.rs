@@ -38,6 +38,9 @@ const OUT_PLAINTEXT_SIZE: usize = (
 const ENC_CIPHERTEXT_SIZE: usize = NOTE_PLAINTEXT_SIZE + 16;
 const OUT_CIPHERTEXT_SIZE: usize = OUT_PLAINTEXT_SIZE + 16;
 
+/// Width in bits of the windows of the batched key agreement.
+const BATCH_WINDOW_BITS: usize = 4;
+
 /// Format a byte array as a colon-delimited hex string.
 ///
 /// Source: https://github.com/tendermint/signatory
@@ -395,7 +398,17 @@ pub fn try_sapling_note_decryption(
     assert_eq!(enc_ciphertext.len(), ENC_CIPHERTEXT_SIZE);
 
     let shared_secret = sapling_ka_agree(ivk, epk);
-    let key = kdf_sapling(&shared_secret, &epk);
+    decrypt_note_with_shared_secret(ivk, &shared_secret, epk, cmu, enc_ciphertext)
+}
+
+fn decrypt_note_with_shared_secret(
+    ivk: &Fs,
+    shared_secret: &[u8; 32],
+    epk: &edwards::Point<Bls12, PrimeOrder>,
+    cmu: &Fr,
+    enc_ciphertext: &[u8],
+) -> Option<(Note<Bls12>, PaymentAddress<Bls12>, Memo)> {
+    let key = kdf_sapling(shared_secret, &epk);
 
     let mut plaintext = vec![0; ENC_CIPHERTEXT_SIZE];
     assert_eq!(
@@ -419,6 +432,72 @@ pub fn try_sapling_note_decryption(
     Some((note, to, Memo(memo)))
 }
 
+/// The multiples `[i * 8] epk` for `i < 2^BATCH_WINDOW_BITS`, shared by the key
+/// agreements of all the incoming viewing keys tried on the same output.
+fn batch_ka_table(epk: &edwards::Point<Bls12, PrimeOrder>) -> Vec<edwards::Point<Bls12, PrimeOrder>> {
+    let p: edwards::Point<Bls12, Unknown> = epk.into();
+    let base = p.mul_by_cofactor(&JUBJUB);
+
+    let mut table = Vec::with_capacity(1 << BATCH_WINDOW_BITS);
+    table.push(edwards::Point::zero());
+    for i in 1..(1 << BATCH_WINDOW_BITS) {
+        let next = table[i - 1].add(&base, &JUBJUB);
+        table.push(next);
+    }
+    table
+}
+
+/// Sapling key agreement `[8 * ivk] epk` using the table of `batch_ka_table`.
+///
+/// Equal to `sapling_ka_agree(ivk, epk)`, with one point addition per window of the
+/// scalar instead of one per set bit.
+fn batch_ka_agree(ivk: &Fs, table: &[edwards::Point<Bls12, PrimeOrder>]) -> [u8; 32] {
+    let repr = ivk.into_repr();
+    let limbs = repr.as_ref();
+    let mask = (1u64 << BATCH_WINDOW_BITS) - 1;
+
+    let mut p = edwards::Point::<Bls12, PrimeOrder>::zero();
+    for window in (0..limbs.len() * 64 / BATCH_WINDOW_BITS).rev() {
+        for _ in 0..BATCH_WINDOW_BITS {
+            p = p.double(&JUBJUB);
+        }
+        let bit = window * BATCH_WINDOW_BITS;
+        let digit = (limbs[bit / 64] >> (bit % 64)) & mask;
+        if digit != 0 {
+            p = p.add(&table[digit as usize], &JUBJUB);
+        }
+    }
+
+    let mut result = [0; 32];
+    p.write(&mut result[..]).expect("length is not 32 bytes");
+    result
+}
+
+/// Trial decryption of the full note plaintext by a batch of recipients.
+///
+/// Attempts to decrypt the given `enc_ciphertext` with each of the `ivks` in turn, the
+/// key agreements sharing a table of multiples of `epk` computed once for the output.
+/// If successful, the index of the first matching `ivk` is returned along with the
+/// outputs of `try_sapling_note_decryption`.
+pub fn try_sapling_note_decryption_batch(
+    ivks: &[Fs],
+    epk: &edwards::Point<Bls12, PrimeOrder>,
+    cmu: &Fr,
+    enc_ciphertext: &[u8],
+) -> Option<(usize, Note<Bls12>, PaymentAddress<Bls12>, Memo)> {
+    assert_eq!(enc_ciphertext.len(), ENC_CIPHERTEXT_SIZE);
+
+    if ivks.is_empty() {
+        return None;
+    }
+    let table = batch_ka_table(epk);
+    ivks.iter().enumerate().filter_map(|(i, ivk)| {
+        let shared_secret = batch_ka_agree(ivk, &table);
+        decrypt_note_with_shared_secret(ivk, &shared_secret, epk, cmu, enc_ciphertext)
+            .map(|(note, to, memo)| (i, note, to, memo))
+    }).next()
+}
+
 /// Trial decryption of the compact note plaintext by the recipient for light clients.
 ///
 /// Attempts to decrypt and validate the first 52 bytes of `enc_ciphertext` using the
@@ -560,9 +639,10 @@ mod tests {
     };
 
     use super::{
-        kdf_sapling, prf_ock, sapling_ka_agree, try_sapling_compact_note_decryption,
-        try_sapling_note_decryption, try_sapling_output_recovery, Memo, SaplingNoteEncryption,
-        COMPACT_NOTE_SIZE, ENC_CIPHERTEXT_SIZE, OUT_CIPHERTEXT_SIZE,
+        batch_ka_agree, batch_ka_table, kdf_sapling, prf_ock, sapling_ka_agree,
+        try_sapling_compact_note_decryption, try_sapling_note_decryption,
+        try_sapling_note_decryption_batch, try_sapling_output_recovery, Memo,
+        SaplingNoteEncryption, COMPACT_NOTE_SIZE, ENC_CIPHERTEXT_SIZE, OUT_CIPHERTEXT_SIZE,
     };
     use crate::{keys::OutgoingViewingKey, JUBJUB};
 
@@ -796,6 +876,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn batch_ka_agree_matches_ka_agree() {
+        let mut rng = thread_rng();
+
+        for _ in 0..10 {
+            let ivk = Fs::rand(&mut rng);
+            let epk = edwards::Point::<Bls12, _>::rand(&mut rng, &JUBJUB).mul_by_cofactor(&JUBJUB);
+            assert_eq!(batch_ka_agree(&ivk, &batch_ka_table(&epk)), sapling_ka_agree(&ivk, &epk));
+        }
+    }
+
+    #[test]
+    fn batch_decryption_finds_matching_ivk() {
+        let mut rng = thread_rng();
+
+        let (_, ivk, _, cmu, epk, enc_ciphertext, _) = random_enc_ciphertext(&mut rng);
+
+        let mut ivks: Vec<Fs> = (0..5).map(|_| Fs::rand(&mut rng)).collect();
+        assert!(try_sapling_note_decryption_batch(&ivks, &epk, &cmu, &enc_ciphertext).is_none());
+        assert!(try_sapling_note_decryption_batch(&[], &epk, &cmu, &enc_ciphertext).is_none());
+
+        ivks.insert(3, ivk);
+        let (index, note, to, memo) =
+            try_sapling_note_decryption_batch(&ivks, &epk, &cmu, &enc_ciphertext).unwrap();
+        assert_eq!(index, 3);
+        assert_eq!(
+            Some((note, to, memo)),
+            try_sapling_note_decryption(&ivk, &epk, &cmu, &enc_ciphertext)
+        );
+    }
+
     #[test]
     fn compact_decryption_with_invalid_ivk() {
         let mut rng = thread_rng();
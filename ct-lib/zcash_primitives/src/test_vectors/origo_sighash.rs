@@ -0,0 +1,444 @@
+pub(crate) struct TestVector {
+    pub chain_id: u64,
+    pub action: Vec<u8>,
+    pub nonce: [u8; 32],
+    pub gas_price: [u8; 32],
+    pub gas: [u8; 32],
+    pub value: [u8; 32],
+    pub data: Vec<u8>,
+    /// Serialized spend descriptions, with their spend authorization signatures.
+    pub spends: Vec<u8>,
+    /// Serialized output descriptions.
+    pub outputs: Vec<u8>,
+    pub public_outputs: Vec<([u8; 20], [u8; 32])>,
+    pub value_balance: i64,
+    pub sighash: [u8; 32],
+}
+
+pub(crate) fn make_test_vectors() -> Vec<TestVector> {
+    vec![
+        TestVector {
+            chain_id: 1,
+            action: vec![0x02],
+            nonce: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x05,
+            ],
+            gas_price: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x3b, 0x9a, 0xca, 0x00,
+            ],
+            gas: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x52, 0x08,
+            ],
+            value: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xe8,
+                0xd4, 0xa5, 0x10, 0x00,
+            ],
+            data: vec![],
+            spends: vec![],
+            outputs: vec![
+                0xa7, 0xb1, 0x7f, 0xb2, 0x7d, 0xd5, 0x23, 0xc7, 0xa2, 0x32, 0x87, 0xf7, 0x0e, 0x4b,
+                0x7f, 0xc0, 0x63, 0x45, 0xc8, 0x65, 0x69, 0x9c, 0x6b, 0x69, 0x38, 0xf7, 0x8f, 0x90,
+                0xe2, 0x02, 0xf4, 0xcd, 0xa5, 0x7b, 0x62, 0xfd, 0x72, 0x10, 0x8d, 0xc2, 0xf5, 0x35,
+                0x5d, 0x23, 0x86, 0xab, 0x19, 0x6f, 0x71, 0xe3, 0x39, 0xe4, 0x3c, 0x1a, 0xf6, 0xde,
+                0xa1, 0x9e, 0xbe, 0x84, 0x31, 0xf8, 0x48, 0x41, 0xc7, 0x1e, 0x77, 0xa8, 0x70, 0xb8,
+                0x37, 0xb7, 0xb0, 0xdb, 0xef, 0x1f, 0xaa, 0xa4, 0xd3, 0x10, 0xee, 0xd0, 0x58, 0x3f,
+                0xa7, 0x92, 0xe8, 0xb0, 0x45, 0xef, 0x68, 0xcf, 0xbe, 0x26, 0xbf, 0xea, 0xdf, 0xe7,
+                0xcd, 0x18, 0x82, 0x0a, 0x64, 0xfe, 0x0e, 0x95, 0x72, 0x35, 0xc9, 0x53, 0x46, 0xd7,
+                0x9d, 0xdb, 0x02, 0x04, 0xdb, 0xd2, 0x26, 0x67, 0x4d, 0xa7, 0x18, 0x90, 0x8f, 0x81,
+                0x06, 0xa8, 0x95, 0x89, 0x8f, 0x3a, 0xca, 0x98, 0x84, 0xab, 0xf3, 0x2b, 0x5e, 0x1e,
+                0x7b, 0x0b, 0xe0, 0x8c, 0xad, 0xe3, 0xa6, 0xf4, 0xbd, 0x67, 0xd5, 0x92, 0x82, 0xc2,
+                0x76, 0x6d, 0xb8, 0x90, 0x27, 0x9f, 0x18, 0xc8, 0x07, 0x21, 0x88, 0x29, 0xa9, 0x63,
+                0x63, 0xcf, 0xa7, 0xac, 0xa3, 0xb6, 0x63, 0xe1, 0xd3, 0xa6, 0x9b, 0x8c, 0xf8, 0x59,
+                0x55, 0xff, 0x15, 0xba, 0x20, 0x1c, 0xdf, 0x21, 0x25, 0x72, 0x74, 0x7d, 0x6c, 0x2f,
+                0x10, 0x63, 0x57, 0x1a, 0x8f, 0x78, 0xa6, 0xba, 0xef, 0x3d, 0x47, 0x95, 0x42, 0x72,
+                0x9a, 0xfb, 0x27, 0xb7, 0xf2, 0xe0, 0x3e, 0x30, 0x15, 0x69, 0x28, 0x7b, 0x2c, 0x87,
+                0x39, 0x21, 0x64, 0x39, 0x66, 0xd3, 0x79, 0x47, 0xa5, 0x23, 0x91, 0x05, 0xff, 0x1e,
+                0x4b, 0x3a, 0x4a, 0xee, 0x14, 0x73, 0x12, 0xca, 0x5c, 0xc4, 0x14, 0xda, 0x35, 0x34,
+                0xab, 0xa0, 0x0c, 0xfb, 0x0a, 0xd1, 0x28, 0x62, 0xe3, 0xe0, 0xf8, 0xfd, 0xed, 0x18,
+                0xe0, 0xf3, 0x91, 0x9e, 0x4f, 0x54, 0xd2, 0xb3, 0x2a, 0xf1, 0xbf, 0x43, 0xe7, 0xd0,
+                0x57, 0x0a, 0x1c, 0x98, 0x61, 0x00, 0x52, 0xa3, 0xa3, 0xb2, 0xa8, 0x1d, 0x3f, 0x5d,
+                0x14, 0x18, 0xeb, 0x60, 0xb4, 0x8c, 0x52, 0xe8, 0x46, 0xb8, 0xa7, 0x83, 0x00, 0x18,
+                0xc9, 0x2f, 0xfc, 0x42, 0xaf, 0xb7, 0xb2, 0x16, 0xf0, 0xd5, 0xe7, 0x67, 0x3f, 0x0c,
+                0x28, 0x2e, 0x03, 0xa5, 0xf2, 0x5e, 0xc7, 0x12, 0xaf, 0xb5, 0xa6, 0x8f, 0x3c, 0xb6,
+                0xf7, 0xde, 0x57, 0xd4, 0x95, 0xab, 0x0d, 0x38, 0x5f, 0x60, 0x16, 0x5e, 0x45, 0xd0,
+                0xf5, 0x1e, 0xd4, 0x99, 0x25, 0x71, 0x64, 0xfc, 0x7f, 0xf7, 0xca, 0x9e, 0xf2, 0xdd,
+                0x15, 0x87, 0x5f, 0xb6, 0x67, 0x20, 0x2f, 0x22, 0x57, 0xe9, 0x82, 0x25, 0xbb, 0x4c,
+                0xc2, 0x40, 0x8c, 0x73, 0xfc, 0x79, 0x5f, 0x34, 0x7f, 0x42, 0x0e, 0x1b, 0x20, 0x8f,
+                0x2f, 0x32, 0xa3, 0x0d, 0xa4, 0xda, 0x31, 0x97, 0x54, 0x23, 0x2d, 0x23, 0x63, 0xbc,
+                0xf2, 0x44, 0x09, 0x32, 0x12, 0xa5, 0xee, 0xa5, 0xa8, 0xda, 0x47, 0x4b, 0xdc, 0x24,
+                0x09, 0x0f, 0x31, 0xe3, 0x21, 0xde, 0xd3, 0x62, 0x25, 0x46, 0xdc, 0x84, 0xeb, 0x73,
+                0x3c, 0x53, 0x85, 0xc6, 0x2b, 0x31, 0x98, 0x3b, 0x52, 0x89, 0x1d, 0xe8, 0x23, 0xba,
+                0xe5, 0x2d, 0xe2, 0xf9, 0xda, 0x0f, 0x60, 0xbd, 0x21, 0x7e, 0xc1, 0xaa, 0xc7, 0xc6,
+                0x8b, 0xcd, 0xce, 0xf4, 0xab, 0x4a, 0xe5, 0xaa, 0xa6, 0x12, 0xfd, 0x04, 0xc9, 0x09,
+                0x65, 0x2c, 0x47, 0xb4, 0xf6, 0xba, 0x52, 0xb9, 0x03, 0x85, 0xb9, 0x34, 0x17, 0x45,
+                0xf3, 0x1b, 0xd4, 0xa8, 0xfa, 0x06, 0xaa, 0xf7, 0x25, 0x58, 0x4c, 0x35, 0x0b, 0xd2,
+                0x9e, 0x21, 0x87, 0x25, 0xc2, 0x56, 0x38, 0x0e, 0xbf, 0xe7, 0x12, 0x84, 0xe4, 0x05,
+                0xdf, 0x69, 0xac, 0xc1, 0xf7, 0xb4, 0x54, 0x95, 0x06, 0x71, 0x6e, 0x3b, 0xcc, 0xa5,
+                0x5f, 0x1d, 0x4d, 0x2e, 0x4c, 0x9f, 0x7e, 0xe1, 0xe0, 0xa3, 0x42, 0x7e, 0x8f, 0x71,
+                0xfe, 0x96, 0x58, 0xb9, 0xd8, 0x72, 0x6a, 0x57, 0x29, 0xac, 0x10, 0xd3, 0x34, 0x58,
+                0x12, 0x30, 0x69, 0xf2, 0xf8, 0x54, 0x5c, 0xa7, 0x64, 0xf1, 0x0d, 0x03, 0xdb, 0xcd,
+                0xe7, 0x0b, 0x5b, 0x46, 0x01, 0x86, 0x6a, 0xcd, 0x31, 0x82, 0x7c, 0x87, 0x56, 0x20,
+                0xc1, 0x55, 0x32, 0x64, 0x21, 0xd7, 0x16, 0x7e, 0x3f, 0xf2, 0xde, 0xc6, 0x9a, 0xad,
+                0xb3, 0x27, 0x84, 0x9b, 0x5e, 0xe4, 0x24, 0x2b, 0x17, 0x1b, 0xda, 0xd3, 0xa8, 0x41,
+                0x23, 0xf0, 0xd5, 0x0b, 0xbd, 0x56, 0xa4, 0xee, 0x99, 0x6e, 0xf9, 0x52, 0x9c, 0xf2,
+                0xd0, 0xea, 0x0d, 0x59, 0x6a, 0x21, 0xe4, 0x23, 0xde, 0x27, 0x80, 0xb6, 0xed, 0xc8,
+                0x90, 0x56, 0x97, 0xf9, 0x45, 0x6c, 0x18, 0x18, 0xea, 0x49, 0x46, 0x22, 0xaf, 0xd1,
+                0xc9, 0x40, 0x9f, 0x75, 0xbf, 0x9f, 0x68, 0x41, 0x77, 0x40, 0x51, 0x59, 0xf0, 0x73,
+                0x36, 0x0d, 0xf2, 0xcb, 0xe8, 0x7d, 0xa0, 0x33, 0x9c, 0x4c, 0x64, 0x43, 0xbc, 0xd4,
+                0x3f, 0x15, 0x6a, 0xad, 0x24, 0xa7, 0x0b, 0x43, 0x65, 0x76, 0xda, 0xed, 0x34, 0xf9,
+                0xc8, 0xe3, 0x54, 0x95, 0x43, 0xb0, 0x5a, 0x71, 0x01, 0xe3, 0xad, 0x2e, 0xfd, 0xba,
+                0x4b, 0x01, 0xb0, 0xe9, 0x94, 0x0b, 0x0a, 0x16, 0x8e, 0x3a, 0xb3, 0x0c, 0x93, 0x84,
+                0x4d, 0xec, 0xde, 0x8e, 0x84, 0xce, 0x56, 0xf8, 0xc7, 0x23, 0x8c, 0x84, 0x21, 0x3e,
+                0x3c, 0x7f, 0xc3, 0x24, 0xef, 0xeb, 0x85, 0x3d, 0xab, 0xfb, 0xb2, 0x8b, 0xb0, 0x99,
+                0x35, 0xff, 0xa5, 0x16, 0x1a, 0x05, 0x9f, 0x1c, 0x33, 0x94, 0x01, 0x10, 0x27, 0x7f,
+                0x60, 0x59, 0x5f, 0xbf, 0xe8, 0x71, 0xf9, 0x95, 0x64, 0xc9, 0x8e, 0xe0, 0x2d, 0x26,
+                0x75, 0x0f, 0xfe, 0x3c, 0x8c, 0x4a, 0x1e, 0x25, 0xe6, 0xb8, 0x97, 0x2a, 0xdd, 0xd1,
+                0xb8, 0x34, 0xb6, 0xd1, 0x11, 0xa7, 0x58, 0x7d, 0xd3, 0x53, 0x8e, 0xed, 0xcb, 0xa0,
+                0xf3, 0x5a, 0xef, 0x2d, 0x9c, 0x85, 0x35, 0x5d, 0x01, 0xf3, 0x46, 0x92, 0x7f, 0x05,
+                0x73, 0xd6, 0xf2, 0x20, 0x44, 0x51, 0xee, 0x46, 0x24, 0x29, 0xc7, 0xa2, 0xaa, 0xa3,
+                0x21, 0x7b, 0x47, 0xf8, 0xbd, 0x92, 0xd4, 0xf4, 0xaa, 0x6e, 0xed, 0x44, 0x23, 0xb0,
+                0xb9, 0x7a, 0xb7, 0x01, 0x71, 0xc1, 0x7e, 0x2f, 0xe1, 0x1c, 0x85, 0x96, 0x2e, 0x2c,
+                0xfb, 0x5d, 0x6f, 0xbc, 0xe4, 0x1c, 0xcb, 0xf1, 0xb9, 0xd0, 0x98, 0x60, 0x33, 0x11,
+                0x05, 0xd3, 0x32, 0x4a, 0x26, 0x8d, 0x33, 0x5e, 0xcc, 0x07, 0xa5, 0x1a, 0xb7, 0x28,
+                0xef, 0x24, 0x0d, 0x2c, 0x60, 0x72, 0x03, 0xbb, 0xbc, 0xd8, 0x56, 0x3f, 0x27, 0x7c,
+                0x72, 0x92, 0xa3, 0x3d, 0xae, 0xf9, 0x83, 0x84, 0x72, 0x8b, 0x5b, 0x18, 0xe6, 0x35,
+                0xd3, 0xd2, 0x7c, 0xda, 0xb9, 0xd2, 0xa5, 0xcb, 0x9d, 0xe0, 0x65, 0x58, 0x4b, 0x8c,
+                0x69, 0x19, 0xf8, 0x6c, 0xc6, 0x38, 0xc5, 0xed, 0xda, 0x0d,
+            ],
+            public_outputs: vec![],
+            value_balance: -1000,
+            sighash: [
+                0x60, 0x6c, 0xc5, 0xb8, 0xdf, 0xb5, 0xc0, 0xb3, 0xb6, 0x63, 0x6a, 0x64, 0xf0, 0x05,
+                0xe4, 0x57, 0xda, 0x7a, 0x67, 0x5b, 0x43, 0x1d, 0xeb, 0xad, 0xed, 0x71, 0x9d, 0x4e,
+                0x19, 0xf6, 0xa8, 0x81,
+            ],
+        },
+        TestVector {
+            chain_id: 42,
+            action: vec![0x01],
+            nonce: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00,
+            ],
+            gas_price: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x0f, 0x42, 0x40,
+            ],
+            gas: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x52, 0x08,
+            ],
+            value: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00,
+            ],
+            data: vec![],
+            spends: vec![
+                0xc1, 0xef, 0x8b, 0x5b, 0xd9, 0xae, 0xd2, 0x47, 0x4d, 0x8f, 0xcf, 0xbf, 0x1f, 0xd8,
+                0x6b, 0x56, 0x0e, 0xd2, 0xfd, 0x06, 0x49, 0x92, 0xb6, 0x0a, 0x12, 0xc4, 0xe8, 0x67,
+                0x18, 0x99, 0x44, 0xac, 0xf0, 0x63, 0xd0, 0xba, 0x3b, 0xe8, 0xd4, 0xf8, 0xdd, 0x88,
+                0x5c, 0xfb, 0xdf, 0x98, 0x2e, 0x1f, 0x7c, 0x2e, 0x36, 0x5b, 0x4e, 0x82, 0x4d, 0xec,
+                0x5b, 0x48, 0xbe, 0x66, 0x51, 0x55, 0x88, 0x27, 0x16, 0xa8, 0xc3, 0x4e, 0x34, 0xbe,
+                0xa1, 0x83, 0x22, 0xf2, 0x77, 0x47, 0xd2, 0xf5, 0x30, 0x0d, 0x67, 0x75, 0xe6, 0x27,
+                0x60, 0x2b, 0x93, 0x6a, 0xd1, 0xa7, 0xf5, 0xba, 0xe4, 0x0b, 0xaf, 0x64, 0xb4, 0x49,
+                0x36, 0x48, 0x92, 0xb4, 0xd5, 0xb7, 0x16, 0x97, 0xe5, 0x3c, 0x02, 0x3e, 0xcd, 0x4f,
+                0x9a, 0x96, 0x59, 0xf7, 0xaa, 0x5c, 0x74, 0x61, 0x03, 0xa5, 0x2c, 0x5b, 0x2e, 0x93,
+                0x30, 0xcc, 0x58, 0xf3, 0x04, 0x17, 0xdb, 0x9a, 0x53, 0x97, 0x4b, 0xc8, 0xa1, 0x36,
+                0x5e, 0x55, 0xce, 0xbc, 0xcf, 0x5c, 0xf3, 0x47, 0xa5, 0x32, 0xa2, 0x02, 0x71, 0x3b,
+                0xb9, 0x51, 0x46, 0x92, 0x93, 0x27, 0x26, 0x87, 0xad, 0x88, 0xb7, 0x80, 0x3f, 0xc0,
+                0x31, 0x5a, 0xab, 0x4f, 0xc6, 0xe2, 0xbb, 0x59, 0x45, 0x0c, 0x92, 0xfe, 0x52, 0x38,
+                0x04, 0x86, 0xf0, 0x58, 0xb9, 0x03, 0x17, 0x06, 0x9d, 0x7d, 0xfa, 0xa0, 0x50, 0x2a,
+                0x8d, 0x3b, 0x24, 0x31, 0xd1, 0x89, 0x59, 0xf2, 0xb5, 0xa0, 0x04, 0x43, 0xf9, 0x96,
+                0x76, 0xc1, 0x8c, 0xcb, 0x5c, 0x45, 0x82, 0x4b, 0xe4, 0xc1, 0xae, 0x94, 0xa1, 0xfc,
+                0xcf, 0x03, 0xaf, 0x30, 0x8d, 0x43, 0x01, 0x9d, 0x7b, 0x8d, 0xb4, 0xc5, 0x5c, 0xf3,
+                0x28, 0x01, 0x3a, 0x1d, 0x29, 0xef, 0x51, 0xc8, 0xce, 0x96, 0xc3, 0x41, 0x96, 0x12,
+                0x1f, 0xfa, 0x8c, 0x0d, 0x73, 0x70, 0xd8, 0x54, 0x0c, 0x04, 0x60, 0x3a, 0xa2, 0xdc,
+                0x1a, 0x36, 0x6a, 0xe2, 0xa5, 0x40, 0xc0, 0x33, 0xce, 0x42, 0x38, 0xd1, 0xa6, 0x7b,
+                0x39, 0xda, 0x36, 0x16, 0xf7, 0x94, 0x5c, 0xe8, 0x17, 0x62, 0x1c, 0xef, 0x00, 0xc2,
+                0xda, 0xd2, 0x9d, 0xb3, 0xa0, 0xf8, 0x45, 0x5f, 0x0a, 0x3d, 0x18, 0x7f, 0x99, 0x19,
+                0x83, 0xcd, 0xdd, 0x81, 0x00, 0x0e, 0x35, 0xe7, 0x0d, 0x97, 0x18, 0x92, 0x73, 0x0b,
+                0xea, 0x20, 0x29, 0x9b, 0x2a, 0x18, 0x1e, 0xaa, 0xe9, 0x18, 0x25, 0x18, 0x14, 0x0f,
+                0xf6, 0xc8, 0x6a, 0xf2, 0xb5, 0x52, 0x4a, 0xe2, 0x0d, 0x12, 0x5b, 0x08, 0x46, 0x5b,
+                0xaf, 0xc6, 0x90, 0x97, 0x65, 0x20, 0x56, 0x13, 0xec, 0xa8, 0x5b, 0x73, 0x4b, 0xd3,
+                0xa0, 0x0a, 0xb4, 0x14, 0x6e, 0x9d, 0x36, 0x65, 0x3a, 0xaf, 0xfd, 0x9a, 0xce, 0x21,
+                0xc2, 0xd2, 0xa8, 0x21, 0x48, 0x66,
+            ],
+            outputs: vec![
+                0xce, 0xdf, 0xf1, 0xfc, 0xf5, 0x95, 0xe5, 0x1b, 0x63, 0x84, 0xce, 0x31, 0x2e, 0x88,
+                0x11, 0x4b, 0x66, 0x86, 0x39, 0x9c, 0xff, 0xb6, 0x64, 0x15, 0xa0, 0xc2, 0x95, 0x68,
+                0xc0, 0xef, 0x07, 0x03, 0xca, 0x5c, 0xa9, 0x38, 0x4b, 0x98, 0x3d, 0xbe, 0x34, 0x11,
+                0x88, 0xcf, 0x8f, 0x97, 0x1f, 0xd3, 0x4b, 0xb0, 0xf9, 0x1a, 0x18, 0x78, 0x4a, 0x3f,
+                0xec, 0xf6, 0xba, 0x4a, 0x0d, 0x19, 0xba, 0x16, 0x5c, 0xc9, 0x10, 0x44, 0x8d, 0x87,
+                0xfa, 0x2b, 0x06, 0x33, 0xb5, 0xc0, 0x4a, 0x26, 0x22, 0x88, 0x91, 0x9a, 0xcf, 0x12,
+                0xe8, 0xa3, 0xbc, 0x87, 0xbe, 0xab, 0xe3, 0x81, 0xdd, 0x66, 0x9e, 0x59, 0x8a, 0x8e,
+                0x0c, 0x3b, 0x77, 0xcd, 0x4f, 0x3d, 0x82, 0x50, 0x0b, 0x01, 0x85, 0x5f, 0x4b, 0x77,
+                0x5b, 0x78, 0x10, 0x94, 0x06, 0xce, 0x06, 0x46, 0xd5, 0x0b, 0x91, 0xed, 0x5e, 0x87,
+                0x73, 0x2a, 0x98, 0xe5, 0xe8, 0x4f, 0x93, 0x17, 0x6b, 0x1f, 0xe2, 0xa9, 0xfb, 0x08,
+                0x1c, 0x4f, 0x72, 0xbb, 0x36, 0x10, 0x53, 0xf6, 0x58, 0x8d, 0x85, 0xb6, 0xa3, 0xc8,
+                0x8c, 0x0c, 0xe9, 0xcb, 0xd4, 0x00, 0xf6, 0xde, 0x26, 0x52, 0x66, 0x5a, 0xc4, 0x1a,
+                0xb9, 0x91, 0x22, 0xa5, 0x11, 0x08, 0x9a, 0x30, 0x2f, 0x2c, 0x1f, 0xfa, 0x68, 0x1b,
+                0xd3, 0x14, 0x99, 0x31, 0x08, 0x54, 0x6a, 0xf8, 0x55, 0xad, 0xf6, 0x90, 0x8c, 0x23,
+                0x00, 0x42, 0xd1, 0xe9, 0xf2, 0x23, 0xe3, 0x43, 0x7e, 0x0e, 0x9c, 0xf5, 0x1a, 0xba,
+                0xe6, 0xcd, 0x46, 0xb6, 0x61, 0xa7, 0xf3, 0xb5, 0x47, 0xae, 0xee, 0x4a, 0xf0, 0x83,
+                0x95, 0x9c, 0xeb, 0xba, 0xa1, 0x71, 0xa8, 0x09, 0x42, 0xc8, 0x8e, 0x44, 0xa8, 0x7a,
+                0x2e, 0x7b, 0x8c, 0x62, 0x10, 0x54, 0x82, 0x9b, 0x61, 0x4e, 0xa8, 0x0a, 0x67, 0x76,
+                0xf4, 0x9b, 0xe2, 0x75, 0x15, 0x1e, 0xef, 0x86, 0x8b, 0x61, 0x5b, 0x69, 0x9b, 0x7c,
+                0x9c, 0xa7, 0x75, 0xb5, 0x1d, 0x56, 0x7a, 0x20, 0x72, 0xd6, 0xdd, 0x2e, 0x72, 0x26,
+                0x46, 0x59, 0x7f, 0xeb, 0x25, 0x8d, 0xff, 0xd9, 0x6d, 0x59, 0x2e, 0x87, 0x70, 0x7c,
+                0x1d, 0x1e, 0x9d, 0x07, 0x89, 0x75, 0x0f, 0x92, 0x3d, 0xd0, 0x7b, 0x40, 0xcd, 0x21,
+                0xd2, 0x33, 0x8b, 0xd7, 0xd4, 0xcb, 0xb2, 0x49, 0xd7, 0x6e, 0xb6, 0xef, 0xb7, 0xd8,
+                0xda, 0x52, 0xee, 0xb3, 0x9b, 0x07, 0x1c, 0xa7, 0x83, 0xdc, 0x7b, 0xa7, 0x53, 0xad,
+                0xaa, 0xc9, 0x97, 0xf4, 0x6b, 0xed, 0xea, 0x0d, 0xad, 0x94, 0x11, 0x76, 0x82, 0x38,
+                0xc0, 0x9d, 0x0c, 0x49, 0x33, 0xd4, 0xe9, 0xc5, 0x95, 0x18, 0x46, 0x8c, 0x20, 0x27,
+                0x21, 0x7c, 0x55, 0x41, 0x83, 0x3b, 0x88, 0xe9, 0xf1, 0x86, 0xee, 0x14, 0xeb, 0x72,
+                0x12, 0x23, 0x38, 0x48, 0x97, 0x7e, 0x06, 0xeb, 0x24, 0x0a, 0x3c, 0x2e, 0x31, 0x28,
+                0x91, 0x6f, 0x55, 0x41, 0xc2, 0x7b, 0xca, 0x57, 0xf3, 0xfc, 0x35, 0x4a, 0x58, 0x00,
+                0x59, 0xba, 0xce, 0x6d, 0xb8, 0x35, 0xf1, 0x77, 0xf0, 0xf5, 0x0e, 0xbb, 0x6e, 0xe5,
+                0xc0, 0x13, 0xeb, 0xd9, 0x72, 0xe8, 0x7c, 0xbc, 0x9e, 0x3e, 0x76, 0x3f, 0x43, 0x0b,
+                0x96, 0xca, 0x81, 0xa8, 0x5b, 0x8e, 0xcc, 0x2a, 0xfe, 0x0d, 0x71, 0xfa, 0x1d, 0x45,
+                0x91, 0x43, 0x64, 0x53, 0xc0, 0x67, 0x87, 0x48, 0xbe, 0x0e, 0x78, 0x89, 0x7f, 0xd5,
+                0x52, 0x2e, 0xa7, 0xed, 0xc8, 0x38, 0x9d, 0x08, 0x63, 0x8d, 0xf1, 0x8c, 0xb3, 0x35,
+                0xdc, 0x27, 0x6f, 0xb1, 0x8a, 0x95, 0x91, 0x0c, 0x18, 0x8b, 0x22, 0xb8, 0x71, 0x8d,
+                0xd5, 0xb0, 0x77, 0x27, 0xc7, 0x9a, 0xf8, 0x5a, 0x0c, 0x9f, 0xf1, 0x74, 0x0d, 0x71,
+                0x7e, 0xa9, 0x7f, 0xe9, 0xdf, 0x8f, 0xa0, 0x60, 0x3d, 0x1c, 0x7e, 0x23, 0x6c, 0xdf,
+                0x60, 0x2e, 0xf7, 0xa7, 0xb5, 0x62, 0x75, 0x54, 0x63, 0xb4, 0x14, 0xce, 0x2d, 0x02,
+                0xf0, 0xbd, 0x8a, 0x64, 0xf4, 0x90, 0x37, 0xc2, 0x67, 0x5f, 0x7c, 0x95, 0x92, 0x44,
+                0xb1, 0xcd, 0x8e, 0xd0, 0x11, 0xdc, 0x79, 0x28, 0x2b, 0x37, 0xe4, 0x4d, 0xe4, 0x1a,
+                0xd5, 0x8a, 0x80, 0xc6, 0x24, 0xb8, 0xae, 0x23, 0x74, 0xa5, 0x6e, 0xf2, 0xc3, 0x1d,
+                0x70, 0xce, 0x51, 0xe7, 0xcd, 0x53, 0x67, 0xcc, 0x11, 0x63, 0xb0, 0xaf, 0xcc, 0xb7,
+                0xdc, 0xd2, 0xd6, 0xad, 0x97, 0x7a, 0xa3, 0x5a, 0x11, 0x46, 0x48, 0xc6, 0x7c, 0x8a,
+                0x60, 0x80, 0x33, 0x8f, 0x79, 0x8f, 0x90, 0xee, 0x37, 0xb7, 0x74, 0x73, 0x53, 0x28,
+                0xa2, 0xd5, 0x72, 0x91, 0xa6, 0x3f, 0xc8, 0xb9, 0x11, 0x1b, 0x40, 0xfa, 0xe1, 0xef,
+                0x2a, 0x31, 0xda, 0xe9, 0xa8, 0xa3, 0x1f, 0xaa, 0x51, 0xec, 0x48, 0x15, 0x4b, 0xe1,
+                0x75, 0x17, 0xfe, 0x6e, 0x48, 0x9c, 0xee, 0xa5, 0x37, 0xa2, 0x9a, 0xfd, 0x22, 0x6a,
+                0x3e, 0x58, 0x8d, 0x64, 0xb8, 0xb0, 0xa2, 0xc2, 0x1f, 0xa0, 0x07, 0x2a, 0x21, 0xe6,
+                0xa0, 0xbd, 0x8c, 0x10, 0xf2, 0xa9, 0xca, 0xb0, 0xcc, 0x24, 0xbc, 0xde, 0x03, 0xba,
+                0x5c, 0x13, 0x4b, 0x6e, 0x39, 0xef, 0xd0, 0xe6, 0xb9, 0x15, 0xf5, 0x4c, 0x2e, 0xbd,
+                0x5b, 0x8e, 0xdd, 0xcc, 0xee, 0x20, 0xc6, 0x64, 0x62, 0x8f, 0x4c, 0x08, 0x37, 0x6a,
+                0x63, 0xcb, 0x1a, 0xff, 0xe9, 0x4f, 0x0a, 0xc3, 0x7c, 0xd9, 0x93, 0xb3, 0x4e, 0xce,
+                0x9a, 0xb7, 0xf2, 0xb4, 0x6e, 0xfe, 0xba, 0x64, 0xb1, 0x1a, 0x62, 0xc1, 0x23, 0xdd,
+                0xfb, 0x38, 0xc6, 0x07, 0x65, 0x77, 0x31, 0xc6, 0x6f, 0x2c, 0xcb, 0x70, 0x54, 0x32,
+                0xd8, 0xee, 0xd3, 0x60, 0x67, 0xfe, 0xbe, 0xc3, 0xb1, 0xf5, 0xcc, 0x29, 0xed, 0xd6,
+                0xc2, 0xd8, 0x22, 0xf8, 0xd9, 0x7e, 0xd5, 0xcb, 0x61, 0xf1, 0x74, 0xfd, 0x90, 0x26,
+                0x21, 0x0a, 0xd8, 0x7f, 0xce, 0x27, 0xd8, 0x45, 0xfb, 0x36, 0x2f, 0xb7, 0xd1, 0xd0,
+                0x3a, 0xc7, 0x06, 0x59, 0xb8, 0x50, 0xf1, 0xec, 0xb6, 0x1b, 0xc7, 0x87, 0x88, 0x26,
+                0xb6, 0xde, 0x8a, 0x8c, 0x2a, 0x70, 0x87, 0xf3, 0x2d, 0xba, 0x0d, 0xfa, 0x53, 0x96,
+                0x48, 0xb2, 0xdf, 0x8e, 0x82, 0xfa, 0xaa, 0x9f, 0x6c, 0xc4, 0x3f, 0xdf, 0x29, 0x1c,
+                0x99, 0x01, 0x19, 0x82, 0xe1, 0xb2, 0xce, 0xe4, 0x89, 0x10, 0x8e, 0x27, 0x2b, 0x22,
+                0xc2, 0x41, 0xbb, 0x6f, 0x18, 0x3a, 0x16, 0x40, 0x26, 0xc3, 0x1e, 0x90, 0xa1, 0x09,
+                0x52, 0x09, 0xc3, 0x4b, 0xf7, 0xde, 0xed, 0x59, 0x44, 0xc7, 0x4d, 0x3a, 0x61, 0xbd,
+                0x31, 0x2d, 0x34, 0xfa, 0x58, 0xf2, 0xbc, 0x6a, 0x47, 0x53, 0x9c, 0x6e, 0x68, 0x6a,
+                0x97, 0x30, 0xb8, 0xf1, 0x1a, 0x8a, 0x3a, 0x92, 0x78, 0x17, 0x05, 0x85, 0x66, 0x15,
+                0x22, 0xdb, 0xa0, 0x54, 0x0e, 0x89, 0xf3, 0xc2, 0x90, 0xc8, 0x8c, 0x69, 0xdf, 0x64,
+                0x66, 0x44, 0xae, 0xda, 0x95, 0x5d, 0xb2, 0x62, 0x10, 0xae, 0xff, 0x15, 0x6c, 0x1a,
+                0x93, 0x4d, 0x03, 0xd9, 0x52, 0x2a, 0x8d, 0xfe, 0x6e, 0x52,
+            ],
+            public_outputs: vec![],
+            value_balance: 21,
+            sighash: [
+                0xcc, 0x74, 0x88, 0xde, 0x5f, 0xda, 0x7d, 0xe4, 0x43, 0x9f, 0x62, 0x55, 0xde, 0x8e,
+                0xe8, 0x29, 0x1d, 0xed, 0x28, 0x5b, 0x67, 0x50, 0x44, 0x5c, 0xed, 0x8b, 0x54, 0xa8,
+                0xfc, 0xf5, 0x46, 0xe1,
+            ],
+        },
+        TestVector {
+            chain_id: 4294967298,
+            action: vec![
+                0x95, 0x03, 0x9e, 0xd4, 0x09, 0x9a, 0xbe, 0xd7, 0xfe, 0x09, 0xe4, 0x85, 0xe3, 0xe6,
+                0xf1, 0xf7, 0xe1, 0xad, 0xa3, 0x0f, 0x6d, 0x5a,
+            ],
+            nonce: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00,
+            ],
+            gas_price: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x0f, 0x42, 0x40,
+            ],
+            gas: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x01, 0x5f, 0x90,
+            ],
+            value: [
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01,
+                0xa1, 0x3b, 0x86, 0x00,
+            ],
+            data: vec![
+                0xa9, 0x05, 0x9c, 0xbb, 0x1e, 0xf6, 0x01, 0xc4, 0x7b, 0x07, 0x36, 0xef, 0x90, 0x1b,
+                0x4b, 0xd7,
+            ],
+            spends: vec![
+                0x9e, 0x9a, 0x42, 0x8e, 0xc7, 0xab, 0x64, 0x72, 0xe4, 0xc3, 0xb4, 0x04, 0x66, 0x08,
+                0x0c, 0x8f, 0xe5, 0x4a, 0x78, 0x3b, 0x6b, 0xbc, 0x17, 0x1a, 0xd0, 0x54, 0x6f, 0xc2,
+                0x2c, 0x50, 0xbb, 0x90, 0x7c, 0x9c, 0xa3, 0x7c, 0x4b, 0x54, 0x22, 0x1a, 0xd3, 0xe1,
+                0xe9, 0x6f, 0xc5, 0x46, 0x3e, 0x4c, 0xe5, 0x7c, 0x53, 0x5e, 0xc2, 0x77, 0xd8, 0x1f,
+                0x99, 0xab, 0xdd, 0x69, 0xb6, 0xfe, 0x90, 0x21, 0x0a, 0x06, 0xd0, 0x04, 0xa7, 0x24,
+                0x8a, 0x86, 0xde, 0x6a, 0x4a, 0x6f, 0x9c, 0x1b, 0xc4, 0x22, 0xcf, 0x89, 0x0e, 0xd2,
+                0x2b, 0xd6, 0x7e, 0x5a, 0xd1, 0x7b, 0x33, 0x78, 0xeb, 0x45, 0x23, 0x0f, 0x1e, 0x82,
+                0x21, 0x4f, 0xa8, 0x33, 0x18, 0x95, 0x4e, 0x18, 0x6d, 0x14, 0xac, 0xe4, 0x23, 0xba,
+                0x8b, 0x5f, 0x74, 0x7a, 0x4a, 0xe1, 0xbd, 0xf0, 0x58, 0xd6, 0xa6, 0x87, 0x9e, 0x28,
+                0x70, 0x82, 0x39, 0xc5, 0x8a, 0xc5, 0xb3, 0xe6, 0xc5, 0xcd, 0xa2, 0xbb, 0x5e, 0x86,
+                0x58, 0x9e, 0x70, 0xde, 0x1a, 0xb9, 0xe0, 0x40, 0xf1, 0x1f, 0xba, 0x26, 0x2e, 0xc5,
+                0xa1, 0xc2, 0x0a, 0xb8, 0xcc, 0xbb, 0x8f, 0x80, 0x84, 0x93, 0x5b, 0x64, 0xd9, 0xd5,
+                0x61, 0x8e, 0x1c, 0x22, 0xad, 0x78, 0x6c, 0xea, 0x0b, 0x21, 0x15, 0x8d, 0x1e, 0x50,
+                0x1f, 0x75, 0x18, 0xa9, 0x7b, 0xb6, 0xaa, 0x59, 0x20, 0x88, 0xd1, 0x95, 0x30, 0x95,
+                0xd4, 0x8d, 0xe5, 0x4a, 0x8b, 0xe1, 0xd3, 0xd5, 0xd0, 0x5e, 0x6e, 0x7a, 0x40, 0xa1,
+                0x2c, 0x0c, 0xbe, 0x7f, 0xc1, 0x8e, 0xe1, 0x18, 0x05, 0x49, 0x83, 0x77, 0xbd, 0xac,
+                0x2a, 0xcb, 0x18, 0x9a, 0x2b, 0x68, 0x60, 0x25, 0x99, 0x9d, 0xf1, 0x3c, 0xc6, 0x65,
+                0xcd, 0x41, 0xd2, 0x7e, 0xef, 0x9e, 0x40, 0xe9, 0x79, 0x85, 0x7e, 0x6f, 0xcc, 0x31,
+                0xe8, 0xa3, 0x4c, 0xc5, 0xa0, 0xe6, 0x33, 0x12, 0x82, 0x38, 0x57, 0x5d, 0x3c, 0xd0,
+                0xc7, 0x84, 0x98, 0x3b, 0x22, 0xe3, 0x2d, 0xac, 0xb9, 0x47, 0x55, 0x48, 0xd2, 0xa1,
+                0xfc, 0xcc, 0xb3, 0x9c, 0x21, 0x0d, 0xa6, 0x71, 0x44, 0x35, 0x93, 0x1c, 0x1c, 0xa0,
+                0x28, 0xd2, 0xc1, 0xf2, 0x30, 0x50, 0x41, 0x77, 0x23, 0x15, 0xfc, 0x09, 0xe2, 0x11,
+                0x6d, 0x2c, 0x35, 0xf0, 0x1a, 0xf0, 0x20, 0x6f, 0x5b, 0xe8, 0xb9, 0xde, 0x76, 0xd8,
+                0x9d, 0x0b, 0x25, 0x22, 0xec, 0x64, 0x6c, 0x9c, 0x51, 0xe9, 0x9b, 0xd8, 0xec, 0x1d,
+                0xe0, 0x12, 0x1a, 0x8c, 0x6c, 0x86, 0x7d, 0xa5, 0xa4, 0x42, 0x77, 0x40, 0xe0, 0x23,
+                0x67, 0x76, 0x1a, 0xfa, 0x2c, 0x8d, 0x66, 0x1a, 0xea, 0x18, 0xe9, 0xc5, 0x0a, 0x07,
+                0x4a, 0x70, 0x23, 0xe9, 0xf7, 0x9e, 0x78, 0xd0, 0x66, 0x0a, 0x22, 0x67, 0x13, 0x8e,
+                0xf8, 0x2a, 0x23, 0x9e, 0x46, 0xf0, 0x69, 0x0d, 0x45, 0x75, 0x9c, 0x72, 0xe6, 0x50,
+                0xa8, 0xb5, 0xc9, 0x40, 0x6d, 0x3e, 0x88, 0xe3, 0xad, 0x52, 0xac, 0x4a, 0x62, 0xa2,
+                0x94, 0xfe, 0xd2, 0xd6, 0xb6, 0x0e, 0x8e, 0x9e, 0x39, 0x1e, 0x91, 0xd8, 0x35, 0x26,
+                0x93, 0xde, 0xc5, 0xa0, 0x33, 0x7e, 0x66, 0x49, 0xb9, 0xd0, 0x64, 0xc0, 0xbb, 0xe9,
+                0x01, 0x13, 0x29, 0x37, 0x75, 0x27, 0x3f, 0x84, 0x74, 0xf7, 0x88, 0x68, 0x1f, 0x0c,
+                0x9c, 0x07, 0xb8, 0x74, 0x90, 0x8f, 0xe6, 0x2c, 0x81, 0xb6, 0xfd, 0x9e, 0xa4, 0x9c,
+                0x42, 0x12, 0xe8, 0xf4, 0x9c, 0x87, 0xa3, 0x08, 0xb7, 0x79, 0xf7, 0xec, 0x8a, 0x73,
+                0x4e, 0xb8, 0x74, 0xd6, 0x68, 0xdb, 0xfc, 0xc3, 0xeb, 0x17, 0x6c, 0x6b, 0xf9, 0x73,
+                0x5d, 0x85, 0x77, 0x0f, 0x3c, 0xdf, 0x94, 0x66, 0x5a, 0x7d, 0xcd, 0xc6, 0xe5, 0x27,
+                0x04, 0x78, 0xb5, 0xb4, 0x93, 0x9d, 0xbb, 0x22, 0x2f, 0xc0, 0x7b, 0xbf, 0x89, 0x8d,
+                0xe3, 0xbf, 0x15, 0xb9, 0x76, 0x5b, 0x7c, 0xc8, 0x7c, 0x3b, 0x7f, 0xed, 0x66, 0xd6,
+                0xa5, 0xfa, 0x30, 0xb2, 0x63, 0xe3, 0x56, 0xc7, 0xe7, 0xe8, 0xf7, 0x98, 0x3b, 0x4b,
+                0x4d, 0x3c, 0x8d, 0x13, 0xa5, 0xf6, 0xa0, 0xfa, 0x84, 0x0e, 0xe9, 0x4e, 0x59, 0xac,
+                0xb7, 0x55, 0x9b, 0x87, 0x32, 0xa9, 0x8b, 0xab, 0xeb, 0x74, 0x4c, 0x79, 0x55, 0xb6,
+                0xdc, 0x43, 0xe4, 0xd2, 0xd2, 0x73, 0x84, 0x41, 0x63, 0x99, 0x1c, 0xb6, 0x49, 0x90,
+                0xf3, 0xa5, 0x66, 0xfa, 0x2b, 0xcc, 0xf6, 0x81, 0xf3, 0x55, 0x36, 0xd2, 0xf4, 0xcd,
+                0xf2, 0xf0, 0x6f, 0x58, 0xdd, 0xc5, 0x02, 0x8d, 0x4e, 0x28, 0x76, 0xb7, 0x77, 0xb4,
+                0xe0, 0xc2, 0x3e, 0xcd, 0x3b, 0x7f, 0x04, 0x2d, 0xf7, 0x5a, 0x3d, 0x98, 0x9e, 0x55,
+                0xb0, 0xf2, 0xe7, 0x1e, 0x10, 0x0b, 0xe0, 0xdf, 0x8d, 0x5d, 0x35, 0xe3, 0x5e, 0x87,
+                0xc9, 0x61, 0x8d, 0x8c, 0xe4, 0x4b, 0x35, 0x4e, 0xf9, 0x66, 0x20, 0xd3, 0xc1, 0xb6,
+                0x61, 0x08, 0x77, 0xfc, 0x06, 0xe1, 0xaa, 0xa3, 0x3d, 0x3c, 0xa8, 0x62, 0x9a, 0xaa,
+                0x31, 0x30, 0xdb, 0xc0, 0x80, 0xc2, 0x02, 0xa4, 0xce, 0xcd, 0x31, 0xbd, 0xd0, 0x44,
+                0x7f, 0x10, 0xa0, 0xee, 0x6e, 0xf6, 0x1d, 0xc7, 0x10, 0x97, 0x03, 0xa1, 0x4a, 0x77,
+                0xeb, 0x9e, 0xaf, 0x32, 0x1f, 0xdd, 0x97, 0x5a, 0xa4, 0xab, 0xd7, 0xad, 0xcc, 0x26,
+                0x17, 0x64, 0x85, 0x61, 0x51, 0xde, 0x3a, 0x7c, 0x58, 0x99, 0xea, 0xb5, 0xfe, 0x4f,
+                0xd5, 0x3a, 0xe0, 0xb7, 0xd1, 0xda, 0x8b, 0x43, 0xaf, 0xf1, 0x80, 0x43, 0xd9, 0xc3,
+                0x4b, 0x74, 0xa6, 0x20, 0x83, 0x97, 0x1c, 0xd9, 0xd5, 0x4c, 0x68, 0x7a, 0xd3, 0xfb,
+                0x58, 0x03, 0xfb, 0x2c, 0x88, 0x0c, 0x8b, 0xff, 0xc1, 0x61, 0x05, 0xc1,
+            ],
+            outputs: vec![
+                0x87, 0xf0, 0x1b, 0xdd, 0xb7, 0x7a, 0x9c, 0xd1, 0xdb, 0x64, 0x2e, 0x3d, 0xdb, 0x2b,
+                0x8a, 0xf3, 0xe2, 0x38, 0x57, 0x23, 0xd5, 0x13, 0x25, 0xc5, 0x6c, 0x29, 0xcd, 0xa9,
+                0xc6, 0x90, 0x8a, 0xd1, 0xca, 0x13, 0xc1, 0x40, 0x8c, 0x96, 0x64, 0x4f, 0xb0, 0x1f,
+                0x6e, 0x5d, 0x08, 0xfd, 0x5d, 0x4f, 0xdf, 0x2e, 0x4d, 0x20, 0x9f, 0x40, 0xcf, 0x56,
+                0xbf, 0xc1, 0x41, 0xc2, 0xe1, 0x62, 0xb6, 0x3e, 0x9a, 0x75, 0x91, 0xf0, 0x64, 0x25,
+                0x6c, 0xa1, 0x2e, 0xf1, 0xac, 0xd5, 0x41, 0x12, 0x38, 0x2c, 0x1a, 0x92, 0xd1, 0x71,
+                0xa8, 0x6b, 0xd6, 0xe2, 0x39, 0x48, 0x25, 0x63, 0xd3, 0x74, 0x2a, 0x0a, 0x09, 0x97,
+                0x09, 0x17, 0x22, 0xff, 0x0b, 0xc2, 0xc6, 0x0f, 0xa2, 0x6b, 0x35, 0x1e, 0x55, 0x4a,
+                0xa7, 0x17, 0x12, 0x0d, 0xe9, 0xf3, 0xd7, 0xd9, 0x33, 0xb5, 0xae, 0xcf, 0xbe, 0x22,
+                0x8e, 0x73, 0x44, 0x9c, 0x13, 0x5f, 0xa1, 0xf5, 0x7b, 0x72, 0x29, 0xc3, 0x48, 0x9c,
+                0x8c, 0x69, 0x26, 0xad, 0xdf, 0x43, 0x97, 0xd3, 0x83, 0x4b, 0xb0, 0x84, 0xbf, 0x77,
+                0x5f, 0xe6, 0xdd, 0xad, 0xc5, 0xc3, 0x06, 0x11, 0xc9, 0x23, 0xae, 0xac, 0x19, 0x34,
+                0x18, 0x53, 0x87, 0x3b, 0x36, 0xd0, 0xb0, 0xa2, 0xc6, 0x67, 0x98, 0x45, 0x9a, 0x54,
+                0x52, 0x43, 0xc8, 0x17, 0x8e, 0x34, 0xb5, 0x34, 0x0f, 0x63, 0xa3, 0x07, 0xfc, 0x43,
+                0xed, 0x8f, 0x19, 0x0b, 0xe1, 0x0a, 0x8f, 0xaa, 0x45, 0x8a, 0xcd, 0xc4, 0x4a, 0x32,
+                0x82, 0xea, 0x83, 0xe6, 0x3e, 0x6e, 0xb4, 0xaf, 0x54, 0x56, 0xfa, 0x6b, 0xf5, 0x65,
+                0xc4, 0x34, 0x7a, 0x45, 0x9a, 0x55, 0x1e, 0x57, 0x15, 0x5f, 0xa8, 0xa3, 0xd4, 0x80,
+                0xe3, 0x1a, 0xc2, 0x43, 0xbf, 0x85, 0x98, 0xc6, 0xa5, 0x77, 0xea, 0x2a, 0x36, 0x78,
+                0x74, 0x8c, 0x24, 0x23, 0xeb, 0xe0, 0xcc, 0xba, 0xea, 0xaf, 0x84, 0xc4, 0xa5, 0xd2,
+                0x4f, 0x54, 0x54, 0x5f, 0xd5, 0x94, 0xbc, 0x36, 0xf5, 0x11, 0x44, 0x4a, 0xb5, 0xed,
+                0x49, 0x31, 0x8b, 0xbe, 0x10, 0x9e, 0x06, 0xf1, 0x1a, 0xba, 0xec, 0x13, 0xe8, 0xa0,
+                0x58, 0xd0, 0xc4, 0x59, 0x40, 0xee, 0x38, 0xec, 0x5d, 0x32, 0x0b, 0x84, 0x2e, 0xa4,
+                0xbc, 0x18, 0x29, 0x35, 0xca, 0x85, 0xa8, 0x68, 0xc2, 0xb5, 0xa8, 0xca, 0xbf, 0x8e,
+                0x89, 0x4b, 0x13, 0xd8, 0x92, 0xb0, 0x38, 0x8f, 0xc9, 0xf9, 0x22, 0xc9, 0x34, 0xf2,
+                0x24, 0xa7, 0x12, 0x78, 0x98, 0xf5, 0x54, 0xd4, 0xec, 0xe0, 0xfa, 0x00, 0x27, 0xd7,
+                0xd3, 0x09, 0xfe, 0xd7, 0xa5, 0x02, 0x2b, 0xfd, 0xb5, 0x45, 0x61, 0x0e, 0x6a, 0xf7,
+                0x6b, 0xfb, 0xeb, 0x88, 0x97, 0x6d, 0xbb, 0xc8, 0x4a, 0x1e, 0x35, 0xb1, 0x74, 0x77,
+                0x44, 0x92, 0x50, 0x96, 0x81, 0xe6, 0x94, 0xee, 0xa2, 0x42, 0x55, 0x63, 0x83, 0xab,
+                0x68, 0xf1, 0x67, 0x88, 0xa6, 0xfb, 0x66, 0xc1, 0xae, 0x4b, 0x38, 0xba, 0x31, 0x49,
+                0x23, 0xc8, 0x8d, 0x66, 0x95, 0xe7, 0x90, 0x6c, 0xa9, 0xaf, 0xda, 0x6f, 0xa5, 0xf7,
+                0xdf, 0xbf, 0xd7, 0x1b, 0xfe, 0xee, 0x1b, 0x16, 0x40, 0xc8, 0x0d, 0x55, 0xf9, 0xd4,
+                0x78, 0xd1, 0xe0, 0x3c, 0x93, 0xd9, 0xa8, 0x14, 0x95, 0xaa, 0xc3, 0x83, 0x82, 0xa8,
+                0x5f, 0x60, 0x44, 0x2e, 0x27, 0xb8, 0x39, 0x3e, 0xc9, 0x62, 0x9d, 0xab, 0x3a, 0x38,
+                0x58, 0x9c, 0x9c, 0x87, 0x50, 0x69, 0xdf, 0x49, 0x1f, 0x74, 0x9e, 0x4a, 0xa2, 0x3e,
+                0x95, 0x25, 0x87, 0xe1, 0x34, 0x52, 0xb7, 0xb2, 0x3b, 0x49, 0xd4, 0xbc, 0x33, 0xd7,
+                0x35, 0x1d, 0x12, 0xa2, 0xe2, 0x9e, 0x83, 0x83, 0xa1, 0x64, 0x7c, 0x6d, 0x6d, 0xe7,
+                0x77, 0x6d, 0xc5, 0xc9, 0x56, 0xef, 0xad, 0xc7, 0xc0, 0x16, 0xc1, 0x62, 0xc0, 0xe7,
+                0xa6, 0x28, 0xfd, 0xff, 0x14, 0x66, 0xb0, 0x8a, 0x9b, 0xec, 0xc2, 0x52, 0x29, 0xf2,
+                0x99, 0x50, 0x45, 0xc2, 0x30, 0xbd, 0x72, 0x67, 0x40, 0x50, 0x01, 0xc7, 0x21, 0xe3,
+                0x1d, 0x57, 0x48, 0xfb, 0x23, 0x76, 0xda, 0xc0, 0xbc, 0xed, 0x3e, 0xba, 0xa2, 0x0e,
+                0x63, 0x9a, 0x5d, 0x7c, 0x1b, 0x83, 0x50, 0x1e, 0xcd, 0xe5, 0xba, 0xb0, 0x07, 0xd5,
+                0x8f, 0x6c, 0xa0, 0x90, 0x4f, 0x3a, 0x2a, 0xa7, 0x45, 0xb8, 0xe5, 0x45, 0x61, 0xdb,
+                0x69, 0xf7, 0x0c, 0x5c, 0x57, 0xd7, 0x53, 0x0d, 0xbf, 0x0c, 0x39, 0xb2, 0x5c, 0xe3,
+                0x92, 0x0b, 0x71, 0x62, 0xbd, 0xe1, 0x85, 0x0c, 0x33, 0xfb, 0xa9, 0xd0, 0xdb, 0x67,
+                0x0a, 0x2b, 0x1b, 0xbe, 0xe8, 0xe6, 0xad, 0x0d, 0x10, 0xb0, 0x71, 0x65, 0x03, 0x2a,
+                0xfc, 0x61, 0x63, 0x4f, 0x08, 0x6e, 0xee, 0xf1, 0x71, 0xf8, 0xa7, 0x3b, 0xd9, 0x7d,
+                0x4d, 0xf8, 0x5b, 0xe0, 0xc4, 0xee, 0x88, 0xd1, 0x20, 0xea, 0x32, 0x91, 0xea, 0x36,
+                0x3a, 0x85, 0x63, 0x68, 0x2d, 0xf8, 0x64, 0x8f, 0xe9, 0xc1, 0x05, 0xd0, 0x9e, 0xf3,
+                0x2d, 0xec, 0x84, 0x89, 0x0b, 0x55, 0xb6, 0xc8, 0xc6, 0x9b, 0x85, 0xbd, 0x1c, 0x99,
+                0x4b, 0xcc, 0x1e, 0x96, 0x9e, 0x38, 0xc1, 0xbc, 0xf4, 0x45, 0x78, 0x8b, 0x4b, 0xf3,
+                0xfa, 0x14, 0x37, 0x09, 0x86, 0x71, 0xb7, 0xc7, 0x5e, 0xde, 0x6a, 0xa2, 0xc6, 0x2a,
+                0x9e, 0xa2, 0xd7, 0xa3, 0xd3, 0xe1, 0x9c, 0xdd, 0x6a, 0x6d, 0xed, 0xd3, 0x42, 0x76,
+                0x34, 0x8d, 0x30, 0x2c, 0x96, 0xa3, 0xf6, 0xec, 0xaa, 0x2d, 0x43, 0x90, 0x59, 0x98,
+                0x56, 0x59, 0xfc, 0x60, 0x73, 0x53, 0x17, 0xf7, 0x75, 0x7c, 0x74, 0xbb, 0xef, 0x57,
+                0x5b, 0x0d, 0x17, 0x66, 0xf6, 0x78, 0x54, 0x36, 0x30, 0x00, 0xdf, 0x2f, 0xaf, 0xdf,
+                0xef, 0x4a, 0x31, 0x84, 0xd0, 0x6b, 0x7b, 0x3f, 0xc9, 0x75, 0x10, 0x12, 0xc4, 0x96,
+                0xe1, 0xf7, 0xc5, 0x11, 0xfc, 0x2e, 0x44, 0x3f, 0x8c, 0x47, 0xc6, 0x92, 0x2d, 0x9d,
+                0x1f, 0xe4, 0x8e, 0x06, 0xa8, 0x88, 0x2d, 0x52, 0xfe, 0xab, 0x25, 0x4d, 0xfb, 0x3d,
+                0xca, 0xcc, 0xaa, 0x52, 0xba, 0x00, 0x0f, 0xf6, 0xf5, 0xe7, 0xc6, 0xdb, 0x05, 0xeb,
+                0x70, 0x64, 0x78, 0xb7, 0xfc, 0x21, 0x62, 0x5f, 0x2b, 0x32, 0xb4, 0xcb, 0x61, 0x76,
+                0x06, 0xb4, 0x7d, 0x0d, 0x9a, 0x6e, 0x0f, 0xfc, 0xfe, 0x75, 0xa2, 0x6c, 0xb1, 0x47,
+                0x09, 0x81, 0xa5, 0xda, 0x29, 0x0b, 0xcf, 0x5c, 0x34, 0x6a, 0x74, 0xe8, 0xd6, 0xb3,
+                0x26, 0x75, 0x24, 0x1a, 0xac, 0x61, 0x62, 0x4a, 0xf8, 0xbf, 0xe5, 0x3f, 0xf5, 0x30,
+                0x79, 0x2d, 0x79, 0x49, 0xff, 0xe6, 0x70, 0xa5, 0x83, 0xb3, 0xe7, 0xe2, 0x56, 0xe9,
+                0xc1, 0xa9, 0x18, 0x9a, 0x28, 0x96, 0x62, 0xcb, 0x6c, 0xf7, 0x48, 0xdd, 0xae, 0xb4,
+                0xe5, 0x2b, 0xd1, 0xaa, 0xf6, 0x10, 0xcc, 0x44, 0xe0, 0xbb, 0xa5, 0x04, 0x07, 0xf8,
+                0xd6, 0x31, 0x01, 0xc2, 0x3b, 0x70, 0xaa, 0x87, 0x9c, 0x53, 0xcf, 0x59, 0x0b, 0xea,
+                0xaa, 0x60, 0xe5, 0x7a, 0x44, 0x48, 0x4b, 0x24, 0x0e, 0x7a,
+            ],
+            public_outputs: vec![
+                (
+                    [
+                        0xea, 0x7a, 0x85, 0xc6, 0xdc, 0x09, 0x41, 0x72, 0x7d, 0xb6, 0xd6, 0xec, 0xf8, 0xfd,
+                        0xa8, 0xd1, 0x66, 0x59, 0x73, 0x86,
+                    ],
+                    [
+                        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                        0xb2, 0xd0, 0x5e, 0x00,
+                    ],
+                ),
+                (
+                    [
+                        0x7f, 0x6b, 0xa9, 0xf6, 0xc9, 0x32, 0xfc, 0x52, 0x51, 0x1a, 0x52, 0x0b, 0x73, 0x49,
+                        0x9b, 0x8f, 0xdb, 0x52, 0x1b, 0xbe,
+                    ],
+                    [
+                        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                        0x77, 0x35, 0x94, 0x00,
+                    ],
+                ),
+            ],
+            value_balance: 102,
+            sighash: [
+                0x4d, 0x7e, 0x0d, 0xe1, 0x90, 0x53, 0x03, 0xe8, 0xb4, 0xea, 0x60, 0xb6, 0xf1, 0x18,
+                0xb3, 0x5f, 0x88, 0x41, 0x83, 0x39, 0xaf, 0xfc, 0x44, 0xe8, 0xf2, 0xeb, 0xc1, 0x8e,
+                0x80, 0x56, 0x1d, 0x8d,
+            ],
+        },
+    ]
+}
@@ -11,7 +11,9 @@ mod sighash;
 #[cfg(test)]
 mod tests;
 
-pub use self::sighash::{signature_hash, signature_hash_data, SIGHASH_ALL};
+pub use self::sighash::{
+    origo_signature_hash, signature_hash, signature_hash_data, OrigoSigHashData, SIGHASH_ALL,
+};
 
 use self::components::{Amount, JSDescription, OutputDescription, SpendDescription, TxIn, TxOut};
 
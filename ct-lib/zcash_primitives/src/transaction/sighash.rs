@@ -3,7 +3,7 @@ use byteorder::{LittleEndian, WriteBytesExt};
 use ff::{PrimeField, PrimeFieldRepr};
 
 use super::{
-    components::{Amount, Script, TxOut},
+    components::{Amount, OutputDescription, Script, SpendDescription, TxOut},
     Transaction, TransactionData, OVERWINTER_VERSION_GROUP_ID, SAPLING_TX_VERSION,
     SAPLING_VERSION_GROUP_ID,
 };
@@ -16,6 +16,12 @@ const ZCASH_JOINSPLITS_HASH_PERSONALIZATION: &'static [u8; 16] = b"ZcashJSplitsH
 const ZCASH_SHIELDED_SPENDS_HASH_PERSONALIZATION: &'static [u8; 16] = b"ZcashSSpendsHash";
 const ZCASH_SHIELDED_OUTPUTS_HASH_PERSONALIZATION: &'static [u8; 16] = b"ZcashSOutputHash";
 
+const ORIGO_SIGHASH_PERSONALIZATION_PREFIX: &'static [u8; 12] = b"OrigoSigHash";
+const ORIGO_DATA_HASH_PERSONALIZATION: &'static [u8; 16] = b"OrigoTxData_Hash";
+const ORIGO_SHIELDED_SPENDS_HASH_PERSONALIZATION: &'static [u8; 16] = b"OrigoSSpendsHash";
+const ORIGO_SHIELDED_OUTPUTS_HASH_PERSONALIZATION: &'static [u8; 16] = b"OrigoSOutputHash";
+const ORIGO_PUBLIC_OUTPUTS_HASH_PERSONALIZATION: &'static [u8; 16] = b"OrigoPOutputHash";
+
 pub const SIGHASH_ALL: u32 = 1;
 const SIGHASH_NONE: u32 = 2;
 const SIGHASH_SINGLE: u32 = 3;
@@ -29,6 +35,13 @@ macro_rules! update_u32 {
     };
 }
 
+macro_rules! update_u64 {
+    ($h:expr, $value:expr, $tmp:expr) => {
+        (&mut $tmp[..8]).write_u64::<LittleEndian>($value).unwrap();
+        $h.update(&$tmp[..8]);
+    };
+}
+
 macro_rules! update_i64 {
     ($h:expr, $value:expr, $tmp:expr) => {
         (&mut $tmp[..8]).write_i64::<LittleEndian>($value).unwrap();
@@ -232,3 +245,104 @@ pub fn signature_hash(
 ) -> Vec<u8> {
     signature_hash_data(tx, consensus_branch_id, hash_type, transparent_input)
 }
+
+/// The fields of an Origo private transaction committed to by its signature hash.
+/// Public amounts are given as 32-byte big-endian integers.
+pub struct OrigoSigHashData<'a> {
+    pub chain_id: u64,
+    /// The RLP encoding of the transaction action.
+    pub action: &'a [u8],
+    pub nonce: [u8; 32],
+    pub gas_price: [u8; 32],
+    pub gas: [u8; 32],
+    pub value: [u8; 32],
+    pub data: &'a [u8],
+    pub shielded_spends: &'a [SpendDescription],
+    pub shielded_outputs: &'a [OutputDescription],
+    /// The address and the value of every public output.
+    pub public_outputs: &'a [([u8; 20], [u8; 32])],
+    pub value_balance: i64,
+}
+
+fn origo_data_hash(data: &[u8]) -> Vec<u8> {
+    let mut h = Blake2b::with_params(32, &[], &[], ORIGO_DATA_HASH_PERSONALIZATION);
+    h.update(data);
+    h.finalize().as_ref().to_vec()
+}
+
+fn origo_shielded_spends_hash(shielded_spends: &[SpendDescription]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(shielded_spends.len() * 320);
+    for s_spend in shielded_spends {
+        s_spend.cv.write(&mut data).unwrap();
+        s_spend.anchor.into_repr().write_le(&mut data).unwrap();
+        data.extend_from_slice(&s_spend.nullifier);
+        s_spend.rk.write(&mut data).unwrap();
+        data.extend_from_slice(&s_spend.zkproof);
+    }
+    let mut h = Blake2b::with_params(32, &[], &[], ORIGO_SHIELDED_SPENDS_HASH_PERSONALIZATION);
+    h.update(&data);
+    h.finalize().as_ref().to_vec()
+}
+
+fn origo_shielded_outputs_hash(shielded_outputs: &[OutputDescription]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(shielded_outputs.len() * 948);
+    for s_out in shielded_outputs {
+        s_out.write(&mut data).unwrap();
+    }
+    let mut h = Blake2b::with_params(32, &[], &[], ORIGO_SHIELDED_OUTPUTS_HASH_PERSONALIZATION);
+    h.update(&data);
+    h.finalize().as_ref().to_vec()
+}
+
+fn origo_public_outputs_hash(public_outputs: &[([u8; 20], [u8; 32])]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(public_outputs.len() * 52);
+    for (address, value) in public_outputs {
+        data.extend_from_slice(address);
+        data.extend_from_slice(value);
+    }
+    let mut h = Blake2b::with_params(32, &[], &[], ORIGO_PUBLIC_OUTPUTS_HASH_PERSONALIZATION);
+    h.update(&data);
+    h.finalize().as_ref().to_vec()
+}
+
+/// The hash signed by the spend authorization signatures and the binding signature
+/// of an Origo private transaction, following ZIP 243 with the chain id in place of
+/// the consensus branch id. The spends are committed to without their signatures.
+pub fn origo_signature_hash(tx: &OrigoSigHashData) -> [u8; 32] {
+    let mut personal = [0; 16];
+    (&mut personal[..12]).copy_from_slice(ORIGO_SIGHASH_PERSONALIZATION_PREFIX);
+    (&mut personal[12..])
+        .write_u32::<LittleEndian>(tx.chain_id as u32)
+        .unwrap();
+
+    let mut h = Blake2b::with_params(32, &[], &[], &personal);
+    let mut tmp = [0; 8];
+
+    update_u64!(h, tx.chain_id, tmp);
+    h.update(tx.action);
+    h.update(&tx.nonce);
+    h.update(&tx.gas_price);
+    h.update(&tx.gas);
+    h.update(&tx.value);
+    update_hash!(h, !tx.data.is_empty(), origo_data_hash(tx.data));
+    update_hash!(
+        h,
+        !tx.shielded_spends.is_empty(),
+        origo_shielded_spends_hash(tx.shielded_spends)
+    );
+    update_hash!(
+        h,
+        !tx.shielded_outputs.is_empty(),
+        origo_shielded_outputs_hash(tx.shielded_outputs)
+    );
+    update_hash!(
+        h,
+        !tx.public_outputs.is_empty(),
+        origo_public_outputs_hash(tx.public_outputs)
+    );
+    update_i64!(h, tx.value_balance, tmp);
+
+    let mut sighash = [0; 32];
+    sighash.copy_from_slice(h.finalize().as_ref());
+    sighash
+}
@@ -3,8 +3,8 @@ use rand::{thread_rng, Rng};
 use sapling_crypto::{jubjub::FixedGenerators, redjubjub::PrivateKey};
 
 use super::{
-    components::{Amount, Script},
-    sighash::signature_hash,
+    components::{Amount, OutputDescription, Script, SpendDescription},
+    sighash::{origo_signature_hash, signature_hash, OrigoSigHashData},
     Transaction, TransactionData,
 };
 use JUBJUB;
@@ -5420,3 +5420,51 @@ fn zip_0243() {
         );
     }
 }
+
+#[test]
+fn origo_sighash() {
+    let test_vectors = ::test_vectors::origo_sighash::make_test_vectors();
+
+    for tv in test_vectors {
+        let mut reader = &tv.spends[..];
+        let mut shielded_spends = vec![];
+        while !reader.is_empty() {
+            shielded_spends.push(SpendDescription::read(&mut reader).unwrap());
+        }
+        let mut reader = &tv.outputs[..];
+        let mut shielded_outputs = vec![];
+        while !reader.is_empty() {
+            shielded_outputs.push(OutputDescription::read(&mut reader).unwrap());
+        }
+
+        let data = OrigoSigHashData {
+            chain_id: tv.chain_id,
+            action: &tv.action,
+            nonce: tv.nonce,
+            gas_price: tv.gas_price,
+            gas: tv.gas,
+            value: tv.value,
+            data: &tv.data,
+            shielded_spends: &shielded_spends,
+            shielded_outputs: &shielded_outputs,
+            public_outputs: &tv.public_outputs,
+            value_balance: tv.value_balance,
+        };
+        assert_eq!(origo_signature_hash(&data), tv.sighash);
+
+        // The spend authorization signatures are not committed to.
+        let unsigned_spends = shielded_spends
+            .iter()
+            .cloned()
+            .map(|mut spend| {
+                spend.spend_auth_sig = None;
+                spend
+            })
+            .collect::<Vec<_>>();
+        let data = OrigoSigHashData {
+            shielded_spends: &unsigned_spends,
+            ..data
+        };
+        assert_eq!(origo_signature_hash(&data), tv.sighash);
+    }
+}
@@ -0,0 +1,112 @@
+//! `origo-prover`: proves the shielded transactions of remote wallets.
+//!
+//! Usage: `origo-prover --params-dir <DIR> [--listen <ADDRESS>]`
+//!
+//! The service loads `sapling-spend.params` and `sapling-output.params` from `<DIR>`, then
+//! serves JSON-RPC requests, one per line, on `<ADDRESS>`: `unix:<path>` or
+//! `[tcp:]<host>:<port>`, by default `127.0.0.1:8560`. It logs at the `info` level unless
+//! `RUST_LOG` says otherwise.
+
+extern crate env_logger;
+#[macro_use]
+extern crate log;
+extern crate zcash_proofs;
+
+use std::env;
+use std::fs;
+use std::io::BufReader;
+use std::net::TcpListener;
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::process;
+use std::sync::Arc;
+use std::thread;
+
+use zcash_proofs::prover::{serve_connection, LocalTxProver, ProverAddress, TxProver};
+
+const DEFAULT_LISTEN: &str = "127.0.0.1:8560";
+
+const USAGE: &str = "Usage: origo-prover --params-dir <DIR> [--listen <ADDRESS>]
+
+Options:
+    --params-dir <DIR>    Directory of sapling-spend.params and sapling-output.params.
+    --listen <ADDRESS>    unix:<path> or [tcp:]<host>:<port> [default: 127.0.0.1:8560].";
+
+fn main() {
+    initialize_logger();
+    if let Err(e) = run() {
+        error!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn initialize_logger() {
+    let mut builder = env_logger::Builder::new();
+    builder.filter(None, log::LevelFilter::Info);
+    if let Ok(filters) = env::var("RUST_LOG") {
+        builder.parse(&filters);
+    }
+    builder.init();
+}
+
+fn run() -> Result<(), String> {
+    let mut params_dir = None;
+    let mut listen = String::from(DEFAULT_LISTEN);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--params-dir" => params_dir = args.next().map(PathBuf::from),
+            "--listen" => listen = args.next().ok_or_else(|| String::from(USAGE))?,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ => return Err(String::from(USAGE)),
+        }
+    }
+    let params_dir = params_dir.ok_or_else(|| String::from(USAGE))?;
+    let address = listen.parse::<ProverAddress>()?;
+
+    let prover: Arc<dyn TxProver> = Arc::new(
+        LocalTxProver::load(
+            &params_dir.join("sapling-spend.params"),
+            &params_dir.join("sapling-output.params"),
+        )
+        .map_err(|e| format!("Failed to load the Sapling parameters from {}: {}", params_dir.display(), e))?,
+    );
+    info!("Proving on {}", address);
+
+    match address {
+        ProverAddress::Tcp(ref address) => {
+            let listener = TcpListener::bind(address.as_str()).map_err(|e| format!("Failed to listen on {}: {}", address, e))?;
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let prover = prover.clone();
+                        thread::spawn(move || serve_connection(&*prover, BufReader::new(&stream), &stream));
+                    }
+                    Err(e) => warn!("Failed to accept a connection: {}", e),
+                }
+            }
+        }
+        #[cfg(unix)]
+        ProverAddress::Unix(ref path) => {
+            // A socket left by a previous run would make binding fail.
+            let _ = fs::remove_file(path);
+            let listener = UnixListener::bind(path).map_err(|e| format!("Failed to listen on {}: {}", path.display(), e))?;
+            for stream in listener.incoming() {
+                match stream {
+                    Ok(stream) => {
+                        let prover = prover.clone();
+                        thread::spawn(move || serve_connection(&*prover, BufReader::new(&stream), &stream));
+                    }
+                    Err(e) => warn!("Failed to accept a connection: {}", e),
+                }
+            }
+        }
+        #[cfg(not(unix))]
+        ProverAddress::Unix(_) => return Err(String::from("Unix sockets are not supported on this platform.")),
+    }
+    Ok(())
+}
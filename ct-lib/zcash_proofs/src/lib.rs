@@ -3,9 +3,12 @@ extern crate blake2_rfc;
 extern crate byteorder;
 extern crate directories;
 extern crate ff;
+extern crate hex;
 extern crate pairing;
 extern crate rand;
 extern crate sapling_crypto;
+#[macro_use]
+extern crate serde_json;
 extern crate zcash_primitives;
 
 use bellman::groth16::{prepare_verifying_key, Parameters, PreparedVerifyingKey, VerifyingKey};
@@ -34,6 +37,37 @@ pub fn load_single_parameters(
     let vk = prepare_verifying_key(&params.vk);
     (params, vk)
 }
+
+/// Loads the parameters in the file at `path`, failing instead of panicking if the file
+/// is missing, can't be deserialized or its BLAKE2b hash is not `hash`.
+pub fn load_verified_parameters(path: &Path, hash: &str) -> io::Result<Parameters<Bls12>> {
+    let fs = File::open(path)?;
+    let mut fs = hashreader::HashReader::new(BufReader::with_capacity(1024 * 1024, fs));
+    let params = Parameters::<Bls12>::read(&mut fs, false)?;
+    io::copy(&mut fs, &mut io::sink())?;
+    check_hash(fs.into_hash(), hash)?;
+    Ok(params)
+}
+
+/// Checks that the BLAKE2b hash of the parameters file at `path` is `hash`,
+/// without deserializing it.
+pub fn verify_parameters_file(path: &Path, hash: &str) -> io::Result<()> {
+    let fs = File::open(path)?;
+    let mut fs = hashreader::HashReader::new(BufReader::with_capacity(1024 * 1024, fs));
+    io::copy(&mut fs, &mut io::sink())?;
+    check_hash(fs.into_hash(), hash)
+}
+
+fn check_hash(found: String, expected: &str) -> io::Result<()> {
+    if found != expected {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("BLAKE2b hash is {}, expected {}", found, expected),
+        ));
+    }
+    Ok(())
+}
+
 pub fn load_parameters_from_bytes(bytes: &[u8])
     -> (Parameters<Bls12>, PreparedVerifyingKey<Bls12>){
     let params = Parameters::<Bls12>::read(bytes, false)
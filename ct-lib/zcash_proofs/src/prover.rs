@@ -1,26 +1,104 @@
 //! Abstractions over the proving system and parameters for ease of use.
+//!
+//! A [`TxProver`] creates the proofs of the shielded transactions, either in-process
+//! with [`LocalTxProver`], or on another machine running the `origo-prover` service
+//! with [`RemoteTxProver`]. The service speaks JSON-RPC, one request per line, over a
+//! Unix socket or TCP.
+//!
+//! The proof generation key of the spent notes is sent to the service, which can't spend
+//! them without the spend authorizing key but learns the nullifiers and values of the
+//! transactions: only use a service on a trusted machine.
 
-use bellman::groth16::{Parameters, PreparedVerifyingKey};
+use bellman::groth16::{prepare_verifying_key, Parameters, PreparedVerifyingKey};
 use directories::BaseDirs;
-use pairing::bls12_381::{Bls12, Fr};
+use ff::{Field, PrimeField, PrimeFieldRepr};
+use pairing::bls12_381::{Bls12, Fr, FrRepr};
 use sapling_crypto::{
-    jubjub::{edwards, fs::Fs, Unknown},
+    jubjub::{
+        edwards,
+        fs::{Fs, FsRepr},
+        Unknown,
+    },
     primitives::{Diversifier, PaymentAddress, ProofGenerationKey},
-    redjubjub::{PublicKey, Signature},
+    redjubjub::PublicKey,
 };
-use std::path::Path;
+use serde_json::{self, Value};
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 use zcash_primitives::{
-    merkle_tree::CommitmentTreeWitness, prover::TxProver, sapling::Node,
-    transaction::components::GROTH_PROOF_SIZE, JUBJUB,
+    merkle_tree::{CommitmentTreeWitness, Hashable},
+    sapling::Node,
+    transaction::components::GROTH_PROOF_SIZE,
+    JUBJUB,
 };
 
-use crate::{load_parameters, sapling::SaplingProvingContext};
+use crate::{load_parameters, load_verified_parameters, sapling::SaplingProvingContext};
 
 const SAPLING_SPEND_HASH: &str = "8270785a1a0d0bc77196f000ee6d221c9c9894f55307bd9357c3f0105d31ca63991ab91324160d8f53e2bbd3c2633a6eb8bdf5205d822e7f3f73edac51b2b70c";
 const SAPLING_OUTPUT_HASH: &str = "657e3d38dbb5cb5e7dd2970e8b03d69b4787dd907285b5a7f0790dcc8072f60bf593b32cc2d1c030e00ff5ae64bf84c5c3beb84ddc841d48264b4a171744d028";
 
-/// An implementation of [`TxProver`] using Sapling Spend and Output parameters from
-/// locally-accessible paths.
+/// JSON-RPC method proving a spend.
+pub const SPEND_PROOF_METHOD: &str = "prover_spendProof";
+/// JSON-RPC method proving an output.
+pub const OUTPUT_PROOF_METHOD: &str = "prover_outputProof";
+/// Default time to connect to a prover service, and for each read or write of a proof exchange.
+pub const DEFAULT_PROVER_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The proof of a Sapling spend, with its value commitment and re-randomized key.
+#[derive(Clone)]
+pub struct SpendProof {
+    pub zkproof: [u8; GROTH_PROOF_SIZE],
+    pub cv: edwards::Point<Bls12, Unknown>,
+    pub rk: PublicKey<Bls12>,
+    /// Randomness of `cv`, summed by the transaction builder to sign the value balance.
+    pub rcv: Fs,
+}
+
+/// The proof of a Sapling output, with its value commitment.
+#[derive(Clone)]
+pub struct OutputProof {
+    pub zkproof: [u8; GROTH_PROOF_SIZE],
+    pub cv: edwards::Point<Bls12, Unknown>,
+    /// Randomness of `cv`, summed by the transaction builder to sign the value balance.
+    pub rcv: Fs,
+}
+
+/// Interface for creating the zero-knowledge proofs of shielded transactions.
+///
+/// A prover is shared by all the transactions: it returns the randomness of the value
+/// commitment of each proof, and the transaction builder creates the binding signature.
+pub trait TxProver: Send + Sync {
+    /// Create the value commitment, re-randomized key, and proof for a Sapling spend.
+    fn spend_proof(
+        &self,
+        proof_generation_key: ProofGenerationKey<Bls12>,
+        diversifier: Diversifier,
+        rcm: Fs,
+        ar: Fs,
+        value: u64,
+        anchor: Fr,
+        witness: CommitmentTreeWitness<Node>,
+    ) -> Result<SpendProof, String>;
+
+    /// Create the value commitment and proof for a Sapling output.
+    fn output_proof(
+        &self,
+        esk: Fs,
+        payment_address: PaymentAddress<Bls12>,
+        rcm: Fs,
+        value: u64,
+    ) -> Result<OutputProof, String>;
+}
+
+/// An implementation of [`TxProver`] proving in-process with Sapling Spend and Output
+/// parameters held in memory.
 pub struct LocalTxProver {
     spend_params: Parameters<Bls12>,
     spend_vk: PreparedVerifyingKey<Bls12>,
@@ -62,6 +140,24 @@ impl LocalTxProver {
         }
     }
 
+    /// Creates a `LocalTxProver` using parameters from the given local paths, failing
+    /// if they are missing or don't have the expected hashes.
+    pub fn load(spend_path: &Path, output_path: &Path) -> io::Result<Self> {
+        let spend_params = load_verified_parameters(spend_path, SAPLING_SPEND_HASH)?;
+        let output_params = load_verified_parameters(output_path, SAPLING_OUTPUT_HASH)?;
+        Ok(LocalTxProver::from_parameters(spend_params, output_params))
+    }
+
+    /// Creates a `LocalTxProver` from parameters already loaded.
+    pub fn from_parameters(spend_params: Parameters<Bls12>, output_params: Parameters<Bls12>) -> Self {
+        let spend_vk = prepare_verifying_key(&spend_params.vk);
+        LocalTxProver {
+            spend_params,
+            spend_vk,
+            output_params,
+        }
+    }
+
     /// Attempts to create a `LocalTxProver` using parameters from the default local
     /// location.
     ///
@@ -109,15 +205,8 @@ impl LocalTxProver {
 }
 
 impl TxProver for LocalTxProver {
-    type SaplingProvingContext = SaplingProvingContext;
-
-    fn new_sapling_proving_context(&self) -> Self::SaplingProvingContext {
-        SaplingProvingContext::new()
-    }
-
     fn spend_proof(
         &self,
-        ctx: &mut Self::SaplingProvingContext,
         proof_generation_key: ProofGenerationKey<Bls12>,
         diversifier: Diversifier,
         rcm: Fs,
@@ -125,43 +214,45 @@ impl TxProver for LocalTxProver {
         value: u64,
         anchor: Fr,
         witness: CommitmentTreeWitness<Node>,
-    ) -> Result<
-        (
-            [u8; GROTH_PROOF_SIZE],
-            edwards::Point<Bls12, Unknown>,
-            PublicKey<Bls12>,
-        ),
-        (),
-    > {
-        let (proof, cv, rk) = ctx.spend_proof(
-            proof_generation_key,
-            diversifier,
-            rcm,
-            ar,
-            value,
-            anchor,
-            witness,
-            &self.spend_params,
-            &self.spend_vk,
-            &JUBJUB,
-        )?;
+    ) -> Result<SpendProof, String> {
+        let mut ctx = SaplingProvingContext::new();
+        let (proof, cv, rk) = ctx
+            .spend_proof(
+                proof_generation_key,
+                diversifier,
+                rcm,
+                ar,
+                value,
+                anchor,
+                witness,
+                &self.spend_params,
+                &self.spend_vk,
+                &JUBJUB,
+            )
+            .map_err(|()| String::from("Spend proof failed."))?;
 
         let mut zkproof = [0u8; GROTH_PROOF_SIZE];
         proof
             .write(&mut zkproof[..])
             .expect("should be able to serialize a proof");
 
-        Ok((zkproof, cv, rk))
+        // The fresh context only accumulated the randomness of this spend.
+        Ok(SpendProof {
+            zkproof,
+            cv,
+            rk,
+            rcv: ctx.bsk(),
+        })
     }
 
     fn output_proof(
         &self,
-        ctx: &mut Self::SaplingProvingContext,
         esk: Fs,
         payment_address: PaymentAddress<Bls12>,
         rcm: Fs,
         value: u64,
-    ) -> ([u8; GROTH_PROOF_SIZE], edwards::Point<Bls12, Unknown>) {
+    ) -> Result<OutputProof, String> {
+        let mut ctx = SaplingProvingContext::new();
         let (proof, cv) = ctx.output_proof(
             esk,
             payment_address,
@@ -176,15 +267,633 @@ impl TxProver for LocalTxProver {
             .write(&mut zkproof[..])
             .expect("should be able to serialize a proof");
 
-        (zkproof, cv)
+        // Outputs subtract their randomness from the context.
+        let mut rcv = ctx.bsk();
+        rcv.negate();
+        Ok(OutputProof { zkproof, cv, rcv })
+    }
+}
+
+/// Address of an `origo-prover` service: `unix:<path>` or `[tcp:]<host>:<port>`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProverAddress {
+    Unix(PathBuf),
+    Tcp(String),
+}
+
+impl FromStr for ProverAddress {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("unix:") {
+            Ok(ProverAddress::Unix(PathBuf::from(&s[5..])))
+        } else {
+            let address = if s.starts_with("tcp:") { &s[4..] } else { s };
+            if !address.contains(':') {
+                return Err(format!(
+                    "Invalid prover address {}, expected unix:<path> or <host>:<port>.",
+                    s
+                ));
+            }
+            Ok(ProverAddress::Tcp(address.to_owned()))
+        }
+    }
+}
+
+impl fmt::Display for ProverAddress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ProverAddress::Unix(ref path) => write!(f, "unix:{}", path.display()),
+            ProverAddress::Tcp(ref address) => write!(f, "tcp:{}", address),
+        }
+    }
+}
+
+/// An implementation of [`TxProver`] delegating the proofs to an `origo-prover` service.
+/// Each proof opens a new connection, so the service may be restarted between proofs.
+pub struct RemoteTxProver {
+    address: ProverAddress,
+    timeout: Duration,
+    next_id: AtomicUsize,
+}
+
+impl RemoteTxProver {
+    pub fn new(address: ProverAddress) -> Self {
+        RemoteTxProver {
+            address,
+            timeout: DEFAULT_PROVER_TIMEOUT,
+            next_id: AtomicUsize::new(1),
+        }
+    }
+
+    /// Sets the time to connect to the service and for each read or write of a proof
+    /// exchange, after which the proof fails.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn address(&self) -> &ProverAddress {
+        &self.address
     }
 
-    fn binding_sig(
+    fn call(&self, method: &str, params: Value) -> Result<Value, String> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": [params],
+        });
+        let response = self
+            .exchange(&request)
+            .map_err(|e| format!("Prover {} failed: {}.", self.address, e))?;
+        if let Some(error) = response.get("error") {
+            return Err(format!(
+                "Prover {} failed: {}",
+                self.address,
+                error["message"].as_str().unwrap_or("unknown error")
+            ));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| format!("Prover {} sent no result.", self.address))
+    }
+
+    fn exchange(&self, request: &Value) -> io::Result<Value> {
+        let result = match self.address {
+            ProverAddress::Tcp(ref address) => {
+                let socket_address = address.as_str().to_socket_addrs()?.next().ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::InvalidInput, "the address resolves to nothing")
+                })?;
+                let stream = TcpStream::connect_timeout(&socket_address, self.timeout)?;
+                stream.set_read_timeout(Some(self.timeout))?;
+                stream.set_write_timeout(Some(self.timeout))?;
+                exchange(BufReader::new(&stream), &stream, request)
+            }
+            #[cfg(unix)]
+            ProverAddress::Unix(ref path) => {
+                let stream = UnixStream::connect(path)?;
+                stream.set_read_timeout(Some(self.timeout))?;
+                stream.set_write_timeout(Some(self.timeout))?;
+                exchange(BufReader::new(&stream), &stream, request)
+            }
+            #[cfg(not(unix))]
+            ProverAddress::Unix(_) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Unix sockets are not supported on this platform",
+            )),
+        };
+        // A read or write timing out is reported as would block on unix.
+        result.map_err(|e| match e.kind() {
+            io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("no response within {:?}", self.timeout),
+            ),
+            _ => e,
+        })
+    }
+}
+
+fn exchange<R: BufRead, W: Write>(mut reader: R, mut writer: W, request: &Value) -> io::Result<Value> {
+    writeln!(writer, "{}", request)?;
+    writer.flush()?;
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "connection closed without a response",
+        ));
+    }
+    serde_json::from_str(&line).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+impl TxProver for RemoteTxProver {
+    fn spend_proof(
         &self,
-        ctx: &mut Self::SaplingProvingContext,
-        value_balance: i64,
-        sighash: &[u8; 32],
-    ) -> Result<Signature, ()> {
-        ctx.binding_sig(value_balance, sighash, &JUBJUB)
+        proof_generation_key: ProofGenerationKey<Bls12>,
+        diversifier: Diversifier,
+        rcm: Fs,
+        ar: Fs,
+        value: u64,
+        anchor: Fr,
+        witness: CommitmentTreeWitness<Node>,
+    ) -> Result<SpendProof, String> {
+        let params = json!({
+            "ak": point_to_hex(&proof_generation_key.ak),
+            "nsk": fs_to_hex(&proof_generation_key.nsk),
+            "diversifier": hex::encode(&diversifier.0[..]),
+            "rcm": fs_to_hex(&rcm),
+            "ar": fs_to_hex(&ar),
+            "value": value,
+            "anchor": fr_to_hex(&anchor),
+            "witness": witness_to_json(&witness),
+        });
+        let result = self.call(SPEND_PROOF_METHOD, params)?;
+        let proof = (|| -> Result<SpendProof, String> {
+            Ok(SpendProof {
+                zkproof: zkproof_from_json(&result["zkproof"])?,
+                cv: point_from_json(&result["cv"], "cv")?,
+                rk: PublicKey(point_from_json(&result["rk"], "rk")?),
+                rcv: fs_from_json(&result["rcv"], "rcv")?,
+            })
+        })();
+        proof.map_err(|e| format!("Prover {} sent an invalid spend proof: {}", self.address, e))
+    }
+
+    fn output_proof(
+        &self,
+        esk: Fs,
+        payment_address: PaymentAddress<Bls12>,
+        rcm: Fs,
+        value: u64,
+    ) -> Result<OutputProof, String> {
+        let params = json!({
+            "esk": fs_to_hex(&esk),
+            "diversifier": hex::encode(&payment_address.diversifier.0[..]),
+            "pkD": point_to_hex(&payment_address.pk_d),
+            "rcm": fs_to_hex(&rcm),
+            "value": value,
+        });
+        let result = self.call(OUTPUT_PROOF_METHOD, params)?;
+        let proof = (|| -> Result<OutputProof, String> {
+            Ok(OutputProof {
+                zkproof: zkproof_from_json(&result["zkproof"])?,
+                cv: point_from_json(&result["cv"], "cv")?,
+                rcv: fs_from_json(&result["rcv"], "rcv")?,
+            })
+        })();
+        proof.map_err(|e| format!("Prover {} sent an invalid output proof: {}", self.address, e))
+    }
+}
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const PROVING_ERROR: i64 = -32000;
+
+/// Serves the JSON-RPC requests of a connection to an `origo-prover` service with
+/// `prover`, one request per line, until the client closes the connection.
+pub fn serve_connection<R: BufRead, W: Write>(
+    prover: &dyn TxProver,
+    reader: R,
+    mut writer: W,
+) -> io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(writer, "{}", handle_request(prover, &line))?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+fn handle_request(prover: &dyn TxProver, line: &str) -> Value {
+    let request: Value = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return error_response(Value::Null, PARSE_ERROR, e.to_string()),
+    };
+    let id = request["id"].clone();
+    let params = &request["params"][0];
+    let result = match request["method"].as_str() {
+        Some(SPEND_PROOF_METHOD) => match spend_request_from_json(params) {
+            Ok((pgk, diversifier, rcm, ar, value, anchor, witness)) => prover
+                .spend_proof(pgk, diversifier, rcm, ar, value, anchor, witness)
+                .map(|proof| {
+                    json!({
+                        "zkproof": hex::encode(&proof.zkproof[..]),
+                        "cv": point_to_hex(&proof.cv),
+                        "rk": point_to_hex(&proof.rk.0),
+                        "rcv": fs_to_hex(&proof.rcv),
+                    })
+                }),
+            Err(e) => return error_response(id, INVALID_PARAMS, e),
+        },
+        Some(OUTPUT_PROOF_METHOD) => match output_request_from_json(params) {
+            Ok((esk, payment_address, rcm, value)) => prover
+                .output_proof(esk, payment_address, rcm, value)
+                .map(|proof| {
+                    json!({
+                        "zkproof": hex::encode(&proof.zkproof[..]),
+                        "cv": point_to_hex(&proof.cv),
+                        "rcv": fs_to_hex(&proof.rcv),
+                    })
+                }),
+            Err(e) => return error_response(id, INVALID_PARAMS, e),
+        },
+        _ => return error_response(id, METHOD_NOT_FOUND, String::from("Method not found")),
+    };
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(e) => error_response(id, PROVING_ERROR, e),
+    }
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+type SpendRequest = (
+    ProofGenerationKey<Bls12>,
+    Diversifier,
+    Fs,
+    Fs,
+    u64,
+    Fr,
+    CommitmentTreeWitness<Node>,
+);
+
+fn spend_request_from_json(params: &Value) -> Result<SpendRequest, String> {
+    let ak = point_from_json(&params["ak"], "ak")?
+        .as_prime_order(&JUBJUB)
+        .ok_or_else(|| String::from("Invalid ak"))?;
+    Ok((
+        ProofGenerationKey {
+            ak,
+            nsk: fs_from_json(&params["nsk"], "nsk")?,
+        },
+        diversifier_from_json(&params["diversifier"])?,
+        fs_from_json(&params["rcm"], "rcm")?,
+        fs_from_json(&params["ar"], "ar")?,
+        u64_from_json(&params["value"], "value")?,
+        fr_from_json(&params["anchor"], "anchor")?,
+        witness_from_json(&params["witness"])?,
+    ))
+}
+
+fn output_request_from_json(params: &Value) -> Result<(Fs, PaymentAddress<Bls12>, Fs, u64), String> {
+    let pk_d = point_from_json(&params["pkD"], "pkD")?
+        .as_prime_order(&JUBJUB)
+        .ok_or_else(|| String::from("Invalid pkD"))?;
+    Ok((
+        fs_from_json(&params["esk"], "esk")?,
+        PaymentAddress {
+            pk_d,
+            diversifier: diversifier_from_json(&params["diversifier"])?,
+        },
+        fs_from_json(&params["rcm"], "rcm")?,
+        u64_from_json(&params["value"], "value")?,
+    ))
+}
+
+fn bytes_from_json(value: &Value, name: &str, len: usize) -> Result<Vec<u8>, String> {
+    let bytes = value
+        .as_str()
+        .and_then(|s| hex::decode(s).ok())
+        .ok_or_else(|| format!("Invalid {}", name))?;
+    if bytes.len() != len {
+        return Err(format!("Invalid {}, expected {} bytes", name, len));
+    }
+    Ok(bytes)
+}
+
+fn u64_from_json(value: &Value, name: &str) -> Result<u64, String> {
+    value.as_u64().ok_or_else(|| format!("Invalid {}", name))
+}
+
+fn fs_to_hex(fs: &Fs) -> String {
+    let mut bytes = [0u8; 32];
+    fs.into_repr()
+        .write_le(&mut bytes[..])
+        .expect("a field element is 32 bytes");
+    hex::encode(&bytes[..])
+}
+
+fn fs_from_json(value: &Value, name: &str) -> Result<Fs, String> {
+    let mut repr = FsRepr::default();
+    repr.read_le(&bytes_from_json(value, name, 32)?[..])
+        .map_err(|_| format!("Invalid {}", name))?;
+    Fs::from_repr(repr).map_err(|_| format!("Invalid {}", name))
+}
+
+fn fr_to_hex(fr: &Fr) -> String {
+    let mut bytes = [0u8; 32];
+    fr.into_repr()
+        .write_le(&mut bytes[..])
+        .expect("a field element is 32 bytes");
+    hex::encode(&bytes[..])
+}
+
+fn fr_from_json(value: &Value, name: &str) -> Result<Fr, String> {
+    let mut repr = FrRepr::default();
+    repr.read_le(&bytes_from_json(value, name, 32)?[..])
+        .map_err(|_| format!("Invalid {}", name))?;
+    Fr::from_repr(repr).map_err(|_| format!("Invalid {}", name))
+}
+
+fn point_to_hex<Subgroup>(point: &edwards::Point<Bls12, Subgroup>) -> String {
+    let mut bytes = [0u8; 32];
+    point
+        .write(&mut bytes[..])
+        .expect("a point is 32 bytes");
+    hex::encode(&bytes[..])
+}
+
+fn point_from_json(value: &Value, name: &str) -> Result<edwards::Point<Bls12, Unknown>, String> {
+    edwards::Point::read(&bytes_from_json(value, name, 32)?[..], &JUBJUB)
+        .map_err(|_| format!("Invalid {}", name))
+}
+
+fn diversifier_from_json(value: &Value) -> Result<Diversifier, String> {
+    let mut diversifier = [0u8; 11];
+    diversifier.copy_from_slice(&bytes_from_json(value, "diversifier", 11)?);
+    Ok(Diversifier(diversifier))
+}
+
+fn zkproof_from_json(value: &Value) -> Result<[u8; GROTH_PROOF_SIZE], String> {
+    let mut zkproof = [0u8; GROTH_PROOF_SIZE];
+    zkproof.copy_from_slice(&bytes_from_json(value, "zkproof", GROTH_PROOF_SIZE)?);
+    Ok(zkproof)
+}
+
+fn witness_to_json(witness: &CommitmentTreeWitness<Node>) -> Value {
+    let auth_path = witness
+        .auth_path
+        .iter()
+        .map(|step| match *step {
+            Some((ref node, is_right)) => {
+                let mut bytes = vec![];
+                node.write(&mut bytes).expect("writing to a vector does not fail");
+                json!({ "node": hex::encode(&bytes), "isRight": is_right })
+            }
+            None => Value::Null,
+        })
+        .collect::<Vec<_>>();
+    json!({ "position": witness.position, "authPath": auth_path })
+}
+
+fn witness_from_json(value: &Value) -> Result<CommitmentTreeWitness<Node>, String> {
+    let auth_path = value["authPath"]
+        .as_array()
+        .ok_or_else(|| String::from("Invalid witness"))?
+        .iter()
+        .map(|step| {
+            if step.is_null() {
+                return Ok(None);
+            }
+            let node = Node::read(&bytes_from_json(&step["node"], "witness node", 32)?[..])
+                .map_err(|_| String::from("Invalid witness node"))?;
+            let is_right = step["isRight"]
+                .as_bool()
+                .ok_or_else(|| String::from("Invalid witness"))?;
+            Ok(Some((node, is_right)))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(CommitmentTreeWitness::from_path(
+        auth_path,
+        u64_from_json(&value["position"], "witness position")?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{OsRng, Rand};
+    use sapling_crypto::{
+        jubjub::{FixedGenerators, PrimeOrder},
+        primitives::ValueCommitment,
+    };
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Stand-in for the proving service: the value commitments are real, the proofs are
+    /// filled with the value so that the tests can tell them apart.
+    struct MockTxProver;
+
+    impl TxProver for MockTxProver {
+        fn spend_proof(
+            &self,
+            proof_generation_key: ProofGenerationKey<Bls12>,
+            _diversifier: Diversifier,
+            _rcm: Fs,
+            ar: Fs,
+            value: u64,
+            _anchor: Fr,
+            witness: CommitmentTreeWitness<Node>,
+        ) -> Result<SpendProof, String> {
+            if witness.auth_path.iter().any(|step| step.is_none()) {
+                return Err(String::from("Incomplete witness."));
+            }
+            let rcv = Fs::rand(&mut OsRng::new().unwrap());
+            let rk = PublicKey::<Bls12>(proof_generation_key.ak.into()).randomize(
+                ar,
+                FixedGenerators::SpendingKeyGenerator,
+                &JUBJUB,
+            );
+            Ok(SpendProof {
+                zkproof: [value as u8; GROTH_PROOF_SIZE],
+                cv: ValueCommitment::<Bls12> { value, randomness: rcv }.cm(&JUBJUB).into(),
+                rk,
+                rcv,
+            })
+        }
+
+        fn output_proof(
+            &self,
+            _esk: Fs,
+            _payment_address: PaymentAddress<Bls12>,
+            _rcm: Fs,
+            value: u64,
+        ) -> Result<OutputProof, String> {
+            let rcv = Fs::rand(&mut OsRng::new().unwrap());
+            Ok(OutputProof {
+                zkproof: [value as u8; GROTH_PROOF_SIZE],
+                cv: ValueCommitment::<Bls12> { value, randomness: rcv }.cm(&JUBJUB).into(),
+                rcv,
+            })
+        }
+    }
+
+    fn start_service() -> RemoteTxProver {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let stream = stream.unwrap();
+                serve_connection(&MockTxProver, BufReader::new(&stream), &stream).unwrap();
+            }
+        });
+        RemoteTxProver::new(format!("{}", address).parse().unwrap())
+    }
+
+    fn random_point(rng: &mut OsRng) -> edwards::Point<Bls12, PrimeOrder> {
+        edwards::Point::<Bls12, Unknown>::rand(rng, &JUBJUB).mul_by_cofactor(&JUBJUB)
+    }
+
+    #[test]
+    fn proves_through_service() {
+        let mut rng = OsRng::new().unwrap();
+        let prover = start_service();
+        let ak = random_point(&mut rng);
+        let ar = Fs::rand(&mut rng);
+        let auth_path = (0..32)
+            .map(|i| Some((Node::new(Fr::rand(&mut rng).into_repr()), i % 3 == 0)))
+            .collect::<Vec<_>>();
+        let witness = CommitmentTreeWitness::from_path(auth_path, 9);
+
+        let spend = prover
+            .spend_proof(
+                ProofGenerationKey {
+                    ak: ak.clone(),
+                    nsk: Fs::rand(&mut rng),
+                },
+                Diversifier([1; 11]),
+                Fs::rand(&mut rng),
+                ar,
+                7,
+                Fr::rand(&mut rng),
+                witness.clone(),
+            )
+            .unwrap();
+        assert_eq!(&spend.zkproof[..], &[7u8; GROTH_PROOF_SIZE][..]);
+        let expected_cv: edwards::Point<Bls12, Unknown> = ValueCommitment::<Bls12> {
+            value: 7,
+            randomness: spend.rcv,
+        }
+        .cm(&JUBJUB)
+        .into();
+        assert!(spend.cv == expected_cv);
+        let expected_rk = PublicKey::<Bls12>(ak.into()).randomize(
+            ar,
+            FixedGenerators::SpendingKeyGenerator,
+            &JUBJUB,
+        );
+        assert!(spend.rk.0 == expected_rk.0);
+        assert_eq!(witness_from_json(&witness_to_json(&witness)), Ok(witness));
+
+        let output = prover
+            .output_proof(
+                Fs::rand(&mut rng),
+                PaymentAddress {
+                    pk_d: random_point(&mut rng),
+                    diversifier: Diversifier([2; 11]),
+                },
+                Fs::rand(&mut rng),
+                5,
+            )
+            .unwrap();
+        assert_eq!(&output.zkproof[..], &[5u8; GROTH_PROOF_SIZE][..]);
+    }
+
+    #[test]
+    fn reports_service_errors() {
+        let mut rng = OsRng::new().unwrap();
+        let prover = start_service();
+        let err = prover
+            .spend_proof(
+                ProofGenerationKey {
+                    ak: random_point(&mut rng),
+                    nsk: Fs::rand(&mut rng),
+                },
+                Diversifier([1; 11]),
+                Fs::rand(&mut rng),
+                Fs::rand(&mut rng),
+                7,
+                Fr::rand(&mut rng),
+                CommitmentTreeWitness::from_path(vec![None; 32], 0),
+            )
+            .unwrap_err();
+        assert!(err.ends_with("failed: Incomplete witness."));
+
+        let response = handle_request(&MockTxProver, r#"{"jsonrpc":"2.0","id":1,"method":"prover_verify","params":[]}"#);
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+        let response = handle_request(&MockTxProver, r#"{"jsonrpc":"2.0","id":2,"method":"prover_outputProof","params":[{}]}"#);
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+
+        let unreachable = RemoteTxProver::new(ProverAddress::Unix(PathBuf::from("/nonexistent/prover.sock")));
+        assert!(unreachable.output_proof(
+            Fs::rand(&mut rng),
+            PaymentAddress { pk_d: random_point(&mut rng), diversifier: Diversifier([2; 11]) },
+            Fs::rand(&mut rng),
+            5,
+        ).is_err());
+    }
+
+    #[test]
+    fn times_out_on_silent_services() {
+        let mut rng = OsRng::new().unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        // The connections are accepted but never answered.
+        thread::spawn(move || {
+            let _streams = listener.incoming().collect::<Vec<_>>();
+        });
+        let prover = RemoteTxProver::new(format!("{}", address).parse().unwrap())
+            .with_timeout(Duration::from_millis(200));
+        let err = prover
+            .output_proof(
+                Fs::rand(&mut rng),
+                PaymentAddress {
+                    pk_d: random_point(&mut rng),
+                    diversifier: Diversifier([2; 11]),
+                },
+                Fs::rand(&mut rng),
+                5,
+            )
+            .unwrap_err();
+        assert!(err.ends_with("failed: no response within 200ms."));
+    }
+
+    #[test]
+    fn parses_prover_addresses() {
+        assert_eq!(
+            "unix:/run/origo-prover.sock".parse::<ProverAddress>(),
+            Ok(ProverAddress::Unix(PathBuf::from("/run/origo-prover.sock")))
+        );
+        assert_eq!(
+            "tcp:10.0.0.2:8560".parse::<ProverAddress>(),
+            Ok(ProverAddress::Tcp(String::from("10.0.0.2:8560")))
+        );
+        assert_eq!(
+            "localhost:8560".parse::<ProverAddress>(),
+            Ok(ProverAddress::Tcp(String::from("localhost:8560")))
+        );
+        assert!("localhost".parse::<ProverAddress>().is_err());
     }
 }
@@ -7,7 +7,7 @@ mod prover;
 mod verifier;
 
 pub use self::prover::{ SaplingProvingContext};
-pub use self::verifier::SaplingVerificationContext;
+pub use self::verifier::{SaplingBatchVerifier, SaplingVerificationContext};
 
 // This function computes `value` in the exponent of the value commitment base
 fn compute_value_balance(
@@ -241,6 +241,49 @@ impl SaplingProvingContext {
         (proof, value_commitment)
     }
 
+    /// Construct the context of a transaction proven elsewhere, from the value commitments
+    /// of its spends and outputs and their accumulated randomness `bsk`, to create its
+    /// bindingSig.
+    pub fn from_value_commitments<'a, S, O>(
+        bsk: Fs,
+        spend_cvs: S,
+        output_cvs: O,
+        params: &JubjubBls12,
+    ) -> Self
+    where
+        S: IntoIterator<Item = &'a edwards::Point<Bls12, Unknown>>,
+        O: IntoIterator<Item = &'a edwards::Point<Bls12, Unknown>>,
+    {
+        let mut bvk = edwards::Point::zero();
+        for cv in spend_cvs {
+            bvk = cv.add(&bvk, params);
+        }
+        for cv in output_cvs {
+            bvk = cv.negate().add(&bvk, params);
+        }
+        SaplingProvingContext { bsk, bvk }
+    }
+
+    /// Accumulate a spend proven outside of this context, whose value commitment `cv`
+    /// has the randomness `rcv`.
+    pub fn add_spend(&mut self, rcv: Fs, cv: &edwards::Point<Bls12, Unknown>, params: &JubjubBls12) {
+        self.bsk.add_assign(&rcv);
+        self.bvk = cv.add(&self.bvk, params);
+    }
+
+    /// Accumulate an output proven outside of this context, whose value commitment `cv`
+    /// has the randomness `rcv`.
+    pub fn add_output(&mut self, rcv: Fs, cv: &edwards::Point<Bls12, Unknown>, params: &JubjubBls12) {
+        self.bsk.sub_assign(&rcv);
+        self.bvk = cv.negate().add(&self.bvk, params);
+    }
+
+    /// The value commitment randomness accumulated so far: the sum of the randomness of
+    /// the spends minus that of the outputs.
+    pub fn bsk(&self) -> Fs {
+        self.bsk
+    }
+
     /// Create the bindingSig for a Sapling transaction. All calls to spend_proof()
     /// and output_proof() must be completed before calling this function.
     pub fn binding_sig(
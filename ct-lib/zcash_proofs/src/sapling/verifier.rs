@@ -1,6 +1,7 @@
-use bellman::groth16::{verify_proof, PreparedVerifyingKey, Proof};
+use bellman::groth16::{verify_proof, BatchVerifier, PreparedVerifyingKey, Proof};
 use ff::Field;
 use pairing::bls12_381::{Bls12, Fr};
+use rand::OsRng;
 use sapling_crypto::{
     circuit::multipack,
     jubjub::{edwards, FixedGenerators, JubjubBls12, Unknown},
@@ -13,6 +14,49 @@ fn is_small_order<Order>(p: &edwards::Point<Bls12, Order>, params: &JubjubBls12)
     p.double(params).double(params).double(params) == edwards::Point::zero()
 }
 
+/// Spend and output proofs queued by `SaplingVerificationContext::check_spend_batched`
+/// and `SaplingVerificationContext::check_output_batched`, possibly across many
+/// transactions, to be verified together.
+pub struct SaplingBatchVerifier {
+    spends: BatchVerifier<Bls12>,
+    outputs: BatchVerifier<Bls12>,
+}
+
+impl SaplingBatchVerifier {
+    pub fn new() -> Self {
+        SaplingBatchVerifier {
+            spends: BatchVerifier::new(),
+            outputs: BatchVerifier::new(),
+        }
+    }
+
+    /// Number of queued spend and output proofs.
+    pub fn len(&self) -> usize {
+        self.spends.len() + self.outputs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spends.is_empty() && self.outputs.is_empty()
+    }
+
+    /// Verify all queued proofs. Returns false if at least one of them is invalid.
+    pub fn verify(
+        &self,
+        spend_vk: &PreparedVerifyingKey<Bls12>,
+        output_vk: &PreparedVerifyingKey<Bls12>,
+    ) -> bool {
+        let mut rng = OsRng::new().expect("should be able to construct RNG");
+
+        match (
+            self.spends.verify(spend_vk, &mut rng),
+            self.outputs.verify(output_vk, &mut rng),
+        ) {
+            (Ok(true), Ok(true)) => true,
+            _ => false,
+        }
+    }
+}
+
 /// A context object for verifying the Sapling components of a Zcash transaction.
 pub struct SaplingVerificationContext {
     bvk: edwards::Point<Bls12, Unknown>,
@@ -40,12 +84,61 @@ impl SaplingVerificationContext {
         verifying_key: &PreparedVerifyingKey<Bls12>,
         params: &JubjubBls12,
     ) -> bool {
+        let public_input =
+            match self.spend_public_input(cv, anchor, nullifier, rk, sighash_value, spend_auth_sig, params) {
+                Some(public_input) => public_input,
+                None => return false,
+            };
+
+        // Verify the proof
+        match verify_proof(verifying_key, &zkproof, &public_input[..]) {
+            // No error, and proof verification successful
+            Ok(true) => true,
+
+            // Any other case
+            _ => false,
+        }
+    }
+
+    /// Same as `check_spend`, but instead of verifying the proof right away
+    /// it is queued into `batch`. The result only covers the non-proof checks.
+    pub fn check_spend_batched(
+        &mut self,
+        cv: edwards::Point<Bls12, Unknown>,
+        anchor: Fr,
+        nullifier: &[u8; 32],
+        rk: PublicKey<Bls12>,
+        sighash_value: &[u8; 32],
+        spend_auth_sig: Signature,
+        zkproof: Proof<Bls12>,
+        batch: &mut SaplingBatchVerifier,
+        params: &JubjubBls12,
+    ) -> bool {
+        match self.spend_public_input(cv, anchor, nullifier, rk, sighash_value, spend_auth_sig, params) {
+            Some(public_input) => {
+                batch.spends.queue(zkproof, public_input.to_vec());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn spend_public_input(
+        &mut self,
+        cv: edwards::Point<Bls12, Unknown>,
+        anchor: Fr,
+        nullifier: &[u8; 32],
+        rk: PublicKey<Bls12>,
+        sighash_value: &[u8; 32],
+        spend_auth_sig: Signature,
+        params: &JubjubBls12,
+    ) -> Option<[Fr; 7]> {
         if is_small_order(&cv, params) {
-            return false;
+            return None;
         }
 
         if is_small_order(&rk.0, params) {
-            return false;
+            return None;
         }
 
         // Accumulate the value commitment in the context
@@ -73,7 +166,7 @@ impl SaplingVerificationContext {
             FixedGenerators::SpendingKeyGenerator,
             params,
         ) {
-            return false;
+            return None;
         }
 
         // Construct public input for circuit
@@ -101,6 +194,25 @@ impl SaplingVerificationContext {
             public_input[6] = nullifier[1];
         }
 
+        Some(public_input)
+    }
+
+    /// Perform consensus checks on a Sapling OutputDescription, while
+    /// accumulating its value commitment inside the context for later use.
+    pub fn check_output(
+        &mut self,
+        cv: edwards::Point<Bls12, Unknown>,
+        cm: Fr,
+        epk: edwards::Point<Bls12, Unknown>,
+        zkproof: Proof<Bls12>,
+        verifying_key: &PreparedVerifyingKey<Bls12>,
+        params: &JubjubBls12,
+    ) -> bool {
+        let public_input = match self.output_public_input(cv, cm, epk, params) {
+            Some(public_input) => public_input,
+            None => return false,
+        };
+
         // Verify the proof
         match verify_proof(verifying_key, &zkproof, &public_input[..]) {
             // No error, and proof verification successful
@@ -111,23 +223,39 @@ impl SaplingVerificationContext {
         }
     }
 
-    /// Perform consensus checks on a Sapling OutputDescription, while
-    /// accumulating its value commitment inside the context for later use.
-    pub fn check_output(
+    /// Same as `check_output`, but instead of verifying the proof right away
+    /// it is queued into `batch`. The result only covers the non-proof checks.
+    pub fn check_output_batched(
         &mut self,
         cv: edwards::Point<Bls12, Unknown>,
         cm: Fr,
         epk: edwards::Point<Bls12, Unknown>,
         zkproof: Proof<Bls12>,
-        verifying_key: &PreparedVerifyingKey<Bls12>,
+        batch: &mut SaplingBatchVerifier,
         params: &JubjubBls12,
     ) -> bool {
+        match self.output_public_input(cv, cm, epk, params) {
+            Some(public_input) => {
+                batch.outputs.queue(zkproof, public_input.to_vec());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn output_public_input(
+        &mut self,
+        cv: edwards::Point<Bls12, Unknown>,
+        cm: Fr,
+        epk: edwards::Point<Bls12, Unknown>,
+        params: &JubjubBls12,
+    ) -> Option<[Fr; 5]> {
         if is_small_order(&cv, params) {
-            return false;
+            return None;
         }
 
         if is_small_order(&epk, params) {
-            return false;
+            return None;
         }
 
         // Accumulate the value commitment in the context
@@ -154,14 +282,7 @@ impl SaplingVerificationContext {
         }
         public_input[4] = cm;
 
-        // Verify the proof
-        match verify_proof(verifying_key, &zkproof, &public_input[..]) {
-            // No error, and proof verification successful
-            Ok(true) => true,
-
-            // Any other case
-            _ => false,
-        }
+        Some(public_input)
     }
 
     /// Perform consensus checks on the valueBalance and bindingSig parts of a
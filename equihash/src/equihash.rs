@@ -77,6 +77,24 @@ impl Equihash for OnChainEquihash {
 	const SOLUTION_PREFIX: [u8; 3] = [0xfd, 0x90, 0x01];
 }
 
+/// Small Equihash parameters, solved in a fraction of a second. For tests only.
+#[cfg(any(test, feature = "test-helpers"))]
+pub struct TestEquihash;
+
+#[cfg(any(test, feature = "test-helpers"))]
+impl Equihash for TestEquihash {
+	const N: u32 = 96;
+	const K: u32 = 5;
+
+	const BLAKE2B_PERSONALIZATION: [u8; 16] = [
+		0x4f, 0x72, 0x69, 0x67, 0x6f, 0x50, 0x6f, 0x57, //b"OrigoPoW"
+		0x60, 0x00, 0x00, 0x00, //LE(N) 96
+		0x05, 0x00, 0x00, 0x00, //LE(K) 5
+	];
+
+	const SOLUTION_PREFIX: [u8; 3] = [0x44, 0x00, 0x00];
+}
+
 const HSIZE: usize = 48;
 
 pub struct Hash([u8; HSIZE]);
@@ -122,15 +140,15 @@ pub fn verify_equihash_solution<Algorithm: Equihash>(input: &[u8], solution: &[u
 	let mut current_rows = &mut rows1;
 	let mut backup_rows = &mut rows2;
 
-	let mut hash = Hash::default();
+	let mut hash = vec![0u8; Algorithm::HASH_SIZE];
 	let mut current_rows_pos = 0;
 	for_each_solution_index::<Algorithm, _>(solution, &mut |index| {
 		let hash_half_index = (index as usize / Algorithm::BSTRS_PER_HASH) as u32;
-		generate_hash(&context, hash_half_index, hash.as_mut());
+		generate_hash(&context, hash_half_index, &mut hash);
 
 		let hash_begin = (index as usize % Algorithm::BSTRS_PER_HASH) * Algorithm::N as usize / 8;
 		let hash_end = hash_begin + Algorithm::N as usize / 8;
-		let sub_hash = &hash.as_ref()[hash_begin..hash_end];
+		let sub_hash = &hash[hash_begin..hash_end];
 
 		let mut current_rows_sub_pos = current_rows_pos;
 		expand_array(
@@ -236,7 +254,7 @@ where
 	);
 }
 
-fn expand_array<E: FnMut(&[u8; 4])>(
+pub(crate) fn expand_array<E: FnMut(&[u8; 4])>(
 	compressed: &[u8],
 	blen: usize,
 	pad: usize,
@@ -274,7 +292,7 @@ fn expand_array<E: FnMut(&[u8; 4])>(
 	}
 }
 
-fn generate_hash(context: &Blake2b, index: u32, hash: &mut [u8]) {
+pub(crate) fn generate_hash(context: &Blake2b, index: u32, hash: &mut [u8]) {
 	let mut context = context.clone();
 	context.update(&index.to_le_bytes());
 	hash.copy_from_slice(context.finalize().as_bytes())
@@ -14,6 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
 
+extern crate crossbeam;
 extern crate either;
 extern crate ethereum_types;
 extern crate memmap;
@@ -37,6 +38,7 @@ extern crate tempdir;
 
 
 pub mod equihash;
+pub mod solver;
 
 use ethereum_types::{U256, U512};
 use parking_lot::Mutex;
@@ -0,0 +1,336 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! CPU Equihash solver.
+//!
+//! A straightforward implementation of Wagner's algorithm. Every step sorts the rows
+//! of the previous step by their next `N / (K + 1)` bits and XORs all pairs which
+//! collide on them. Instead of carrying the BSTR indices along, each row only keeps
+//! the positions of the two rows it was merged from, so the indices of a solution
+//! are recovered by walking these references back down to the initial rows.
+//!
+//! For the on-chain `EquihashN192K7` instance a single run needs a couple of GB of
+//! memory, which is fine for dev chains and CI, but no match for dedicated miners.
+
+use std::cmp;
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use blake2_rfc::blake2b::Blake2b;
+use crossbeam;
+
+use equihash::{copy_from_u32, expand_array, generate_hash, verify_equihash_solution, Equihash};
+
+/// Colliding groups larger than this are truncated. Large groups are extremely
+/// unlikely for honest inputs and would only blow up the number of rows.
+const MAX_GROUP_SIZE: usize = 64;
+
+/// Find the Equihash solutions for the given input (block header ++ nonce), using up to
+/// `threads` threads. Returns compressed solutions, without the solution prefix.
+///
+/// Returns early with no solutions once `abort` is set.
+pub fn solve_equihash<Algorithm: Equihash>(input: &[u8], threads: usize, abort: &AtomicBool) -> Vec<Vec<u8>> {
+	if abort.load(Ordering::SeqCst) {
+		return Vec::new();
+	}
+
+	let threads = cmp::max(threads, 1);
+	let initial_rows = 1usize << (Algorithm::BSTR_INDEX_BITS + 1);
+	// leave some headroom, the number of rows stays around `initial_rows` on every step.
+	let max_rows = 2 * initial_rows;
+
+	let mut hashes = generate_rows::<Algorithm>(input, threads);
+	let mut stride = Algorithm::ROW_HASH_LENGTH;
+	let mut levels = Vec::with_capacity(Algorithm::K as usize);
+
+	for step in 0..Algorithm::K {
+		if abort.load(Ordering::SeqCst) {
+			return Vec::new();
+		}
+
+		let last = step + 1 == Algorithm::K;
+		let (next, refs) = collide(
+			&hashes,
+			stride,
+			Algorithm::BSTR_INDEX_BYTES,
+			Algorithm::BSTR_INDEX_BITS,
+			threads,
+			max_rows,
+			last,
+		);
+		trace!("equihash step {}: {} rows", step, refs.len());
+
+		hashes = next;
+		stride -= Algorithm::BSTR_INDEX_BYTES;
+		levels.push(refs);
+	}
+
+	let candidates = levels.last().map_or(0, |refs| refs.len());
+	let mut solutions: Vec<Vec<u8>> = (0..candidates)
+		.map(|row| collect_indices(&levels, row as u32))
+		.filter(|indices| {
+			let mut sorted = indices.clone();
+			sorted.sort();
+			sorted.dedup();
+			sorted.len() == indices.len()
+		})
+		.map(|indices| copy_from_u32::<Algorithm>(&indices))
+		.filter(|solution| verify_equihash_solution::<Algorithm>(input, solution))
+		.collect();
+
+	solutions.sort();
+	solutions.dedup();
+	solutions
+}
+
+// Generate the initial rows: every BSTR expanded into `K + 1` chunks of `BSTR_INDEX_BYTES`.
+// The BSTR index of a row is its position.
+fn generate_rows<Algorithm: Equihash>(input: &[u8], threads: usize) -> Vec<u8> {
+	let count = 1usize << (Algorithm::BSTR_INDEX_BITS + 1);
+	let stride = Algorithm::ROW_HASH_LENGTH;
+	let bstr_bytes = Algorithm::N as usize / 8;
+
+	let mut context = Blake2b::with_params(
+		Algorithm::HASH_SIZE,
+		&[],
+		&[],
+		&Algorithm::BLAKE2B_PERSONALIZATION,
+	);
+	context.update(input);
+
+	// every thread handles a whole number of blake2b hashes.
+	let hashes_count = (count + Algorithm::BSTRS_PER_HASH - 1) / Algorithm::BSTRS_PER_HASH;
+	let hashes_per_thread = (hashes_count + threads - 1) / threads;
+	let rows_per_thread = hashes_per_thread * Algorithm::BSTRS_PER_HASH;
+
+	let mut rows = vec![0u8; count * stride];
+	crossbeam::scope(|scope| {
+		for (chunk_index, chunk) in rows.chunks_mut(rows_per_thread * stride).enumerate() {
+			let context = &context;
+			scope.spawn(move || {
+				let mut hash = vec![0u8; Algorithm::HASH_SIZE];
+				let first_index = chunk_index * rows_per_thread;
+
+				for (row_index, row) in chunk.chunks_mut(stride).enumerate() {
+					let index = first_index + row_index;
+					let bstr = index % Algorithm::BSTRS_PER_HASH;
+					if bstr == 0 {
+						generate_hash(context, (index / Algorithm::BSTRS_PER_HASH) as u32, &mut hash);
+					}
+
+					let sub_hash = &hash[bstr * bstr_bytes..(bstr + 1) * bstr_bytes];
+					let mut pos = 0;
+					expand_array(sub_hash, Algorithm::BSTR_INDEX_BITS, 0, &mut |buffer: &[u8; 4]| {
+						row[pos..pos + Algorithm::BSTR_INDEX_BYTES]
+							.copy_from_slice(&buffer[0..Algorithm::BSTR_INDEX_BYTES]);
+						pos += Algorithm::BSTR_INDEX_BYTES;
+					});
+				}
+			});
+		}
+	});
+
+	rows
+}
+
+// Perform a single step of Wagner's algorithm over `hashes` (rows of `stride` bytes).
+//
+// Rows are grouped by their first `trim` bytes. For every colliding pair the XOR of the
+// remaining bytes and the positions of both rows are returned. On the `last` step the
+// remaining bytes must be equal as well and only the positions are returned.
+fn collide(
+	hashes: &[u8],
+	stride: usize,
+	trim: usize,
+	key_bits: usize,
+	threads: usize,
+	max_rows: usize,
+	last: bool,
+) -> (Vec<u8>, Vec<(u32, u32)>) {
+	let rows = Rows { hashes: hashes, stride: stride, trim: trim };
+	let len = hashes.len() / stride;
+
+	// counting sort of the rows by their key.
+	let mut starts = vec![0usize; (1 << key_bits) + 1];
+	for row in 0..len {
+		starts[rows.key(row as u32) + 1] += 1;
+	}
+	for bucket in 1..starts.len() {
+		starts[bucket] += starts[bucket - 1];
+	}
+	let mut order = vec![0u32; len];
+	for row in 0..len {
+		let bucket = &mut starts[rows.key(row as u32)];
+		order[*bucket] = row as u32;
+		*bucket += 1;
+	}
+	drop(starts);
+
+	// split the sorted rows between threads without breaking up colliding groups.
+	let mut bounds = vec![0];
+	for thread in 1..threads {
+		let mut pos = cmp::max(thread * len / threads, *bounds.last().expect("bounds is never empty; qed"));
+		while pos > 0 && pos < len && rows.key(order[pos]) == rows.key(order[pos - 1]) {
+			pos += 1;
+		}
+		bounds.push(pos);
+	}
+	bounds.push(len);
+
+	let rows_per_thread = max_rows / threads;
+	let results = crossbeam::scope(|scope| {
+		let order = &order;
+		let rows = &rows;
+
+		let guards: Vec<_> = bounds.windows(2).map(|bound| {
+			let (begin, end) = (bound[0], bound[1]);
+			scope.spawn(move || {
+				let mut next = Vec::new();
+				let mut refs = Vec::new();
+
+				let mut group_begin = begin;
+				while group_begin < end && refs.len() < rows_per_thread {
+					let group_key = rows.key(order[group_begin]);
+					let mut group_end = group_begin + 1;
+					while group_end < end && rows.key(order[group_end]) == group_key {
+						group_end += 1;
+					}
+
+					let group = &order[group_begin..cmp::min(group_end, group_begin + MAX_GROUP_SIZE)];
+					for (i, &row1) in group.iter().enumerate() {
+						for &row2 in &group[i + 1..] {
+							let (hash1, hash2) = (rows.hash(row1), rows.hash(row2));
+							if last {
+								if hash1 == hash2 {
+									refs.push((row1, row2));
+								}
+								continue;
+							}
+
+							// rows colliding on all remaining bits can't lead to a valid solution.
+							if hash1 == hash2 {
+								continue;
+							}
+
+							next.extend(hash1.iter().zip(hash2).map(|(a, b)| a ^ b));
+							refs.push((row1, row2));
+						}
+					}
+
+					group_begin = group_end;
+				}
+
+				(next, refs)
+			})
+		}).collect();
+
+		guards.into_iter()
+			.map(|guard| guard.join().expect("Sub-thread never panics; qed"))
+			.collect::<Vec<_>>()
+	});
+
+	let mut next = Vec::new();
+	let mut refs = Vec::new();
+	for (part_next, part_refs) in results {
+		next.extend(part_next);
+		refs.extend(part_refs);
+	}
+
+	(next, refs)
+}
+
+// Rows of a single step, `stride` bytes each, of which the first `trim` bytes are
+// being collided on.
+struct Rows<'a> {
+	hashes: &'a [u8],
+	stride: usize,
+	trim: usize,
+}
+
+impl<'a> Rows<'a> {
+	// the bits being collided on.
+	fn key(&self, row: u32) -> usize {
+		let begin = row as usize * self.stride;
+		self.hashes[begin..begin + self.trim].iter().fold(0, |key, byte| (key << 8) | *byte as usize)
+	}
+
+	// the bits left after the collision.
+	fn hash(&self, row: u32) -> &'a [u8] {
+		let begin = row as usize * self.stride;
+		&self.hashes[begin + self.trim..begin + self.stride]
+	}
+}
+
+// Recover the BSTR indices of the row at `row` of the topmost level, ordered the way
+// the verifier expects: of any two merged subtrees the one with the lower first index
+// comes first.
+fn collect_indices(levels: &[Vec<(u32, u32)>], row: u32) -> Vec<u32> {
+	match levels.split_last() {
+		None => vec![row],
+		Some((refs, lower)) => {
+			let (row1, row2) = refs[row as usize];
+			let mut left = collect_indices(lower, row1);
+			let mut right = collect_indices(lower, row2);
+			if right[0] < left[0] {
+				mem::swap(&mut left, &mut right);
+			}
+
+			left.extend(right);
+			left
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use equihash::{Equihash, OnChainEquihash, TestEquihash};
+
+	fn solve_with_nonces(threads: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+		let abort = AtomicBool::new(false);
+		let header = b"Equihash is an asymmetric PoW based on the Generalised Birthday problem.";
+
+		(0u8..8).flat_map(|nonce| {
+			let mut input = header.to_vec();
+			input.extend(&[nonce; 32]);
+			let solutions = solve_equihash::<TestEquihash>(&input, threads, &abort);
+			solutions.into_iter().map(move |solution| (input.clone(), solution))
+		}).collect()
+	}
+
+	#[test]
+	fn solutions_are_valid() {
+		let solutions = solve_with_nonces(1);
+		assert!(!solutions.is_empty());
+
+		for (input, solution) in solutions {
+			assert_eq!(solution.len(), TestEquihash::SOLUTION_COMPRESSED_SIZE);
+			assert!(verify_equihash_solution::<TestEquihash>(&input, &solution));
+		}
+	}
+
+	#[test]
+	fn threads_find_same_solutions() {
+		assert_eq!(solve_with_nonces(1), solve_with_nonces(4));
+	}
+
+	#[test]
+	fn aborted_solver_returns_nothing() {
+		let abort = AtomicBool::new(true);
+		assert!(solve_equihash::<OnChainEquihash>(&[0u8; 96], 1, &abort).is_empty());
+	}
+}
@@ -16,7 +16,7 @@
 
 //! Blockchain database.
 
-use std::collections::{HashMap, HashSet, btree_map::BTreeMap};
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::Arc;
 use std::thread;
@@ -43,7 +43,7 @@ use common_types::BlockNumber;
 use ethcore_db::cache_manager::CacheManager;
 use ethcore_db::keys::{
 	BlockDetails, BlockReceipts, CommitmentTreeSerialization, EpochTransitions, TransactionAddress,
-	EPOCH_KEY_PREFIX,
+	EPOCH_KEY_PREFIX, NULLIFIER_KEY_PREFIX,
 };
 use ethcore_db::{self as db, CacheUpdatePolicy, Readable, Writable};
 use ethereum_types::{Bloom, BloomRef, H256, U256};
@@ -60,10 +60,8 @@ use rlp_compress::{blocks_swapper, compress, decompress};
 use std::ops::DerefMut;
 use std::sync::mpsc;
 use std::sync::Mutex as STD_Mutex;
-use std::sync::RwLock as STD_RwLock;
 
 use crate::ff::PrimeField;
-use crate::wallet::wallet::{Wallet, WalletTxHashes, ReloadBlockInfo, WalletTxAddresses};
 use crate::zcash_primitives::merkle_tree::CommitmentTree;
 use crate::zcash_primitives::sapling::Node;
 
@@ -71,7 +69,6 @@ use crate::best_block::{BestAncientBlock, BestBlock};
 use crate::block_info::{BlockInfo, BlockLocation, BranchBecomingCanonChainData};
 use crate::update::{ExtrasInsert, ExtrasUpdate};
 use crate::{CacheSize, Config, ImportRoute};
-use crate::wallet::wallet_types::TxHash;
 
 /// Database backing `BlockChain`.
 pub trait BlockChainDB: Send + Sync {
@@ -271,9 +268,6 @@ pub struct BlockChain {
 	// Stores the last block of the last sequence of blocks. `None` if there are no gaps.
 	// This is calculated on start and does not get updated.
 	first_block: Option<H256>,
-	// Store the last processed private user addresses in wallet.
-	// only updated with 'insert_block'.
-	pt_wallet_addresses: RwLock<WalletTxAddresses>,
 
 	// block cache
 	block_headers: RwLock<HashMap<H256, encoded::Header>>,
@@ -293,15 +287,12 @@ pub struct BlockChain {
 	// store reward of each era.
 	rewards: RwLock<Rewards>,
 
-	pt_wallet: Arc<STD_RwLock<Wallet>>,
-
 	db: Arc<BlockChainDB>,
 
 	cache_man: Mutex<CacheManager<CacheId>>,
 
 	pending_best_ancient_block: RwLock<Option<Option<BestAncientBlock>>>,
 	pending_best_block: RwLock<Option<BestBlock>>,
-	pending_pt_wallet_addresses: RwLock<Option<WalletTxAddresses>>,
 	pending_block_hashes: RwLock<HashMap<BlockNumber, H256>>,
 	pending_block_details: RwLock<HashMap<H256, BlockDetails>>,
 	pending_commitment_root_blocks: RwLock<HashMap<Node, Option<H256>>>,
@@ -695,11 +686,6 @@ impl BlockChain {
 	) -> BlockChain {
 		// 400 is the average size of the key
 		let cache_man = CacheManager::new(config.pref_cache_size, config.max_cache_size, 400);
-		let wallet = match config.file_path_prefix {
-			//Notice: Only For Test 
-			Some(file_path) => Arc::new(STD_RwLock::new(Wallet::new_from_file(file_path.as_str(), "./"))),
-			None =>  Arc::new(STD_RwLock::new(Wallet::new_from_default_dir())),
-		};
 		let mut bc = BlockChain {
 			reward_config: config.reward_config,
 			first_block: None,
@@ -710,7 +696,6 @@ impl BlockChain {
 				block: encoded::Block::new(genesis.into()),
 			}),
 			best_ancient_block: RwLock::new(None),
-			pt_wallet_addresses: RwLock::new(WalletTxAddresses::new()),
 			block_headers: RwLock::new(HashMap::new()),
 			block_bodies: RwLock::new(HashMap::new()),
 			block_details: RwLock::new(HashMap::new()),
@@ -721,13 +706,11 @@ impl BlockChain {
 			transaction_nullifiers: RwLock::new(HashMap::new()),
 			block_receipts: RwLock::new(HashMap::new()),
 			rewards: RwLock::new(Rewards::new()),
-			pt_wallet: wallet,
 			pending_routes: RwLock::new(ImportRoute::none()),
 			db: db.clone(),
 			cache_man: Mutex::new(cache_man),
 			pending_best_ancient_block: RwLock::new(None),
 			pending_best_block: RwLock::new(None),
-			pending_pt_wallet_addresses: RwLock::new(None),
 			pending_block_hashes: RwLock::new(HashMap::new()),
 			pending_block_details: RwLock::new(HashMap::new()),
 			pending_commitment_root_blocks: RwLock::new(HashMap::new()),
@@ -745,8 +728,6 @@ impl BlockChain {
 						let r = rx.lock();
 						for item in r.iter() {
 							// println!("received SyncTransaction");
-							// let w = bc.pt_wallet.write();
-							// let mut ww = w.unwrap();
 							//TODO(Kui): The receiver should be removed.
 							// ww.sync_transaction(&item);
 						}
@@ -942,14 +923,9 @@ impl BlockChain {
 			}
 		}
 
-		bc.reload_wallet_info();
 		bc
 	}
 
-	pub fn pt_wallet(&self) -> Arc<STD_RwLock<Wallet>> {
-		return self.pt_wallet.clone();
-	}
-
 	/// Returns true if the given parent block has given child
 	/// (though not necessarily a part of the canon chain).
 	fn is_known_child(&self, parent: &H256, hash: &H256) -> bool {
@@ -1120,7 +1096,6 @@ impl BlockChain {
 				location: BlockLocation::CanonChain,
 			};
 
-			self.prepare_wallet_update(batch, &maybe_parent_commitment_tree_ser, &block, &info);
 			self.prepare_update(
 				batch,
 				ExtrasUpdate {
@@ -1185,7 +1160,6 @@ impl BlockChain {
 			let mut update = HashMap::new();
 			update.insert(hash, block_details);
 
-			self.prepare_wallet_update(batch, &maybe_parent_commitment_tree_ser, &block, &info);
 			self.prepare_update(
 				batch,
 				ExtrasUpdate {
@@ -1353,6 +1327,22 @@ impl BlockChain {
 		}
 	}
 
+	/// Iterate over all nullifiers revealed in the canonical chain, together with
+	/// the hash of the transaction revealing them.
+	pub fn nullifiers<'a>(&'a self) -> impl Iterator<Item = (U256, H256)> + 'a {
+		self.db
+			.key_value()
+			.iter_from_prefix(db::COL_EXTRA, &NULLIFIER_KEY_PREFIX[..])
+			// iterator may continue beyond values beginning with this prefix.
+			.take_while(|(key, _)| key.starts_with(&NULLIFIER_KEY_PREFIX[..]))
+			.filter(|(key, _)| key.len() == 33)
+			.map(|(key, val)| {
+				let tx_hash: H256 = ::rlp::decode(&val[..])
+					.expect("decode error: the db is corrupted or the data structure has changed");
+				(U256::from(&key[1..]), tx_hash)
+			})
+	}
+
 	/// Get a specific epoch transition by block number and provided block hash.
 	pub fn epoch_transition(&self, block_num: u64, block_hash: H256) -> Option<EpochTransition> {
 		trace!(target: "blockchain", "Loading epoch transition at block {}, {}",
@@ -1456,10 +1446,6 @@ impl BlockChain {
 		receipts: Vec<Receipt>,
 		extras: ExtrasInsert,
 	) -> ImportRoute {
-		// Before insert new block, check whether there is new imported users in wallet.
-		// If there is new imported users, the wallet should scan the blockchain and add the related saplingNodes.
-		self.scan_for_new_imported_users_in_wallet();
-
 		let parent_hash = block.header_view().parent_hash();
 		let best_hash = self.best_block_hash();
 
@@ -1508,7 +1494,6 @@ impl BlockChain {
 			);
 		}
 
-		self.prepare_wallet_update(batch, &maybe_parent_commitment_tree_ser, &block, &info);
 		self.prepare_update(
 			batch,
 			ExtrasUpdate {
@@ -1726,7 +1711,6 @@ impl BlockChain {
 		{
 			let mut pending_best_ancient_block = self.pending_best_ancient_block.write();
 			let mut pending_best_block = self.pending_best_block.write();
-			let mut pending_wallet_addresses = self.pending_pt_wallet_addresses.write();
 			let mut pending_write_hashes = self.pending_block_hashes.write();
 			let mut pending_write_commitment_sers = self.pending_block_commitment_sers.write();
 			let mut pending_write_commitment_roots = self.pending_commitment_root_blocks.write();
@@ -1737,7 +1721,6 @@ impl BlockChain {
 
 			let mut best_block = self.best_block.write();
 			let mut best_ancient_block = self.best_ancient_block.write();
-			let mut wallet_addresses = self.pt_wallet_addresses.write();
 			let mut write_block_details = self.block_details.write();
 			let mut write_hashes = self.block_hashes.write();
 			let mut write_commitment_sers = self.block_commitment_sers.write();
@@ -1760,9 +1743,6 @@ impl BlockChain {
 			if let Some(s) = pending_rewards.take() {
 				*rewards = s;
 			}
-			if let Some(addresses) = pending_wallet_addresses.take() {
-				*wallet_addresses = addresses;
-			}
 
 			let pending_txs = mem::replace(&mut *pending_write_txs, HashMap::new());
 			let (retracted_txs, enacted_txs) = pending_txs
@@ -2215,141 +2195,6 @@ impl BlockChain {
 		result
 	}
 
-	/// This function update the block info into the wallet.
-	fn prepare_wallet_update(&self, batch: &mut DBTransaction,
-							 parent_commitment_tree_ser_option: &Option<CommitmentTreeSerialization>,
-							 block: &encoded::Block,
-							 info: &BlockInfo) {
-		let mut pt_wallet = self.pt_wallet.write().unwrap();
-		match info.location {
-			BlockLocation::CanonChain => {
-				if let Some(parent_commitment_tree_ser) = parent_commitment_tree_ser_option {
-					let mut parent_commitment_tree = parent_commitment_tree_ser.get_commitment_tree();
-					pt_wallet.sync_new_block(&mut parent_commitment_tree, &block);
-				}
-			}
-			BlockLocation::BranchBecomingCanonChain(ref data) => {
-				let mut retracted_tx_hashes = vec![];
-				for block_hash in data.retracted.iter() {
-					if let Some(block) = self.block(block_hash) {
-						for tx in block.view().localized_private_txs().iter() {
-							retracted_tx_hashes.push(tx.hash());
-						}
-					}
-				}
-				pt_wallet.remove_retracted_txs(&retracted_tx_hashes);
-				for enacted_hash in data.enacted.iter() {
-					if let Some(block) = self.block(enacted_hash) {
-						let parent_commitment_tree_ser = self.block_commitment_ser(&block.parent_hash());
-						assert!(parent_commitment_tree_ser.is_some());
-						let mut parent_commitment_tree =
-							parent_commitment_tree_ser.unwrap().get_commitment_tree();
-						pt_wallet.sync_new_block(&mut parent_commitment_tree, &block);
-					}
-				}
-				if let Some(parent_commitment_tree_ser) = parent_commitment_tree_ser_option {
-					let mut parent_commitment_tree = parent_commitment_tree_ser.get_commitment_tree();
-					pt_wallet.sync_new_block(&mut parent_commitment_tree, &block);
-				}
-			}
-			BlockLocation::Branch => {},
-		}
-		pt_wallet.write_wallet_data(batch);
-		let mut pending_wallet_addresses = self.pending_pt_wallet_addresses.write();
-		*pending_wallet_addresses = Some(pt_wallet.get_wallet_addresses());
-	}
-
-	/// Reload the wallet info when blockchain restarts.
-	pub fn reload_wallet_info(&self) {
-		// Step 1: reload the private transaction info into the wallet.
-		let wallet_txs_option: Option<WalletTxHashes> = match self.db.key_value().get(db::COL_EXTRA, b"wallet_txs").expect(
-			"Low-level database error when fetching 'wallet_txs'. Some issue with disk?",
-		) {
-			Some(wallet_txs) => Some(rlp::decode(&wallet_txs.into_vec()).unwrap()),
-			None => None,
-		};
-		if let Some(wallet_txs) = wallet_txs_option {
-			let mut pt_wallet = self.pt_wallet.write().unwrap();
-			let mut related_blocks: BTreeMap<u64, ReloadBlockInfo> = BTreeMap::new();
-
-			for tx_hash in wallet_txs.hashes {
-				if let Some(transaction_address) = self.transaction_address(&tx_hash) {
-					if let Some(block) = self.block(&transaction_address.block_hash) {
-						let block_height = block.view().header().number();
-						match related_blocks.get_mut(&block_height) {
-							None => {
-								let mut tx_hashes = HashSet::new();
-								tx_hashes.insert(tx_hash);
-								let block_info = ReloadBlockInfo {
-									block_hash: transaction_address.block_hash.clone(),
-									block,
-									tx_hashes,
-								};
-								related_blocks.insert(block_height.clone(), block_info);
-							}
-							Some(mut v) => {
-								v.tx_hashes.insert(tx_hash);
-							}
-						}
-					}
-				}
-			}
-
-			for (block_height, block_info) in related_blocks.iter() {
-				if let Some(parent_commitment_tree_ser) = self.block_commitment_ser(&block_info.block.parent_hash()) {
-					let mut parent_commitment_tree = parent_commitment_tree_ser.get_commitment_tree();
-					let filtered_tx_hashes = block_info.tx_hashes.iter().cloned().collect::<HashSet<TxHash>>();
-					pt_wallet.reload_new_block(&mut parent_commitment_tree, &block_info.block,  &Some(filtered_tx_hashes));
-				}
-			}
-		}
-
-		// Step 2: reload the imported addresses into the wallet.
-		let mut wallet_addresses  = self.pt_wallet_addresses.write();
-		let wallet_addresses_option: Option<WalletTxAddresses> = match self.db.key_value().get(db::COL_EXTRA, b"wallet_addresses").expect(
-			"Low-level database error when fetching 'wallet_addresses'. Some issue with disk?",
-		) {
-			Some(wallet_addresses) => Some(rlp::decode(&wallet_addresses.into_vec()).unwrap()),
-			None => None,
-		};
-		if let Some(addresses) = wallet_addresses_option {
-			*wallet_addresses = addresses;
-		}
-	}
-
-	/// Scan the blockchian for new imported users.
-	fn scan_for_new_imported_users_in_wallet(&self) {
-		let handled_addresses: HashSet<String> = self.pt_wallet_addresses.read().addresses.iter().cloned().collect::<HashSet<_>>();
-		let key_store_addresses = self.pt_wallet.read().unwrap().list_addresses();
-
-		let mut exist_new_imported_user = false;
-		for address in key_store_addresses.iter() {
-			if !handled_addresses.contains(address) {
-				exist_new_imported_user = true;
-				break;
-			}
-		}
-
-		if exist_new_imported_user {
-			self.scan_for_wallet_transactions(&Some(handled_addresses));
-		}
-	}
-
-	pub fn scan_for_wallet_transactions(&self, filter_exist_addresses: &Option<HashSet<String>>) {
-		let tail = self.best_block_hash();
-		let hash_vec = self.ancestry_iter(tail).unwrap().collect::<Vec<_>>();
-		let mut wallet = self.pt_wallet.write().unwrap();
-
-		for hash in hash_vec.iter().rev() {
-			if let Some(block) = self.block(hash) {
-				if let Some(parent_commitment_tree_ser) = self.block_commitment_ser(&block.parent_hash()) {
-					let mut parent_commitment_tree = parent_commitment_tree_ser.get_commitment_tree();
-					wallet.scan_new_block(&mut parent_commitment_tree, &block, filter_exist_addresses);
-				}
-			}
-		}
-	}
-
 	/// This functions returns modified blocks blooms.
 	///
 	/// To accelerate blooms lookups, blomms are stored in multiple
@@ -2694,32 +2539,14 @@ mod tests {
 	use super::*;
 
 	use std::iter;
-	use std::fs;
-
-	use rand::{OsRng, Rand};
-	use crate::wallet::wallet_types::{
-		SaplingExtendedFullViewingKey, SaplingExtendedSpendingKey,
-	};
-	use crate::wallet::transaction_builder::TransactionBuilder;
-	use sapling_crypto::{
-		jubjub::fs::Fs,
-		redjubjub::Signature,
-	};
-	use zcash_primitives::{
-		merkle_tree::{CommitmentTree,IncrementalWitness},
-		sapling::Node,
-		JUBJUB,
-	};
-	use ff::PrimeField;
 
 	use crate::generator::{BlockBuilder, BlockGenerator, BlockOptions};
 	use common_types::receipt::{Receipt, TransactionOutcome};
-	use common_types::transaction::{Action, Transaction, CONVERSION_FACTOR};
+	use common_types::transaction::{Action, Transaction};
 	use ethkey::Secret;
 	use keccak_hash::keccak;
 	use rustc_hex::FromHex;
 	use tempdir::TempDir;
-	use crate::wallet::wallet::WalletTxHashes;
 
 	const TEST_CHAIN_ID: u64 = 0;
 
@@ -2823,67 +2650,6 @@ mod tests {
 		)
 	}
 
-	fn create_private_transaction(mut wallet: &mut Wallet) -> Transaction {
-		let mut rng = OsRng::new().expect("should be able to construct RNG");
-
-		let (_, extsk) = wallet.add_address_from_seed([0u8;32], "".to_string());
-		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
-		let ovk = extfvk.fvk.ovk;
-		let to = extfvk.default_address().unwrap().1;
-
-		let mut builder = TransactionBuilder::new(0.into(), U256::from(0), U256::from(21000), vec![], TEST_CHAIN_ID);
-
-
-		// Add input
-		let note1 = to.create_note(400, Fs::rand(&mut rng), &JUBJUB).unwrap();
-		let mut tree = CommitmentTree::new();
-		let cm1 = Node::new(note1.cm(&JUBJUB).into_repr());
-
-		tree.append(cm1).unwrap();
-		let inc_tree = IncrementalWitness::from_tree(&tree);
-		let witness1 = inc_tree.path().unwrap();
-		assert!(builder
-			.add_sapling_spend(
-				extsk.expsk,
-				to.diversifier,
-				note1,
-				inc_tree.root().into(),
-				witness1
-			)
-			.is_ok());
-
-		// Add output
-		assert!(builder.add_sapling_output(ovk, to, &(U256::from(200) * CONVERSION_FACTOR), "haha").is_ok());
-
-		let result = builder.build();
-		assert!(result.is_ok());
-		let mut tx = result.unwrap();
-		tx
-	}
-
-	fn remove_old_key_files(file_name_prefix_op: &str, file_path: &str) {
-		//Remove old key files
-		let mut dir = format!("{}", file_path);
-		if file_path == "" {
-			dir = "./".to_string();
-		}
-
-		let paths = fs::read_dir(dir).unwrap();
-
-		let mut file_name_prefix = "wallet";
-		if file_name_prefix_op != "" {
-			file_name_prefix = file_name_prefix_op;
-		}
-
-		for path in paths {
-			let mut file_name = format!("{}", path.unwrap().path().display());
-			file_name = file_name.as_str()[2..].to_string();
-			if file_name.starts_with(file_name_prefix) {
-				fs::remove_file(file_name);
-			}
-		}
-	}
-
 	#[test]
 	fn should_cache_best_block() {
 		// given
@@ -2988,101 +2754,6 @@ mod tests {
 		assert_eq!(block_hashes.len(), 11);
 	}
 
-	#[test]
-	fn scan_for_wallet_transactions() {
-		let genesis = BlockBuilder::genesis();
-		let db = new_db();
-		let config = Config::new("wallet_for_scan".to_string());
-		let mut bc = BlockChain::new(config, genesis.last().encoded().raw(), db.clone(), None);
-		let t1 = {
-			let mut wallet = bc.pt_wallet.write().unwrap();
-			create_private_transaction(&mut wallet)
-		};
-		assert_eq!(bc.pt_wallet.read().unwrap().key_store.get_sapling_payment_addresses().len(), 1);
-		let b1a = genesis.add_block_with_transactions(vec![t1.sign_for_private(TEST_CHAIN_ID)]);
-		let generator = BlockGenerator::new(vec![b1a]);
-		let mut batch = db.key_value().transaction();
-		for block in generator {
-			insert_block_batch(&mut batch, &bc, block.encoded(), vec![]);
-			bc.commit();
-		}
-		db.key_value().write(batch).unwrap();
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 1);
-		assert_eq!(bc.pt_wallet.read().unwrap().nullifier_notes.len(), 2);
-		assert_eq!(bc.pt_wallet.read().unwrap().address_nullifiers.len(), 1);
-		{
-			let mut wallet = bc.pt_wallet.write().unwrap();
-			wallet.clear_wallet_data();
-		}
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 0);
-		let handled_addresses: HashSet<String> = HashSet::new();
-		bc.scan_for_wallet_transactions(&Some(handled_addresses));
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 1);
-		assert_eq!(bc.pt_wallet.read().unwrap().nullifier_notes.len(), 2);
-		assert_eq!(bc.pt_wallet.read().unwrap().address_nullifiers.len(), 1);
-	}
-
-	#[test]
-	fn test_blockchain_wallet_import_new_users() {
-		let wallet_file_prefix = "wallet_for_scan";
-		let genesis = BlockBuilder::genesis();
-		let db = new_db();
-		let config = Config::new(wallet_file_prefix.to_string());
-		let mut bc = BlockChain::new(config, genesis.last().encoded().raw(), db.clone(), None);
-		let t1 = {
-			let mut wallet = bc.pt_wallet.write().unwrap();
-			create_private_transaction(&mut wallet)
-		};
-		let b1a = genesis.add_block_with_transactions(vec![t1.sign_for_private(TEST_CHAIN_ID)]);
-		let b2a = b1a.add_block_with_transactions(vec![]);
-		let b3a = b2a.add_block_with_transactions(vec![]);
-		let generator = BlockGenerator::new(vec![b1a]);
-		let mut batch = db.key_value().transaction();
-		for block in generator {
-			insert_block_batch(&mut batch, &bc, block.encoded(), vec![]);
-			bc.commit();
-		}
-		db.key_value().write(batch).unwrap();
-		{
-			let mut wallet = bc.pt_wallet.write().unwrap();
-			wallet.clear_wallet_data();
-		}
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 0);
-
-		let empty_wallet_addresses = WalletTxAddresses {
-			addresses: vec![],
-		};
-
-		let previous_wallet_addresses= bc.pt_wallet_addresses.read().addresses.iter().cloned().collect::<Vec<String>>();
-		assert_eq!(previous_wallet_addresses.len(), 1);
-		// Clear the pt_wallet_addresses.
-		{
-			let mut wallet_addresses = bc.pt_wallet_addresses.write();
-			*wallet_addresses = empty_wallet_addresses;
-		}
-		// The insert_block trigger scan_for_wallet_imported_users.
-		let mut batch = db.key_value().transaction();
-		insert_block_batch(&mut batch, &bc, b2a.last().encoded(), vec![]);
-		bc.commit();
-		assert_eq!(bc.pt_wallet_addresses.read().addresses.iter().cloned().collect::<Vec<String>>(), previous_wallet_addresses);
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 1);
-		assert_eq!(bc.pt_wallet.read().unwrap().nullifier_notes.len(), 2);
-		assert_eq!(bc.pt_wallet.read().unwrap().address_nullifiers.len(), 1);
-
-		// Import new private address.
-		{
-			let mut pt_wallet = bc.pt_wallet.write().unwrap();
-			pt_wallet.create_new_private_address(None);
-		}
-		let mut batch = db.key_value().transaction();
-		insert_block_batch(&mut batch, &bc, b3a.last().encoded(), vec![]);
-		bc.commit();
-		let updated_wallet_addresses = bc.pt_wallet_addresses.read().addresses.iter().cloned().collect::<Vec<String>>();
-		assert_eq!(updated_wallet_addresses.len(), 2);
-
-		remove_old_key_files(wallet_file_prefix, "./");
-	}
-
 	#[test]
 	fn test_db_transaction_batch_insert_and_get() {
 		let db = new_db();
@@ -3103,49 +2774,6 @@ mod tests {
 		assert_eq!(result, String::from("test"));
 	}
 
-	#[test]
-	fn test_blockchain_wallet_write_and_reload() {
-		let genesis = BlockBuilder::genesis();
-		let db = new_db();
-		let config = Config::new("wallet_for_scan".to_string());
-		let mut bc = BlockChain::new(config, genesis.last().encoded().raw(), db.clone(), None);
-		let t1 = {
-			let mut wallet = bc.pt_wallet.write().unwrap();
-			create_private_transaction(&mut wallet)
-		}.sign_for_private(TEST_CHAIN_ID);
-		let t2 = Transaction {
-			nonce: 0.into(),
-			gas_price: 0.into(),
-			gas: 100_000.into(),
-			action: Action::Create,
-			value: 100.into(),
-			data: "601080600c6000396000f3006000355415600957005b60203560003555"
-				.from_hex()
-				.unwrap(),
-			private: None,
-		}.sign(&secret(), None);
-		let tx_hash = t1.hash();
-		assert_eq!(bc.pt_wallet.read().unwrap().key_store.get_sapling_payment_addresses().len(), 1);
-		let b1a = genesis.add_block_with_transactions(vec![t1, t2]);
-		let generator = BlockGenerator::new(vec![b1a]);
-		let mut batch = db.key_value().transaction();
-		for block in generator {
-			insert_block_batch(&mut batch, &bc, block.encoded(), vec![]);
-			bc.commit();
-		}
-		db.key_value().write(batch).unwrap();
-		// Clear the wallet data.
-		{
-			let mut wallet = bc.pt_wallet.write().unwrap();
-			wallet.clear_wallet_data();
-		}
-		bc.reload_wallet_info();
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 1);
-		assert!(bc.pt_wallet.read().unwrap().tx_nullifiers.contains_key(&tx_hash));
-		assert_eq!(bc.pt_wallet.read().unwrap().nullifier_notes.len(), 2);
-		assert_eq!(bc.pt_wallet.read().unwrap().address_nullifiers.len(), 1);
-	}
-
 	#[test]
 	fn test_find_uncles() {
 		let genesis = BlockBuilder::genesis();
@@ -3385,64 +3013,6 @@ mod tests {
 	/// We could create some transactions using the commitments in merkle_tree tests,
 	/// and then check the root and serialization we get matches those in the tests as well.
 
-	#[test]
-	fn test_fork_blockchain_wallet() {
-		let genesis = BlockBuilder::genesis();
-		let db = new_db();
-		let config = Config::new("wallet_for_scan".to_string());
-		let mut bc = BlockChain::new(config, genesis.last().encoded().raw(), db.clone(), None);
-		let t1 = {
-			let mut wallet = bc.pt_wallet.write().unwrap();
-			create_private_transaction(&mut wallet)
-		};
-		let t2 = Transaction {
-			nonce: 0.into(),
-			gas_price: 0.into(),
-			gas: 100_000.into(),
-			action: Action::Create,
-			value: 100.into(),
-			data: "601080600c6000396000f3006000355415600957005b60203560003555"
-				.from_hex()
-				.unwrap(),
-			private: None,
-		}.sign(&secret(), None);
-		assert_eq!(bc.pt_wallet.read().unwrap().key_store.get_sapling_payment_addresses().len(), 1);
-
-		let b1a = genesis.add_block_with_transactions(vec![t1.sign_for_private(TEST_CHAIN_ID), t2]);
-		let b2a = b1a.add_block();
-		let b1b = genesis.add_block_with_difficulty(9);
-		let b2 = b1b.add_block();
-
-		let mut batch = db.key_value().transaction();
-		let _ = insert_block_batch(&mut batch, &bc, b1a.last().encoded(), vec![]);
-		bc.commit();
-		let _ = insert_block_batch(&mut batch, &bc, b1b.last().encoded(), vec![]);
-		bc.commit();
-		db.key_value().write(batch).unwrap();
-
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 1);
-		assert_eq!(bc.pt_wallet.read().unwrap().nullifier_notes.len(), 2);
-		assert_eq!(bc.pt_wallet.read().unwrap().address_nullifiers.len(), 1);
-
-		let mut batch = db.key_value().transaction();
-		let _ = insert_block_batch(&mut batch, &bc, b2.last().encoded(), vec![]);
-		bc.commit();
-		db.key_value().write(batch).unwrap();
-
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 0);
-		assert_eq!(bc.pt_wallet.read().unwrap().nullifier_notes.len(), 0);
-		assert_eq!(bc.pt_wallet.read().unwrap().address_nullifiers.len(), 0);
-
-		let mut batch = db.key_value().transaction();
-		let _ = insert_block_batch(&mut batch, &bc, b2a.last().encoded(), vec![]);
-		bc.commit();
-		db.key_value().write(batch).unwrap();
-
-		assert_eq!(bc.pt_wallet.read().unwrap().tx_nullifiers.len(), 1);
-		assert_eq!(bc.pt_wallet.read().unwrap().nullifier_notes.len(), 2);
-		assert_eq!(bc.pt_wallet.read().unwrap().address_nullifiers.len(), 1);
-	}
-
 	#[test]
 	fn test_overwriting_transaction_addresses() {
 		let t1 = Transaction {
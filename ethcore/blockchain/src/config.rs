@@ -27,8 +27,6 @@ pub struct Config {
 	pub max_cache_size: usize,
 	/// Reward config
 	pub reward_config: RewardConfig,
-	/// File path prefix for wallet.
-	pub file_path_prefix: Option<String>,
 }
 
 impl Default for Config {
@@ -37,19 +35,6 @@ impl Default for Config {
 			pref_cache_size: 1 << 14,
 			max_cache_size: 1 << 20,
 			reward_config: RewardConfig::default(),
-			file_path_prefix: None,
 		}
 	}
 }
-
-impl Config {
-	pub fn new(file_path: String) -> Self {
-		Config {
-			pref_cache_size: 1 << 14,
-			max_cache_size: 1 << 20,
-			reward_config: RewardConfig::default(),
-			file_path_prefix: Some(file_path),
-		}
-	}
-}
-
@@ -79,6 +79,8 @@ pub struct BlockOptions {
 	pub bloom: Bloom,
 	/// Transactions included in blocks
 	pub transactions: Vec<SignedTransaction>,
+	/// Final Sapling root committed to by the header
+	pub final_sapling_root: Option<H256>,
 }
 
 impl Default for BlockOptions {
@@ -87,6 +89,7 @@ impl Default for BlockOptions {
 			difficulty: 10.into(),
 			bloom: Bloom::default(),
 			transactions: Vec::new(),
+			final_sapling_root: None,
 		}
 	}
 }
@@ -170,6 +173,7 @@ impl BlockBuilder {
 			block.header.set_number(block_number);
 			block.header.set_log_bloom(metadata.bloom);
 			block.header.set_difficulty(metadata.difficulty);
+			block.header.set_final_sapling_root(metadata.final_sapling_root);
 			block.transactions = metadata.transactions;
 
 			parent_hash = block.hash();
@@ -25,7 +25,6 @@ mod cache;
 mod config;
 mod import_route;
 mod update;
-pub mod wallet;
 
 #[macro_use]
 extern crate lazy_static;
@@ -33,7 +32,6 @@ extern crate lazy_static;
 extern crate log;
 
 extern crate bellman;
-extern crate dir;
 extern crate zip32;
 extern crate pairing;
 extern crate group;
@@ -42,9 +40,8 @@ extern crate zcash_primitives;
 extern crate zcash_proofs;
 extern crate sapling_crypto;
 
-extern crate backtrace;
-
 pub mod generator;
+pub mod zkp;
 pub use self::blockchain::{BlockProvider, BlockChain, BlockChainDB, BlockChainDBHandler};
 pub use self::cache::CacheSize;
 pub use self::config::Config;
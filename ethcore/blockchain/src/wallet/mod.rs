@@ -1,8 +0,0 @@
-pub mod key_store;
-pub mod origo_account;
-pub mod origo_key_file;
-pub mod sendmany;
-pub mod transaction_builder;
-pub mod wallet;
-pub mod wallet_types;
-pub mod zkp;
@@ -0,0 +1,255 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+use bellman::groth16::{
+	prepare_verifying_key, Parameters, PreparedVerifyingKey, VerifyingKey as BellmanVerifyingKey,
+};
+use group::EncodedPoint;
+use pairing::bls12_381::{Bls12, G1Uncompressed, G2Uncompressed};
+use parking_lot::Mutex;
+use rustc_hex::FromHex;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde_derive::Deserialize;
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use zcash_proofs::prover::LocalTxProver;
+use zcash_proofs::{load_verified_parameters, verify_parameters_file};
+
+pub use zcash_proofs::prover::{ProverAddress, RemoteTxProver, TxProver};
+
+/// File name of the Sapling spend parameters.
+pub const SPEND_PARAM_FILE: &str = "sapling-spend.params";
+const SPEND_PARAM_HASH: &str = "8270785a1a0d0bc77196f000ee6d221c9c9894f55307bd9357c3f0105d31ca63991ab91324160d8f53e2bbd3c2633a6eb8bdf5205d822e7f3f73edac51b2b70c";
+
+/// File name of the Sapling output parameters.
+pub const OUTPUT_PARAM_FILE: &str = "sapling-output.params";
+const OUTPUT_PARAM_HASH: &str = "657e3d38dbb5cb5e7dd2970e8b03d69b4787dd907285b5a7f0790dcc8072f60bf593b32cc2d1c030e00ff5ae64bf84c5c3beb84ddc841d48264b4a171744d028";
+
+pub type SaplingPreparedVerifyingKey = PreparedVerifyingKey<Bls12>;
+pub type SaplingParam = Parameters<Bls12>;
+
+lazy_static! {
+	pub static ref SPEND_VK: SaplingPreparedVerifyingKey =
+		{ load_sapling_spend_verifying_key().unwrap() };
+	pub static ref OUTPUT_VK: SaplingPreparedVerifyingKey =
+		{ load_sapling_output_verifying_key().unwrap() };
+	static ref PARAMS_DIR: Mutex<PathBuf> = Mutex::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("res"));
+	// The proving parameters take hundreds of megabytes, so they are only loaded
+	// once a transaction is proven in-process. A failed load is retried on next use.
+	static ref LOCAL_PROVER: Mutex<Option<Arc<dyn TxProver>>> = Mutex::new(None);
+	static ref REMOTE_PROVER: Mutex<Option<Arc<dyn TxProver>>> = Mutex::new(None);
+}
+
+/// Sets the directory the Sapling parameters are read from.
+/// Has no effect on parameters already loaded successfully.
+pub fn set_sapling_params_dir<P: Into<PathBuf>>(dir: P) {
+	*PARAMS_DIR.lock() = dir.into();
+}
+
+/// The directory the Sapling parameters are read from.
+pub fn sapling_params_dir() -> PathBuf {
+	PARAMS_DIR.lock().clone()
+}
+
+/// Makes the transactions proven by `prover` instead of in-process.
+pub fn set_tx_prover(prover: Arc<dyn TxProver>) {
+	*REMOTE_PROVER.lock() = Some(prover);
+}
+
+/// The prover of the transactions: the one set with `set_tx_prover`, otherwise an
+/// in-process prover whose Sapling parameters are loaded and verified on first use.
+pub fn tx_prover() -> Result<Arc<dyn TxProver>, String> {
+	if let Some(ref prover) = *REMOTE_PROVER.lock() {
+		return Ok(prover.clone());
+	}
+	let mut local_prover = LOCAL_PROVER.lock();
+	if let Some(ref prover) = *local_prover {
+		return Ok(prover.clone());
+	}
+	let prover = load_local_prover(&sapling_params_dir())?;
+	*local_prover = Some(prover.clone());
+	Ok(prover)
+}
+
+/// Checks that both Sapling parameter files are in the parameters directory and match
+/// their pinned hashes, without keeping them in memory.
+pub fn check_sapling_params() -> Result<(), String> {
+	let dir = sapling_params_dir();
+	check_sapling_param(&dir, SPEND_PARAM_FILE, SPEND_PARAM_HASH)?;
+	check_sapling_param(&dir, OUTPUT_PARAM_FILE, OUTPUT_PARAM_HASH)
+}
+
+fn check_sapling_param(dir: &Path, file: &str, hash: &str) -> Result<(), String> {
+	let path = dir.join(file);
+	verify_parameters_file(&path, hash).map_err(|e| sapling_param_error(&path, e))
+}
+
+fn load_sapling_param(dir: &Path, file: &str, hash: &str) -> Result<SaplingParam, String> {
+	let path = dir.join(file);
+	load_verified_parameters(&path, hash).map_err(|e| sapling_param_error(&path, e))
+}
+
+fn load_local_prover(dir: &Path) -> Result<Arc<dyn TxProver>, String> {
+	let spend_params = load_sapling_param(dir, SPEND_PARAM_FILE, SPEND_PARAM_HASH)?;
+	let output_params = load_sapling_param(dir, OUTPUT_PARAM_FILE, OUTPUT_PARAM_HASH)?;
+	Ok(Arc::new(LocalTxProver::from_parameters(spend_params, output_params)))
+}
+
+fn sapling_param_error(path: &Path, e: io::Error) -> String {
+	match e.kind() {
+		io::ErrorKind::NotFound => format!("Sapling parameters {} not found.", path.display()),
+		_ => format!("Sapling parameters {} are corrupted: {}.", path.display(), e),
+	}
+}
+
+fn clean_0x(s: &str) -> &str {
+	if s.starts_with("0x") {
+		&s[2..]
+	} else {
+		s
+	}
+}
+
+#[derive(Debug, Clone)]
+struct Point<EP: EncodedPoint>(EP::Affine);
+
+impl<'de, EP: EncodedPoint> Deserialize<'de> for Point<EP> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		struct EncodedPointVisitor<EP: EncodedPoint>(::std::marker::PhantomData<EP>);
+
+		impl<'de, EP: EncodedPoint> Visitor<'de> for EncodedPointVisitor<EP> {
+			type Value = Point<EP>;
+
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a hex string")
+			}
+
+			fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+			where
+				E: de::Error,
+			{
+				let mut point = EP::empty();
+				let point_raw = clean_0x(value)
+					.from_hex::<Vec<_>>()
+					.map_err(|e| de::Error::custom(format!("Expected hex string: {}", e)))?;
+				if point.as_ref().len() != point_raw.len() {
+					return Err(de::Error::custom(format!(
+						"Expected hex string of length {}",
+						point.as_ref().len()
+					)));
+				}
+
+				point.as_mut().copy_from_slice(&point_raw);
+				point
+					.into_affine()
+					.map_err(|e| de::Error::custom(format!("Invalid curve point: {}", e)))
+					.map(Point)
+			}
+		}
+
+		deserializer.deserialize_str(EncodedPointVisitor::<EP>(Default::default()))
+	}
+}
+
+type G1 = Point<G1Uncompressed>;
+type G2 = Point<G2Uncompressed>;
+#[derive(Clone, Deserialize)]
+struct VerifyingKey {
+	#[serde(rename = "alphaG1")]
+	pub alpha_g1: G1,
+	#[serde(rename = "betaG1")]
+	pub beta_g1: G1,
+	#[serde(rename = "betaG2")]
+	pub beta_g2: G2,
+	#[serde(rename = "gammaG2")]
+	pub gamma_g2: G2,
+	#[serde(rename = "deltaG1")]
+	pub delta_g1: G1,
+	#[serde(rename = "deltaG2")]
+	pub delta_g2: G2,
+	#[serde(rename = "ic")]
+	pub ic: Vec<G1>,
+}
+
+impl From<VerifyingKey> for BellmanVerifyingKey<Bls12> {
+	fn from(vk: VerifyingKey) -> BellmanVerifyingKey<Bls12> {
+		BellmanVerifyingKey {
+			alpha_g1: vk.alpha_g1.0,
+			beta_g1: vk.beta_g1.0,
+			beta_g2: vk.beta_g2.0,
+			gamma_g2: vk.gamma_g2.0,
+			delta_g1: vk.delta_g1.0,
+			delta_g2: vk.delta_g2.0,
+			ic: vk.ic.into_iter().map(|p| p.0).collect(),
+		}
+	}
+}
+
+pub fn load_sapling_spend_verifying_key() -> Result<SaplingPreparedVerifyingKey, String> {
+	let spend_vk_json = include_bytes!("../res/sapling-spend-verifying-key.json");
+	let spend_vk = serde_json::from_slice::<VerifyingKey>(&spend_vk_json[..]).unwrap();
+	Ok(prepare_verifying_key(&spend_vk.into()))
+}
+
+pub fn load_sapling_output_verifying_key() -> Result<SaplingPreparedVerifyingKey, String> {
+	let output_vk_json = include_bytes!("../res/sapling-output-verifying-key.json");
+	let output_vk = serde_json::from_slice::<VerifyingKey>(&output_vk_json[..]).unwrap();
+	Ok(prepare_verifying_key(&output_vk.into()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempdir::TempDir;
+
+	#[test]
+	fn load_vk_key() {
+		assert_eq!(load_sapling_spend_verifying_key().is_ok(), true);
+		assert_eq!(load_sapling_output_verifying_key().is_ok(), true);
+	}
+
+	#[test]
+	fn load_parameters() {
+		let res = Path::new(env!("CARGO_MANIFEST_DIR")).join("res");
+		assert!(check_sapling_param(&res, OUTPUT_PARAM_FILE, OUTPUT_PARAM_HASH).is_ok());
+		assert!(load_sapling_param(&res, OUTPUT_PARAM_FILE, OUTPUT_PARAM_HASH).is_ok());
+		// The hashes are pinned per file.
+		assert!(check_sapling_param(&res, OUTPUT_PARAM_FILE, SPEND_PARAM_HASH).is_err());
+	}
+
+	#[test]
+	fn reject_missing_or_corrupted_parameters() {
+		let res = Path::new(env!("CARGO_MANIFEST_DIR")).join("res");
+		let dir = TempDir::new("sapling-params").unwrap();
+		let err = check_sapling_param(dir.path(), OUTPUT_PARAM_FILE, OUTPUT_PARAM_HASH).unwrap_err();
+		assert!(err.ends_with("not found."));
+
+		let mut bytes = fs::read(res.join(OUTPUT_PARAM_FILE)).unwrap();
+		let last = bytes.len() - 1;
+		bytes[last] ^= 1;
+		fs::write(dir.path().join(OUTPUT_PARAM_FILE), &bytes).unwrap();
+		let err = check_sapling_param(dir.path(), OUTPUT_PARAM_FILE, OUTPUT_PARAM_HASH).unwrap_err();
+		assert!(err.contains("corrupted"));
+		let err = load_sapling_param(dir.path(), OUTPUT_PARAM_FILE, OUTPUT_PARAM_HASH).unwrap_err();
+		assert!(err.contains("corrupted"));
+	}
+}
@@ -151,6 +151,10 @@ impl Key<common_types::engines::epoch::PendingTransition> for H256 {
 /// length of epoch keys.
 pub const EPOCH_KEY_LEN: usize = DB_PREFIX_LEN + 16;
 
+/// nullifier key prefix.
+/// used to iterate over all revealed nullifiers.
+pub const NULLIFIER_KEY_PREFIX: &'static [u8; 1] = &[ExtrasIndex::TransactionNullifier as u8];
+
 /// epoch key prefix.
 /// used to iterate over all epoch transitions in order from genesis.
 pub const EPOCH_KEY_PREFIX: &'static [u8; DB_PREFIX_LEN] = &[
@@ -319,6 +323,10 @@ impl CommitmentTreeSerialization {
 		CommitmentTree::<Node>::read(&self.value[..])
 			.expect("failed to create the commit tree from serialization")
 	}
+
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.value
+	}
 }
 
 impl HeapSizeOf for CommitmentTreeSerialization {
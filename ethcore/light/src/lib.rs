@@ -43,7 +43,7 @@ pub mod provider;
 mod types;
 
 pub use self::cache::Cache;
-pub use self::provider::{Provider, MAX_HEADERS_PER_REQUEST};
+pub use self::provider::{Provider, MAX_HEADERS_PER_REQUEST, MAX_SHIELDED_BLOCKS_PER_REQUEST};
 pub use self::transaction_queue::TransactionQueue;
 pub use types::request as request;
 
@@ -93,3 +93,5 @@ extern crate kvdb_memorydb;
 #[cfg(test)]
 extern crate tempdir;
 extern crate journaldb;
+extern crate ff;
+extern crate zcash_primitives;
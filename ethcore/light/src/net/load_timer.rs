@@ -63,6 +63,9 @@ fn hardcoded_serve_time(kind: Kind) -> Duration {
 		Kind::Code => 1_500_000,
 		Kind::Execution => 250, // per gas.
 		Kind::Signal => 500_000,
+		Kind::CommitmentTree => 1_000_000,
+		Kind::Nullifier => 1_500_000,
+		Kind::ShieldedOutputs => 1_000_000, // per block.
 	})
 }
 
@@ -102,6 +105,7 @@ impl LoadDistribution {
 		let kind = req.kind();
 		let n = match *req {
 			CompleteRequest::Headers(ref req) => req.max,
+			CompleteRequest::ShieldedOutputs(ref req) => req.max,
 			CompleteRequest::Execution(ref req) => req.gas.low_u64(),
 			_ => 1,
 		};
@@ -88,10 +88,15 @@ const PACKET_COUNT_V1: u8 = 9;
 /// Supported protocol versions.
 pub const PROTOCOL_VERSIONS: &[(u8, u8)] = &[
 	(1, PACKET_COUNT_V1),
+	(2, PACKET_COUNT_V1),
 ];
 
 /// Max protocol version.
-pub const MAX_PROTOCOL_VERSION: u8 = 1;
+pub const MAX_PROTOCOL_VERSION: u8 = 2;
+
+/// First protocol version with the shielded requests: commitment trees, nullifiers
+/// and shielded outputs. Their costs are neither announced nor served to older peers.
+pub const SHIELDED_PROTOCOL_VERSION: u8 = 2;
 
 // packet ID definitions.
 mod packet {
@@ -134,6 +139,9 @@ mod timeout {
 	pub const HEADER_PROOF: u64 = 100;
 	pub const TRANSACTION_PROOF: u64 = 1000; // per gas?
 	pub const EPOCH_SIGNAL: u64 = 200;
+	pub const COMMITMENT_TREE: u64 = 100;
+	pub const NULLIFIER: u64 = 200;
+	pub const SHIELDED_OUTPUTS: u64 = 500;
 }
 
 /// A request id.
@@ -776,7 +784,8 @@ impl LightProtocol {
 		} else {
 			&**cost_local_flow
 		};
-		let status_packet = status::write_handshake(&status, &capabilities, Some(local_flow));
+		let local_flow = local_flow.for_protocol_version(proto_version);
+		let status_packet = status::write_handshake(&status, &capabilities, Some(&local_flow));
 
 		self.pending_peers.write().insert(peer, PendingPeer {
 			sent_head: chain_info.best_block_hash,
@@ -848,19 +857,20 @@ impl LightProtocol {
 		let peers = self.peers.read();
 		let now = Instant::now();
 
-		let packet_body = {
-			let mut stream = RlpStream::new_list(3);
-			stream.append(new_params.limit())
-				.append(new_params.recharge_rate())
-				.append(new_params.cost_table());
-			stream.out()
-		};
-
 		for (peer_id, peer_info) in peers.iter() {
 			let mut peer_info = peer_info.lock();
+			let peer_params = Arc::new(new_params.for_protocol_version(peer_info.status.protocol_version as u8));
+
+			let packet_body = {
+				let mut stream = RlpStream::new_list(3);
+				stream.append(peer_params.limit())
+					.append(peer_params.recharge_rate())
+					.append(peer_params.cost_table());
+				stream.out()
+			};
 
-			io.send(*peer_id, packet::UPDATE_CREDITS, packet_body.clone());
-			peer_info.awaiting_acknowledge = Some((now, new_params.clone()));
+			io.send(*peer_id, packet::UPDATE_CREDITS, packet_body);
+			peer_info.awaiting_acknowledge = Some((now, peer_params));
 		}
 	}
 
@@ -901,6 +911,7 @@ impl LightProtocol {
 		} else {
 			self.flow_params.read().clone()
 		};
+		let local_flow = Arc::new(local_flow.for_protocol_version(status.protocol_version as u8));
 
 		self.peers.write().insert(peer, Mutex::new(Peer {
 			local_credits: local_flow.create_credits(),
@@ -1034,6 +1045,9 @@ impl LightProtocol {
 				CompleteRequest::Code(req) => self.provider.contract_code(req).map(Response::Code),
 				CompleteRequest::Execution(req) => self.provider.transaction_proof(req).map(Response::Execution),
 				CompleteRequest::Signal(req) => self.provider.epoch_signal(req).map(Response::Signal),
+				CompleteRequest::CommitmentTree(req) => self.provider.commitment_tree(req).map(Response::CommitmentTree),
+				CompleteRequest::Nullifier(req) => self.provider.nullifier(req).map(Response::Nullifier),
+				CompleteRequest::ShieldedOutputs(req) => self.provider.shielded_outputs(req).map(Response::ShieldedOutputs),
 			}
 		});
 
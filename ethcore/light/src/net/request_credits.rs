@@ -28,6 +28,7 @@
 
 use request::{self, Request};
 use super::error::Error;
+use super::SHIELDED_PROTOCOL_VERSION;
 
 use rlp::{Rlp, RlpStream, Decodable, Encodable, DecoderError};
 use ethereum_types::U256;
@@ -92,6 +93,9 @@ pub struct CostTable {
 	header_proof: Option<U256>,
 	transaction_proof: Option<U256>, // cost per gas.
 	epoch_signal: Option<U256>,
+	commitment_tree: Option<U256>,
+	nullifier: Option<U256>,
+	shielded_outputs: Option<U256>, // cost per block.
 }
 
 impl CostTable {
@@ -110,10 +114,27 @@ impl CostTable {
 			incr_if_set(&self.header_proof);
 			incr_if_set(&self.transaction_proof);
 			incr_if_set(&self.epoch_signal);
+			incr_if_set(&self.commitment_tree);
+			incr_if_set(&self.nullifier);
+			incr_if_set(&self.shielded_outputs);
 		}
 
 		num_set
 	}
+
+	// The costs of the requests known by a peer on the given protocol version.
+	fn for_protocol_version(&self, version: u8) -> CostTable {
+		if version >= SHIELDED_PROTOCOL_VERSION {
+			return self.clone();
+		}
+
+		CostTable {
+			commitment_tree: None,
+			nullifier: None,
+			shielded_outputs: None,
+			..self.clone()
+		}
+	}
 }
 
 impl Default for CostTable {
@@ -131,6 +152,9 @@ impl Default for CostTable {
 			header_proof: Some(15000.into()),
 			transaction_proof: Some(2.into()),
 			epoch_signal: Some(10000.into()),
+			commitment_tree: Some(15000.into()),
+			nullifier: Some(20000.into()),
+			shielded_outputs: Some(15000.into()),
 		}
 	}
 }
@@ -157,6 +181,9 @@ impl Encodable for CostTable {
 		append_cost(s, &self.header_proof, request::Kind::HeaderProof);
 		append_cost(s, &self.transaction_proof, request::Kind::Execution);
 		append_cost(s, &self.epoch_signal, request::Kind::Signal);
+		append_cost(s, &self.commitment_tree, request::Kind::CommitmentTree);
+		append_cost(s, &self.nullifier, request::Kind::Nullifier);
+		append_cost(s, &self.shielded_outputs, request::Kind::ShieldedOutputs);
 	}
 }
 
@@ -174,6 +201,9 @@ impl Decodable for CostTable {
 		let mut header_proof = None;
 		let mut transaction_proof = None;
 		let mut epoch_signal = None;
+		let mut commitment_tree = None;
+		let mut nullifier = None;
+		let mut shielded_outputs = None;
 
 		for cost_list in rlp.iter().skip(1) {
 			let cost = cost_list.val_at(1)?;
@@ -188,6 +218,9 @@ impl Decodable for CostTable {
 				request::Kind::HeaderProof => header_proof = Some(cost),
 				request::Kind::Execution => transaction_proof = Some(cost),
 				request::Kind::Signal => epoch_signal = Some(cost),
+				request::Kind::CommitmentTree => commitment_tree = Some(cost),
+				request::Kind::Nullifier => nullifier = Some(cost),
+				request::Kind::ShieldedOutputs => shielded_outputs = Some(cost),
 			}
 		}
 
@@ -203,6 +236,9 @@ impl Decodable for CostTable {
 			header_proof,
 			transaction_proof,
 			epoch_signal,
+			commitment_tree,
+			nullifier,
+			shielded_outputs,
 		};
 
 		if table.costs_set() == 0 {
@@ -279,6 +315,9 @@ impl FlowParams {
 			header_proof: cost_for_kind(Kind::HeaderProof),
 			transaction_proof: cost_for_kind(Kind::Execution),
 			epoch_signal: cost_for_kind(Kind::Signal),
+			commitment_tree: cost_for_kind(Kind::CommitmentTree),
+			nullifier: cost_for_kind(Kind::Nullifier),
+			shielded_outputs: cost_for_kind(Kind::ShieldedOutputs),
 		};
 
 		FlowParams {
@@ -306,10 +345,22 @@ impl FlowParams {
 				header_proof: free_cost,
 				transaction_proof: free_cost,
 				epoch_signal: free_cost,
+				commitment_tree: free_cost,
+				nullifier: free_cost,
+				shielded_outputs: free_cost,
 			}
 		}
 	}
 
+	/// The flow params of a peer on the given protocol version: the requests
+	/// it doesn't know are neither announced nor served.
+	pub fn for_protocol_version(&self, version: u8) -> FlowParams {
+		FlowParams {
+			costs: self.costs.for_protocol_version(version),
+			..self.clone()
+		}
+	}
+
 	/// Get a reference to the credit limit.
 	pub fn limit(&self) -> &U256 { &self.limit }
 
@@ -336,6 +387,9 @@ impl FlowParams {
 			Request::Code(_) => self.costs.code,
 			Request::Execution(ref req) => self.costs.transaction_proof.map(|c| c * req.gas),
 			Request::Signal(_) => self.costs.epoch_signal,
+			Request::CommitmentTree(_) => self.costs.commitment_tree,
+			Request::Nullifier(_) => self.costs.nullifier,
+			Request::ShieldedOutputs(ref req) => self.costs.shielded_outputs.map(|c| c * U256::from(req.max)),
 		}
 	}
 
@@ -411,6 +465,22 @@ mod tests {
 		assert_eq!(costs, new_costs);
 	}
 
+	#[test]
+	fn shielded_costs_only_for_new_peers() {
+		let flow_params = FlowParams::default();
+
+		let costs = flow_params.for_protocol_version(1).costs;
+		assert_eq!(costs.costs_set(), CostTable::default().costs_set() - 3);
+		let new_costs: CostTable = ::rlp::decode(&*::rlp::encode(&costs)).unwrap();
+		assert_eq!(costs, new_costs);
+		for cost_list in Rlp::new(&::rlp::encode(&costs)).iter().skip(1) {
+			let kind: request::Kind = cost_list.val_at(0).unwrap();
+			assert!((kind as u8) < request::Kind::CommitmentTree as u8);
+		}
+
+		assert_eq!(flow_params.for_protocol_version(SHIELDED_PROTOCOL_VERSION), flow_params);
+	}
+
 	#[test]
 	fn credits_mechanism() {
 		use std::thread;
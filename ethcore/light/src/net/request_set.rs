@@ -139,6 +139,9 @@ fn compute_timeout(reqs: &Requests) -> Duration {
 			Request::Code(_) => timeout::CONTRACT_CODE,
 			Request::Execution(_) => timeout::TRANSACTION_PROOF,
 			Request::Signal(_) => timeout::EPOCH_SIGNAL,
+			Request::CommitmentTree(_) => timeout::COMMITMENT_TREE,
+			Request::Nullifier(_) => timeout::NULLIFIER,
+			Request::ShieldedOutputs(_) => timeout::SHIELDED_OUTPUTS,
 		}
 	}))
 }
@@ -176,6 +176,22 @@ impl Provider for TestProvider {
 		})
 	}
 
+	fn commitment_tree(&self, _req: request::CompleteCommitmentTreeRequest) -> Option<request::CommitmentTreeResponse> {
+		Some(request::CommitmentTreeResponse {
+			tree: vec![0, 0, 0],
+		})
+	}
+
+	fn nullifier(&self, _req: request::CompleteNullifierRequest) -> Option<request::NullifierResponse> {
+		Some(request::NullifierResponse {
+			block: None,
+		})
+	}
+
+	fn shielded_outputs(&self, req: request::CompleteShieldedOutputsRequest) -> Option<request::ShieldedOutputsResponse> {
+		self.0.client.shielded_outputs(req)
+	}
+
 	fn transactions_to_propagate(&self) -> Vec<PendingTransaction> {
 		self.0.client.transactions_to_propagate()
 	}
@@ -217,7 +233,7 @@ fn setup(capabilities: Capabilities) -> (Arc<TestProviderInner>, LightProtocol)
 
 fn status(chain_info: BlockChainInfo) -> Status {
 	Status {
-		protocol_version: 1,
+		protocol_version: super::MAX_PROTOCOL_VERSION as u32,
 		network_id: 2,
 		head_td: chain_info.total_difficulty,
 		head_hash: chain_info.best_block_hash,
@@ -599,6 +615,49 @@ fn epoch_signal() {
 	proto.handle_packet(&expected, 1, packet::REQUEST, &request_body);
 }
 
+#[test]
+fn commitment_tree() {
+	let capabilities = capabilities();
+
+	let (provider, proto) = setup(capabilities);
+	let flow_params = proto.flow_params.read().clone();
+
+	let cur_status = status(provider.client.chain_info());
+
+	{
+		let packet_body = write_handshake(&cur_status, &capabilities, &proto);
+		proto.on_connect(1, &Expect::Send(1, packet::STATUS, packet_body.clone()));
+		proto.handle_packet(&Expect::Nothing, 1, packet::STATUS, &packet_body);
+	}
+
+	let req_id = 113;
+	let request = Request::CommitmentTree(request::IncompleteCommitmentTreeRequest {
+		block_hash: H256([1; 32]).into(),
+	});
+
+	let requests = encode_single(request.clone());
+	let request_body = make_packet(req_id, &requests);
+
+	let response = {
+		let response = vec![Response::CommitmentTree(request::CommitmentTreeResponse {
+			tree: vec![0, 0, 0],
+		})];
+
+		let limit = *flow_params.limit();
+		let cost = flow_params.compute_cost_multi(requests.requests()).unwrap();
+
+		let new_creds = limit - cost;
+
+		let mut response_stream = RlpStream::new_list(3);
+		response_stream.append(&req_id).append(&new_creds).append_list(&response);
+
+		response_stream.out()
+	};
+
+	let expected = Expect::Respond(packet::RESPONSE, response);
+	proto.handle_packet(&expected, 1, packet::REQUEST, &request_body);
+}
+
 #[test]
 fn proof_of_execution() {
 	let capabilities = capabilities();
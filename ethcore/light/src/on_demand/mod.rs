@@ -263,6 +263,9 @@ impl Pending {
 				CheckedRequest::Code(_, _) => "Code",
 				CheckedRequest::Execution(_, _) => "Execution",
 				CheckedRequest::Signal(_, _) => "Signal",
+				CheckedRequest::CommitmentTree(_, _) => "CommitmentTree",
+				CheckedRequest::Nullifier(_, _) => "Nullifier",
+				CheckedRequest::ShieldedOutputs(_, _) => "ShieldedOutputs",
 			}
 		}).collect();
 
@@ -329,6 +332,13 @@ fn guess_capabilities(requests: &[CheckedRequest]) -> Capabilities {
 			CheckedRequest::Execution(ref req, _) => if let Ok(ref hdr) = req.header.as_ref() {
 				update_since(&mut caps.serve_state_since, hdr.number());
 			},
+			CheckedRequest::CommitmentTree(ref req, _) => if let Ok(ref hdr) = req.0.as_ref() {
+				update_since(&mut caps.serve_chain_since, hdr.number());
+			},
+			CheckedRequest::Nullifier(_, _) => {} // nullifiers yield no info.
+			CheckedRequest::ShieldedOutputs(ref req, _) => if let Some(hdr) = req.headers.first() {
+				update_since(&mut caps.serve_chain_since, hdr.number());
+			},
 		}
 	}
 
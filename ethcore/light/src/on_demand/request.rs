@@ -37,6 +37,8 @@ use request::{self as net_request, IncompleteRequest, CompleteRequest, Output, O
 use rlp::{RlpStream, Rlp};
 use trie::Trie;
 use vm::EnvInfo;
+use zcash_primitives::merkle_tree::{CommitmentTree as NoteCommitmentTree, Hashable};
+use zcash_primitives::sapling::Node;
 
 const SUPPLIED_MATCHES: &str = "supplied responses always match produced requests; enforced by `check_response`; qed";
 
@@ -63,6 +65,12 @@ pub enum Request {
 	Execution(TransactionProof),
 	/// A request for epoch change signal.
 	Signal(Signal),
+	/// A request for the note commitment tree as of a block.
+	CommitmentTree(CommitmentTree),
+	/// A request for the block spending a nullifier.
+	Nullifier(Nullifier),
+	/// A request for the shielded outputs of a range of blocks.
+	ShieldedOutputs(ShieldedOutputs),
 }
 
 /// A request argument.
@@ -146,6 +154,9 @@ impl_single!(Account, Account, Option<BasicAccount>);
 impl_single!(Code, Code, Bytes);
 impl_single!(Execution, TransactionProof, super::ExecutionResult);
 impl_single!(Signal, Signal, Vec<u8>);
+impl_single!(CommitmentTree, CommitmentTree, NoteCommitmentTree<Node>);
+impl_single!(Nullifier, Nullifier, Option<encoded::Block>);
+impl_single!(ShieldedOutputs, ShieldedOutputs, Vec<net_request::ShieldedBlock>);
 
 macro_rules! impl_args {
 	() => {
@@ -256,7 +267,10 @@ pub enum CheckedRequest {
 	Account(Account, net_request::IncompleteAccountRequest),
 	Code(Code, net_request::IncompleteCodeRequest),
 	Execution(TransactionProof, net_request::IncompleteExecutionRequest),
-	Signal(Signal, net_request::IncompleteSignalRequest)
+	Signal(Signal, net_request::IncompleteSignalRequest),
+	CommitmentTree(CommitmentTree, net_request::IncompleteCommitmentTreeRequest),
+	Nullifier(Nullifier, net_request::IncompleteNullifierRequest),
+	ShieldedOutputs(ShieldedOutputs, net_request::IncompleteShieldedOutputsRequest),
 }
 
 impl From<Request> for CheckedRequest {
@@ -346,6 +360,29 @@ impl From<Request> for CheckedRequest {
 				trace!(target: "on_demand", "Signal Request, {:?}", net_req);
 				CheckedRequest::Signal(req, net_req)
 			}
+			Request::CommitmentTree(req) => {
+				let net_req = net_request::IncompleteCommitmentTreeRequest {
+					block_hash: req.0.field(),
+				};
+				trace!(target: "on_demand", "CommitmentTree Request, {:?}", net_req);
+				CheckedRequest::CommitmentTree(req, net_req)
+			}
+			Request::Nullifier(req) => {
+				let net_req = net_request::IncompleteNullifierRequest {
+					nullifier: req.0.into(),
+				};
+				trace!(target: "on_demand", "Nullifier Request, {:?}", net_req);
+				CheckedRequest::Nullifier(req, net_req)
+			}
+			Request::ShieldedOutputs(req) => {
+				let start = req.headers.first().map_or(H256::zero(), |hdr| hdr.hash());
+				let net_req = net_request::IncompleteShieldedOutputsRequest {
+					start: net_request::HashOrNumber::Hash(start).into(),
+					max: req.headers.len() as u64,
+				};
+				trace!(target: "on_demand", "ShieldedOutputs Request, {:?}", net_req);
+				CheckedRequest::ShieldedOutputs(req, net_req)
+			}
 		}
 	}
 }
@@ -366,6 +403,9 @@ impl CheckedRequest {
 			CheckedRequest::Code(_, req) => NetRequest::Code(req),
 			CheckedRequest::Execution(_, req) => NetRequest::Execution(req),
 			CheckedRequest::Signal(_, req) => NetRequest::Signal(req),
+			CheckedRequest::CommitmentTree(_, req) => NetRequest::CommitmentTree(req),
+			CheckedRequest::Nullifier(_, req) => NetRequest::Nullifier(req),
+			CheckedRequest::ShieldedOutputs(_, req) => NetRequest::ShieldedOutputs(req),
 		}
 	}
 
@@ -380,6 +420,7 @@ impl CheckedRequest {
 			CheckedRequest::Account(ref x, _) => x.header.needs_header(),
 			CheckedRequest::Code(ref x, _) => x.header.needs_header(),
 			CheckedRequest::Execution(ref x, _) => x.header.needs_header(),
+			CheckedRequest::CommitmentTree(ref x, _) => x.0.needs_header(),
 			_ => None,
 		}
 	}
@@ -394,6 +435,7 @@ impl CheckedRequest {
 			CheckedRequest::Account(ref mut x, _) => x.header = HeaderRef::Stored(header),
 			CheckedRequest::Code(ref mut x, _) => x.header = HeaderRef::Stored(header),
 			CheckedRequest::Execution(ref mut x, _) => x.header = HeaderRef::Stored(header),
+			CheckedRequest::CommitmentTree(ref mut x, _) => x.0 = HeaderRef::Stored(header),
 			_ => {},
 		}
 	}
@@ -511,6 +553,9 @@ macro_rules! match_me {
 			CheckedRequest::Code($check, $req) => $e,
 			CheckedRequest::Execution($check, $req) => $e,
 			CheckedRequest::Signal($check, $req) => $e,
+			CheckedRequest::CommitmentTree($check, $req) => $e,
+			CheckedRequest::Nullifier($check, $req) => $e,
+			CheckedRequest::ShieldedOutputs($check, $req) => $e,
 		}
 	}
 }
@@ -549,6 +594,9 @@ impl IncompleteRequest for CheckedRequest {
 			CheckedRequest::Code(_, ref req) => req.check_outputs(f),
 			CheckedRequest::Execution(_, ref req) => req.check_outputs(f),
 			CheckedRequest::Signal(_, ref req) => req.check_outputs(f),
+			CheckedRequest::CommitmentTree(_, ref req) => req.check_outputs(f),
+			CheckedRequest::Nullifier(_, ref req) => req.check_outputs(f),
+			CheckedRequest::ShieldedOutputs(_, ref req) => req.check_outputs(f),
 		}
 	}
 
@@ -602,6 +650,18 @@ impl IncompleteRequest for CheckedRequest {
 				trace!(target: "on_demand", "Signal request completed {:?}", req);
 				req.complete().map(CompleteRequest::Signal)
 			}
+			CheckedRequest::CommitmentTree(_, req) => {
+				trace!(target: "on_demand", "CommitmentTree request completed {:?}", req);
+				req.complete().map(CompleteRequest::CommitmentTree)
+			}
+			CheckedRequest::Nullifier(_, req) => {
+				trace!(target: "on_demand", "Nullifier request completed {:?}", req);
+				req.complete().map(CompleteRequest::Nullifier)
+			}
+			CheckedRequest::ShieldedOutputs(_, req) => {
+				trace!(target: "on_demand", "ShieldedOutputs request completed {:?}", req);
+				req.complete().map(CompleteRequest::ShieldedOutputs)
+			}
 		}
 	}
 
@@ -661,6 +721,15 @@ impl net_request::CheckedRequest for CheckedRequest {
 			CheckedRequest::Signal(ref prover, _) =>
 				expect!((&NetResponse::Signal(ref res), _) =>
 					prover.check_response(cache, &res.signal).map(Response::Signal)),
+			CheckedRequest::CommitmentTree(ref prover, _) =>
+				expect!((&NetResponse::CommitmentTree(ref res), _) =>
+					prover.check_response(cache, &res.tree).map(Response::CommitmentTree)),
+			CheckedRequest::Nullifier(ref prover, _) =>
+				expect!((&NetResponse::Nullifier(ref res), _) =>
+					prover.check_response(cache, &res.block).map(Response::Nullifier)),
+			CheckedRequest::ShieldedOutputs(ref prover, _) =>
+				expect!((&NetResponse::ShieldedOutputs(ref res), _) =>
+					prover.check_response(cache, &res.blocks).map(Response::ShieldedOutputs)),
 		}
 	 }
 }
@@ -690,6 +759,12 @@ pub enum Response {
 	Execution(super::ExecutionResult),
 	/// Response to a request for epoch change signal.
 	Signal(Vec<u8>),
+	/// Response to a request for a note commitment tree.
+	CommitmentTree(NoteCommitmentTree<Node>),
+	/// Response to a request for the block spending a nullifier.
+	Nullifier(Option<encoded::Block>),
+	/// Response to a request for shielded outputs.
+	ShieldedOutputs(Vec<net_request::ShieldedBlock>),
 }
 
 impl net_request::ResponseLike for Response {
@@ -1095,6 +1170,131 @@ impl Signal {
 	}
 }
 
+// the root of a note commitment tree, as found in headers.
+fn sapling_root(tree: &NoteCommitmentTree<Node>) -> H256 {
+	let mut root = H256::default();
+	tree.root().write(&mut root[..]).expect("a node is 32 bytes; qed");
+	root
+}
+
+/// Request for the note commitment tree as of the end of a block,
+/// with header for verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitmentTree(pub HeaderRef);
+
+impl CommitmentTree {
+	/// Check a serialized commitment tree against the final sapling root of the header.
+	pub fn check_response(&self, _: &Mutex<::cache::Cache>, tree: &[u8]) -> Result<NoteCommitmentTree<Node>, Error> {
+		let header = self.0.as_ref()?;
+		let expected_root = match header.final_sapling_root() {
+			Some(root) => root,
+			None => {
+				trace!(target: "on_demand", "CommitmentTree Response: no sapling root in header {}", header.hash());
+				return Err(Error::BadProof);
+			}
+		};
+
+		let tree = NoteCommitmentTree::<Node>::read(tree).map_err(|_| Error::BadProof)?;
+		let found_root = sapling_root(&tree);
+		if found_root == expected_root {
+			Ok(tree)
+		} else {
+			trace!(target: "on_demand", "CommitmentTree Response: \"WrongTrieRoot\" sapling_root: {:?} found_root: {:?}", expected_root, found_root);
+			Err(Error::WrongTrieRoot(expected_root, found_root))
+		}
+	}
+}
+
+/// Request for the block spending a nullifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nullifier(pub U256);
+
+impl Nullifier {
+	/// Check that the block returned, if any, matches its header and spends the nullifier.
+	//
+	// As for the transaction index, the canonicality of the block is left to the caller.
+	//
+	// An unspent nullifier can't be proven: the chain doesn't commit to the nullifier set,
+	// so a peer may hide a spend.
+	pub fn check_response(
+		&self,
+		_: &Mutex<::cache::Cache>,
+		block: &Option<encoded::Block>,
+	) -> Result<Option<encoded::Block>, Error> {
+		let block = match *block {
+			Some(ref block) => block,
+			None => return Ok(None),
+		};
+
+		let tx_root = ::triehash::ordered_trie_root(block.view().transactions_rlp().rlp.iter().map(|r| r.as_raw()));
+		if tx_root != block.transactions_root() {
+			trace!(target: "on_demand", "Nullifier Response: \"WrongTrieRoot\" tx_root: {:?} header_root: {:?}", tx_root, block.transactions_root());
+			return Err(Error::WrongTrieRoot(block.transactions_root(), tx_root));
+		}
+
+		if block.transactions().iter().any(|tx| tx.get_nullifier_set().contains(&self.0)) {
+			Ok(Some(block.clone()))
+		} else {
+			trace!(target: "on_demand", "Nullifier Response: block {} doesn't spend {}", block.hash(), self.0);
+			Err(Error::BadProof)
+		}
+	}
+}
+
+/// Request for the shielded outputs of a range of consecutive blocks, along with
+/// the parts necessary to verify the note commitments.
+#[derive(Clone)]
+pub struct ShieldedOutputs {
+	/// Headers of the blocks, in ascending order. Must not be empty.
+	pub headers: Vec<encoded::Header>,
+	/// Note commitment tree as of the end of the parent of the first block.
+	pub tree: NoteCommitmentTree<Node>,
+}
+
+impl ShieldedOutputs {
+	/// Check the note commitments of each block against the final sapling root of its header.
+	///
+	/// Only the note commitments are covered by the headers: the nullifiers and ciphertexts
+	/// returned aren't, and private transactions without outputs may be missing.
+	pub fn check_response(
+		&self,
+		_: &Mutex<::cache::Cache>,
+		blocks: &[net_request::ShieldedBlock],
+	) -> Result<Vec<net_request::ShieldedBlock>, Error> {
+		let expected_len = self.headers.len() as u64;
+		let actual_len = blocks.len() as u64;
+		match actual_len.cmp(&expected_len) {
+			cmp::Ordering::Less =>
+				return Err(Error::TooFewResults(expected_len, actual_len)),
+			cmp::Ordering::Greater =>
+				return Err(Error::TooManyResults(expected_len, actual_len)),
+			cmp::Ordering::Equal => (),
+		};
+
+		let mut tree = self.tree.clone();
+		for (header, block) in self.headers.iter().zip(blocks) {
+			let hash = header.hash();
+			if block.hash != hash {
+				return Err(Error::WrongHash(hash, block.hash));
+			}
+
+			for cmu in block.commitments() {
+				let node = Node::read(&cmu[..]).map_err(|_| Error::BadProof)?;
+				tree.append(node).map_err(|_| Error::BadProof)?;
+			}
+
+			let expected_root = header.final_sapling_root().ok_or(Error::BadProof)?;
+			let found_root = sapling_root(&tree);
+			if found_root != expected_root {
+				trace!(target: "on_demand", "ShieldedOutputs Response: \"WrongTrieRoot\" sapling_root: {:?} found_root: {:?}", expected_root, found_root);
+				return Err(Error::WrongTrieRoot(expected_root, found_root));
+			}
+		}
+
+		Ok(blocks.to_vec())
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1338,4 +1538,104 @@ mod tests {
 		assert!(req.check_response(&cache, &code_hash, &code).is_ok());
 		assert!(req.check_response(&cache, &code_hash, &[]).is_err());
 	}
+
+	#[test]
+	fn check_commitment_tree() {
+		let mut tree = NoteCommitmentTree::<Node>::new();
+		tree.append(Node::read(&H256::from(5)[..]).unwrap()).unwrap();
+		let mut serialized = Vec::new();
+		tree.write(&mut serialized).unwrap();
+
+		let mut header = Header::new();
+		header.set_final_sapling_root(Some(sapling_root(&tree)));
+		let req = CommitmentTree(encoded::Header::new(::rlp::encode(&header)).into());
+
+		let cache = Mutex::new(make_cache());
+		assert!(req.check_response(&cache, &serialized).is_ok());
+
+		let mut empty = Vec::new();
+		NoteCommitmentTree::<Node>::new().write(&mut empty).unwrap();
+		assert!(req.check_response(&cache, &empty).is_err());
+
+		let no_root = CommitmentTree(encoded::Header::new(::rlp::encode(&Header::new())).into());
+		assert!(no_root.check_response(&cache, &serialized).is_err());
+	}
+
+	#[test]
+	fn check_nullifier() {
+		let mut stream = RlpStream::new_list(3);
+		stream.append(&Header::new()).begin_list(0).begin_list(0);
+		let block = encoded::Block::new(stream.out());
+
+		let cache = Mutex::new(make_cache());
+		let req = Nullifier(5.into());
+		assert_eq!(req.check_response(&cache, &None), Ok(None));
+		// a block which doesn't spend the nullifier.
+		assert_eq!(req.check_response(&cache, &Some(block)), Err(Error::BadProof));
+	}
+
+	#[test]
+	fn check_shielded_outputs() {
+		let output = net_request::ShieldedOutput {
+			cv: H256::default(),
+			cmu: H256::from(5),
+			ephemeral_key: H256::default(),
+			enc_ciphertext: vec![0; 580],
+			out_ciphertext: vec![0; 80],
+		};
+
+		let mut tree = NoteCommitmentTree::<Node>::new();
+		let parent_root = sapling_root(&tree);
+		tree.append(Node::read(&output.cmu[..]).unwrap()).unwrap();
+
+		let mut first = Header::new();
+		first.set_number(1);
+		first.set_final_sapling_root(Some(sapling_root(&tree)));
+		let mut second = Header::new();
+		second.set_number(2);
+		second.set_parent_hash(first.hash());
+		second.set_final_sapling_root(Some(sapling_root(&tree)));
+
+		let blocks = vec![
+			net_request::ShieldedBlock {
+				hash: first.hash(),
+				transactions: vec![net_request::ShieldedTransaction {
+					hash: H256::from(1),
+					nullifiers: vec![],
+					outputs: vec![output],
+				}],
+			},
+			net_request::ShieldedBlock {
+				hash: second.hash(),
+				transactions: vec![],
+			},
+		];
+
+		let req = ShieldedOutputs {
+			headers: vec![
+				encoded::Header::new(::rlp::encode(&first)),
+				encoded::Header::new(::rlp::encode(&second)),
+			],
+			tree: NoteCommitmentTree::new(),
+		};
+
+		let cache = Mutex::new(make_cache());
+		assert_eq!(req.check_response(&cache, &blocks), Ok(blocks.clone()));
+		assert_eq!(req.check_response(&cache, &blocks[..1]), Err(Error::TooFewResults(2, 1)));
+
+		// a hidden output changes the root.
+		let mut hidden = blocks.clone();
+		hidden[0].transactions.clear();
+		assert_eq!(
+			req.check_response(&cache, &hidden),
+			Err(Error::WrongTrieRoot(sapling_root(&tree), parent_root))
+		);
+
+		let mut swapped = blocks.clone();
+		swapped.swap(0, 1);
+		assert_eq!(
+			req.check_response(&cache, &swapped),
+			Err(Error::WrongHash(first.hash(), second.hash()))
+		);
+	}
 }
@@ -23,8 +23,8 @@ use common_types::blockchain_info::BlockChainInfo;
 use common_types::encoded;
 use common_types::ids::BlockId;
 use common_types::transaction::PendingTransaction;
-use ethcore::client::{BlockChainClient, ProvingBlockChainClient, ChainInfo, BlockInfo as ClientBlockInfo};
-use ethereum_types::H256;
+use ethcore::client::{BlockChainClient, ProvingBlockChainClient, ChainInfo, BlockInfo as ClientBlockInfo, TransactionInfo};
+use ethereum_types::{H256, U256};
 use parking_lot::RwLock;
 
 use cht::{self, BlockInfo};
@@ -36,6 +36,9 @@ use request;
 /// Maximum allowed size of a headers request.
 pub const MAX_HEADERS_PER_REQUEST: u64 = 512;
 
+/// Maximum number of blocks served in a shielded outputs request.
+pub const MAX_SHIELDED_BLOCKS_PER_REQUEST: u64 = 128;
+
 /// Defines the operations that a provider for the light subprotocol must fulfill.
 pub trait Provider: Send + Sync {
 	/// Provide current blockchain info.
@@ -138,6 +141,18 @@ pub trait Provider: Send + Sync {
 
 	/// Provide epoch signal data at given block hash. This should be just the
 	fn epoch_signal(&self, req: request::CompleteSignalRequest) -> Option<request::SignalResponse>;
+
+	/// Provide the serialized commitment tree as of the end of the given block.
+	fn commitment_tree(&self, req: request::CompleteCommitmentTreeRequest) -> Option<request::CommitmentTreeResponse>;
+
+	/// Provide the canonical block which spends the given nullifier, if any.
+	fn nullifier(&self, req: request::CompleteNullifierRequest) -> Option<request::NullifierResponse>;
+
+	/// Provide the shielded outputs and nullifiers of a range of canonical blocks.
+	///
+	/// The returned vector may have any length in the range [0, `max`], starting
+	/// at the requested block.
+	fn shielded_outputs(&self, req: request::CompleteShieldedOutputsRequest) -> Option<request::ShieldedOutputsResponse>;
 }
 
 // Implementation of a light client data provider for a client.
@@ -298,6 +313,105 @@ impl<T: ProvingBlockChainClient + ?Sized> Provider for T {
 			signal,
 		})
 	}
+
+	fn commitment_tree(&self, req: request::CompleteCommitmentTreeRequest) -> Option<request::CommitmentTreeResponse> {
+		ProvingBlockChainClient::commitment_tree(self, req.block_hash).map(|tree| request::CommitmentTreeResponse {
+			tree,
+		})
+	}
+
+	fn nullifier(&self, req: request::CompleteNullifierRequest) -> Option<request::NullifierResponse> {
+		use common_types::ids::TransactionId;
+
+		let block = match self.nullifier_transaction(&req.nullifier) {
+			Some(hash) => {
+				let block_hash = self.transaction_block(TransactionId::Hash(hash))?;
+				Some(ClientBlockInfo::block(self, BlockId::Hash(block_hash))?)
+			}
+			None => None,
+		};
+
+		Some(request::NullifierResponse { block })
+	}
+
+	fn shielded_outputs(&self, req: request::CompleteShieldedOutputsRequest) -> Option<request::ShieldedOutputsResponse> {
+		use request::HashOrNumber;
+
+		if req.max == 0 { return None }
+
+		let start_num = match req.start {
+			HashOrNumber::Number(start_num) => start_num,
+			HashOrNumber::Hash(hash) => {
+				let num = self.block_header(BlockId::Hash(hash))?.number();
+				let canon_hash = self.block_header(BlockId::Number(num))
+					.map(|h| h.hash());
+
+				if canon_hash != Some(hash) {
+					trace!(target: "pip_provider", "Shielded outputs requested from non-canonical block {}", hash);
+					return None;
+				}
+
+				num
+			}
+		};
+
+		let max = ::std::cmp::min(MAX_SHIELDED_BLOCKS_PER_REQUEST, req.max);
+
+		let blocks: Vec<_> = (0_u64..max)
+			.map(|x| ClientBlockInfo::block(self, BlockId::Number(start_num.saturating_add(x))))
+			.take_while(|x| x.is_some())
+			.flat_map(|x| x)
+			.map(|block| shielded_block(&block))
+			.collect();
+
+		if blocks.is_empty() {
+			None
+		} else {
+			Some(request::ShieldedOutputsResponse { blocks })
+		}
+	}
+}
+
+// Strip a block down to the shielded parts of its private transactions.
+fn shielded_block(block: &encoded::Block) -> request::ShieldedBlock {
+	use ff::{PrimeField, PrimeFieldRepr};
+
+	let transactions = block.transactions().into_iter()
+		.filter(|tx| tx.is_private())
+		.map(|tx| {
+			let nullifiers = tx.v_shielded_spend().into_iter()
+				.map(|spend| U256::from(spend.nullifier))
+				.collect();
+
+			let outputs = tx.v_shielded_output().into_iter()
+				.map(|output| {
+					let (mut cv, mut cmu, mut ephemeral_key) = (H256::zero(), H256::zero(), H256::zero());
+					output.cv.write(&mut cv[..]).expect("points are 32 bytes; qed");
+					output.cmu.into_repr().write_le(&mut cmu[..]).expect("field elements are 32 bytes; qed");
+					output.ephemeral_key.write(&mut ephemeral_key[..]).expect("points are 32 bytes; qed");
+
+					request::ShieldedOutput {
+						cv,
+						cmu,
+						ephemeral_key,
+						enc_ciphertext: output.enc_ciphertext.to_vec(),
+						out_ciphertext: output.out_ciphertext.to_vec(),
+					}
+				})
+				.collect();
+
+			request::ShieldedTransaction {
+				hash: tx.hash(),
+				nullifiers,
+				outputs,
+			}
+		})
+		.collect();
+
+	request::ShieldedBlock {
+		hash: block.hash(),
+		transactions,
+	}
 }
 
 /// The light client "provider" implementation. This wraps a `LightClient` and
@@ -373,6 +487,18 @@ impl<L: AsLightClient + Send + Sync> Provider for LightProvider<L> {
 		None
 	}
 
+	fn commitment_tree(&self, _req: request::CompleteCommitmentTreeRequest) -> Option<request::CommitmentTreeResponse> {
+		None
+	}
+
+	fn nullifier(&self, _req: request::CompleteNullifierRequest) -> Option<request::NullifierResponse> {
+		None
+	}
+
+	fn shielded_outputs(&self, _req: request::CompleteShieldedOutputsRequest) -> Option<request::ShieldedOutputsResponse> {
+		None
+	}
+
 	fn transactions_to_propagate(&self) -> Vec<PendingTransaction> {
 		let chain_info = self.chain_info();
 		self.txqueue.read()
@@ -22,6 +22,9 @@
 //! Under the assumption that light nodes will have a relatively limited set of
 //! accounts for which they create transactions, this queue is structured in an
 //! address-wise manner.
+//!
+//! Transactions spending shielded notes have no real sender nor nonce: they are
+//! stored apart, by the nullifiers they reveal, and removed once one of them is spent.
 
 use std::fmt;
 use std::collections::{BTreeMap, HashMap};
@@ -133,6 +136,9 @@ type Listener = Box<Fn(&[H256]) + Send + Sync>;
 pub struct TransactionQueue {
 	by_account: HashMap<Address, AccountTransactions>,
 	by_hash: H256FastMap<PendingTransaction>,
+	// transactions spending shielded notes, in order of import.
+	private: Vec<TransactionInfo>,
+	by_nullifier: HashMap<U256, H256>,
 	listeners: Vec<Listener>,
 }
 
@@ -141,6 +147,8 @@ impl fmt::Debug for TransactionQueue {
 		fmt.debug_struct("TransactionQueue")
 			.field("by_account", &self.by_account)
 			.field("by_hash", &self.by_hash)
+			.field("private", &self.private)
+			.field("by_nullifier", &self.by_nullifier)
 			.field("listeners", &self.listeners.len())
 			.finish()
 	}
@@ -155,6 +163,7 @@ impl TransactionQueue {
 		let tx_info = TransactionInfo::from(&tx);
 
 		if self.by_hash.contains_key(&hash) { return Err(transaction::Error::AlreadyImported) }
+		if tx.action.is_input_private() { return self.import_private(tx) }
 
 		let (res, promoted) = match self.by_account.entry(sender) {
 			Entry::Vacant(entry) => {
@@ -235,6 +244,24 @@ impl TransactionQueue {
 		Ok(res)
 	}
 
+	// Import a transaction spending shielded notes, unless one of them is spent by a queued transaction.
+	fn import_private(&mut self, tx: PendingTransaction) -> Result<ImportDestination, transaction::Error> {
+		let hash = tx.hash();
+		let nullifiers = tx.get_nullifier_set();
+
+		if nullifiers.iter().any(|nullifier| self.by_nullifier.contains_key(nullifier)) {
+			return Err(transaction::Error::ConflictNullifier)
+		}
+
+		trace!(target: "txqueue", "Queued private transaction {} spending {} notes", hash, nullifiers.len());
+
+		self.by_nullifier.extend(nullifiers.into_iter().map(|nullifier| (nullifier, hash)));
+		self.private.push(TransactionInfo::from(&tx));
+		self.by_hash.insert(hash, tx);
+		self.notify(&[hash]);
+		Ok(ImportDestination::Current)
+	}
+
 	/// Get pending transaction by hash.
 	pub fn transaction(&self, hash: &H256) -> Option<SignedTransaction> {
 		self.by_hash.get(hash).map(|tx| (&**tx).clone())
@@ -251,16 +278,20 @@ impl TransactionQueue {
 	/// `best_block_number` and `best_block_timestamp` are used to filter out conditionally
 	/// propagated transactions.
 	///
-	/// Returned transactions are batched by sender, in order of ascending nonce.
+	/// Returned transactions are batched by sender, in order of ascending nonce,
+	/// followed by the private transactions in order of import.
 	pub fn ready_transactions(&self, best_block_number: u64, best_block_timestamp: u64) -> Vec<PendingTransaction> {
+		let is_ready = |tx: &&TransactionInfo| match tx.condition {
+			None => true,
+			Some(Condition::Number(blk_num)) => blk_num <= best_block_number,
+			Some(Condition::Timestamp(time)) => time <= best_block_timestamp,
+		};
+
 		self.by_account.values()
 			.flat_map(|acct_txs| {
-				acct_txs.current.iter().take_while(|tx| match tx.condition {
-					None => true,
-					Some(Condition::Number(blk_num)) => blk_num <= best_block_number,
-					Some(Condition::Timestamp(time)) => time <= best_block_timestamp,
-				}).map(|info| info.hash)
+				acct_txs.current.iter().take_while(&is_ready).map(|info| info.hash)
 			})
+			.chain(self.private.iter().filter(&is_ready).map(|info| info.hash))
 			.filter_map(|hash| match self.by_hash.get(&hash) {
 				Some(tx) => Some(tx.clone()),
 				None => {
@@ -276,16 +307,20 @@ impl TransactionQueue {
 	/// `best_block_number` and `best_block_timestamp` are used to filter out conditionally
 	/// propagated transactions.
 	///
-	/// Returned transactions are batched by sender, in order of ascending nonce.
+	/// Returned transactions are batched by sender, in order of ascending nonce,
+	/// followed by the private transactions in order of import.
 	pub fn future_transactions(&self, best_block_number: u64, best_block_timestamp: u64) -> Vec<PendingTransaction> {
+		let is_ready = |tx: &&TransactionInfo| match tx.condition {
+			None => true,
+			Some(Condition::Number(blk_num)) => blk_num <= best_block_number,
+			Some(Condition::Timestamp(time)) => time <= best_block_timestamp,
+		};
+
 		self.by_account.values()
 			.flat_map(|acct_txs| {
-				acct_txs.current.iter().skip_while(|tx| match tx.condition {
-					None => true,
-					Some(Condition::Number(blk_num)) => blk_num <= best_block_number,
-					Some(Condition::Timestamp(time)) => time <= best_block_timestamp,
-				}).chain(acct_txs.future.values()).map(|info| info.hash)
+				acct_txs.current.iter().skip_while(&is_ready).chain(acct_txs.future.values()).map(|info| info.hash)
 			})
+			.chain(self.private.iter().filter(|tx| !is_ready(tx)).map(|info| info.hash))
 			.filter_map(|hash| match self.by_hash.get(&hash) {
 				Some(tx) => Some(tx.clone()),
 				None => {
@@ -298,10 +333,30 @@ impl TransactionQueue {
 	}
 
 	/// Addresses for which we store transactions.
+	/// Private transactions have no sender and aren't accounted for.
 	pub fn queued_senders(&self) -> Vec<Address> {
 		self.by_account.keys().cloned().collect()
 	}
 
+	/// Nullifiers revealed by the queued private transactions.
+	pub fn queued_nullifiers(&self) -> Vec<U256> {
+		self.by_nullifier.keys().cloned().collect()
+	}
+
+	/// Cull out the private transaction revealing the given nullifier, which was spent.
+	pub fn cull_nullifier(&mut self, nullifier: &U256) {
+		let hash = match self.by_nullifier.get(nullifier) {
+			Some(hash) => *hash,
+			None => return,
+		};
+
+		self.by_nullifier.retain(|_, tx_hash| *tx_hash != hash);
+		self.private.retain(|info| info.hash != hash);
+		self.by_hash.remove(&hash);
+
+		trace!(target: "txqueue", "Culled private transaction {} (nullifier={})", hash, nullifier);
+	}
+
 	/// Cull out all transactions by the given address which are invalidated by the given nonce.
 	pub fn cull(&mut self, address: Address, cur_nonce: U256) {
 		let mut removed_hashes = vec![];
@@ -369,8 +424,8 @@ impl TransactionQueue {
 #[cfg(test)]
 mod tests {
 	use super::TransactionQueue;
-	use ethereum_types::Address;
-	use common_types::transaction::{Transaction, PendingTransaction, Condition};
+	use ethereum_types::{Address, U256};
+	use common_types::transaction::{self, Action, Transaction, PendingTransaction, Condition};
 
 	#[test]
 	fn queued_senders() {
@@ -558,4 +613,41 @@ mod tests {
 		assert_eq!(txq.future_transactions(0, 0).len(), 7);
 		assert_eq!(txq.next_nonce(&sender).unwrap(), 1.into());
 	}
+
+	#[test]
+	fn private_transactions() {
+		let mut txq = TransactionQueue::default();
+
+		let tx_a = Transaction::create_private(vec![1, 2], 0.into(), Action::PrivateToPrivate).sign_for_private(0);
+		let tx_b = Transaction::create_private(vec![3], 0.into(), Action::PrivateToPrivate).sign_for_private(0);
+		let hash_a = tx_a.hash();
+
+		txq.import(tx_a.into()).unwrap();
+		txq.import(PendingTransaction::new(tx_b, Some(Condition::Number(100)))).unwrap();
+
+		assert!(txq.queued_senders().is_empty());
+		assert_eq!(txq.queued_nullifiers().len(), 3);
+		assert_eq!(txq.ready_transactions(0, 0).len(), 1);
+		assert_eq!(txq.future_transactions(0, 0).len(), 1);
+		assert_eq!(txq.ready_transactions(100, 0).len(), 2);
+
+		txq.cull_nullifier(&U256::from([2u8; 32]));
+
+		assert!(txq.transaction(&hash_a).is_none());
+		assert_eq!(txq.queued_nullifiers(), vec![U256::from([3u8; 32])]);
+		assert_eq!(txq.ready_transactions(100, 0).len(), 1);
+	}
+
+	#[test]
+	fn conflicting_nullifier() {
+		let mut txq = TransactionQueue::default();
+
+		let tx_a = Transaction::create_private(vec![1, 2], 0.into(), Action::PrivateToPrivate).sign_for_private(0);
+		let tx_b = Transaction::create_private(vec![2, 3], 1.into(), Action::PrivateToPrivate).sign_for_private(0);
+
+		txq.import(tx_a.into()).unwrap();
+
+		assert_eq!(txq.import(tx_b.into()).err(), Some(transaction::Error::ConflictNullifier));
+		assert_eq!(txq.queued_nullifiers().len(), 2);
+	}
 }
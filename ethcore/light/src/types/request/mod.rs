@@ -72,6 +72,22 @@ pub use self::epoch_signal::{
 	Incomplete as IncompleteSignalRequest,
 	Response as SignalResponse,
 };
+pub use self::commitment_tree::{
+	Complete as CompleteCommitmentTreeRequest,
+	Incomplete as IncompleteCommitmentTreeRequest,
+	Response as CommitmentTreeResponse,
+};
+pub use self::nullifier::{
+	Complete as CompleteNullifierRequest,
+	Incomplete as IncompleteNullifierRequest,
+	Response as NullifierResponse,
+};
+pub use self::shielded_outputs::{
+	Complete as CompleteShieldedOutputsRequest,
+	Incomplete as IncompleteShieldedOutputsRequest,
+	Response as ShieldedOutputsResponse,
+	ShieldedBlock, ShieldedTransaction, ShieldedOutput,
+};
 
 pub use self::batch::{Batch, Builder};
 
@@ -265,6 +281,12 @@ pub enum Request {
 	Execution(IncompleteExecutionRequest),
 	/// A request for an epoch signal.
 	Signal(IncompleteSignalRequest),
+	/// A request for the Sapling commitment tree after a block.
+	CommitmentTree(IncompleteCommitmentTreeRequest),
+	/// A request for the block spending a nullifier.
+	Nullifier(IncompleteNullifierRequest),
+	/// A request for the shielded outputs of a range of blocks.
+	ShieldedOutputs(IncompleteShieldedOutputsRequest),
 }
 
 /// All request types, in an answerable state.
@@ -290,6 +312,12 @@ pub enum CompleteRequest {
 	Execution(CompleteExecutionRequest),
 	/// A request for an epoch signal.
 	Signal(CompleteSignalRequest),
+	/// A request for the Sapling commitment tree after a block.
+	CommitmentTree(CompleteCommitmentTreeRequest),
+	/// A request for the block spending a nullifier.
+	Nullifier(CompleteNullifierRequest),
+	/// A request for the shielded outputs of a range of blocks.
+	ShieldedOutputs(CompleteShieldedOutputsRequest),
 }
 
 impl CompleteRequest {
@@ -306,6 +334,9 @@ impl CompleteRequest {
 			CompleteRequest::Code(_) => Kind::Code,
 			CompleteRequest::Execution(_) => Kind::Execution,
 			CompleteRequest::Signal(_) => Kind::Signal,
+			CompleteRequest::CommitmentTree(_) => Kind::CommitmentTree,
+			CompleteRequest::Nullifier(_) => Kind::Nullifier,
+			CompleteRequest::ShieldedOutputs(_) => Kind::ShieldedOutputs,
 		}
 	}
 }
@@ -324,6 +355,9 @@ impl Request {
 			Request::Code(_) => Kind::Code,
 			Request::Execution(_) => Kind::Execution,
 			Request::Signal(_) => Kind::Signal,
+			Request::CommitmentTree(_) => Kind::CommitmentTree,
+			Request::Nullifier(_) => Kind::Nullifier,
+			Request::ShieldedOutputs(_) => Kind::ShieldedOutputs,
 		}
 	}
 }
@@ -341,6 +375,9 @@ impl Decodable for Request {
 			Kind::Code => Ok(Request::Code(rlp.val_at(1)?)),
 			Kind::Execution => Ok(Request::Execution(rlp.val_at(1)?)),
 			Kind::Signal => Ok(Request::Signal(rlp.val_at(1)?)),
+			Kind::CommitmentTree => Ok(Request::CommitmentTree(rlp.val_at(1)?)),
+			Kind::Nullifier => Ok(Request::Nullifier(rlp.val_at(1)?)),
+			Kind::ShieldedOutputs => Ok(Request::ShieldedOutputs(rlp.val_at(1)?)),
 		}
 	}
 }
@@ -363,6 +400,9 @@ impl Encodable for Request {
 			Request::Code(ref req) => s.append(req),
 			Request::Execution(ref req) => s.append(req),
 			Request::Signal(ref req) => s.append(req),
+			Request::CommitmentTree(ref req) => s.append(req),
+			Request::Nullifier(ref req) => s.append(req),
+			Request::ShieldedOutputs(ref req) => s.append(req),
 		};
 	}
 }
@@ -385,6 +425,9 @@ impl IncompleteRequest for Request {
 			Request::Code(ref req) => req.check_outputs(f),
 			Request::Execution(ref req) => req.check_outputs(f),
 			Request::Signal(ref req) => req.check_outputs(f),
+			Request::CommitmentTree(ref req) => req.check_outputs(f),
+			Request::Nullifier(ref req) => req.check_outputs(f),
+			Request::ShieldedOutputs(ref req) => req.check_outputs(f),
 		}
 	}
 
@@ -400,6 +443,9 @@ impl IncompleteRequest for Request {
 			Request::Code(ref req) => req.note_outputs(f),
 			Request::Execution(ref req) => req.note_outputs(f),
 			Request::Signal(ref req) => req.note_outputs(f),
+			Request::CommitmentTree(ref req) => req.note_outputs(f),
+			Request::Nullifier(ref req) => req.note_outputs(f),
+			Request::ShieldedOutputs(ref req) => req.note_outputs(f),
 		}
 	}
 
@@ -415,6 +461,9 @@ impl IncompleteRequest for Request {
 			Request::Code(ref mut req) => req.fill(oracle),
 			Request::Execution(ref mut req) => req.fill(oracle),
 			Request::Signal(ref mut req) => req.fill(oracle),
+			Request::CommitmentTree(ref mut req) => req.fill(oracle),
+			Request::Nullifier(ref mut req) => req.fill(oracle),
+			Request::ShieldedOutputs(ref mut req) => req.fill(oracle),
 		}
 	}
 
@@ -430,6 +479,9 @@ impl IncompleteRequest for Request {
 			Request::Code(req) => req.complete().map(CompleteRequest::Code),
 			Request::Execution(req) => req.complete().map(CompleteRequest::Execution),
 			Request::Signal(req) => req.complete().map(CompleteRequest::Signal),
+			Request::CommitmentTree(req) => req.complete().map(CompleteRequest::CommitmentTree),
+			Request::Nullifier(req) => req.complete().map(CompleteRequest::Nullifier),
+			Request::ShieldedOutputs(req) => req.complete().map(CompleteRequest::ShieldedOutputs),
 		}
 	}
 
@@ -445,6 +497,9 @@ impl IncompleteRequest for Request {
 			Request::Code(ref mut req) => req.adjust_refs(mapping),
 			Request::Execution(ref mut req) => req.adjust_refs(mapping),
 			Request::Signal(ref mut req) => req.adjust_refs(mapping),
+			Request::CommitmentTree(ref mut req) => req.adjust_refs(mapping),
+			Request::Nullifier(ref mut req) => req.adjust_refs(mapping),
+			Request::ShieldedOutputs(ref mut req) => req.adjust_refs(mapping),
 		}
 	}
 }
@@ -488,6 +543,12 @@ pub enum Kind {
 	Execution = 8,
 	/// A request for epoch transition signal.
 	Signal = 9,
+	/// A request for the Sapling commitment tree after a block.
+	CommitmentTree = 10,
+	/// A request for the block spending a nullifier.
+	Nullifier = 11,
+	/// A request for the shielded outputs of a range of blocks.
+	ShieldedOutputs = 12,
 }
 
 impl Decodable for Kind {
@@ -503,6 +564,9 @@ impl Decodable for Kind {
 			7 => Ok(Kind::Code),
 			8 => Ok(Kind::Execution),
 			9 => Ok(Kind::Signal),
+			10 => Ok(Kind::CommitmentTree),
+			11 => Ok(Kind::Nullifier),
+			12 => Ok(Kind::ShieldedOutputs),
 			_ => Err(DecoderError::Custom("Unknown PIP request ID.")),
 		}
 	}
@@ -537,6 +601,12 @@ pub enum Response {
 	Execution(ExecutionResponse),
 	/// A response for epoch change signal.
 	Signal(SignalResponse),
+	/// A response for the Sapling commitment tree after a block.
+	CommitmentTree(CommitmentTreeResponse),
+	/// A response for the block spending a nullifier.
+	Nullifier(NullifierResponse),
+	/// A response for the shielded outputs of a range of blocks.
+	ShieldedOutputs(ShieldedOutputsResponse),
 }
 
 impl ResponseLike for Response {
@@ -553,6 +623,9 @@ impl ResponseLike for Response {
 			Response::Code(ref res) => res.fill_outputs(f),
 			Response::Execution(ref res) => res.fill_outputs(f),
 			Response::Signal(ref res) => res.fill_outputs(f),
+			Response::CommitmentTree(ref res) => res.fill_outputs(f),
+			Response::Nullifier(ref res) => res.fill_outputs(f),
+			Response::ShieldedOutputs(ref res) => res.fill_outputs(f),
 		}
 	}
 }
@@ -571,6 +644,9 @@ impl Response {
 			Response::Code(_) => Kind::Code,
 			Response::Execution(_) => Kind::Execution,
 			Response::Signal(_) => Kind::Signal,
+			Response::CommitmentTree(_) => Kind::CommitmentTree,
+			Response::Nullifier(_) => Kind::Nullifier,
+			Response::ShieldedOutputs(_) => Kind::ShieldedOutputs,
 		}
 	}
 }
@@ -588,6 +664,9 @@ impl Decodable for Response {
 			Kind::Code => Ok(Response::Code(rlp.val_at(1)?)),
 			Kind::Execution => Ok(Response::Execution(rlp.val_at(1)?)),
 			Kind::Signal => Ok(Response::Signal(rlp.val_at(1)?)),
+			Kind::CommitmentTree => Ok(Response::CommitmentTree(rlp.val_at(1)?)),
+			Kind::Nullifier => Ok(Response::Nullifier(rlp.val_at(1)?)),
+			Kind::ShieldedOutputs => Ok(Response::ShieldedOutputs(rlp.val_at(1)?)),
 		}
 	}
 }
@@ -610,6 +689,9 @@ impl Encodable for Response {
 			Response::Code(ref res) => s.append(res),
 			Response::Execution(ref res) => s.append(res),
 			Response::Signal(ref res) => s.append(res),
+			Response::CommitmentTree(ref res) => s.append(res),
+			Response::Nullifier(ref res) => s.append(res),
+			Response::ShieldedOutputs(ref res) => s.append(res),
 		};
 	}
 }
@@ -1626,6 +1708,340 @@ pub mod epoch_signal {
 	}
 }
 
+/// A request for the Sapling note commitment tree after a block.
+pub mod commitment_tree {
+	use super::{Field, NoSuchOutput, OutputKind, Output};
+	use rlp::{Encodable, Decodable, DecoderError, RlpStream, Rlp};
+	use ethereum_types::H256;
+	use bytes::Bytes;
+
+	/// Potentially incomplete commitment tree request.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct Incomplete {
+		/// The block hash to request the commitment tree after.
+		pub block_hash: Field<H256>,
+	}
+
+	impl Decodable for Incomplete {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			Ok(Incomplete {
+				block_hash: rlp.val_at(0)?,
+			})
+		}
+	}
+
+	impl Encodable for Incomplete {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			s.begin_list(1).append(&self.block_hash);
+		}
+	}
+
+	impl super::IncompleteRequest for Incomplete {
+		type Complete = Complete;
+		type Response = Response;
+
+		fn check_outputs<F>(&self, mut f: F) -> Result<(), NoSuchOutput>
+			where F: FnMut(usize, usize, OutputKind) -> Result<(), NoSuchOutput>
+		{
+			if let Field::BackReference(req, idx) = self.block_hash {
+				f(req, idx, OutputKind::Hash)?;
+			}
+
+			Ok(())
+		}
+
+		fn note_outputs<F>(&self, _: F) where F: FnMut(usize, OutputKind) {}
+
+		fn fill<F>(&mut self, oracle: F) where F: Fn(usize, usize) -> Result<Output, NoSuchOutput> {
+			if let Field::BackReference(req, idx) = self.block_hash {
+				self.block_hash = match oracle(req, idx) {
+					Ok(Output::Hash(block_hash)) => Field::Scalar(block_hash),
+					_ => Field::BackReference(req, idx),
+				}
+			}
+		}
+
+		fn complete(self) -> Result<Self::Complete, NoSuchOutput> {
+			Ok(Complete {
+				block_hash: self.block_hash.into_scalar()?,
+			})
+		}
+
+		fn adjust_refs<F>(&mut self, mapping: F) where F: FnMut(usize) -> usize {
+			self.block_hash.adjust_req(mapping)
+		}
+	}
+
+	/// A complete commitment tree request.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct Complete {
+		/// The block hash to request the commitment tree after.
+		pub block_hash: H256,
+	}
+
+	/// The output of a request for a commitment tree.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct Response {
+		/// The commitment tree, as serialized by `CommitmentTree::write`.
+		pub tree: Bytes,
+	}
+
+	impl super::ResponseLike for Response {
+		/// Fill reusable outputs by providing them to the function.
+		fn fill_outputs<F>(&self, _: F) where F: FnMut(usize, Output) {}
+	}
+
+	impl Decodable for Response {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			Ok(Response {
+				tree: rlp.as_val()?,
+			})
+		}
+	}
+
+	impl Encodable for Response {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			s.append(&self.tree);
+		}
+	}
+}
+
+/// A request for the block revealing a nullifier.
+///
+/// The chain doesn't commit to the set of nullifiers revealed, so only a spend
+/// can be checked, against the transactions root of the block returned.
+pub mod nullifier {
+	use super::{Field, NoSuchOutput, OutputKind, Output};
+	use common_types::encoded;
+	use rlp::{Encodable, Decodable, DecoderError, RlpStream, Rlp};
+	use ethereum_types::U256;
+
+	/// Potentially incomplete nullifier request.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct Incomplete {
+		/// The nullifier to look for.
+		pub nullifier: Field<U256>,
+	}
+
+	impl Decodable for Incomplete {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			Ok(Incomplete {
+				nullifier: rlp.val_at(0)?,
+			})
+		}
+	}
+
+	impl Encodable for Incomplete {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			s.begin_list(1).append(&self.nullifier);
+		}
+	}
+
+	impl super::IncompleteRequest for Incomplete {
+		type Complete = Complete;
+		type Response = Response;
+
+		fn check_outputs<F>(&self, mut f: F) -> Result<(), NoSuchOutput>
+			where F: FnMut(usize, usize, OutputKind) -> Result<(), NoSuchOutput>
+		{
+			if let Field::BackReference(req, idx) = self.nullifier {
+				f(req, idx, OutputKind::Hash)?;
+			}
+
+			Ok(())
+		}
+
+		fn note_outputs<F>(&self, _: F) where F: FnMut(usize, OutputKind) {}
+
+		fn fill<F>(&mut self, oracle: F) where F: Fn(usize, usize) -> Result<Output, NoSuchOutput> {
+			if let Field::BackReference(req, idx) = self.nullifier {
+				self.nullifier = match oracle(req, idx) {
+					Ok(Output::Hash(hash)) => Field::Scalar(U256::from(&*hash)),
+					_ => Field::BackReference(req, idx),
+				}
+			}
+		}
+
+		fn complete(self) -> Result<Self::Complete, NoSuchOutput> {
+			Ok(Complete {
+				nullifier: self.nullifier.into_scalar()?,
+			})
+		}
+
+		fn adjust_refs<F>(&mut self, mapping: F) where F: FnMut(usize) -> usize {
+			self.nullifier.adjust_req(mapping)
+		}
+	}
+
+	/// A complete nullifier request.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct Complete {
+		/// The nullifier to look for.
+		pub nullifier: U256,
+	}
+
+	/// The output of a request for a nullifier.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct Response {
+		/// The canonical block whose transactions reveal the nullifier,
+		/// `None` if it isn't spent yet.
+		pub block: Option<encoded::Block>,
+	}
+
+	impl super::ResponseLike for Response {
+		/// Fill reusable outputs by providing them to the function.
+		fn fill_outputs<F>(&self, _: F) where F: FnMut(usize, Output) {}
+	}
+
+	impl Decodable for Response {
+		fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+			use common_types::header::Header as FullHeader;
+			use common_types::transaction::UnverifiedTransaction;
+
+			if rlp.item_count()? == 0 {
+				return Ok(Response { block: None });
+			}
+
+			// check block validity.
+			let _: FullHeader = rlp.val_at(0)?;
+			let _: Vec<UnverifiedTransaction> = rlp.list_at(1)?;
+			let _: Vec<FullHeader> = rlp.list_at(2)?;
+
+			Ok(Response {
+				block: Some(encoded::Block::new(rlp.as_raw().to_owned())),
+			})
+		}
+	}
+
+	impl Encodable for Response {
+		fn rlp_append(&self, s: &mut RlpStream) {
+			match self.block {
+				Some(ref block) => s.append_raw(block.rlp().as_raw(), 1),
+				None => s.begin_list(0),
+			};
+		}
+	}
+}
+
+/// A request for the shielded outputs and nullifiers of a range of canonical blocks.
+pub mod shielded_outputs {
+	use super::{Field, HashOrNumber, NoSuchOutput, OutputKind, Output};
+	use ethereum_types::{H256, U256};
+	use bytes::Bytes;
+
+	/// Potentially incomplete shielded outputs request.
+	#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+	pub struct Incomplete {
+		/// Start block.
+		pub start: Field<HashOrNumber>,
+		/// Maximum number of blocks to return.
+		pub max: u64,
+	}
+
+	impl super::IncompleteRequest for Incomplete {
+		type Complete = Complete;
+		type Response = Response;
+
+		fn check_outputs<F>(&self, mut f: F) -> Result<(), NoSuchOutput>
+			where F: FnMut(usize, usize, OutputKind) -> Result<(), NoSuchOutput>
+		{
+			match self.start {
+				Field::Scalar(_) => Ok(()),
+				Field::BackReference(req, idx) =>
+					f(req, idx, OutputKind::Hash).or_else(|_| f(req, idx, OutputKind::Number))
+			}
+		}
+
+		fn note_outputs<F>(&self, _: F) where F: FnMut(usize, OutputKind) { }
+
+		fn fill<F>(&mut self, oracle: F) where F: Fn(usize, usize) -> Result<Output, NoSuchOutput> {
+			if let Field::BackReference(req, idx) = self.start {
+				self.start = match oracle(req, idx) {
+					Ok(Output::Hash(hash)) => Field::Scalar(hash.into()),
+					Ok(Output::Number(num)) => Field::Scalar(num.into()),
+					Err(_) => Field::BackReference(req, idx),
+				}
+			}
+		}
+
+		fn complete(self) -> Result<Self::Complete, NoSuchOutput> {
+			Ok(Complete {
+				start: self.start.into_scalar()?,
+				max: self.max,
+			})
+		}
+
+		fn adjust_refs<F>(&mut self, mapping: F) where F: FnMut(usize) -> usize {
+			self.start.adjust_req(mapping)
+		}
+	}
+
+	/// A complete shielded outputs request.
+	#[derive(Debug, Clone, PartialEq, Eq)]
+	pub struct Complete {
+		/// Start block.
+		pub start: HashOrNumber,
+		/// Maximum number of blocks to return.
+		pub max: u64,
+	}
+
+	/// A shielded output, without its proof.
+	#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+	pub struct ShieldedOutput {
+		/// The value commitment.
+		pub cv: H256,
+		/// The note commitment, as appended to the commitment tree.
+		pub cmu: H256,
+		/// The ephemeral public key of the note encryption.
+		pub ephemeral_key: H256,
+		/// The note encrypted to the recipient.
+		pub enc_ciphertext: Bytes,
+		/// The note encrypted to the sender.
+		pub out_ciphertext: Bytes,
+	}
+
+	/// The shielded part of a private transaction.
+	#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+	pub struct ShieldedTransaction {
+		/// The transaction hash.
+		pub hash: H256,
+		/// The nullifiers revealed by the spends.
+		pub nullifiers: Vec<U256>,
+		/// The outputs, in order.
+		pub outputs: Vec<ShieldedOutput>,
+	}
+
+	/// The private transactions of a block.
+	#[derive(Debug, Clone, PartialEq, Eq, RlpEncodable, RlpDecodable)]
+	pub struct ShieldedBlock {
+		/// The block hash.
+		pub hash: H256,
+		/// The private transactions, in order.
+		pub transactions: Vec<ShieldedTransaction>,
+	}
+
+	impl ShieldedBlock {
+		/// The note commitments of the block, in the order they are appended to the tree.
+		pub fn commitments(&self) -> Vec<H256> {
+			self.transactions.iter()
+				.flat_map(|tx| tx.outputs.iter().map(|output| output.cmu))
+				.collect()
+		}
+	}
+
+	/// The output of a request for shielded outputs.
+	#[derive(Debug, Clone, PartialEq, Eq, RlpEncodableWrapper, RlpDecodableWrapper)]
+	pub struct Response {
+		/// The blocks, from the start block onwards.
+		pub blocks: Vec<ShieldedBlock>,
+	}
+
+	impl super::ResponseLike for Response {
+		/// Fill reusable outputs by providing them to the function.
+		fn fill_outputs<F>(&self, _: F) where F: FnMut(usize, Output) {}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -1935,4 +2351,89 @@ mod tests {
 		check_roundtrip(res);
 		check_roundtrip(full_res);
 	}
+
+	#[test]
+	fn commitment_tree_roundtrip() {
+		let req = IncompleteCommitmentTreeRequest {
+			block_hash: Field::Scalar(Default::default()),
+		};
+
+		let full_req = Request::CommitmentTree(req.clone());
+		let res = CommitmentTreeResponse {
+			tree: vec![1, 2, 3, 4, 5, 6, 7, 6, 5, 4],
+		};
+		let full_res = Response::CommitmentTree(res.clone());
+
+		check_roundtrip(req);
+		check_roundtrip(full_req);
+		check_roundtrip(res);
+		check_roundtrip(full_res);
+	}
+
+	#[test]
+	fn nullifier_roundtrip() {
+		use rlp::RlpStream;
+
+		let req = IncompleteNullifierRequest {
+			nullifier: Field::Scalar(12345.into()),
+		};
+
+		let full_req = Request::Nullifier(req.clone());
+		let unspent = NullifierResponse {
+			block: None,
+		};
+		let full_unspent = Response::Nullifier(unspent.clone());
+
+		let mut stream = RlpStream::new_list(3);
+		stream.append(&Header::default()).begin_list(0).begin_list(0);
+		let spent = NullifierResponse {
+			block: Some(::common_types::encoded::Block::new(stream.out())),
+		};
+		let full_spent = Response::Nullifier(spent.clone());
+
+		check_roundtrip(req);
+		check_roundtrip(full_req);
+		check_roundtrip(unspent);
+		check_roundtrip(full_unspent);
+		check_roundtrip(spent);
+		check_roundtrip(full_spent);
+	}
+
+	#[test]
+	fn shielded_outputs_roundtrip() {
+		let req = IncompleteShieldedOutputsRequest {
+			start: Field::Scalar(5u64.into()),
+			max: 100,
+		};
+
+		let full_req = Request::ShieldedOutputs(req.clone());
+		let res = ShieldedOutputsResponse {
+			blocks: vec![
+				ShieldedBlock {
+					hash: H256::random(),
+					transactions: vec![ShieldedTransaction {
+						hash: H256::random(),
+						nullifiers: vec![1.into(), 2.into()],
+						outputs: vec![ShieldedOutput {
+							cv: H256::random(),
+							cmu: H256::random(),
+							ephemeral_key: H256::random(),
+							enc_ciphertext: vec![3; 580],
+							out_ciphertext: vec![4; 80],
+						}],
+					}],
+				},
+				ShieldedBlock {
+					hash: H256::random(),
+					transactions: vec![],
+				},
+			],
+		};
+		let full_res = Response::ShieldedOutputs(res.clone());
+
+		check_roundtrip(req);
+		check_roundtrip(full_req);
+		check_roundtrip(res);
+		check_roundtrip(full_res);
+	}
 }
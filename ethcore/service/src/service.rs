@@ -191,6 +191,9 @@ impl IoHandler<ClientIoMessage> for ClientIoHandler {
 			ClientIoMessage::FeedBlockChunk(ref hash, ref chunk) => {
 				self.snapshot.feed_block_chunk(*hash, chunk)
 			}
+			ClientIoMessage::FeedShieldedChunk(ref hash, ref chunk) => {
+				self.snapshot.feed_shielded_chunk(*hash, chunk)
+			}
 			ClientIoMessage::TakeSnapshot(num) => {
 				let client = self.client.clone();
 				let snapshot = self.snapshot.clone();
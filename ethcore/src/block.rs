@@ -49,8 +49,11 @@ use unexpected::{Mismatch, OutOfBounds};
 use verification::PreverifiedBlock;
 use vm::{EnvInfo, LastHashes};
 
+use ff::PrimeField;
 use hash::keccak;
 use rlp::{RlpStream, Encodable, encode_list};
+use zcash_primitives::merkle_tree::{CommitmentTree, Hashable};
+use zcash_primitives::sapling::Node;
 use types::transaction::{SignedTransaction, Error as TransactionError};
 use types::header::{Header, ExtendedHeader};
 use types::receipt::{Receipt, TransactionOutcome};
@@ -108,6 +111,8 @@ pub struct ExecutedBlock {
 	pub traces: Tracing,
 	/// Hashes of last 256 blocks.
 	pub last_hashes: Arc<LastHashes>,
+	/// Sapling note commitment tree of the parent block, if known.
+	pub parent_commitment_tree: Option<CommitmentTree<Node>>,
 }
 
 impl ExecutedBlock {
@@ -126,6 +131,7 @@ impl ExecutedBlock {
 				Tracing::Disabled
 			},
 			last_hashes: last_hashes,
+			parent_commitment_tree: None,
 		}
 	}
 
@@ -152,6 +158,22 @@ impl ExecutedBlock {
 	pub fn traces_mut(&mut self) -> &mut Tracing {
 		&mut self.traces
 	}
+
+	/// Root of the Sapling note commitment tree after appending the notes created by
+	/// this block's transactions to the parent's tree.
+	/// Returns `None` if the parent's tree is unknown or overflows.
+	pub fn final_sapling_root(&self) -> Option<H256> {
+		let mut tree = self.parent_commitment_tree.clone()?;
+		for t in self.transactions.iter().filter(|t| t.is_private()) {
+			for cmu in t.get_commitment_notes() {
+				tree.append(Node::new(cmu.into_repr())).ok()?;
+			}
+		}
+
+		let mut root = H256::default();
+		tree.root().write(&mut root[..]).expect("a node is 32 bytes; qed");
+		Some(root)
+	}
 }
 
 /// Trait for an object that owns an `ExecutedBlock`
@@ -200,6 +222,11 @@ impl<'x> OpenBlock<'x> {
 		Ok(r)
 	}
 
+	/// Provide the parent's Sapling note commitment tree, needed to compute the final Sapling root.
+	pub fn set_parent_commitment_tree(&mut self, tree: Option<CommitmentTree<Node>>) {
+		self.block.parent_commitment_tree = tree;
+	}
+
 	/// Alter the timestamp of the block.
 	pub fn set_timestamp(&mut self, timestamp: u64) {
 		self.block.header.set_timestamp(timestamp);
@@ -312,6 +339,12 @@ impl<'x> OpenBlock<'x> {
 		s.engine.on_close_block(&mut s.block)?;
 		s.block.state.commit()?;
 
+		// Commit to the note commitment tree once the block's shielded outputs are known.
+		if s.block.header.number() >= s.engine.params().final_sapling_root_transition {
+			let root = s.block.final_sapling_root();
+			s.block.header.set_final_sapling_root(root);
+		}
+
 		s.block.header.set_transactions_root(ordered_trie_root(s.block.transactions.iter().map(|e| e.rlp_bytes())));
 		let uncle_bytes = encode_list(&s.block.uncles);
 		s.block.header.set_uncles_hash(keccak(&uncle_bytes));
@@ -477,6 +510,7 @@ pub(crate) fn enact(
 	db: StateDB,
 	parent: &Header,
 	last_hashes: Arc<LastHashes>,
+	parent_commitment_tree: Option<CommitmentTree<Node>>,
 	factories: Factories,
 	is_epoch_begin: bool,
 	ancestry: &mut Iterator<Item=ExtendedHeader>,
@@ -512,6 +546,7 @@ pub(crate) fn enact(
 				b.block.header.number(), root, env.author, author_balance);
 	}
 
+	b.set_parent_commitment_tree(parent_commitment_tree);
 	b.populate_from(&header);
 	b.push_transactions(transactions)?;
 
@@ -530,6 +565,7 @@ pub fn enact_verified(
 	db: StateDB,
 	parent: &Header,
 	last_hashes: Arc<LastHashes>,
+	parent_commitment_tree: Option<CommitmentTree<Node>>,
 	factories: Factories,
 	is_epoch_begin: bool,
 	ancestry: &mut Iterator<Item=ExtendedHeader>,
@@ -544,6 +580,7 @@ pub fn enact_verified(
 		db,
 		parent,
 		last_hashes,
+		parent_commitment_tree,
 		factories,
 		is_epoch_begin,
 		ancestry,
@@ -647,6 +684,29 @@ mod tests {
 		let _ = b.seal(&*spec.engine, vec![]);
 	}
 
+	#[test]
+	fn close_block_with_final_sapling_root() {
+		use spec::*;
+		use engines::NullEngine;
+		use machine::EthereumMachine;
+		use std::collections::BTreeMap;
+
+		let spec = Spec::new_test();
+		let mut params = spec.params().clone();
+		params.final_sapling_root_transition = 0;
+		let engine = NullEngine::new(Default::default(), EthereumMachine::regular(params, BTreeMap::new()));
+		let genesis_header = spec.genesis_header();
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let mut b = OpenBlock::new(&engine, Default::default(), false, db, &genesis_header, last_hashes, Address::zero(), (3141562.into(), 31415620.into()), vec![], false, None).unwrap();
+		b.set_parent_commitment_tree(Some(CommitmentTree::new()));
+		let b = b.close_and_lock().unwrap();
+
+		let mut root = H256::default();
+		CommitmentTree::<Node>::new().root().write(&mut root[..]).unwrap();
+		assert_eq!(b.header.final_sapling_root(), Some(&root));
+	}
+
 	#[test]
 	fn enact_block() {
 		use spec::*;
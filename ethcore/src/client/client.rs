@@ -19,11 +19,9 @@ use std::collections::{BTreeMap, HashSet, VecDeque};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
 use std::sync::mpsc;
-use std::sync::RwLock as STD_RwLock;
 use std::sync::{Arc, Weak};
 use std::time::{Duration, Instant};
 
-use blockchain::wallet::wallet::Wallet;
 use blockchain::{
 	BlockChain, BlockChainDB, BlockNumberKey, BlockProvider, BlockReceipts, ExtrasInsert,
 	ImportRoute, TransactionAddress, TreeRoute,
@@ -461,6 +459,10 @@ impl Importer {
 			.epoch_transition(parent.number(), *header.parent_hash())
 			.is_some();
 
+		let parent_commitment_tree = chain
+			.block_commitment_ser(header.parent_hash())
+			.map(|ser| ser.get_commitment_tree());
+
 		let enact_result = enact_verified(
 			block,
 			engine,
@@ -468,6 +470,7 @@ impl Importer {
 			db,
 			&parent,
 			last_hashes,
+			parent_commitment_tree,
 			client.factories.clone(),
 			is_epoch_begin,
 			&mut chain.ancestry_with_metadata_iter(*header.parent_hash()),
@@ -493,7 +496,7 @@ impl Importer {
 		// Final Verification
 		if let Err(e) = self
 			.verifier
-			.verify_block_final(&header, &locked_block.header)
+			.verify_block_final(&header, &locked_block.header, engine)
 		{
 			warn!(target: "client", "Stage 5 block verification failed for #{} ({})\nError: {:?}", header.number(), header.hash(), e);
 			bail!(e);
@@ -1216,13 +1219,14 @@ impl Client {
 		self.state_db.read()
 	}
 
-	#[cfg(test)]
+	/// Get the blockchain.
 	pub fn chain(&self) -> Arc<BlockChain> {
 		self.chain.read().clone()
 	}
 
-	pub fn wallet(&self) -> Arc<STD_RwLock<Wallet>> {
-		self.chain.read().pt_wallet()
+	/// Get the database of the blockchain.
+	pub fn db(&self) -> Arc<BlockChainDB> {
+		self.db.read().clone()
 	}
 
 	/// Replace io channel. Useful for testing.
@@ -1726,6 +1730,14 @@ impl BlockInfo for Client {
 	fn commitment_root_block(&self, root: &Node) -> Option<H256> {
 		self.chain.read().commitment_root_block(root)
 	}
+
+	fn commitment_tree_size(&self, id: BlockId) -> Option<u64> {
+		let chain = self.chain.read();
+
+		Self::block_hash(&chain, id)
+			.and_then(|hash| chain.block_commitment_ser(&hash))
+			.map(|ser| ser.get_commitment_tree().size() as u64)
+	}
 }
 
 impl TransactionInfo for Client {
@@ -2568,6 +2580,11 @@ impl BlockChainClient for Client {
 		self.engine.signing_chain_id(&self.latest_env_info())
 	}
 
+	fn shielded_sighash(&self) -> bool {
+		let next_block = self.chain.read().best_block_number() + 1;
+		self.engine.machine().shielded_sighash(next_block)
+	}
+
 	fn block_extra_info(&self, id: BlockId) -> Option<BTreeMap<String, String>> {
 		self.block_header_decoded(id)
 			.map(|header| self.engine.extra_info(&header))
@@ -2797,6 +2814,9 @@ impl PrepareOpenBlock for Client {
 			is_epoch_begin,
 			chain.ancestry_with_metadata_iter(best_header.hash()),
 		)?;
+		open_block.set_parent_commitment_tree(
+			chain.block_commitment_ser(&h).map(|ser| ser.get_commitment_tree())
+		);
 
 		// Add uncles
 		chain
@@ -3022,6 +3042,13 @@ impl ProvingBlockChainClient for Client {
 			.get_pending_transition(hash)
 			.map(|pending| pending.proof)
 	}
+
+	fn commitment_tree(&self, hash: H256) -> Option<Bytes> {
+		self.chain
+			.read()
+			.block_commitment_ser(&hash)
+			.map(|ser| ser.as_bytes().to_vec())
+	}
 }
 
 impl SnapshotClient for Client {}
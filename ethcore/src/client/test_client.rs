@@ -566,7 +566,13 @@ impl BlockInfo for TestBlockChainClient {
 	/// TODO(Kui): In test case, we assume the anchor exist in block for mocked private transaction.
 	/// Indeed we should verify the anchor for valid private transaction later.
 	fn commitment_root_block(&self, root: &Node) -> Option<H256> {
-		Some(H256::from(0))
+		// The best block, so that the anchor is always recent.
+		Some(self.last_hash.read().clone())
+	}
+
+	fn commitment_tree_size(&self, id: BlockId) -> Option<u64> {
+		// The blocks of the test chain have no shielded outputs.
+		self.block(id).map(|_| 0)
 	}
 }
 
@@ -1011,6 +1017,10 @@ impl BlockChainClient for TestBlockChainClient {
 		None
 	}
 
+	fn shielded_sighash(&self) -> bool {
+		false
+	}
+
 	fn mode(&self) -> Mode {
 		Mode::Active
 	}
@@ -1100,6 +1110,10 @@ impl ProvingBlockChainClient for TestBlockChainClient {
 	fn epoch_signal(&self, _: H256) -> Option<Vec<u8>> {
 		None
 	}
+
+	fn commitment_tree(&self, _: H256) -> Option<Bytes> {
+		None
+	}
 }
 
 impl super::traits::EngineClient for TestBlockChainClient {
@@ -138,6 +138,9 @@ pub trait BlockInfo {
 
 	/// Get the block hash for the given commitment tree root.
 	fn commitment_root_block(&self, root: &Node) -> Option<H256>;
+
+	/// Get the number of note commitments in the sapling commitment tree as of the end of the block.
+	fn commitment_tree_size(&self, id: BlockId) -> Option<u64>;
 }
 
 /// Provides various information on a transaction by it's ID
@@ -416,6 +419,9 @@ pub trait BlockChainClient:
 	/// Get the preferred chain ID to sign on
 	fn signing_chain_id(&self) -> Option<u64>;
 
+	/// Whether the private transactions built for the next block sign the shielded signature hash.
+	fn shielded_sighash(&self) -> bool;
+
 	/// Get the mode.
 	fn mode(&self) -> Mode;
 
@@ -541,6 +547,10 @@ pub trait ProvingBlockChainClient: BlockChainClient {
 
 	/// Get an epoch change signal by block hash.
 	fn epoch_signal(&self, hash: H256) -> Option<Vec<u8>>;
+
+	/// Get the serialized sapling commitment tree as of the end of the
+	/// block with the given hash.
+	fn commitment_tree(&self, hash: H256) -> Option<Bytes>;
 }
 
 /// resets the blockchain
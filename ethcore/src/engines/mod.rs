@@ -296,6 +296,10 @@ pub trait Engine<M: Machine>: Sync + Send {
 		Ok(())
 	}
 
+	/// Reward of the author of block `number` declaring `uncles` uncles, excluding the fees.
+	/// `None` if the engine doesn't pay a fixed reward which can be paid by a shielded coinbase.
+	fn author_reward(&self, _number: BlockNumber, _uncles: usize) -> Option<U256> { None }
+
 	/// Block transformation functions, after the transactions.
 	fn on_close_block(&self, _block: &mut ExecutedBlock) -> Result<(), M::Error> {
 		Ok(())
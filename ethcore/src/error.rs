@@ -105,6 +105,26 @@ pub enum BlockError {
 	UnknownEpochTransition(u64),
 	/// solution is incorrect.
 	InvalidSolution,
+	/// Private transaction has an invalid spend proof or spend authorization signature.
+	InvalidShieldedSpend(H256),
+	/// Private transaction has an invalid output proof.
+	InvalidShieldedOutput(H256),
+	/// Private transaction has an invalid balancing value or binding signature.
+	InvalidBindingSignature(H256),
+	/// Private transaction spends from an unknown commitment tree anchor.
+	InvalidCommitmentAnchor(H256),
+	/// The same nullifier is revealed twice within the block.
+	DuplicateNullifier(U256),
+	/// Nullifier has already been revealed in the chain.
+	NullifierAlreadySpent(U256),
+	/// Final Sapling root header field is invalid. A missing root is reported as the zero hash.
+	InvalidFinalSaplingRoot(Mismatch<H256>),
+	/// Final Sapling root header field is missing after its transition, or present before it.
+	InvalidFinalSaplingRootPresence(Mismatch<bool>),
+	/// Coinbase transaction isn't the first of the block or isn't enabled at the block.
+	InvalidCoinbase(H256),
+	/// Coinbase transaction pays more than the author reward.
+	InvalidCoinbaseValue(OutOfBounds<U256>),
 }
 
 impl fmt::Display for BlockError {
@@ -149,6 +169,16 @@ impl fmt::Display for BlockError {
 			TimestampOverflow => format!("Timestamp overflow"),
 			TooManyTransactions(ref address) => format!("Too many transactions from: {}", address),
 			InvalidSolution => "Solution is invalid .".into(),
+			InvalidShieldedSpend(ref hash) => format!("Invalid shielded spend in transaction {}", hash),
+			InvalidShieldedOutput(ref hash) => format!("Invalid shielded output in transaction {}", hash),
+			InvalidBindingSignature(ref hash) => format!("Invalid binding signature in transaction {}", hash),
+			InvalidCommitmentAnchor(ref hash) => format!("Unknown or expired commitment anchor in transaction {}", hash),
+			DuplicateNullifier(ref nullifier) => format!("Nullifier {} revealed twice in block", nullifier),
+			NullifierAlreadySpent(ref nullifier) => format!("Nullifier {} already spent", nullifier),
+			InvalidFinalSaplingRoot(ref mis) => format!("Invalid final Sapling root in header: {}", mis),
+			InvalidFinalSaplingRootPresence(ref mis) => format!("Invalid final Sapling root presence in header: {}", mis),
+			InvalidCoinbase(ref hash) => format!("Unexpected coinbase transaction {}", hash),
+			InvalidCoinbaseValue(ref oob) => format!("Invalid coinbase value: {}", oob),
 		};
 
 		f.write_fmt(format_args!("Block error ({})", msg))
@@ -26,6 +26,7 @@ use hash::{KECCAK_EMPTY_LIST_RLP};
 use rlp::Rlp;
 use types::header::{Header, ExtendedHeader};
 use types::BlockNumber;
+use types::transaction::CONVERSION_FACTOR;
 use types::rewards::{EraRewardInfo, Rewards};
 use unexpected::{OutOfBounds, Mismatch};
 
@@ -234,6 +235,16 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 
 	fn maximum_gas_limit(&self) -> Option<U256> { Some(0x7fff_ffff_ffff_ffffu64.into()) }
 
+	fn author_reward(&self, number: BlockNumber, uncles: usize) -> Option<U256> {
+		match self.ethash_params.block_reward_contract {
+			Some(_) if number >= self.ethash_params.block_reward_contract_transition => None,
+			_ => {
+				let (_, reward) = self.era_block_reward(number);
+				Some(self.machine.params().reward_config.calc_author_miner_reward(reward, uncles))
+			},
+		}
+	}
+
 	fn populate_from_parent(&self, header: &mut Header, parent: &Header) {
 		let difficulty = self.calculate_difficulty(header, parent);
 		header.set_difficulty(difficulty);
@@ -247,8 +258,16 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 		let author = *block.header.author();
 		let number = block.header.number();
 
+		let coinbase_value = block.transactions.first()
+			.filter(|t| t.is_coinbase())
+			.map(|t| U256::from(-t.balancing_value() as u64) * CONVERSION_FACTOR);
+
 		let rewards = match self.ethash_params.block_reward_contract {
 			Some(ref c) if number >= self.ethash_params.block_reward_contract_transition => {
+				if coinbase_value.is_some() {
+					return Err(From::from(BlockError::InvalidCoinbase(block.transactions[0].hash())));
+				}
+
 				let mut beneficiaries = Vec::new();
 
 				beneficiaries.push((author, RewardKind::Author));
@@ -265,23 +284,7 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 			_ => {
 				let mut rewards = Vec::new();
 
-				let  base_reward = match  self.ethash_params.block_reward.iter()
-					.rev().find(|&(block, _)| *block <= number){
-					Some(r)=> *r.1,
-					None => {
-						let era = self.machine.params().reward_config.calc_era_by_block_number(number);
-						match self.rewards.read().get_era_base_reward(era as usize) {
-							Some(r) => r,
-							None => U256::zero(),
-						}
-					}
-				};
-
-				trace!(target: "equihash","number {:?} base reward {:?}", number, base_reward);
-
-				// Applies ECIP-1017 eras.
-				let eras_rounds = self.ethash_params.ecip1017_era_rounds;
-				let (eras, reward) = ecip1017_eras_block_reward(eras_rounds, base_reward, number);
+				let (eras, reward) = self.era_block_reward(number);
 
 				//let n_uncles = LiveBlock::uncles(&*block).len();
 				let n_uncles = block.uncles.len();
@@ -290,6 +293,19 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 				let author_reward = self.machine.params().reward_config.calc_author_miner_reward(reward, n_uncles);
 				trace!(target: "equihash","result_block_reward {:?}", author_reward);
 
+				// The part of the reward paid by the coinbase note isn't credited to the author.
+				let author_reward = match coinbase_value {
+					Some(value) if value > author_reward => {
+						return Err(From::from(BlockError::InvalidCoinbaseValue(OutOfBounds {
+							min: None,
+							max: Some(author_reward),
+							found: value,
+						})))
+					},
+					Some(value) => author_reward - value,
+					None => author_reward,
+				};
+
 				rewards.push((author, RewardKind::Author, author_reward));
 
 				// Bestow uncle rewards.
@@ -376,6 +392,27 @@ impl Engine<EthereumMachine> for Arc<Ethash> {
 }
 
 impl Ethash {
+	// The eras elapsed and the block reward at block `number`.
+	fn era_block_reward(&self, number: BlockNumber) -> (u64, U256) {
+		let  base_reward = match  self.ethash_params.block_reward.iter()
+			.rev().find(|&(block, _)| *block <= number){
+			Some(r)=> *r.1,
+			None => {
+				let era = self.machine.params().reward_config.calc_era_by_block_number(number);
+				match self.rewards.read().get_era_base_reward(era as usize) {
+					Some(r) => r,
+					None => U256::zero(),
+				}
+			}
+		};
+
+		trace!(target: "equihash","number {:?} base reward {:?}", number, base_reward);
+
+		// Applies ECIP-1017 eras.
+		let eras_rounds = self.ethash_params.ecip1017_era_rounds;
+		ecip1017_eras_block_reward(eras_rounds, base_reward, number)
+	}
+
 	fn calculate_difficulty(&self, header: &Header, parent: &Header) -> U256 {
 		if header.number() == 0 {
 			panic!("Can't calculate genesis block difficulty");
@@ -577,6 +614,81 @@ mod tests {
 		assert_eq!(b.state.balance(&uncle_author).unwrap(), "103df9d3e2d19a400".into());
 	}
 
+	#[test]
+	fn on_close_block_with_coinbase() {
+		use types::transaction::{Action, Transaction, CONVERSION_FACTOR};
+
+		let spec = test_spec();
+		let engine = &*spec.engine;
+		let genesis_header = spec.genesis_header();
+		let last_hashes = Arc::new(vec![genesis_header.hash()]);
+		let author_reward = engine.author_reward(1, 0).unwrap();
+		let coinbase = |value: U256| {
+			let mut tx = Transaction::create_private(vec![], U256::zero(), Action::PrivateToPrivate);
+			tx.gas = U256::zero();
+			tx.data = vec![];
+			tx.private.as_mut().unwrap().outputs.pop();
+			tx.private.as_mut().unwrap().balancing_value = -((value / CONVERSION_FACTOR).low_u64() as i64);
+			tx.sign_for_private(spec.chain_id())
+		};
+		let open_block = |db| OpenBlock::new(engine, Default::default(), false, db, &genesis_header, last_hashes.clone(), Address::zero(), (3141562.into(), 31415620.into()), vec![], false, None).unwrap();
+
+		// The author is paid the part of the reward the coinbase doesn't pay.
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let mut b = open_block(db);
+		b.push_transaction(coinbase(CONVERSION_FACTOR * 1000), None).unwrap();
+		let b = b.close().unwrap();
+		assert_eq!(b.state.balance(&Address::zero()).unwrap(), author_reward - CONVERSION_FACTOR * 1000);
+
+		// The coinbase can't pay more than the reward.
+		let db = spec.ensure_db_good(get_temp_state_db(), &Default::default()).unwrap();
+		let mut b = open_block(db);
+		b.push_transaction(coinbase(author_reward + CONVERSION_FACTOR), None).unwrap();
+		match b.close() {
+			Err(Error(ErrorKind::Block(BlockError::InvalidCoinbaseValue(_)), _)) => {},
+			Err(e) => panic!("should be invalid coinbase value error (got {:?})", e),
+			Ok(_) => panic!("should be error, got Ok"),
+		}
+	}
+
+	#[test]
+	fn verify_coinbase_value() {
+		use machine::EthereumMachine;
+		use spec::CommonParams;
+		use test_helpers::create_test_block_with_data;
+		use types::transaction::{Action, SignedTransaction, Transaction, CONVERSION_FACTOR};
+		use verification::queue::kind::blocks::Unverified;
+		use verification::verify_coinbase;
+
+		let mut params = CommonParams::default();
+		params.shielded_coinbase_transition = 0;
+		let machine = EthereumMachine::regular(params, BTreeMap::new());
+		let mut ethash_params = get_default_ethash_params();
+		ethash_params.block_reward.insert(0, CONVERSION_FACTOR * 1000);
+		let engine = Ethash::new(ethash_params, machine);
+		let author_reward = engine.author_reward(1, 0).unwrap();
+		let coinbase = |value: U256| {
+			let mut tx = Transaction::create_private(vec![], U256::zero(), Action::PrivateToPrivate);
+			tx.gas = U256::zero();
+			tx.data = vec![];
+			tx.private.as_mut().unwrap().outputs.pop();
+			tx.private.as_mut().unwrap().balancing_value = -((value / CONVERSION_FACTOR).low_u64() as i64);
+			tx.sign_for_private(2)
+		};
+		let unverified = |transactions: &[SignedTransaction]| {
+			let mut header = Header::default();
+			header.set_number(1);
+			Unverified::from_rlp(create_test_block_with_data(&header, transactions, &[])).unwrap()
+		};
+
+		assert!(verify_coinbase(&unverified(&[coinbase(author_reward)]), &*engine).is_ok());
+		match verify_coinbase(&unverified(&[coinbase(author_reward + CONVERSION_FACTOR)]), &*engine) {
+			Err(BlockError::InvalidCoinbaseValue(_)) => {},
+			Err(e) => panic!("should be invalid coinbase value error (got {:?})", e),
+			Ok(_) => panic!("should be error, got Ok"),
+		}
+	}
+
 	#[test]
 	fn has_valid_mcip3_era_block_rewards() {
 		let spec = new_mcip3_test();
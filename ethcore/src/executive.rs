@@ -31,7 +31,9 @@ use vm::{
 use factory::VmFactory;
 use externalities::*;
 use trace::{self, Tracer, VMTracer};
-use types::transaction::{Action, SignedTransaction, PRIVATE_EXECUTIVE_ADDRESS};
+use types::transaction::{
+	Action, SignedTransaction, CONVERSION_FACTOR, MAX_PUBLIC_OUTPUTS, PRIVATE_EXECUTIVE_ADDRESS,
+};
 use transaction_ext::Transaction;
 use crossbeam;
 pub use executed::{Executed, ExecutionResult};
@@ -859,6 +861,24 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 			return Err(ExecutionError::NotEnoughCash { required: total_cost, got: balance512 });
 		}
 
+		// The public outputs of a private call are paid by its spent notes, with the value and the gas.
+		if !t.public_outputs().is_empty() {
+			match t.action {
+				Action::PrivateCall(_) => (),
+				_ => return Err(ExecutionError::TransactionMalformed("Public outputs without private call".into())),
+			}
+			if t.public_outputs().len() > MAX_PUBLIC_OUTPUTS {
+				return Err(ExecutionError::TransactionMalformed("Too many public outputs".into()));
+			}
+			let public_value = t.public_outputs()
+				.iter()
+				.fold(total_cost, |total, output| total + U512::from(output.value));
+			let balancing_value = t.balancing_value();
+			if balancing_value < 0 || U512::from(balancing_value as u64) * U512::from(CONVERSION_FACTOR) != public_value {
+				return Err(ExecutionError::TransactionMalformed("Balancing value doesn't match the public outputs".into()));
+			}
+		}
+
 		let mut substate = Substate::new();
 		if !schedule.keep_unsigned_nonce || !t.is_unsigned() {
 			if !t.action.is_input_private() {
@@ -919,6 +939,15 @@ impl<'a, B: 'a + StateBackend> Executive<'a, B> {
 				Ok(self.finalize(t, substate, res, out, tracer.drain(), vm_tracer.drain())?)
 			}
 			Action::PrivateCall(ref address) => {
+				// The public outputs are paid whatever the outcome of the call, like the shielded outputs.
+				for output in t.public_outputs() {
+					self.state.transfer_balance_from_private_to_public(
+						&PRIVATE_EXECUTIVE_ADDRESS,
+						&output.address,
+						&output.value,
+						substate.to_cleanup_mode(&schedule),
+					)?;
+				}
 				let params = ActionParams {
 					code_address: address.clone(),
 					address: address.clone(),
@@ -2053,6 +2082,50 @@ mod tests {
 		assert_eq!(state.balance(&PRIVATE_EXECUTIVE_ADDRESS).unwrap(), U256::from(100_000 - 21000));
 	}
 
+	evm_test!{test_transact_private_call_with_public_outputs: test_transact_private_call_with_public_outputs_int}
+	fn test_transact_private_call_with_public_outputs(factory: Factory) {
+		use types::transaction::PublicOutput;
+
+		let receiver = Address::from_str("cd1722f3947def4cf144679da39c4c32bdc35681").unwrap();
+		let first = Address::from(6);
+		let second = Address::from(7);
+		let mut t = Transaction::create_executive_transaction(vec![0], U256::zero(), Action::PrivateCall(receiver),
+															  CONVERSION_FACTOR, U256::from(100_000), U256::zero());
+		t.private.as_mut().unwrap().public_outputs = vec![
+			PublicOutput { address: first, value: CONVERSION_FACTOR * 2 },
+			PublicOutput { address: second, value: CONVERSION_FACTOR * 3 },
+		];
+
+		let mut state = get_temp_state_with_factory(factory);
+		let mut info = EnvInfo::default();
+		info.gas_limit = U256::from(100_000);
+		let machine = make_frontier_machine(0);
+		let schedule = machine.schedule(info.number);
+
+		// The balancing value doesn't cover the public outputs.
+		let invalid = t.clone().sign_for_private(0);
+		let res = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.transact(&invalid, TransactOptions::with_no_tracing())
+		};
+		match res {
+			Err(ExecutionError::TransactionMalformed(_)) => (),
+			_ => panic!("Expected a malformed transaction error, got {:?}", res),
+		}
+
+		t.private.as_mut().unwrap().balancing_value = 6;
+		let t = t.sign_for_private(0);
+		let executed = {
+			let mut ex = Executive::new(&mut state, &info, &machine, &schedule);
+			ex.transact(&t, TransactOptions::with_no_tracing()).unwrap()
+		};
+
+		assert_eq!(executed.gas_used, U256::from(21000 + 2 * 9000));
+		assert_eq!(state.balance(&receiver).unwrap(), CONVERSION_FACTOR);
+		assert_eq!(state.balance(&first).unwrap(), CONVERSION_FACTOR * 2);
+		assert_eq!(state.balance(&second).unwrap(), CONVERSION_FACTOR * 3);
+	}
+
 	evm_test!{test_transact_simple_private_to_private: test_transact_simple_private_to_private_int}
 	fn test_transact_simple_private_to_private(factory: Factory) {
 		let t = Transaction::create_executive_transaction(vec![0], U256::zero(), Action::PrivateToPrivate,
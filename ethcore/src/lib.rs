@@ -113,6 +113,8 @@ extern crate crypto as rust_crypto;
 
 #[cfg(test)]
 extern crate ethcore_accounts as accounts;
+#[cfg(test)]
+extern crate ethcore_wallet as wallet;
 #[cfg(feature = "stratum")]
 extern crate ethcore_stratum;
 #[cfg(any(test, feature = "tempdir"))]
@@ -192,5 +194,4 @@ pub mod test_helpers;
 pub use executive::contract_address;
 pub use evm::CreateContractAddress;
 pub use trie::TrieSpec;
-pub use blockchain::{wallet::wallet::Wallet, wallet::wallet::DUMMY_WALLET_PASS,  wallet::key_store::{decode_payment_address, decode_transparent_destination, encode_payment_address},
-					 wallet::sendmany::{SendMany, SendManyInputs}};
+pub use blockchain::zkp::{check_sapling_params, set_sapling_params_dir, set_tx_prover, ProverAddress, RemoteTxProver};
@@ -345,6 +345,16 @@ impl EthereumMachine {
 		}
 	}
 
+	/// Whether the private transactions of the block `number` sign the shielded signature hash.
+	pub fn shielded_sighash(&self, number: BlockNumber) -> bool {
+		number >= self.params().shielded_sighash_transition
+	}
+
+	/// Whether the private calls of the block `number` can carry public outputs.
+	pub fn public_outputs(&self, number: BlockNumber) -> bool {
+		number >= self.params().public_outputs_transition
+	}
+
 	/// Returns new contract address generation scheme at given block number.
 	pub fn create_address_scheme(&self, _number: BlockNumber) -> CreateContractAddress {
 		CreateContractAddress::FromSenderAndNonce
@@ -373,6 +383,9 @@ impl EthereumMachine {
 
 		if t.is_private() {
 			t.verify_private_tx_basic()?;
+			if !t.public_outputs().is_empty() && !self.public_outputs(header.number()) {
+				return Err(transaction::Error::InvalidPrivateTx(transaction::PrivateTxError::InvalidPublicOutputs));
+			}
 		}
 
 		Ok(())
@@ -497,6 +510,32 @@ mod tests {
 		assert_eq!(res, Err(transaction::Error::InvalidSignature("Crypto error (Invalid EC signature)".into())));
 	}
 
+	#[test]
+	fn should_disallow_public_outputs_before_transition() {
+		use types::transaction::{Action, PrivateTxError, PublicOutput, Transaction, CONVERSION_FACTOR};
+
+		let mut params = CommonParams::default();
+		params.chain_id = 2;
+		params.public_outputs_transition = 10;
+		let machine = EthereumMachine::regular(params, BTreeMap::new());
+
+		let mut t = Transaction::create_private(vec![0], U256::zero(), Action::PrivateCall(Address::from(5)));
+		t.private.as_mut().unwrap().public_outputs = vec![
+			PublicOutput { address: Address::from(6), value: CONVERSION_FACTOR },
+		];
+		t.private.as_mut().unwrap().balancing_value = 1;
+		let transaction = t.sign_for_private(2);
+		let mut header = ::types::header::Header::new();
+
+		header.set_number(9);
+		assert_eq!(
+			machine.verify_transaction_basic(&transaction, &header),
+			Err(transaction::Error::InvalidPrivateTx(PrivateTxError::InvalidPublicOutputs))
+		);
+		header.set_number(10);
+		assert_eq!(machine.verify_transaction_basic(&transaction, &header), Ok(()));
+	}
+
 	#[test]
 	fn ethash_gas_limit_is_multiple_of_determinant() {
 		use ethereum_types::U256;
@@ -0,0 +1,198 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Built-in CPU miner sealing PoW blocks with the equihash solver.
+
+use std::sync::{Arc, Weak};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use client::{Client, ImportSealedBlock};
+use equihash;
+use equihash::equihash::{Equihash, OnChainEquihash};
+use equihash::solver::solve_equihash;
+use ethcore_miner::work_notify::NotifyWork;
+use ethereum_types::{H256, H512, U256};
+use miner::{Miner, MinerService};
+use parking_lot::{Condvar, Mutex};
+use rand;
+use rlp::encode;
+use types::solution::EquihashSolution;
+use verification::equihash_verifier::solution_meets_boundary;
+
+/// Work package to be sealed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Work {
+	pow_hash: H512,
+	difficulty: U256,
+	number: u64,
+}
+
+struct Shared {
+	// latest work package, taken by the mining thread.
+	work: Mutex<Option<Work>>,
+	new_work: Condvar,
+	// aborts the solver run in progress.
+	abort: AtomicBool,
+	exit: AtomicBool,
+}
+
+/// Seals the work packages of the miner on a dedicated thread.
+pub struct InternalMiner {
+	shared: Arc<Shared>,
+}
+
+impl InternalMiner {
+	/// Start mining with the given number of solver threads.
+	pub fn start(threads: usize, miner: Weak<Miner>, client: Weak<Client>) -> Result<InternalMiner, String> {
+		let shared = Arc::new(Shared {
+			work: Mutex::new(None),
+			new_work: Condvar::new(),
+			abort: AtomicBool::new(false),
+			exit: AtomicBool::new(false),
+		});
+
+		let thread_shared = shared.clone();
+		thread::Builder::new()
+			.name("Internal Miner".into())
+			.spawn(move || mine_loop(&thread_shared, threads, miner, client))
+			.map_err(|e| format!("Unable to spawn internal miner thread: {}", e))?;
+
+		info!(target: "miner", "Internal miner started with {} threads", threads);
+		Ok(InternalMiner { shared })
+	}
+
+	/// Start the internal miner and register it in the miner.
+	pub fn register(threads: usize, miner: Arc<Miner>, client: Weak<Client>) -> Result<(), String> {
+		let internal_miner = InternalMiner::start(threads, Arc::downgrade(&miner), client)?;
+		miner.add_work_listener(Box::new(internal_miner) as Box<NotifyWork>);
+		Ok(())
+	}
+}
+
+impl NotifyWork for InternalMiner {
+	fn notify(&self, pow_hash: H512, difficulty: U256, number: u64) {
+		trace!(target: "miner", "Internal miner got new work #{}: {}", number, pow_hash);
+
+		let mut work = self.shared.work.lock();
+		*work = Some(Work { pow_hash, difficulty, number });
+		self.shared.abort.store(true, Ordering::SeqCst);
+		self.shared.new_work.notify_one();
+	}
+}
+
+impl Drop for InternalMiner {
+	fn drop(&mut self) {
+		let _work = self.shared.work.lock();
+		self.shared.exit.store(true, Ordering::SeqCst);
+		self.shared.abort.store(true, Ordering::SeqCst);
+		self.shared.new_work.notify_one();
+	}
+}
+
+fn mine_loop(shared: &Shared, threads: usize, miner: Weak<Miner>, client: Weak<Client>) {
+	loop {
+		let work = {
+			let mut work = shared.work.lock();
+			while work.is_none() && !shared.exit.load(Ordering::SeqCst) {
+				shared.new_work.wait(&mut work);
+			}
+
+			if shared.exit.load(Ordering::SeqCst) {
+				return;
+			}
+
+			shared.abort.store(false, Ordering::SeqCst);
+			work.take().expect("loop above exits only when work is present; qed")
+		};
+
+		let sealed = solve::<OnChainEquihash>(&work, threads, &shared.abort)
+			.and_then(|(nonce, solution)| EquihashSolution::try_from(solution).ok().map(|solution| (nonce, solution)));
+		if let Some((nonce, solution)) = sealed {
+			match (miner.upgrade(), client.upgrade()) {
+				(Some(miner), Some(client)) => {
+					let seal = vec![encode(&nonce), encode(&solution)];
+					let import = miner.submit_seal(work.pow_hash, seal)
+						.and_then(|block| client.import_sealed_block(block));
+					if let Err(e) = import {
+						warn!(target: "miner", "Internal miner failed to seal block #{}: {}", work.number, e);
+					}
+				},
+				_ => return,
+			}
+		}
+	}
+}
+
+// Try nonces until a solution meeting the work's difficulty is found or the run is aborted.
+fn solve<Algorithm: Equihash>(work: &Work, threads: usize, abort: &AtomicBool) -> Option<(H256, Vec<u8>)> {
+	let boundary = equihash::difficulty_to_boundary(&work.difficulty);
+	let mut nonce = U256::from(rand::random::<u64>()) << 192;
+
+	while !abort.load(Ordering::SeqCst) {
+		let nonce_hash = H256::from(nonce);
+		let mut input = work.pow_hash.to_vec();
+		input.extend_from_slice(&nonce_hash);
+
+		for solution in solve_equihash::<Algorithm>(&input, threads, abort) {
+			if solution_meets_boundary(&work.pow_hash, &nonce_hash, &solution, &boundary) {
+				trace!(target: "miner", "Internal miner solved #{} with nonce {}", work.number, nonce_hash);
+				return Some((nonce_hash, solution));
+			}
+		}
+
+		nonce = nonce.overflowing_add(U256::one()).0;
+	}
+
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::atomic::AtomicBool;
+
+	use equihash;
+	use equihash::equihash::{verify_equihash_solution, TestEquihash};
+	use ethereum_types::{H512, U256};
+	use verification::equihash_verifier::solution_meets_boundary;
+
+	use super::{solve, Work};
+
+	fn work(difficulty: u64) -> Work {
+		Work {
+			pow_hash: H512::from(7),
+			difficulty: U256::from(difficulty),
+			number: 1,
+		}
+	}
+
+	#[test]
+	fn solves_work() {
+		let work = work(2);
+		let (nonce, solution) = solve::<TestEquihash>(&work, 2, &AtomicBool::new(false)).unwrap();
+
+		let mut input = work.pow_hash.to_vec();
+		input.extend_from_slice(&nonce);
+		assert!(verify_equihash_solution::<TestEquihash>(&input, &solution));
+		let boundary = equihash::difficulty_to_boundary(&work.difficulty);
+		assert!(solution_meets_boundary(&work.pow_hash, &nonce, &solution, &boundary));
+	}
+
+	#[test]
+	fn aborted_work_is_not_solved() {
+		assert_eq!(solve::<TestEquihash>(&work(1), 1, &AtomicBool::new(true)), None);
+	}
+}
@@ -15,6 +15,7 @@
 // along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
 
 use std::cmp;
+use std::fmt;
 use std::time::{Instant, Duration};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
@@ -38,6 +39,7 @@ use rayon::prelude::*;
 use types::transaction::{
 	self,
 	Action,
+	CONVERSION_FACTOR,
 	UnverifiedTransaction,
 	SignedTransaction,
 	PendingTransaction,
@@ -49,7 +51,7 @@ use types::header::Header;
 use types::receipt::RichReceipt;
 use using_queue::{UsingQueue, GetAction};
 
-use block::{ClosedBlock, SealedBlock};
+use block::{ClosedBlock, OpenBlock, SealedBlock};
 use client::{
 	BlockChain, ChainInfo, BlockProducer, SealedBlockImporter, Nonce, TransactionInfo, TransactionId
 };
@@ -195,6 +197,8 @@ pub struct AuthoringParams {
 	pub gas_range_target: (U256, U256),
 	/// Block author
 	pub author: Address,
+	/// Shielded author paid the author reward by a coinbase transaction, if any.
+	pub shielded_author: Option<Arc<ShieldedAuthor>>,
 	/// Block extra data
 	pub extra_data: Bytes,
 }
@@ -205,6 +209,16 @@ pub enum Author {
 	External(Address),
 	/// Sealing is done internally, we need a way to create signatures to seal block (i.e. PoA)
 	Sealer(Box<EngineSigner>),
+	/// Sealing is external and the author reward is paid to a shielded address by a coinbase
+	/// transaction. The transaction fees are paid to the zero address.
+	Shielded(Arc<ShieldedAuthor>),
+}
+
+/// Shielded address paid the author reward of the blocks authored. The coinbase transactions
+/// paying it are proven by the wallet, outside of the consensus code.
+pub trait ShieldedAuthor: fmt::Debug + Send + Sync {
+	/// Build the unsigned coinbase transaction paying `value` to the shielded address.
+	fn build_coinbase(&self, value: &U256, chain_id: u64, shielded_sighash: bool) -> Result<transaction::Transaction, String>;
 }
 
 impl Author {
@@ -213,6 +227,7 @@ impl Author {
 		match *self {
 			Author::External(address) => address,
 			Author::Sealer(ref sealer) => sealer.address(),
+			Author::Shielded(_) => Address::default(),
 		}
 	}
 }
@@ -398,6 +413,32 @@ impl Miner {
 		)
 	}
 
+	/// Pays the author reward of a fresh block to `to` with a coinbase transaction,
+	/// if the chain enables shielded coinbases at the block.
+	fn push_coinbase(&self, open_block: &mut OpenBlock, to: &ShieldedAuthor) -> Result<(), String> {
+		let number = open_block.header.number();
+		if number < self.engine.params().shielded_coinbase_transition {
+			return Ok(());
+		}
+		let reward = match self.engine.author_reward(number, open_block.uncles.len()) {
+			Some(reward) => reward,
+			None => return Ok(()),
+		};
+		// Notes hold whole multiples of the conversion factor, the remainder is paid to the author.
+		let value = reward - reward % CONVERSION_FACTOR;
+		if value.is_zero() {
+			return Ok(());
+		}
+
+		let chain_id = self.engine.params().chain_id;
+		let shielded_sighash = self.engine.machine().shielded_sighash(number);
+		let coinbase = to.build_coinbase(&value, chain_id, shielded_sighash)?;
+		open_block.push_transaction(coinbase.sign_for_private(chain_id), None)
+			.map_err(|e| format!("{}", e))?;
+		debug!(target: "miner", "Paying {} to the shielded author of block #{}", value, number);
+		Ok(())
+	}
+
 	/// Prepares new block for sealing including top transactions from queue.
 	fn prepare_block<C>(&self, chain: &C) -> Option<(ClosedBlock, Option<H256>)> where
 		C: BlockChain + CallContract + BlockProducer + Nonce + Sync,
@@ -428,7 +469,7 @@ impl Miner {
 					trace!(target: "miner", "prepare_block: No existing work - making new block");
 					let params = self.params.read().clone();
 
-					match chain.prepare_open_block(
+					let mut open_block = match chain.prepare_open_block(
 						params.author,
 						params.gas_range_target,
 						params.extra_data,
@@ -438,7 +479,14 @@ impl Miner {
 							warn!(target: "miner", "Open new block failed with error {:?}. This is likely an error in chain specificiations or on-chain consensus smart contracts.", err);
 							return None;
 						}
+					};
+
+					if let Some(ref shielded_author) = params.shielded_author {
+						if let Err(err) = self.push_coinbase(&mut open_block, &**shielded_author) {
+							warn!(target: "miner", "Failed to pay the block reward to the shielded author: {}", err);
+						}
 					}
+					open_block
 				}
 			};
 
@@ -878,7 +926,14 @@ impl miner::MinerService for Miner {
 	}
 
 	fn set_author(&self, author: Author) {
-		self.params.write().author = author.address();
+		{
+			let mut params = self.params.write();
+			params.author = author.address();
+			params.shielded_author = match author {
+				Author::Shielded(ref shielded_author) => Some(shielded_author.clone()),
+				_ => None,
+			};
+		}
 
 		if let Author::Sealer(signer) = author {
 			if self.engine.seals_internally().is_some() {
@@ -1264,6 +1319,24 @@ impl miner::MinerService for Miner {
 		if has_new_best_block {
 			// Clear nonce cache
 			self.nonce_cache.clear();
+
+			// The private transactions queued before the shielded sighash transition sign
+			// the transaction hash, which the next blocks don't accept.
+			let transition = self.engine.params().shielded_sighash_transition;
+			let next_block = chain.best_block_header().number() + 1;
+			let crossed_transition = enacted.iter()
+				.filter_map(|hash| chain.block_header(BlockId::Hash(*hash)).map(|header| header.number()))
+				.min()
+				.map_or(false, |first| first < transition && transition <= next_block);
+			if crossed_transition {
+				let private = self.transaction_queue.all_transactions()
+					.iter()
+					.filter(|tx| tx.signed().is_private())
+					.map(|tx| tx.signed().hash())
+					.collect::<Vec<_>>();
+				debug!(target: "miner", "Dropping {} private transactions signed before the shielded sighash transition", private.len());
+				self.transaction_queue.remove(private.iter(), true);
+			}
 		}
 
 		// First update gas limit in transaction queue and minimal gas price.
@@ -1390,10 +1463,10 @@ mod tests {
 	use types::transaction::{Transaction, CONVERSION_FACTOR};
 
 	use rand::{OsRng, Rand};
-	use blockchain::wallet::wallet_types::{
+	use wallet::wallet_types::{
 		SaplingExtendedFullViewingKey, SaplingExtendedSpendingKey,
 	};
-	use blockchain::wallet::transaction_builder::TransactionBuilder;
+	use wallet::transaction_builder::TransactionBuilder;
 	use sapling_crypto::jubjub::fs::Fs;
 	use zcash_primitives::{
 		merkle_tree::{CommitmentTree,IncrementalWitness},
@@ -21,11 +21,13 @@
 
 mod miner;
 
+#[cfg(feature = "work-notify")]
+pub mod internal_miner;
 pub mod pool_client;
 #[cfg(feature = "stratum")]
 pub mod stratum;
 
-pub use self::miner::{Miner, MinerOptions, Penalization, PendingSet, AuthoringParams, Author};
+pub use self::miner::{Miner, MinerOptions, Penalization, PendingSet, AuthoringParams, Author, ShieldedAuthor};
 pub use ethcore_miner::local_accounts::LocalAccounts;
 pub use ethcore_miner::pool::PendingOrdering;
 
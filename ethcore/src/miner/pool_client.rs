@@ -28,10 +28,11 @@ use types::header::Header;
 use types::transaction::{self, SignedTransaction, UnverifiedTransaction};
 
 use call_contract::CallContract;
-use client::{BlockInfo, Nonce, TransactionId, TransactionInfo};
+use client::{BlockId, BlockInfo, Nonce, TransactionId, TransactionInfo};
 use engines::EthEngine;
 use miner;
 use transaction_ext::Transaction;
+use verification::MAX_ANCHOR_AGE;
 use zcash_primitives::sapling::Node;
 use ff::PrimeField;
 
@@ -149,13 +150,15 @@ where
 			}
 		}
 
-		// Second, check whether the anchors are valid.
+		// Second, check whether the anchors are valid in the next block.
+		let number = self.best_block_header.number() + 1;
 		for ref anchor in t.get_commitment_anchors() {
-			if self
+			let anchor_number = self
 				.chain
 				.commitment_root_block(&Node::new(anchor.into_repr()))
-				.is_none()
-			{
+				.and_then(|hash| self.chain.block_header(BlockId::Hash(hash)))
+				.map(|header| header.number());
+			if anchor_number.map_or(true, |anchor_number| anchor_number + MAX_ANCHOR_AGE < number) {
 				return Err(transaction::Error::InvalidCommitmentAnchor);
 			}
 		}
@@ -163,10 +166,18 @@ where
 		Ok(())
 	}
 
+	fn shielded_sighash(&self) -> bool {
+		self.engine.machine().shielded_sighash(self.best_block_header.number() + 1)
+	}
+
 	fn verify_transaction(
 		&self,
 		tx: UnverifiedTransaction,
 	) -> Result<SignedTransaction, transaction::Error> {
+		// Coinbase transactions are only included by the author of a block.
+		if tx.is_coinbase() {
+			return Err(transaction::Error::InvalidPrivateTx(transaction::PrivateTxError::InvalidCoinbase));
+		}
 		self.engine
 			.verify_transaction_basic(&tx, &self.best_block_header)?;
 		let tx = self
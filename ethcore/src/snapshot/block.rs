@@ -22,7 +22,7 @@ use hash::keccak;
 use rlp::{DecoderError, RlpStream, Rlp};
 use triehash::ordered_trie_root;
 use types::block::Block;
-use types::header::Header;
+use types::header::{Header, is_final_sapling_root};
 use types::views::BlockView;
 
 const HEADER_FIELDS: usize = 8;
@@ -50,10 +50,13 @@ impl AbridgedBlock {
 	pub fn from_block_view(block_view: &BlockView) -> Self {
 		let header = block_view.header_view();
 		let seal_fields = header.seal();
+		let final_sapling_root = header.final_sapling_root();
 
-		// 10 header fields, unknown number of seal fields, and 2 block fields.
+		// 8 header fields, an optional final Sapling root, unknown number of seal fields,
+		// and 2 block fields.
 		let mut stream = RlpStream::new_list(
 			HEADER_FIELDS +
+			final_sapling_root.map_or(0, |_| 1) +
 			seal_fields.len() +
 			BLOCK_FIELDS
 		);
@@ -74,6 +77,11 @@ impl AbridgedBlock {
 			.append_list(&block_view.transactions())
 			.append_list(&block_view.uncles());
 
+		// write the final Sapling root as a single-item list, so it can't be mistaken for a seal field.
+		if let Some(root) = final_sapling_root {
+			stream.begin_list(1).append(&root);
+		}
+
 		// write seal fields.
 		for field in seal_fields {
 			stream.append_raw(&field, 1);
@@ -114,8 +122,14 @@ impl AbridgedBlock {
 		uncles_rlp.append_list(&uncles);
 		header.set_uncles_hash(keccak(uncles_rlp.as_raw()));
 
+		let mut seal_start = HEADER_FIELDS + BLOCK_FIELDS;
+		if rlp.item_count()? > seal_start && is_final_sapling_root(&rlp.at(seal_start)?)? {
+			header.set_final_sapling_root(Some(rlp.at(seal_start)?.val_at(0)?));
+			seal_start += 1;
+		}
+
 		let mut seal_fields = Vec::new();
-		for i in (HEADER_FIELDS + BLOCK_FIELDS)..rlp.item_count()? {
+		for i in seal_start..rlp.item_count()? {
 			let seal_rlp = rlp.at(i)?;
 			seal_fields.push(seal_rlp.as_raw().to_owned());
 		}
@@ -155,6 +169,18 @@ mod tests {
 		assert_eq!(abridged.to_block(H256::new(), 0, receipts_root).unwrap(), b);
 	}
 
+	#[test]
+	fn final_sapling_root_abridging() {
+		let mut b = Block::default();
+		b.header.set_final_sapling_root(Some(H256::from(42)));
+		b.header.set_seal(vec![::rlp::encode(&H256::from(1)), ::rlp::encode(&vec![2u8; 8])]);
+		let receipts_root = b.header.receipts_root().clone();
+		let encoded = encode_block(&b);
+
+		let abridged = AbridgedBlock::from_block_view(&view!(BlockView, &encoded));
+		assert_eq!(abridged.to_block(H256::new(), 0, receipts_root).unwrap(), b);
+	}
+
 	#[test]
 	#[should_panic]
 	fn wrong_number() {
@@ -67,6 +67,10 @@ pub enum Error {
 	WrongChunkFormat(String),
 	/// Unlinked ancient block chain
 	UnlinkedAncientBlockChain,
+	/// Commitment tree of the snapshot block is not known.
+	MissingCommitmentTree(H256),
+	/// Restored commitment tree or anchor doesn't match the final Sapling root of its block.
+	InvalidCommitmentRoot(H256),
 }
 
 impl fmt::Display for Error {
@@ -94,6 +98,8 @@ impl fmt::Display for Error {
 			Error::BadEpochProof(i) => write!(f, "Bad epoch proof for transition to epoch {}", i),
 			Error::WrongChunkFormat(ref msg) => write!(f, "Wrong chunk format: {}", msg),
 			Error::UnlinkedAncientBlockChain => write!(f, "Unlinked ancient blocks chain"),
+			Error::MissingCommitmentTree(ref hash) => write!(f, "Commitment tree of block {} not found.", hash),
+			Error::InvalidCommitmentRoot(ref hash) => write!(f, "Restored commitment root of block {} doesn't match its header.", hash),
 		}
 	}
 }
@@ -43,6 +43,9 @@ pub trait SnapshotWriter {
 	/// Write a compressed block chunk.
 	fn write_block_chunk(&mut self, hash: H256, chunk: &[u8]) -> io::Result<()>;
 
+	/// Write a compressed shielded pool chunk.
+	fn write_shielded_chunk(&mut self, hash: H256, chunk: &[u8]) -> io::Result<()>;
+
 	/// Complete writing. The manifest's chunk lists must be consistent
 	/// with the chunks written.
 	fn finish(self, manifest: ManifestData) -> io::Result<()> where Self: Sized;
@@ -66,6 +69,7 @@ pub struct PackedWriter {
 	file: File,
 	state_hashes: Vec<ChunkInfo>,
 	block_hashes: Vec<ChunkInfo>,
+	shielded_hashes: Vec<ChunkInfo>,
 	cur_len: u64,
 }
 
@@ -76,6 +80,7 @@ impl PackedWriter {
 			file: File::create(path)?,
 			state_hashes: Vec::new(),
 			block_hashes: Vec::new(),
+			shielded_hashes: Vec::new(),
 			cur_len: 0,
 		})
 	}
@@ -102,17 +107,28 @@ impl SnapshotWriter for PackedWriter {
 		Ok(())
 	}
 
+	fn write_shielded_chunk(&mut self, hash: H256, chunk: &[u8]) -> io::Result<()> {
+		self.file.write_all(chunk)?;
+
+		let len = chunk.len() as u64;
+		self.shielded_hashes.push(ChunkInfo(hash, len, self.cur_len));
+
+		self.cur_len += len;
+		Ok(())
+	}
+
 	fn finish(mut self, manifest: ManifestData) -> io::Result<()> {
 		// we ignore the hashes fields of the manifest under the assumption that
 		// they are consistent with ours.
-		let mut stream = RlpStream::new_list(6);
+		let mut stream = RlpStream::new_list(7);
 		stream
 			.append(&SNAPSHOT_VERSION)
 			.append_list(&self.state_hashes)
 			.append_list(&self.block_hashes)
 			.append(&manifest.state_root)
 			.append(&manifest.block_number)
-			.append(&manifest.block_hash);
+			.append(&manifest.block_hash)
+			.append_list(&self.shielded_hashes);
 
 		let manifest_rlp = stream.out();
 
@@ -172,6 +188,10 @@ impl SnapshotWriter for LooseWriter {
 		self.write_chunk(hash, chunk)
 	}
 
+	fn write_shielded_chunk(&mut self, hash: H256, chunk: &[u8]) -> io::Result<()> {
+		self.write_chunk(hash, chunk)
+	}
+
 	fn finish(self, manifest: ManifestData) -> io::Result<()> {
 		let rlp = manifest.into_rlp();
 		let mut path = self.dir.clone();
@@ -199,6 +219,7 @@ pub struct PackedReader {
 	file: File,
 	state_hashes: HashMap<H256, (u64, u64)>, // len, offset
 	block_hashes: HashMap<H256, (u64, u64)>, // len, offset
+	shielded_hashes: HashMap<H256, (u64, u64)>, // len, offset
 	manifest: ManifestData,
 }
 
@@ -251,11 +272,17 @@ impl PackedReader {
 
 		let state: Vec<ChunkInfo> = rlp.list_at(0 + start)?;
 		let blocks: Vec<ChunkInfo> = rlp.list_at(1 + start)?;
+		let shielded: Vec<ChunkInfo> = if rlp.item_count()? > 5 + start {
+			rlp.list_at(5 + start)?
+		} else {
+			Vec::new()
+		};
 
 		let manifest = ManifestData {
 			version: version,
 			state_hashes: state.iter().map(|c| c.0).collect(),
 			block_hashes: blocks.iter().map(|c| c.0).collect(),
+			shielded_hashes: shielded.iter().map(|c| c.0).collect(),
 			state_root: rlp.val_at(2 + start)?,
 			block_number: rlp.val_at(3 + start)?,
 			block_hash: rlp.val_at(4 + start)?,
@@ -265,6 +292,7 @@ impl PackedReader {
 			file: file,
 			state_hashes: state.into_iter().map(|c| (c.0, (c.1, c.2))).collect(),
 			block_hashes: blocks.into_iter().map(|c| (c.0, (c.1, c.2))).collect(),
+			shielded_hashes: shielded.into_iter().map(|c| (c.0, (c.1, c.2))).collect(),
 			manifest: manifest
 		}))
 	}
@@ -276,7 +304,9 @@ impl SnapshotReader for PackedReader {
 	}
 
 	fn chunk(&self, hash: H256) -> io::Result<Bytes> {
-		let &(len, off) = self.state_hashes.get(&hash).or_else(|| self.block_hashes.get(&hash))
+		let &(len, off) = self.state_hashes.get(&hash)
+			.or_else(|| self.block_hashes.get(&hash))
+			.or_else(|| self.shielded_hashes.get(&hash))
 			.expect("only chunks in the manifest can be requested; qed");
 
 		let mut file = &self.file;
@@ -341,6 +371,7 @@ mod tests {
 
 	const STATE_CHUNKS: &'static [&'static [u8]] = &[b"dog", b"cat", b"hello world", b"hi", b"notarealchunk"];
 	const BLOCK_CHUNKS: &'static [&'static [u8]] = &[b"hello!", b"goodbye!", b"abcdefg", b"hijklmnop", b"qrstuvwxy", b"and", b"z"];
+	const SHIELDED_CHUNKS: &'static [&'static [u8]] = &[b"frontier", b"anchors", b"nullifiers"];
 
 	#[test]
 	fn packed_write_and_read() {
@@ -350,6 +381,7 @@ mod tests {
 
 		let mut state_hashes = Vec::new();
 		let mut block_hashes = Vec::new();
+		let mut shielded_hashes = Vec::new();
 
 		for chunk in STATE_CHUNKS {
 			let hash = keccak(&chunk);
@@ -363,10 +395,17 @@ mod tests {
 			writer.write_block_chunk(keccak(&chunk), chunk).unwrap();
 		}
 
+		for chunk in SHIELDED_CHUNKS {
+			let hash = keccak(&chunk);
+			shielded_hashes.push(hash.clone());
+			writer.write_shielded_chunk(hash, chunk).unwrap();
+		}
+
 		let manifest = ManifestData {
 			version: SNAPSHOT_VERSION,
 			state_hashes: state_hashes,
 			block_hashes: block_hashes,
+			shielded_hashes: shielded_hashes,
 			state_root: keccak(b"notarealroot"),
 			block_number: 12345678987654321,
 			block_hash: keccak(b"notarealblock"),
@@ -377,7 +416,7 @@ mod tests {
 		let reader = PackedReader::new(&path).unwrap().unwrap();
 		assert_eq!(reader.manifest(), &manifest);
 
-		for hash in manifest.state_hashes.iter().chain(&manifest.block_hashes) {
+		for hash in manifest.state_hashes.iter().chain(&manifest.block_hashes).chain(&manifest.shielded_hashes) {
 			reader.chunk(hash.clone()).unwrap();
 		}
 	}
@@ -389,6 +428,7 @@ mod tests {
 
 		let mut state_hashes = Vec::new();
 		let mut block_hashes = Vec::new();
+		let mut shielded_hashes = Vec::new();
 
 		for chunk in STATE_CHUNKS {
 			let hash = keccak(&chunk);
@@ -402,10 +442,17 @@ mod tests {
 			writer.write_block_chunk(keccak(&chunk), chunk).unwrap();
 		}
 
+		for chunk in SHIELDED_CHUNKS {
+			let hash = keccak(&chunk);
+			shielded_hashes.push(hash.clone());
+			writer.write_shielded_chunk(hash, chunk).unwrap();
+		}
+
 		let manifest = ManifestData {
 			version: SNAPSHOT_VERSION,
 			state_hashes: state_hashes,
 			block_hashes: block_hashes,
+			shielded_hashes: shielded_hashes,
 			state_root: keccak(b"notarealroot"),
 			block_number: 12345678987654321,
 			block_hash: keccak(b"notarealblock)"),
@@ -416,7 +463,7 @@ mod tests {
 		let reader = LooseReader::new(tempdir.path().into()).unwrap();
 		assert_eq!(reader.manifest(), &manifest);
 
-		for hash in manifest.state_hashes.iter().chain(&manifest.block_hashes) {
+		for hash in manifest.state_hashes.iter().chain(&manifest.block_hashes).chain(&manifest.shielded_hashes) {
 			reader.chunk(hash.clone()).unwrap();
 		}
 	}
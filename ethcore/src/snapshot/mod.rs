@@ -65,6 +65,7 @@ pub use types::basic_account::BasicAccount;
 
 pub mod io;
 pub mod service;
+pub mod shielded;
 
 mod account;
 mod block;
@@ -204,12 +205,16 @@ pub fn take_snapshot<W: SnapshotWriter + Send>(
 		Ok((state_hashes, block_hashes))
 	})?;
 
-	info!(target: "snapshot", "produced {} state chunks and {} block chunks.", state_hashes.len(), block_hashes.len());
+	let shielded_hashes = shielded::chunk_shielded(chain, block_at, &writer, p)?;
+
+	info!(target: "snapshot", "produced {} state chunks, {} block chunks and {} shielded chunks.",
+		state_hashes.len(), block_hashes.len(), shielded_hashes.len());
 
 	let manifest_data = ManifestData {
 		version: snapshot_version,
 		state_hashes: state_hashes,
 		block_hashes: block_hashes,
+		shielded_hashes: shielded_hashes,
 		state_root: state_root,
 		block_number: number,
 		block_hash: block_at,
@@ -25,6 +25,7 @@ use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::cmp;
 
 use super::{ManifestData, StateRebuilder, Rebuilder, RestorationStatus, SnapshotService, MAX_CHUNK_SIZE};
+use super::shielded::ShieldedRebuilder;
 use super::io::{SnapshotReader, LooseReader, SnapshotWriter, LooseWriter};
 
 use blockchain::{BlockChain, BlockChainDB, BlockChainDBHandler};
@@ -70,13 +71,23 @@ pub trait DatabaseRestore: Send + Sync {
 	fn restore_db(&self, new_db: &str) -> Result<(), Error>;
 }
 
+/// Kind of a snapshot chunk being restored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ChunkKind {
+	State,
+	Block,
+	Shielded,
+}
+
 /// State restoration manager.
 struct Restoration {
 	manifest: ManifestData,
 	state_chunks_left: HashSet<H256>,
 	block_chunks_left: HashSet<H256>,
+	shielded_chunks_left: HashSet<H256>,
 	state: StateRebuilder,
 	secondary: Box<Rebuilder>,
+	shielded: ShieldedRebuilder,
 	writer: Option<LooseWriter>,
 	snappy_buffer: Bytes,
 	final_state_root: H256,
@@ -101,6 +112,7 @@ impl Restoration {
 
 		let state_chunks = manifest.state_hashes.iter().cloned().collect();
 		let block_chunks = manifest.block_hashes.iter().cloned().collect();
+		let shielded_chunks = manifest.shielded_hashes.iter().cloned().collect();
 
 		let raw_db = params.db;
 
@@ -109,6 +121,7 @@ impl Restoration {
 			.ok_or_else(|| ::snapshot::Error::SnapshotsUnsupported)?;
 
 		let secondary = components.rebuilder(chain, raw_db.clone(), &manifest)?;
+		let shielded_chain = BlockChain::new(Default::default(), params.genesis, raw_db.clone(), None);
 
 		let root = manifest.state_root.clone();
 
@@ -116,8 +129,10 @@ impl Restoration {
 			manifest: manifest,
 			state_chunks_left: state_chunks,
 			block_chunks_left: block_chunks,
+			shielded_chunks_left: shielded_chunks,
 			state: StateRebuilder::new(raw_db.key_value().clone(), params.pruning),
 			secondary: secondary,
+			shielded: ShieldedRebuilder::new(shielded_chain, raw_db.clone()),
 			writer: params.writer,
 			snappy_buffer: Vec::new(),
 			final_state_root: root,
@@ -169,6 +184,27 @@ impl Restoration {
 		Ok(())
 	}
 
+	// feeds a shielded pool chunk
+	fn feed_shielded(&mut self, hash: H256, chunk: &[u8], flag: &AtomicBool) -> Result<(), Error> {
+		if self.shielded_chunks_left.contains(&hash) {
+			let expected_len = snappy::decompressed_len(chunk)?;
+			if expected_len > MAX_CHUNK_SIZE {
+				trace!(target: "snapshot", "Discarding large chunk: {} vs {}", expected_len, MAX_CHUNK_SIZE);
+				return Err(::snapshot::Error::ChunkTooLarge.into());
+			}
+			let len = snappy::decompress_into(chunk, &mut self.snappy_buffer)?;
+
+			self.shielded.feed(&self.snappy_buffer[..len], flag)?;
+			if let Some(ref mut writer) = self.writer.as_mut() {
+				writer.write_shielded_chunk(hash, chunk)?;
+			}
+
+			self.shielded_chunks_left.remove(&hash);
+		}
+
+		Ok(())
+	}
+
 	// finish up restoration.
 	fn finalize(mut self, engine: &EthEngine) -> Result<(), Error> {
 		use trie::TrieError;
@@ -188,6 +224,11 @@ impl Restoration {
 		// connect out-of-order chunks and verify chain integrity.
 		self.secondary.finalize(engine)?;
 
+		// snapshots taken before the shielded pool was included carry no shielded chunks.
+		if !self.manifest.shielded_hashes.is_empty() {
+			self.shielded.finalize(self.manifest.block_hash)?;
+		}
+
 		if let Some(writer) = self.writer {
 			writer.finish(self.manifest)?;
 		}
@@ -198,7 +239,7 @@ impl Restoration {
 
 	// is everything done?
 	fn is_done(&self) -> bool {
-		self.block_chunks_left.is_empty() && self.state_chunks_left.is_empty()
+		self.block_chunks_left.is_empty() && self.state_chunks_left.is_empty() && self.shielded_chunks_left.is_empty()
 	}
 }
 
@@ -241,6 +282,7 @@ pub struct Service {
 	genesis_block: Bytes,
 	state_chunks: AtomicUsize,
 	block_chunks: AtomicUsize,
+	shielded_chunks: AtomicUsize,
 	client: Arc<SnapshotClient>,
 	progress: super::Progress,
 	taking_snapshot: AtomicBool,
@@ -262,6 +304,7 @@ impl Service {
 			genesis_block: params.genesis_block,
 			state_chunks: AtomicUsize::new(0),
 			block_chunks: AtomicUsize::new(0),
+			shielded_chunks: AtomicUsize::new(0),
 			client: params.client,
 			progress: Default::default(),
 			taking_snapshot: AtomicBool::new(false),
@@ -539,6 +582,7 @@ impl Service {
 
 		self.state_chunks.store(0, Ordering::SeqCst);
 		self.block_chunks.store(0, Ordering::SeqCst);
+		self.shielded_chunks.store(0, Ordering::SeqCst);
 
 		// tear down existing restoration.
 		*res = None;
@@ -573,7 +617,8 @@ impl Service {
 			engine: &*self.engine,
 		};
 
-		let state_chunks = manifest.state_hashes.len();
+		// shielded chunks are reported together with the state chunks.
+		let state_chunks = manifest.state_hashes.len() + manifest.shielded_hashes.len();
 		let block_chunks = manifest.block_hashes.len();
 
 		*res = Some(Restoration::new(params)?);
@@ -589,7 +634,7 @@ impl Service {
 			*restoration_status = RestorationStatus::Ongoing {
 				state_chunks: state_chunks as u32,
 				block_chunks: block_chunks as u32,
-				state_chunks_done: self.state_chunks.load(Ordering::SeqCst) as u32,
+				state_chunks_done: self.state_chunks_done(),
 				block_chunks_done: self.block_chunks.load(Ordering::SeqCst) as u32,
 			};
 		}
@@ -638,15 +683,17 @@ impl Service {
 
 		let hash = keccak(&buffer);
 
-		let is_state = if manifest.block_hashes.contains(&hash) {
-			false
+		let kind = if manifest.block_hashes.contains(&hash) {
+			ChunkKind::Block
 		} else if manifest.state_hashes.contains(&hash) {
-			true
+			ChunkKind::State
+		} else if manifest.shielded_hashes.contains(&hash) {
+			ChunkKind::Shielded
 		} else {
 			return Ok(false);
 		};
 
-		self.feed_chunk_with_restoration(restoration, hash, &buffer, is_state)?;
+		self.feed_chunk_with_restoration(restoration, hash, &buffer, kind)?;
 
 		trace!(target: "snapshot", "Fed chunk {:?}", hash);
 
@@ -691,11 +738,11 @@ impl Service {
 		Ok(())
 	}
 
-	/// Feed a chunk of either kind (block or state). no-op if no restoration or status is wrong.
-	fn feed_chunk(&self, hash: H256, chunk: &[u8], is_state: bool) {
+	/// Feed a chunk of any kind (block, state or shielded). no-op if no restoration or status is wrong.
+	fn feed_chunk(&self, hash: H256, chunk: &[u8], kind: ChunkKind) {
 		// TODO: be able to process block chunks and state chunks at same time?
 		let mut restoration = self.restoration.lock();
-		match self.feed_chunk_with_restoration(&mut restoration, hash, chunk, is_state) {
+		match self.feed_chunk_with_restoration(&mut restoration, hash, chunk, kind) {
 			Ok(()) |
 			Err(Error(SnapshotErrorKind::Snapshot(SnapshotError::RestorationAborted), _)) => (),
 			Err(e) => {
@@ -708,7 +755,7 @@ impl Service {
 	}
 
 	/// Feed a chunk with the Restoration
-	fn feed_chunk_with_restoration(&self, restoration: &mut Option<Restoration>, hash: H256, chunk: &[u8], is_state: bool) -> Result<(), Error> {
+	fn feed_chunk_with_restoration(&self, restoration: &mut Option<Restoration>, hash: H256, chunk: &[u8], kind: ChunkKind) -> Result<(), Error> {
 		let (result, db) = {
 			match self.status() {
 				RestorationStatus::Inactive | RestorationStatus::Failed => {
@@ -722,17 +769,19 @@ impl Service {
 							None => return Ok(()),
 						};
 
-						(match is_state {
-							true => rest.feed_state(hash, chunk, &self.restoring_snapshot),
-							false => rest.feed_blocks(hash, chunk, &*self.engine, &self.restoring_snapshot),
+						(match kind {
+							ChunkKind::State => rest.feed_state(hash, chunk, &self.restoring_snapshot),
+							ChunkKind::Block => rest.feed_blocks(hash, chunk, &*self.engine, &self.restoring_snapshot),
+							ChunkKind::Shielded => rest.feed_shielded(hash, chunk, &self.restoring_snapshot),
 						}.map(|_| rest.is_done()), rest.db.clone())
 					};
 
 					let res = match res {
 						Ok(is_done) => {
-							match is_state {
-								true => self.state_chunks.fetch_add(1, Ordering::SeqCst),
-								false => self.block_chunks.fetch_add(1, Ordering::SeqCst),
+							match kind {
+								ChunkKind::State => self.state_chunks.fetch_add(1, Ordering::SeqCst),
+								ChunkKind::Block => self.block_chunks.fetch_add(1, Ordering::SeqCst),
+								ChunkKind::Shielded => self.shielded_chunks.fetch_add(1, Ordering::SeqCst),
 							};
 
 							match is_done {
@@ -758,12 +807,22 @@ impl Service {
 
 	/// Feed a state chunk to be processed synchronously.
 	pub fn feed_state_chunk(&self, hash: H256, chunk: &[u8]) {
-		self.feed_chunk(hash, chunk, true);
+		self.feed_chunk(hash, chunk, ChunkKind::State);
 	}
 
 	/// Feed a block chunk to be processed synchronously.
 	pub fn feed_block_chunk(&self, hash: H256, chunk: &[u8]) {
-		self.feed_chunk(hash, chunk, false);
+		self.feed_chunk(hash, chunk, ChunkKind::Block);
+	}
+
+	/// Feed a shielded pool chunk to be processed synchronously.
+	pub fn feed_shielded_chunk(&self, hash: H256, chunk: &[u8]) {
+		self.feed_chunk(hash, chunk, ChunkKind::Shielded);
+	}
+
+	// number of state and shielded chunks restored so far.
+	fn state_chunks_done(&self) -> u32 {
+		(self.state_chunks.load(Ordering::SeqCst) + self.shielded_chunks.load(Ordering::SeqCst)) as u32
 	}
 }
 
@@ -794,6 +853,11 @@ impl SnapshotService for Service {
 							.iter()
 							.filter(|h| !restoration.state_chunks_left.contains(h))
 					)
+					.chain(
+						restoration.manifest.shielded_hashes
+							.iter()
+							.filter(|h| !restoration.shielded_chunks_left.contains(h))
+					)
 					.map(|h| *h)
 					.collect();
 
@@ -808,11 +872,11 @@ impl SnapshotService for Service {
 
 		match *cur_status {
 			RestorationStatus::Initializing { ref mut chunks_done } => {
-				*chunks_done = self.state_chunks.load(Ordering::SeqCst) as u32 +
+				*chunks_done = self.state_chunks_done() +
 					self.block_chunks.load(Ordering::SeqCst) as u32;
 			}
 			RestorationStatus::Ongoing { ref mut state_chunks_done, ref mut block_chunks_done, .. } => {
-				*state_chunks_done = self.state_chunks.load(Ordering::SeqCst) as u32;
+				*state_chunks_done = self.state_chunks_done();
 				*block_chunks_done = self.block_chunks.load(Ordering::SeqCst) as u32;
 			},
 			_ => (),
@@ -846,6 +910,12 @@ impl SnapshotService for Service {
 		}
 	}
 
+	fn restore_shielded_chunk(&self, hash: H256, chunk: Bytes) {
+		if let Err(e) = self.io_channel.lock().send(ClientIoMessage::FeedShieldedChunk(hash, chunk)) {
+			trace!("Error sending snapshot service message: {:?}", e);
+		}
+	}
+
 	fn shutdown(&self) {
 		self.abort_restore();
 	}
@@ -898,6 +968,7 @@ mod tests {
 			version: 2,
 			state_hashes: vec![],
 			block_hashes: vec![],
+			shielded_hashes: vec![],
 			state_root: Default::default(),
 			block_number: 0,
 			block_hash: Default::default(),
@@ -928,6 +999,7 @@ mod tests {
 				version: 2,
 				state_hashes: state_hashes.clone(),
 				block_hashes: block_hashes.clone(),
+				shielded_hashes: vec![],
 				state_root: H256::default(),
 				block_number: 100000,
 				block_hash: H256::default(),
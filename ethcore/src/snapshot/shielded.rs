@@ -0,0 +1,266 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shielded pool chunk creation and restoration.
+//!
+//! Shielded chunks carry the Sapling data `BlockChain` keeps next to the blocks:
+//! the note commitment tree of the snapshot block, the commitment roots which may
+//! still be used as anchors, and the set of all revealed nullifiers.
+//!
+//! Each chunk is an RLP list `[kind, [item, ...]]` where the items are
+//! `[block_hash, tree]` for the frontier, `[root, block_hash]` for anchors and
+//! `[nullifier, transaction_hash]` for nullifiers.
+//!
+//! The frontier and anchors are checked against the final Sapling roots of the restored
+//! headers, so snapshots of blocks before the root transition can't be restored. Headers
+//! don't commit to the nullifier set, which is taken on trust from the snapshot.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use blockchain::{BlockChain, BlockChainDB, BlockProvider};
+use bytes::Bytes;
+use db::{self, Writable};
+use db::keys::CommitmentTreeSerialization;
+use ethereum_types::{H256, U256};
+use hash::keccak;
+use kvdb::DBTransaction;
+use parking_lot::Mutex;
+use rlp::{RlpStream, Rlp};
+use snappy;
+use verification::MAX_ANCHOR_AGE;
+use zcash_primitives::merkle_tree::{CommitmentTree, Hashable};
+use zcash_primitives::sapling::Node;
+
+use super::{Error, Progress, PREFERRED_CHUNK_SIZE};
+use super::io::SnapshotWriter;
+
+/// Number of blocks, counting back from the snapshot block, whose commitment roots
+/// are exported as valid anchors. Older anchors are rejected by the block verification.
+pub const ANCHOR_WINDOW: u64 = MAX_ANCHOR_AGE;
+
+const FRONTIER_CHUNK: u8 = 0;
+const ANCHORS_CHUNK: u8 = 1;
+const NULLIFIERS_CHUNK: u8 = 2;
+
+/// Shielded pool chunker.
+struct ShieldedChunker<'a> {
+	hashes: Vec<H256>,
+	snappy_buffer: Vec<u8>,
+	writer: &'a Mutex<SnapshotWriter + 'a>,
+	progress: &'a Progress,
+}
+
+impl<'a> ShieldedChunker<'a> {
+	// Write out `items` as chunks of the given kind, splitting them
+	// so that no chunk grows beyond the preferred size.
+	fn write_all<I: IntoIterator<Item = Bytes>>(&mut self, kind: u8, items: I) -> Result<(), Error> {
+		let mut rlps = Vec::new();
+		let mut cur_size = 0;
+
+		for item in items {
+			if cur_size + item.len() > PREFERRED_CHUNK_SIZE && !rlps.is_empty() {
+				self.write_chunk(kind, &rlps)?;
+				rlps.clear();
+				cur_size = 0;
+			}
+
+			cur_size += item.len();
+			rlps.push(item);
+		}
+
+		if !rlps.is_empty() {
+			self.write_chunk(kind, &rlps)?;
+		}
+
+		Ok(())
+	}
+
+	fn write_chunk(&mut self, kind: u8, rlps: &[Bytes]) -> Result<(), Error> {
+		let mut stream = RlpStream::new_list(2);
+		stream.append(&kind);
+		stream.begin_list(rlps.len());
+		for rlp in rlps {
+			stream.append_raw(rlp, 1);
+		}
+
+		let raw_data = stream.out();
+
+		let compressed_size = snappy::compress_into(&raw_data, &mut self.snappy_buffer);
+		let compressed = &self.snappy_buffer[..compressed_size];
+		let hash = keccak(&compressed);
+
+		self.writer.lock().write_shielded_chunk(hash, compressed)?;
+		trace!(target: "snapshot", "wrote shielded chunk. kind: {}, size: {}, uncompressed size: {}", kind, compressed_size, raw_data.len());
+
+		self.progress.size.fetch_add(compressed_size, Ordering::SeqCst);
+		self.hashes.push(hash);
+
+		Ok(())
+	}
+}
+
+/// Create and write out all shielded pool chunks for the block with the given hash,
+/// returning the hashes of the chunks created.
+pub fn chunk_shielded<'a>(chain: &'a BlockChain, block_at: H256, writer: &Mutex<SnapshotWriter + 'a>, progress: &'a Progress) -> Result<Vec<H256>, Error> {
+	let mut chunker = ShieldedChunker {
+		hashes: Vec::new(),
+		snappy_buffer: vec![0; snappy::max_compressed_len(PREFERRED_CHUNK_SIZE)],
+		writer: writer,
+		progress: progress,
+	};
+
+	let frontier = chain.block_commitment_ser(&block_at)
+		.ok_or_else(|| Error::MissingCommitmentTree(block_at))?;
+	let mut tree = Vec::new();
+	frontier.get_commitment_tree().write(&mut tree)?;
+
+	let mut stream = RlpStream::new_list(2);
+	stream.append(&block_at).append(&tree);
+	chunker.write_all(FRONTIER_CHUNK, Some(stream.out()))?;
+
+	let anchors = anchors(chain, block_at);
+	debug!(target: "snapshot", "Chunking {} commitment anchors", anchors.len());
+	chunker.write_all(ANCHORS_CHUNK, anchors.into_iter().map(|(root, hash)| {
+		let mut stream = RlpStream::new_list(2);
+		stream.append(&root).append(&hash);
+		stream.out()
+	}))?;
+
+	chunker.write_all(NULLIFIERS_CHUNK, chain.nullifiers().map(|(nullifier, hash)| {
+		let mut stream = RlpStream::new_list(2);
+		stream.append(&nullifier).append(&hash);
+		stream.out()
+	}))?;
+
+	Ok(chunker.hashes)
+}
+
+// Commitment roots of the last `ANCHOR_WINDOW` blocks up to `block_at`, each mapped
+// to the most recent block with that root.
+fn anchors(chain: &BlockChain, block_at: H256) -> Vec<(H256, H256)> {
+	let mut seen = HashSet::new();
+	let mut anchors = Vec::new();
+
+	for hash in chain.ancestry_iter(block_at).into_iter().flat_map(|iter| iter).take(ANCHOR_WINDOW as usize) {
+		let root = match chain.block_commitment_ser(&hash) {
+			Some(ser) => ser.get_commitment_tree().root(),
+			// blocks restored from an older snapshot don't keep their tree.
+			None => break,
+		};
+
+		if seen.insert(root) {
+			let mut root_hash = H256::default();
+			root.write(&mut root_hash[..]).expect("a node is 32 bytes; qed");
+			anchors.push((root_hash, hash));
+		}
+	}
+
+	anchors
+}
+
+/// Used to rebuild the shielded pool from shielded chunks.
+pub struct ShieldedRebuilder {
+	chain: BlockChain,
+	db: Arc<BlockChainDB>,
+	frontier: Option<(H256, CommitmentTree<Node>)>,
+	anchors: Vec<(Node, H256)>,
+}
+
+impl ShieldedRebuilder {
+	/// Create a new shielded pool rebuilder writing into the given database. The frontier
+	/// and anchors are checked against the headers `chain` reads from the same database.
+	pub fn new(chain: BlockChain, db: Arc<BlockChainDB>) -> Self {
+		ShieldedRebuilder {
+			chain: chain,
+			db: db,
+			frontier: None,
+			anchors: Vec::new(),
+		}
+	}
+
+	/// Feed an uncompressed shielded chunk into the rebuilder.
+	pub fn feed(&mut self, chunk: &[u8], flag: &AtomicBool) -> Result<(), ::error::Error> {
+		let rlp = Rlp::new(chunk);
+		let kind: u8 = rlp.val_at(0)?;
+		let mut batch = DBTransaction::new();
+
+		for item in rlp.at(1)?.iter() {
+			if !flag.load(Ordering::SeqCst) { return Err(Error::RestorationAborted.into()) }
+
+			match kind {
+				// the frontier and anchors are only written once checked in `finalize`.
+				FRONTIER_CHUNK => {
+					let hash: H256 = item.val_at(0)?;
+					let tree: Bytes = item.val_at(1)?;
+					let tree = CommitmentTree::<Node>::read(&tree[..]).map_err(Error::Io)?;
+
+					self.frontier = Some((hash, tree));
+				},
+				ANCHORS_CHUNK => {
+					let root: H256 = item.val_at(0)?;
+					let hash: H256 = item.val_at(1)?;
+					let root = Node::read(&root[..]).map_err(Error::Io)?;
+
+					self.anchors.push((root, hash));
+				},
+				NULLIFIERS_CHUNK => {
+					let nullifier: U256 = item.val_at(0)?;
+					let hash: H256 = item.val_at(1)?;
+
+					batch.write(db::COL_EXTRA, &nullifier, &hash);
+				},
+				_ => return Err(Error::WrongChunkFormat(format!("Unknown shielded chunk kind {}", kind)).into()),
+			}
+		}
+
+		self.db.key_value().write_buffered(batch);
+		Ok(())
+	}
+
+	/// Check the restored commitment tree of the snapshot block and the anchors against
+	/// the final Sapling roots of their blocks, and write them out. The blocks must have
+	/// been restored already.
+	pub fn finalize(&self, block_hash: H256) -> Result<(), ::error::Error> {
+		let tree = match self.frontier {
+			Some((hash, ref tree)) if hash == block_hash => tree,
+			_ => return Err(Error::MissingCommitmentTree(block_hash).into()),
+		};
+		self.check_root(block_hash, &tree.root())?;
+
+		let mut batch = DBTransaction::new();
+		batch.write(db::COL_EXTRA, &block_hash, &CommitmentTreeSerialization::new(tree));
+		for &(ref root, hash) in &self.anchors {
+			self.check_root(hash, root)?;
+			batch.write(db::COL_EXTRA, root, &hash);
+		}
+
+		self.db.key_value().write_buffered(batch);
+		Ok(())
+	}
+
+	// Check that the given block commits to the given root.
+	fn check_root(&self, block_hash: H256, root: &Node) -> Result<(), Error> {
+		let mut expected = H256::default();
+		root.write(&mut expected[..]).expect("a node is 32 bytes; qed");
+
+		match self.chain.block_header_data(&block_hash).and_then(|header| header.final_sapling_root()) {
+			Some(ref found) if *found == expected => Ok(()),
+			_ => Err(Error::InvalidCommitmentRoot(block_hash)),
+		}
+	}
+}
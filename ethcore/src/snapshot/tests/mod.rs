@@ -20,6 +20,7 @@ mod proof_of_work;
 mod proof_of_authority;
 mod state;
 mod service;
+mod shielded;
 
 pub mod helpers;
 
@@ -30,6 +31,7 @@ fn manifest_rlp() {
 	let manifest = ManifestData {
 		version: 2,
 		block_hashes: Vec::new(),
+		shielded_hashes: Vec::new(),
 		state_hashes: Vec::new(),
 		block_number: 1234567,
 		state_root: Default::default(),
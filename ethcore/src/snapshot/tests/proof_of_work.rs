@@ -73,6 +73,7 @@ fn chunk_and_restore(amount: u64) {
 		version: 2,
 		state_hashes: Vec::new(),
 		block_hashes: block_hashes,
+		shielded_hashes: Vec::new(),
 		state_root: ::hash::KECCAK_NULL_RLP,
 		block_number: amount,
 		block_hash: best_hash,
@@ -135,6 +136,7 @@ fn checks_flag() {
 		version: 2,
 		state_hashes: Vec::new(),
 		block_hashes: Vec::new(),
+		shielded_hashes: Vec::new(),
 		state_root: ::hash::KECCAK_NULL_RLP,
 		block_number: 102,
 		block_hash: H256::default(),
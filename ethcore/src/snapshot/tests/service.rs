@@ -128,6 +128,7 @@ fn guards_delete_folders() {
 		version: 2,
 		state_hashes: vec![],
 		block_hashes: vec![],
+		shielded_hashes: vec![],
 		block_number: 0,
 		block_hash: Default::default(),
 		state_root: Default::default(),
@@ -197,6 +198,7 @@ fn keep_ancient_blocks() {
 		state_hashes: state_hashes,
 		state_root: state_root,
 		block_hashes: block_hashes,
+		shielded_hashes: vec![],
 		block_number: NUM_BLOCKS,
 		block_hash: best_hash,
 	};
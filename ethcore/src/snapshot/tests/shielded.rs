@@ -0,0 +1,191 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shielded pool chunker and rebuilder tests.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use tempdir::TempDir;
+
+use blockchain::generator::{BlockGenerator, BlockBuilder, BlockOptions};
+use blockchain::{BlockChain, BlockChainDB, BlockProvider, ExtrasInsert};
+use db::{self, Writable};
+use ethereum_types::{H256, U256};
+use snapshot::Progress;
+use snapshot::io::{PackedReader, PackedWriter, SnapshotReader, SnapshotWriter};
+use snapshot::shielded::{chunk_shielded, ShieldedRebuilder};
+use zcash_primitives::merkle_tree::{CommitmentTree, Hashable};
+use zcash_primitives::sapling::Node;
+
+use parking_lot::Mutex;
+use rlp::RlpStream;
+use snappy;
+use kvdb::DBTransaction;
+use test_helpers;
+
+// Root of the empty commitment tree, shared by all blocks without shielded outputs.
+fn empty_root() -> H256 {
+	let mut root = H256::default();
+	CommitmentTree::<Node>::new().root().write(&mut root[..]).unwrap();
+	root
+}
+
+// Insert the blocks of `generator` into a new chain backed by `db`.
+fn import(generator: BlockGenerator, genesis: &[u8], db: Arc<BlockChainDB>) -> BlockChain {
+	let bc = BlockChain::new(Default::default(), genesis, db.clone(), None);
+	let mut batch = DBTransaction::new();
+	for block in generator {
+		bc.insert_block(&mut batch, block.encoded(), vec![], ExtrasInsert {
+			fork_choice: ::engines::ForkChoice::New,
+			is_finalized: false,
+		});
+		bc.commit();
+	}
+	db.key_value().write(batch).unwrap();
+	bc
+}
+
+// Feed the given uncompressed chunks into a rebuilder restoring into `db`.
+fn rebuild(chunks: &[Vec<u8>], genesis: &[u8], db: Arc<BlockChainDB>) -> ShieldedRebuilder {
+	let chain = BlockChain::new(Default::default(), genesis, db.clone(), None);
+	let mut rebuilder = ShieldedRebuilder::new(chain, db);
+	let flag = AtomicBool::new(true);
+	for chunk in chunks {
+		rebuilder.feed(chunk, &flag).unwrap();
+	}
+	rebuilder
+}
+
+#[test]
+fn chunk_and_restore_shielded() {
+	let genesis = BlockBuilder::genesis();
+	let rest = genesis.add_blocks_with(100, || BlockOptions {
+		final_sapling_root: Some(empty_root()),
+		..Default::default()
+	});
+	let generator = BlockGenerator::new(vec![rest]);
+	let genesis = genesis.last();
+
+	let tempdir = TempDir::new("").unwrap();
+	let snapshot_path = tempdir.path().join("SNAP");
+
+	let old_db = test_helpers::new_db();
+	let bc = import(generator.clone(), genesis.encoded().raw(), old_db.clone());
+
+	let mut batch = DBTransaction::new();
+	let nullifiers: Vec<(U256, H256)> = (1..10u64).map(|i| (U256::from(i), H256::random())).collect();
+	for &(nullifier, tx_hash) in &nullifiers {
+		batch.write(db::COL_EXTRA, &nullifier, &tx_hash);
+	}
+	old_db.key_value().write(batch).unwrap();
+
+	let best_hash = bc.best_block_hash();
+	let tree = bc.block_commitment_ser(&best_hash).unwrap().get_commitment_tree();
+
+	// snapshot it.
+	let writer = Mutex::new(PackedWriter::new(&snapshot_path).unwrap());
+	let shielded_hashes = chunk_shielded(&bc, best_hash, &writer, &Progress::default()).unwrap();
+
+	let manifest = ::snapshot::ManifestData {
+		version: 2,
+		state_hashes: Vec::new(),
+		block_hashes: Vec::new(),
+		shielded_hashes: shielded_hashes,
+		state_root: ::hash::KECCAK_NULL_RLP,
+		block_number: 100,
+		block_hash: best_hash,
+	};
+
+	writer.into_inner().finish(manifest.clone()).unwrap();
+
+	// restore it, on top of the restored blocks.
+	let new_db = test_helpers::new_db();
+	let _ = import(generator, genesis.encoded().raw(), new_db.clone());
+
+	let reader = PackedReader::new(&snapshot_path).unwrap().unwrap();
+	assert_eq!(reader.manifest().shielded_hashes, manifest.shielded_hashes);
+
+	let chunks: Vec<_> = reader.manifest().shielded_hashes.iter()
+		.map(|chunk_hash| snappy::decompress(&reader.chunk(*chunk_hash).unwrap()).unwrap())
+		.collect();
+	let rebuilder = rebuild(&chunks, genesis.encoded().raw(), new_db.clone());
+	rebuilder.finalize(best_hash).unwrap();
+	drop(rebuilder);
+
+	// and test it.
+	let new_chain = BlockChain::new(Default::default(), genesis.encoded().raw(), new_db, None);
+	let restored_tree = new_chain.block_commitment_ser(&best_hash).unwrap().get_commitment_tree();
+	assert_eq!(restored_tree.root(), tree.root());
+	// no shielded outputs, so every block shares the root of the genesis block.
+	assert_eq!(new_chain.commitment_root_block(&tree.root()), bc.commitment_root_block(&tree.root()));
+	assert!(new_chain.commitment_root_block(&tree.root()).is_some());
+
+	for &(nullifier, tx_hash) in &nullifiers {
+		assert_eq!(new_chain.nullifier_transaction(&nullifier), Some(tx_hash));
+	}
+}
+
+#[test]
+fn finalize_requires_frontier() {
+	let genesis = BlockBuilder::genesis();
+	let db = test_helpers::new_db();
+	let rebuilder = rebuild(&[], genesis.last().encoded().raw(), db);
+
+	assert!(rebuilder.finalize(H256::random()).is_err());
+}
+
+#[test]
+fn finalize_rejects_roots_not_in_headers() {
+	let genesis = BlockBuilder::genesis();
+	let with_root = genesis.add_block_with(|| BlockOptions {
+		final_sapling_root: Some(empty_root()),
+		..Default::default()
+	});
+	let without_root = with_root.add_block();
+	let generator = BlockGenerator::new(vec![with_root.clone(), without_root.clone()]);
+	let genesis_rlp = genesis.last().encoded().raw().to_vec();
+
+	let db = test_helpers::new_db();
+	let _ = import(generator, &genesis_rlp, db.clone());
+
+	let chunk = |kind: u8, a: H256, b: Vec<u8>| {
+		let mut item = RlpStream::new_list(2);
+		item.append(&a).append(&b);
+		let mut stream = RlpStream::new_list(2);
+		stream.append(&kind);
+		stream.begin_list(1).append_raw(&item.out(), 1);
+		stream.out()
+	};
+	let mut tree = Vec::new();
+	CommitmentTree::<Node>::new().write(&mut tree).unwrap();
+	let frontier = |hash: H256| chunk(0, hash, tree.clone());
+	let anchor = |root: H256, hash: H256| chunk(1, root, hash.to_vec());
+
+	let with_root = with_root.last().hash();
+	let without_root = without_root.last().hash();
+
+	// the block commits to the restored tree.
+	let rebuilder = rebuild(&[frontier(with_root), anchor(empty_root(), with_root)], &genesis_rlp, db.clone());
+	assert!(rebuilder.finalize(with_root).is_ok());
+
+	// the block doesn't commit to any tree.
+	let rebuilder = rebuild(&[frontier(without_root)], &genesis_rlp, db.clone());
+	assert!(rebuilder.finalize(without_root).is_err());
+
+	// the anchor isn't the root of its block.
+	let rebuilder = rebuild(&[frontier(with_root), anchor(H256::from(1), with_root)], &genesis_rlp, db);
+	assert!(rebuilder.finalize(with_root).is_err());
+}
@@ -63,6 +63,7 @@ fn snap_and_restore() {
 		version: 2,
 		state_hashes: state_hashes,
 		block_hashes: Vec::new(),
+		shielded_hashes: Vec::new(),
 		state_root: state_root,
 		block_number: 1000,
 		block_hash: H256::default(),
@@ -177,6 +178,7 @@ fn checks_flag() {
 		version: 2,
 		state_hashes: state_hashes,
 		block_hashes: Vec::new(),
+		shielded_hashes: Vec::new(),
 		state_root: state_root,
 		block_number: 0,
 		block_hash: H256::default(),
@@ -55,6 +55,10 @@ pub trait SnapshotService : Sync + Send {
 	/// no-op if currently restoring.
 	fn restore_block_chunk(&self, hash: H256, chunk: Bytes);
 
+	/// Feed a raw shielded pool chunk to the service to be processed asynchronously.
+	/// no-op if currently restoring.
+	fn restore_shielded_chunk(&self, hash: H256, chunk: Bytes);
+
 	/// Shutdown the Snapshot Service by aborting any ongoing restore
 	fn shutdown(&self);
 }
@@ -135,6 +135,14 @@ pub struct CommonParams {
 	pub kip4_transition: BlockNumber,
 	/// Number of first block where KIP-6 rules begin. Only has effect if Wasm is activated.
 	pub kip6_transition: BlockNumber,
+	/// Number of first block whose header commits to the final Sapling note commitment tree root.
+	pub final_sapling_root_transition: BlockNumber,
+	/// Number of first block which can pay the author reward to a shielded address with a coinbase transaction.
+	pub shielded_coinbase_transition: BlockNumber,
+	/// Number of first block whose private transactions sign the Blake2b shielded signature hash.
+	pub shielded_sighash_transition: BlockNumber,
+	/// Number of first block whose private calls can carry public outputs.
+	pub public_outputs_transition: BlockNumber,
 	/// Gas limit bound divisor (how much gas limit can change per block)
 	pub gas_limit_bound_divisor: U256,
 	/// Registrar contract address.
@@ -345,6 +353,22 @@ impl From<ethjson::spec::Params> for CommonParams {
 				BlockNumber::max_value,
 				Into::into
 			),
+			final_sapling_root_transition: p.final_sapling_root_transition.map_or_else(
+				BlockNumber::max_value,
+				Into::into
+			),
+			shielded_coinbase_transition: p.shielded_coinbase_transition.map_or_else(
+				BlockNumber::max_value,
+				Into::into
+			),
+			shielded_sighash_transition: p.shielded_sighash_transition.map_or_else(
+				BlockNumber::max_value,
+				Into::into
+			),
+			public_outputs_transition: p.public_outputs_transition.map_or_else(
+				BlockNumber::max_value,
+				Into::into
+			),
 			reward_config: reward_config,
 		}
 	}
@@ -27,10 +27,16 @@ pub trait Transaction {
 
 impl Transaction for transaction::Transaction {
 	fn gas_required(&self, schedule: &Schedule) -> u64 {
+		// The coinbase is created by the block author and executes nothing.
+		if self.is_coinbase() {
+			return 0;
+		}
 		gas_required_for(match self.action {
 			Action::Create => true,
 			Action::Call(_) | Action::PrivateToPrivate | Action::PublicToPrivate | Action::PrivateCall(_) => false
 		}, &self.data, schedule)
+			// Each public output of a private transaction is a value transfer.
+			+ self.public_outputs().len() as u64 * schedule.call_value_transfer_gas as u64
 	}
 }
 
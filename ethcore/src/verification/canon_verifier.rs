@@ -38,8 +38,8 @@ impl<C: BlockInfo + CallContract> Verifier<C> for CanonVerifier {
 		verification::verify_block_family(header, parent, engine, do_full)
 	}
 
-	fn verify_block_final(&self, expected: &Header, got: &Header) -> Result<(), Error> {
-		verification::verify_block_final(expected, got)
+	fn verify_block_final(&self, expected: &Header, got: &Header, engine: &EthEngine) -> Result<(), Error> {
+		verification::verify_block_final(expected, got, engine)
 	}
 
 	fn verify_block_external(&self, header: &Header, engine: &EthEngine) -> Result<(), Error> {
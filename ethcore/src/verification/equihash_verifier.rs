@@ -15,13 +15,12 @@
 // along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
 
 use error::{BlockError, Error};
-use equihash::equihash::{get_solution_prefix, verify_equihash_solution, OnChainEquihash};
-use ethereum_types::{H256, U256};
+use equihash::equihash::{get_solution_prefix, verify_equihash_solution, Equihash, OnChainEquihash};
+use ethereum_types::{H256, H512, U256};
 use rlp::decode;
 use rust_crypto::digest::Digest;
 use rust_crypto::sha2::Sha256;
 use types::header::Header;
-use types::solution::EquihashSolution;
 use unexpected::OutOfBounds;
 
 fn calc_proof(input: Vec<u8>) -> H256 {
@@ -39,7 +38,24 @@ fn calc_proof(input: Vec<u8>) -> H256 {
 	proof
 }
 
-fn check_block_seal(header: &Header) -> Result<(H256, EquihashSolution), Error> {
+fn solution_proof(pow_hash: &H512, nonce: &H256, solution: &[u8]) -> H256 {
+	let mut input = pow_hash.0.to_vec();
+	input.extend(nonce.0.to_vec());
+	input.extend(get_solution_prefix::<OnChainEquihash>());
+	input.extend(solution);
+
+	trace!(target: "miner", "input {:?}", input);
+
+	calc_proof(input)
+}
+
+/// Check whether an equihash solution found for the given work meets the difficulty `boundary`.
+pub fn solution_meets_boundary(pow_hash: &H512, nonce: &H256, solution: &[u8], boundary: &H256) -> bool {
+	solution_proof(pow_hash, nonce, solution) <= *boundary
+}
+
+// Decode the nonce and the solution of the seal, the solution sized for the given parameters.
+fn check_block_seal<Algorithm: Equihash>(header: &Header) -> Result<(H256, Vec<u8>), Error> {
 	trace!(target: "miner", "check_block_seal");
 	let seal = header.seal();
 
@@ -48,19 +64,11 @@ fn check_block_seal(header: &Header) -> Result<(H256, EquihashSolution), Error>
 		return Err(BlockError::InvalidSeal.into());
 	}
 
-	let nonce = match decode::<H256>(&seal[0]) {
-		Ok(n) => n,
-		Err(_) => {
-			return Err(BlockError::InvalidSeal.into());
-		}
-	};
-
-	let solution = match decode::<EquihashSolution>(&seal[1]) {
-		Ok(s) => s,
-		Err(_) => {
-			return Err(BlockError::InvalidSeal.into());
-		}
-	};
+	let nonce = decode::<H256>(&seal[0]).map_err(|_| BlockError::InvalidSeal)?;
+	let solution = decode::<Vec<u8>>(&seal[1]).map_err(|_| BlockError::InvalidSeal)?;
+	if solution.len() != Algorithm::SOLUTION_COMPRESSED_SIZE {
+		return Err(BlockError::InvalidSeal.into());
+	}
 
 	Ok((nonce, solution))
 }
@@ -68,22 +76,10 @@ fn check_block_seal(header: &Header) -> Result<(H256, EquihashSolution), Error>
 pub fn verify_block_pow(header: &Header) -> Result<(), Error> {
 	trace!(target: "miner", "verify_block_solution");
 
-	let (nonce, solution) =  match check_block_seal(header) {
-		Ok((n, s))=>(n,s),
-		Err(e)=> return Err(e),
-	};
-
-	let pow_hash = header.pow_hash();
-	let mut input = pow_hash.0.to_vec();
-	input.extend(nonce.0.to_vec());
+	let (nonce, solution) = check_block_seal::<OnChainEquihash>(header)?;
 
 	let target = equihash::difficulty_to_boundary(&header.difficulty());
-	input.extend(get_solution_prefix::<OnChainEquihash>());
-	input.extend(solution.as_ref());
-
-	trace!(target: "miner", "input {:?}", input);
-
-	let proof = calc_proof(input);
+	let proof = solution_proof(&header.pow_hash(), &nonce, &solution);
 	trace!(target: "miner", "verify proof hash {:?} target {:?}", proof, target);
 
 	match target.cmp(&proof) {
@@ -101,18 +97,19 @@ pub fn verify_block_pow(header: &Header) -> Result<(), Error> {
 /// verify block equihash solution
 pub fn verify_block_solution(header: &Header) -> Result<(), Error> {
 	trace!(target: "miner", "verify_block_solution");
+	verify_solution::<OnChainEquihash>(header)
+}
 
-	let (nonce, solution) =  match check_block_seal(header) {
-		Ok((n, s))=>(n,s),
-		Err(e)=> return Err(e),
-	};
+// Verify the equihash solution of the seal with the given parameters.
+fn verify_solution<Algorithm: Equihash>(header: &Header) -> Result<(), Error> {
+	let (nonce, solution) = check_block_seal::<Algorithm>(header)?;
 
 	let pow_hash = header.pow_hash();
 	let mut input = pow_hash.0.to_vec();
 	input.extend(nonce.0.to_vec());
 
 	trace!(target: "miner", "verify solution input {:?},  solution {:?}", input, solution);
-	if (!verify_equihash_solution::<OnChainEquihash>(&input, &solution.as_ref())) {
+	if !verify_equihash_solution::<Algorithm>(&input, &solution) {
 		return Err(BlockError::InvalidSolution.into());
 	}
 
@@ -121,17 +118,51 @@ pub fn verify_block_solution(header: &Header) -> Result<(), Error> {
 
 #[cfg(test)]
 mod tests {
-	use equihash::equihash::{get_solution_prefix, verify_equihash_solution, OnChainEquihash};
-	use ethereum_types::H256;
+	use std::sync::atomic::AtomicBool;
+	use equihash::equihash::{get_solution_prefix, Equihash, OnChainEquihash, TestEquihash};
+	use equihash::solver::solve_equihash;
+	use ethereum_types::{H256, U256};
+	use rlp::encode;
 	use rustc_hex::FromHex;
 	use types::header::Header;
-	use verification::equihash_verifier::calc_proof;
+	use verification::equihash_verifier::{calc_proof, verify_solution};
 
 	#[test]
 	//TODO(eric) do equihash verify test
 	#[ignore]
 	fn test_equihash_on_real_block() {}
 
+	#[test]
+	fn test_solver_solution_verifies() {
+		let mut header = Header::default();
+		header.set_number(1);
+		header.set_difficulty(U256::from(1));
+
+		let solutions: Vec<_> = (0..8u64).flat_map(|nonce| {
+			let nonce = H256::from(nonce);
+			let mut input = header.pow_hash().to_vec();
+			input.extend_from_slice(&nonce);
+			solve_equihash::<TestEquihash>(&input, 1, &AtomicBool::new(false))
+				.into_iter()
+				.map(move |solution| (nonce, solution))
+		}).collect();
+		assert!(!solutions.is_empty());
+
+		for (nonce, mut solution) in solutions {
+			header.set_seal(vec![encode(&nonce), encode(&solution)]);
+			assert!(verify_solution::<TestEquihash>(&header).is_ok());
+
+			// any tampering with the solution must be caught.
+			solution[5] ^= 0x01;
+			header.set_seal(vec![encode(&nonce), encode(&solution)]);
+			assert!(verify_solution::<TestEquihash>(&header).is_err());
+
+			// and so must a solution for other parameters.
+			header.set_seal(vec![encode(&nonce), encode(&vec![0u8; OnChainEquihash::SOLUTION_COMPRESSED_SIZE])]);
+			assert!(verify_solution::<TestEquihash>(&header).is_err());
+		}
+	}
+
 	#[test]
 	fn test_calc_proof() {
 		let pow_hash = "d5475a47f5905fd62e052fd49d0a9aa012da7324288cb0aea60d85f4126321bcf7a84f7fb00b65852b5ac7b5b987fd5f2e52a69a774ff0d998545fd6cfdc2ff0".from_hex().unwrap();
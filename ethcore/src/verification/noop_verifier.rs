@@ -38,7 +38,7 @@ impl<C: BlockInfo + CallContract> Verifier<C> for NoopVerifier {
 		Ok(())
 	}
 
-	fn verify_block_final(&self, _expected: &Header, _got: &Header) -> Result<(), Error> {
+	fn verify_block_final(&self, _expected: &Header, _got: &Header, _engine: &EthEngine) -> Result<(), Error> {
 		Ok(())
 	}
 
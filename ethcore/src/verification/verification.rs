@@ -36,14 +36,22 @@ use call_contract::CallContract;
 use client::BlockInfo;
 use db::keys::CommitmentTreeSerialization;
 use engines::{EthEngine, MAX_UNCLE_AGE};
+use ethereum_types::U256;
 use error::{BlockError, Error};
-use types::transaction::SignedTransaction;
+use ethcore_miner::pool::verifier::verify_private_transactions;
+use ff::PrimeField;
+use types::transaction::{Error as TransactionError, SignedTransaction, CONVERSION_FACTOR};
 use types::{header::Header, BlockNumber};
 use verification::queue::kind::blocks::Unverified;
+use zcash_primitives::sapling::Node;
 
 #[cfg(not(time_checked_add))]
 use time_utils::CheckedSystemTime;
 
+/// The number of blocks back that the commitment root anchoring a spend can be,
+/// the depth of the witnesses kept by the wallets.
+pub const MAX_ANCHOR_AGE: BlockNumber = 100;
+
 /// Preprocessed block data gathered in `verify_block_unordered` call
 pub struct PreverifiedBlock {
 	/// Populated block header
@@ -87,7 +95,33 @@ pub fn verify_block_basic(
 	for t in &block.transactions {
 		engine.verify_transaction_basic(t, &block.header)?;
 	}
+	verify_coinbase(block, engine)?;
+
+	Ok(())
+}
 
+/// Check that a coinbase transaction is only the first transaction of a block
+/// enabling shielded coinbases, and that it pays at most the author reward of the engine.
+/// Engines without an author reward can't have coinbases.
+pub(crate) fn verify_coinbase(block: &Unverified, engine: &EthEngine) -> Result<(), BlockError> {
+	for (index, t) in block.transactions.iter().enumerate() {
+		if !t.is_coinbase() {
+			continue;
+		}
+		if index != 0 || block.header.number() < engine.params().shielded_coinbase_transition {
+			return Err(BlockError::InvalidCoinbase(t.hash()));
+		}
+		let reward = engine.author_reward(block.header.number(), block.uncles.len())
+			.ok_or_else(|| BlockError::InvalidCoinbase(t.hash()))?;
+		let value = U256::from(t.balancing_value().wrapping_neg() as u64) * CONVERSION_FACTOR;
+		if value > reward {
+			return Err(BlockError::InvalidCoinbaseValue(OutOfBounds {
+				min: None,
+				max: Some(reward),
+				found: value,
+			}));
+		}
+	}
 	Ok(())
 }
 
@@ -127,6 +161,8 @@ pub fn verify_block_unordered(
 		})
 		.collect::<Result<Vec<_>, Error>>()?;
 
+	verify_shielded_proofs(&transactions, engine.machine().shielded_sighash(header.number()))?;
+
 	Ok(PreverifiedBlock {
 		header,
 		transactions,
@@ -164,6 +200,7 @@ pub fn verify_block_family<C: BlockInfo + CallContract>(
 	};
 
 	verify_uncles(params.block, params.block_provider, engine)?;
+	verify_shielded_state(params.block, params.block_provider)?;
 
 	for tx in &params.block.transactions {
 		// transactions are verified against the parent header since the current
@@ -176,6 +213,48 @@ pub fn verify_block_family<C: BlockInfo + CallContract>(
 	Ok(())
 }
 
+/// Check the Sapling proofs and signatures of all private transactions in a block,
+/// batching the proofs of the whole block into one verification.
+fn verify_shielded_proofs(transactions: &[SignedTransaction], shielded_sighash: bool) -> Result<(), BlockError> {
+	verify_private_transactions(transactions.iter().filter(|t| t.is_private()), shielded_sighash).map_err(|(hash, e)| match e {
+		TransactionError::InvalidSpendDescription => BlockError::InvalidShieldedSpend(hash),
+		TransactionError::InvalidOutputDescription => BlockError::InvalidShieldedOutput(hash),
+		_ => BlockError::InvalidBindingSignature(hash),
+	})
+}
+
+/// Check anchors and nullifiers of the block's private transactions against the chain.
+/// Anchors must be the commitment root of one of the last `MAX_ANCHOR_AGE` blocks.
+/// Nullifiers must be unique within the block and must not have been revealed before.
+fn verify_shielded_state(block: &PreverifiedBlock, bc: &BlockProvider) -> Result<(), Error> {
+	let mut nullifiers = HashSet::new();
+	for tx in block.transactions.iter().filter(|tx| tx.is_private()) {
+		for anchor in tx.get_commitment_anchors() {
+			// The root is mapped to the last block having it, an unchanged root stays recent.
+			let anchor_number = bc.commitment_root_block(&Node::new(anchor.into_repr()))
+				.and_then(|hash| bc.block_number(&hash));
+			match anchor_number {
+				Some(number) if number + MAX_ANCHOR_AGE >= block.header.number() => {},
+				_ => return Err(From::from(BlockError::InvalidCommitmentAnchor(tx.hash()))),
+			}
+		}
+
+		// Go through the spends rather than the nullifier set, so that a nullifier
+		// revealed twice in the same transaction is caught too.
+		let spends = tx.private.as_ref().map_or(&[][..], |private| &private.spends[..]);
+		for nullifier in spends.iter().map(|spend| U256::from(spend.nullifier)) {
+			if bc.nullifier_transaction(&nullifier).is_some() {
+				return Err(From::from(BlockError::NullifierAlreadySpent(nullifier)));
+			}
+			if !nullifiers.insert(nullifier) {
+				return Err(From::from(BlockError::DuplicateNullifier(nullifier)));
+			}
+		}
+	}
+
+	Ok(())
+}
+
 fn verify_uncles(
 	block: &PreverifiedBlock,
 	bc: &BlockProvider,
@@ -287,7 +366,7 @@ fn verify_uncles(
 }
 
 /// Phase 4 verification. Check block information against transaction enactment results,
-pub fn verify_block_final(expected: &Header, got: &Header) -> Result<(), Error> {
+pub fn verify_block_final(expected: &Header, got: &Header, engine: &EthEngine) -> Result<(), Error> {
 	if expected.state_root() != got.state_root() {
 		return Err(From::from(BlockError::InvalidStateRoot(Mismatch {
 			expected: *expected.state_root(),
@@ -314,6 +393,19 @@ pub fn verify_block_final(expected: &Header, got: &Header) -> Result<(), Error>
 			found: *got.receipts_root(),
 		})));
 	}
+	if expected.final_sapling_root() != got.final_sapling_root() {
+		return Err(From::from(BlockError::InvalidFinalSaplingRoot(Mismatch {
+			expected: expected.final_sapling_root().cloned().unwrap_or_default(),
+			found: got.final_sapling_root().cloned().unwrap_or_default(),
+		})));
+	}
+	// Both roots are missing after the transition when the parent's tree is unknown.
+	if got.number() >= engine.params().final_sapling_root_transition && got.final_sapling_root().is_none() {
+		return Err(From::from(BlockError::InvalidFinalSaplingRootPresence(Mismatch {
+			expected: true,
+			found: false,
+		})));
+	}
 	Ok(())
 }
 
@@ -367,6 +459,13 @@ pub fn verify_header_params(
 			)));
 		}
 	}
+	let final_sapling_root_expected = header.number() >= engine.params().final_sapling_root_transition;
+	if header.final_sapling_root().is_some() != final_sapling_root_expected {
+		return Err(From::from(BlockError::InvalidFinalSaplingRootPresence(Mismatch {
+			expected: final_sapling_root_expected,
+			found: header.final_sapling_root().is_some(),
+		})));
+	}
 	let maximum_extra_data_size = engine.maximum_extra_data_size();
 	if header.number() != 0 && header.extra_data().len() > maximum_extra_data_size {
 		return Err(From::from(BlockError::ExtraDataOutOfBounds(OutOfBounds {
@@ -513,7 +612,6 @@ mod tests {
 	use types::log_entry::{LocalizedLogEntry, LogEntry};
 	use types::transaction::{Action, SignedTransaction, Transaction, UnverifiedTransaction};
 	use types::rewards::Rewards;
-	use zcash_primitives::sapling::Node;
 
 	fn check_ok(result: Result<(), Error>) {
 		result.unwrap_or_else(|e| panic!("Block verification failed: {:?}", e));
@@ -551,6 +649,8 @@ mod tests {
 		blocks: HashMap<H256, Bytes>,
 		numbers: HashMap<BlockNumber, H256>,
 		era_rewards: Rewards,
+		nullifiers: HashMap<U256, H256>,
+		commitment_roots: HashMap<Node, H256>,
 	}
 
 	impl Default for TestBlockChain {
@@ -565,6 +665,8 @@ mod tests {
 				blocks: HashMap::new(),
 				numbers: HashMap::new(),
 				era_rewards: Rewards::new(),
+				nullifiers: HashMap::new(),
+				commitment_roots: HashMap::new(),
 			}
 		}
 
@@ -624,12 +726,12 @@ mod tests {
 			unimplemented!()
 		}
 
-		fn nullifier_transaction(&self, _nullifier: &U256) -> Option<H256> {
-			unimplemented!()
+		fn nullifier_transaction(&self, nullifier: &U256) -> Option<H256> {
+			self.nullifiers.get(nullifier).cloned()
 		}
 
-		fn commitment_root_block(&self, _root: &Node) -> Option<H256> {
-			unimplemented!()
+		fn commitment_root_block(&self, root: &Node) -> Option<H256> {
+			self.commitment_roots.get(root).cloned()
 		}
 
 		fn block_commitment_ser(&self, _hash: &H256) -> Option<CommitmentTreeSerialization> {
@@ -850,6 +952,7 @@ mod tests {
 		bc.insert(create_test_block(&parent6));
 		bc.insert(create_test_block(&parent7));
 		bc.insert(create_test_block(&parent8));
+		bc.commitment_roots.insert(Node::new(tr1.get_commitment_anchors()[0].into_repr()), parent.hash());
 
 		check_ok(basic_test(&create_test_block(&good), engine));
 
@@ -1084,6 +1187,190 @@ mod tests {
 		// TODO: some additional uncle checks
 	}
 
+	#[test]
+	fn test_verify_shielded_state() {
+		let block = |transactions: Vec<SignedTransaction>| PreverifiedBlock {
+			header: Header::default(),
+			transactions,
+			uncles: Vec::new(),
+			bytes: Vec::new(),
+		};
+		let private_tx = |nullifiers: Vec<u8>| {
+			Transaction::create_private(nullifiers, U256::zero(), Action::PrivateToPrivate)
+				.sign_for_private(2)
+		};
+
+		let mut bc = TestBlockChain::new();
+		let unknown_anchor = block(vec![private_tx(vec![1])]);
+		check_fail(
+			verify_shielded_state(&unknown_anchor, &bc),
+			InvalidCommitmentAnchor(unknown_anchor.transactions[0].hash()),
+		);
+
+		let anchor = unknown_anchor.transactions[0].get_commitment_anchors()[0];
+		bc.commitment_roots.insert(Node::new(anchor.into_repr()), H256::random());
+		check_fail(
+			verify_shielded_state(&unknown_anchor, &bc),
+			InvalidCommitmentAnchor(unknown_anchor.transactions[0].hash()),
+		);
+
+		let anchor_header = Header::default();
+		bc.insert(create_test_block(&anchor_header));
+		bc.commitment_roots.insert(Node::new(anchor.into_repr()), anchor_header.hash());
+		check_ok(verify_shielded_state(&block(vec![private_tx(vec![1]), private_tx(vec![2])]), &bc));
+
+		// Anchors older than MAX_ANCHOR_AGE blocks are rejected.
+		let mut recent = block(vec![private_tx(vec![1])]);
+		recent.header.set_number(MAX_ANCHOR_AGE);
+		check_ok(verify_shielded_state(&recent, &bc));
+		let mut expired = block(vec![private_tx(vec![1])]);
+		expired.header.set_number(MAX_ANCHOR_AGE + 1);
+		check_fail(
+			verify_shielded_state(&expired, &bc),
+			InvalidCommitmentAnchor(expired.transactions[0].hash()),
+		);
+
+		check_fail(
+			verify_shielded_state(&block(vec![private_tx(vec![1]), private_tx(vec![1, 2])]), &bc),
+			DuplicateNullifier(U256::from([1u8; 32])),
+		);
+
+		check_fail(
+			verify_shielded_state(&block(vec![private_tx(vec![1, 1])]), &bc),
+			DuplicateNullifier(U256::from([1u8; 32])),
+		);
+
+		bc.nullifiers.insert(U256::from([2u8; 32]), H256::random());
+		check_fail(
+			verify_shielded_state(&block(vec![private_tx(vec![1]), private_tx(vec![2])]), &bc),
+			NullifierAlreadySpent(U256::from([2u8; 32])),
+		);
+	}
+
+	#[test]
+	fn test_verify_unordered_rejects_invalid_proofs() {
+		let spec = Spec::new_test();
+		let engine = &*spec.engine;
+
+		let mut header = Header::default();
+		header.set_number(1);
+		let tx = Transaction::create_private(vec![0], U256::zero(), Action::PrivateToPrivate)
+			.sign_for_private(2);
+
+		check_fail(
+			unordered_test(&create_test_block_with_data(&header, &[tx.clone()], &[]), engine),
+			InvalidShieldedSpend(tx.hash()),
+		);
+	}
+
+	#[test]
+	fn test_verify_coinbase() {
+		use engines::NullEngine;
+		use machine::EthereumMachine;
+
+		let mut params = CommonParams::default();
+		params.shielded_coinbase_transition = 2;
+		let machine = EthereumMachine::regular(params, BTreeMap::new());
+		let engine = NullEngine::new(Default::default(), machine);
+
+		let mut coinbase = Transaction::create_private(vec![], U256::zero(), Action::PrivateToPrivate);
+		coinbase.gas = U256::zero();
+		coinbase.data = Vec::new();
+		coinbase.private.as_mut().unwrap().outputs.pop();
+		coinbase.private.as_mut().unwrap().balancing_value = -5;
+		let coinbase = coinbase.sign_for_private(2);
+		let private_tx = Transaction::create_private(vec![1], U256::zero(), Action::PrivateToPrivate)
+			.sign_for_private(2);
+		let unverified = |number: BlockNumber, transactions: &[SignedTransaction]| {
+			let mut header = Header::default();
+			header.set_number(number);
+			Unverified::from_rlp(create_test_block_with_data(&header, transactions, &[])).unwrap()
+		};
+
+		assert!(verify_coinbase(&unverified(2, &[private_tx.clone()]), &engine).is_ok());
+		assert_eq!(
+			verify_coinbase(&unverified(1, &[coinbase.clone()]), &engine),
+			Err(InvalidCoinbase(coinbase.hash()))
+		);
+		assert_eq!(
+			verify_coinbase(&unverified(2, &[private_tx, coinbase.clone()]), &engine),
+			Err(InvalidCoinbase(coinbase.hash()))
+		);
+		// The engine has no author reward for the coinbase to pay.
+		assert_eq!(
+			verify_coinbase(&unverified(2, &[coinbase.clone()]), &engine),
+			Err(InvalidCoinbase(coinbase.hash()))
+		);
+	}
+
+	#[test]
+	fn test_verify_block_final_sapling_root() {
+		use engines::NullEngine;
+		use machine::EthereumMachine;
+
+		let spec = Spec::new_test();
+		let engine = &*spec.engine;
+
+		let mut expected = Header::default();
+		let mut got = Header::default();
+		check_ok(verify_block_final(&expected, &got, engine));
+
+		expected.set_final_sapling_root(Some(H256::from(1)));
+		check_fail(
+			verify_block_final(&expected, &got, engine),
+			InvalidFinalSaplingRoot(Mismatch { expected: H256::from(1), found: H256::zero() }),
+		);
+
+		got.set_final_sapling_root(Some(H256::from(2)));
+		check_fail(
+			verify_block_final(&expected, &got, engine),
+			InvalidFinalSaplingRoot(Mismatch { expected: H256::from(1), found: H256::from(2) }),
+		);
+
+		got.set_final_sapling_root(Some(H256::from(1)));
+		check_ok(verify_block_final(&expected, &got, engine));
+
+		let mut params = CommonParams::default();
+		params.final_sapling_root_transition = 0;
+		let machine = EthereumMachine::regular(params, BTreeMap::new());
+		let engine = NullEngine::new(Default::default(), machine);
+		check_fail(
+			verify_block_final(&Header::default(), &Header::default(), &engine),
+			InvalidFinalSaplingRootPresence(Mismatch { expected: true, found: false }),
+		);
+	}
+
+	#[test]
+	fn test_verify_header_params_final_sapling_root() {
+		use engines::NullEngine;
+		use machine::EthereumMachine;
+
+		let mut params = CommonParams::default();
+		params.final_sapling_root_transition = 2;
+		params.min_gas_limit = 0.into();
+		let machine = EthereumMachine::regular(params, BTreeMap::new());
+		let engine = NullEngine::new(Default::default(), machine);
+
+		let mut header = Header::default();
+		header.set_number(1);
+		check_ok(verify_header_params(&header, &engine, false, false));
+
+		header.set_final_sapling_root(Some(H256::from(1)));
+		check_fail(
+			verify_header_params(&header, &engine, false, false),
+			InvalidFinalSaplingRootPresence(Mismatch { expected: false, found: true }),
+		);
+
+		header.set_number(2);
+		check_ok(verify_header_params(&header, &engine, false, false));
+
+		header.set_final_sapling_root(None);
+		check_fail(
+			verify_header_params(&header, &engine, false, false),
+			InvalidFinalSaplingRootPresence(Mismatch { expected: true, found: false }),
+		);
+	}
+
 	#[test]
 	fn dust_protection() {
 		use engines::NullEngine;
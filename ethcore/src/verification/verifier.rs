@@ -37,7 +37,7 @@ pub trait Verifier<C>: Send + Sync
 	) -> Result<(), Error>;
 
 	/// Do a final verification check for an enacted header vs its expected counterpart.
-	fn verify_block_final(&self, expected: &Header, got: &Header) -> Result<(), Error>;
+	fn verify_block_final(&self, expected: &Header, got: &Header, engine: &EthEngine) -> Result<(), Error>;
 	/// Verify a block, inspecing external state.
 	fn verify_block_external(&self, header: &Header, engine: &EthEngine) -> Result<(), Error>;
 }
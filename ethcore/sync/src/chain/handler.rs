@@ -544,6 +544,10 @@ impl SyncHandler {
 				trace!(target: "sync", "{}: Processing state chunk", peer_id);
 				io.snapshot_service().restore_state_chunk(hash, snapshot_data);
 			}
+			Ok(ChunkType::Shielded(hash)) => {
+				trace!(target: "sync", "{}: Processing shielded chunk", peer_id);
+				io.snapshot_service().restore_shielded_chunk(hash, snapshot_data);
+			}
 			Err(()) => {
 				trace!(target: "sync", "{}: Got bad snapshot chunk", peer_id);
 				io.disconnect_peer(peer_id);
@@ -25,11 +25,13 @@ use std::iter::FromIterator;
 pub enum ChunkType {
 	State(H256),
 	Block(H256),
+	Shielded(H256),
 }
 
 pub struct Snapshot {
 	pending_state_chunks: Vec<H256>,
 	pending_block_chunks: Vec<H256>,
+	pending_shielded_chunks: Vec<H256>,
 	downloading_chunks: HashSet<H256>,
 	completed_chunks: HashSet<H256>,
 	snapshot_hash: Option<H256>,
@@ -43,6 +45,7 @@ impl Snapshot {
 		Snapshot {
 			pending_state_chunks: Vec::new(),
 			pending_block_chunks: Vec::new(),
+			pending_shielded_chunks: Vec::new(),
 			downloading_chunks: HashSet::new(),
 			completed_chunks: HashSet::new(),
 			snapshot_hash: None,
@@ -74,6 +77,7 @@ impl Snapshot {
 	pub fn clear(&mut self) {
 		self.pending_state_chunks.clear();
 		self.pending_block_chunks.clear();
+		self.pending_shielded_chunks.clear();
 		self.downloading_chunks.clear();
 		self.completed_chunks.clear();
 		self.snapshot_hash = None;
@@ -90,6 +94,7 @@ impl Snapshot {
 		self.clear();
 		self.pending_state_chunks = manifest.state_hashes.clone();
 		self.pending_block_chunks = manifest.block_hashes.clone();
+		self.pending_shielded_chunks = manifest.shielded_hashes.clone();
 		self.snapshot_hash = Some(hash.clone());
 	}
 
@@ -109,30 +114,26 @@ impl Snapshot {
 			self.completed_chunks.insert(hash.clone());
 			return Ok(ChunkType::State(hash));
 		}
+		if self.pending_shielded_chunks.iter().any(|h| h == &hash) {
+			self.completed_chunks.insert(hash.clone());
+			return Ok(ChunkType::Shielded(hash));
+		}
 		trace!(target: "sync", "Ignored unknown chunk: {:x}", hash);
 		Err(())
 	}
 
 	/// Find a chunk to download
 	pub fn needed_chunk(&mut self) -> Option<H256> {
-		// Find next needed chunk: first block, then state chunks
+		// Find next needed chunk: first block, then shielded, then state chunks
 		let chunk = {
 			let chunk_filter = |h| !self.downloading_chunks.contains(h) && !self.completed_chunks.contains(h);
 
-			let needed_block_chunk = self.pending_block_chunks.iter()
+			self.pending_block_chunks.iter()
+				.chain(self.pending_shielded_chunks.iter())
+				.chain(self.pending_state_chunks.iter())
 				.filter(|&h| chunk_filter(h))
 				.map(|h| *h)
-				.next();
-
-			// If no block chunks to download, get the state chunks
-			if needed_block_chunk.is_none() {
-				self.pending_state_chunks.iter()
-					.filter(|&h| chunk_filter(h))
-					.map(|h| *h)
-					.next()
-			} else {
-				needed_block_chunk
-			}
+				.next()
 		};
 
 		if let Some(hash) = chunk {
@@ -160,7 +161,7 @@ impl Snapshot {
 	}
 
 	pub fn total_chunks(&self) -> usize {
-		self.pending_block_chunks.len() + self.pending_state_chunks.len()
+		self.pending_block_chunks.len() + self.pending_state_chunks.len() + self.pending_shielded_chunks.len()
 	}
 
 	pub fn done_chunks(&self) -> usize {
@@ -182,6 +183,7 @@ mod test {
 	fn is_empty(snapshot: &Snapshot) -> bool {
 		snapshot.pending_block_chunks.is_empty() &&
 		snapshot.pending_state_chunks.is_empty() &&
+		snapshot.pending_shielded_chunks.is_empty() &&
 		snapshot.completed_chunks.is_empty() &&
 		snapshot.downloading_chunks.is_empty() &&
 		snapshot.snapshot_hash.is_none()
@@ -194,6 +196,7 @@ mod test {
 			version: 2,
 			state_hashes: state_chunks.iter().map(|data| keccak(data)).collect(),
 			block_hashes: block_chunks.iter().map(|data| keccak(data)).collect(),
+			shielded_hashes: Vec::new(),
 			state_root: H256::new(),
 			block_number: 42,
 			block_hash: H256::new(),
@@ -260,6 +263,26 @@ mod test {
 		assert_eq!(snapshot.snapshot_hash(), Some(keccak(manifest.into_rlp())));
 	}
 
+	#[test]
+	fn validate_shielded_chunks() {
+		let mut snapshot = Snapshot::new();
+		let (mut manifest, _, _, _) = test_manifest();
+		let shielded_chunks: Vec<Bytes> = (0..3).map(|_| H256::random().to_vec()).collect();
+		manifest.shielded_hashes = shielded_chunks.iter().map(|data| keccak(data)).collect();
+		let mhash = keccak(manifest.clone().into_rlp());
+		snapshot.reset_to(&manifest, &mhash);
+		assert_eq!(snapshot.total_chunks(), 43);
+
+		// shielded chunks are requested after the block chunks and before the state chunks.
+		let requested: Vec<H256> = (0..43).map(|_| snapshot.needed_chunk().unwrap()).collect();
+		assert_eq!(&requested[20..23], &manifest.shielded_hashes[..]);
+		assert!(snapshot.needed_chunk().is_none());
+
+		assert_eq!(snapshot.validate_chunk(&shielded_chunks[1]), Ok(ChunkType::Shielded(manifest.shielded_hashes[1].clone())));
+		assert_eq!(snapshot.completed_chunks.len(), 1);
+		assert!(snapshot.validate_chunk(&shielded_chunks[1]).is_err());
+	}
+
 	#[test]
 	fn tracks_known_bad() {
 		let mut snapshot = Snapshot::new();
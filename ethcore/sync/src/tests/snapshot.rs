@@ -55,6 +55,7 @@ impl TestSnapshotService {
 			version: 2,
 			state_hashes: state_chunks.iter().map(|data| keccak(data)).collect(),
 			block_hashes: block_chunks.iter().map(|data| keccak(data)).collect(),
+			shielded_hashes: Vec::new(),
 			state_root: H256::new(),
 			block_number: block_number,
 			block_hash: block_hash,
@@ -134,6 +135,8 @@ impl SnapshotService for TestSnapshotService {
 		}
 	}
 
+	fn restore_shielded_chunk(&self, _hash: H256, _chunk: Bytes) { }
+
 	fn shutdown(&self) {
 		self.abort_restore();
 	}
@@ -0,0 +1,147 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Compact shielded blocks.
+//!
+//! A compact block only keeps what a thin wallet needs to find its notes: the nullifiers
+//! revealed by each private transaction, and for each of its outputs the note commitment,
+//! the ephemeral key and the start of the note ciphertext, enough to trial decrypt the note
+//! without its memo. The full transaction is fetched once a note is found.
+
+use ethereum_types::H256;
+use ff::{PrimeField, PrimeFieldRepr};
+
+use encoded;
+use BlockNumber;
+
+/// Number of bytes of the note ciphertext kept in a compact output: the lead byte,
+/// diversifier, value and commitment randomness of the note plaintext.
+pub const COMPACT_NOTE_SIZE: usize = 52;
+
+/// A shielded output without its value commitment, proof and memo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactOutput {
+	/// The note commitment.
+	pub cmu: H256,
+	/// The ephemeral public key the note was encrypted with.
+	pub ephemeral_key: H256,
+	/// The first `COMPACT_NOTE_SIZE` bytes of the note ciphertext.
+	pub ciphertext: Vec<u8>,
+}
+
+/// A private transaction reduced to its nullifiers and compact outputs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactTransaction {
+	/// Index of the transaction in its block.
+	pub index: usize,
+	/// Hash of the transaction.
+	pub hash: H256,
+	/// The nullifiers of the notes spent.
+	pub nullifiers: Vec<H256>,
+	/// The outputs, in the order their commitments are appended to the tree.
+	pub outputs: Vec<CompactOutput>,
+}
+
+/// A block reduced to its private transactions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactBlock {
+	/// Number of the block.
+	pub number: BlockNumber,
+	/// Hash of the block.
+	pub hash: H256,
+	/// Hash of the parent block.
+	pub parent_hash: H256,
+	/// Timestamp of the block.
+	pub timestamp: u64,
+	/// The private transactions of the block.
+	pub transactions: Vec<CompactTransaction>,
+	/// Number of note commitments in the tree as of the end of the block. The first output
+	/// of the block is at this position minus the number of outputs of the block.
+	pub commitment_tree_size: u64,
+}
+
+impl CompactBlock {
+	/// Compact the given block, whose parent ends with `parent_tree_size` note commitments.
+	pub fn new(block: &encoded::Block, parent_tree_size: u64) -> Self {
+		let header = block.header_view();
+		let transactions = block.transactions().into_iter()
+			.enumerate()
+			.filter(|&(_, ref tx)| tx.is_private())
+			.map(|(index, tx)| CompactTransaction {
+				index,
+				hash: tx.hash(),
+				nullifiers: tx.v_shielded_spend().iter()
+					.map(|spend| H256::from(spend.nullifier))
+					.collect(),
+				outputs: tx.v_shielded_output().iter()
+					.map(|output| {
+						let (mut cmu, mut ephemeral_key) = (H256::zero(), H256::zero());
+						output.cmu.into_repr().write_le(&mut cmu[..]).expect("field elements are 32 bytes; qed");
+						output.ephemeral_key.write(&mut ephemeral_key[..]).expect("points are 32 bytes; qed");
+						CompactOutput {
+							cmu,
+							ephemeral_key,
+							ciphertext: output.enc_ciphertext[..COMPACT_NOTE_SIZE].to_vec(),
+						}
+					})
+					.collect(),
+			})
+			.collect();
+
+		let mut compact = CompactBlock {
+			number: header.number(),
+			hash: header.hash(),
+			parent_hash: header.parent_hash(),
+			timestamp: header.timestamp(),
+			transactions,
+			commitment_tree_size: parent_tree_size,
+		};
+		compact.commitment_tree_size += compact.outputs_count();
+		compact
+	}
+
+	/// Number of shielded outputs of the block.
+	pub fn outputs_count(&self) -> u64 {
+		self.transactions.iter().map(|tx| tx.outputs.len() as u64).sum()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rlp::RlpStream;
+	use header::Header;
+
+	#[test]
+	fn compacts_block_without_private_transactions() {
+		let mut header = Header::default();
+		header.set_number(7);
+		header.set_timestamp(42);
+		let mut stream = RlpStream::new_list(3);
+		stream.append(&header);
+		stream.begin_list(0);
+		stream.begin_list(0);
+		let block = encoded::Block::new(stream.out());
+
+		let compact = CompactBlock::new(&block, 5);
+		assert_eq!(compact.number, 7);
+		assert_eq!(compact.hash, header.hash());
+		assert_eq!(compact.timestamp, 42);
+		assert!(compact.transactions.is_empty());
+		assert_eq!(compact.outputs_count(), 0);
+		assert_eq!(compact.commitment_tree_size, 5);
+	}
+}
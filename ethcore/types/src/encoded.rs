@@ -108,6 +108,9 @@ impl Header {
 	/// Block extra data.
 	pub fn extra_data(&self) -> Vec<u8> { self.view().extra_data() }
 
+	/// Sapling note commitment tree root after this block, if committed to.
+	pub fn final_sapling_root(&self) -> Option<H256> { self.view().final_sapling_root() }
+
 	/// Engine-specific seal fields.
 	pub fn seal(&self) -> Vec<Vec<u8>> { self.view().seal() }
 }
@@ -80,6 +80,9 @@ pub struct Header {
 
 	/// Block difficulty.
 	difficulty: U256,
+	/// Root of the Sapling note commitment tree after this block's transactions.
+	/// Only present once the `finalSaplingRootTransition` is active.
+	final_sapling_root: Option<H256>,
 	/// Vector of post-RLP-encoded fields.
 	//  For equihash seal is nonce + solution
 	seal: Vec<Bytes>,
@@ -109,8 +112,8 @@ impl PartialEq for Header {
 		self.gas_used == c.gas_used &&
 		self.gas_limit == c.gas_limit &&
 		self.difficulty == c.difficulty &&
-		self.seal == c.seal //&&
-		//self.hash_final_cm_root == c.hash_final_cm_root
+		self.final_sapling_root == c.final_sapling_root &&
+		self.seal == c.seal
 	}
 }
 
@@ -133,10 +136,9 @@ impl Default for Header {
 			gas_limit: U256::default(),
 
 			difficulty: U256::default(),
+			final_sapling_root: None,
 			seal: vec![],
 			hash: None,
-
-			//hash_final_cm_root: H256::from(0),
 		}
 	}
 }
@@ -184,7 +186,8 @@ impl Header {
 	/// Get the difficulty field of the header.
 	pub fn difficulty(&self) -> &U256 { &self.difficulty }
 
-	//pub fn hash_final_cm_root(&self) -> H256 { self.hash_final_cm_root }
+	/// Get the final Sapling root field of the header.
+	pub fn final_sapling_root(&self) -> Option<&H256> { self.final_sapling_root.as_ref() }
 
 	/// Get the seal field of the header.
 	pub fn seal(&self) -> &[Bytes] { &self.seal }
@@ -261,6 +264,11 @@ impl Header {
 		change_field(&mut self.hash, &mut self.difficulty, a);
 	}
 
+	/// Set the final Sapling root field of the header.
+	pub fn set_final_sapling_root(&mut self, a: Option<H256>) {
+		change_field(&mut self.hash, &mut self.final_sapling_root, a);
+	}
+
 	/// Set the seal field of the header.
 	pub fn set_seal(&mut self, a: Vec<Bytes>) {
 		change_field(&mut self.hash, &mut self.seal, a)
@@ -304,10 +312,11 @@ impl Header {
 
 	/// Place this header into an RLP stream `s`, optionally `with_seal`.
 	fn stream_rlp(&self, s: &mut RlpStream, with_seal: Seal) {
+		let fields = if self.final_sapling_root.is_some() { 14 } else { 13 };
 		if let Seal::With = with_seal {
-			s.begin_list(13 + self.seal.len());
+			s.begin_list(fields + self.seal.len());
 		} else {
-			s.begin_list(13);
+			s.begin_list(fields);
 		}
 
 		s.append(&self.parent_hash);
@@ -323,7 +332,9 @@ impl Header {
 		s.append(&self.gas_used);
 		s.append(&self.timestamp);
 		s.append(&self.extra_data);
-		//s.append(&self.hash_final_cm_root);
+		if let Some(ref root) = self.final_sapling_root {
+			s.begin_list(1).append(root);
+		}
 
 		if let Seal::With = with_seal {
 			for b in &self.seal {
@@ -341,6 +352,22 @@ fn change_field<T>(hash: &mut Option<H256>, field: &mut T, value: T) where T: Pa
 	}
 }
 
+/// Whether the header RLP `r` carries a final Sapling root right after the extra data.
+pub(crate) fn has_final_sapling_root(r: &Rlp) -> Result<bool, DecoderError> {
+	if r.item_count()? <= 13 {
+		return Ok(false);
+	}
+	is_final_sapling_root(&r.at(13)?)
+}
+
+/// Whether `item` is an encoded final Sapling root.
+///
+/// The root is encoded as a single-item list so that it can't be mistaken for a seal field:
+/// Equihash seal fields are plain data items.
+pub fn is_final_sapling_root(item: &Rlp) -> Result<bool, DecoderError> {
+	Ok(item.is_list() && item.item_count()? == 1 && item.at(0)?.size() == 32)
+}
+
 impl Decodable for Header {
 	fn decode(r: &Rlp) -> Result<Self, DecoderError> {
 		let mut blockheader = Header {
@@ -357,12 +384,18 @@ impl Decodable for Header {
 			gas_used: r.val_at(10)?,
 			timestamp: r.val_at(11)?,
 			extra_data: r.val_at(12)?,
+			final_sapling_root: None,
 			seal: vec![],
-			//hash_final_cm_root: r.val_at(13)?,
 			hash: keccak(r.as_raw()).into(),
 		};
 
-		for i in 13..r.item_count()? {
+		let mut seal_start = 13;
+		if has_final_sapling_root(r)? {
+			blockheader.final_sapling_root = Some(r.at(13)?.val_at(0)?);
+			seal_start = 14;
+		}
+
+		for i in seal_start..r.item_count()? {
 			blockheader.seal.push(r.at(i)?.as_raw().to_vec())
 		}
 
@@ -392,6 +425,7 @@ impl ExtendedHeader {
 #[cfg(test)]
 mod tests {
 	use rustc_hex::FromHex;
+	use ethereum_types::H256;
 	use rlp;
 	use super::Header;
 
@@ -437,4 +471,25 @@ mod tests {
 		let header: Result<Header, _> = rlp::decode(&header_rlp);
 		assert_eq!(header.unwrap_err(), rlp::DecoderError::RlpIsTooBig);
 	}
+
+	#[test]
+	fn decode_and_encode_header_with_final_sapling_root() {
+		let mut header = Header::default();
+		header.set_number(10);
+		header.set_final_sapling_root(Some(H256::from(7)));
+		header.set_seal(vec![rlp::encode(&H256::from(1)), rlp::encode(&vec![2u8; 8])]);
+
+		let encoded = rlp::encode(&header);
+		let decoded: Header = rlp::decode(&encoded).expect("error decoding header");
+		assert_eq!(decoded.final_sapling_root(), Some(&H256::from(7)));
+		assert_eq!(decoded.seal(), header.seal());
+		assert_eq!(decoded.hash(), header.hash());
+		assert_eq!(decoded.encoded().seal(), header.seal().to_vec());
+		assert_eq!(decoded.encoded().final_sapling_root(), Some(H256::from(7)));
+
+		// the root is part of the bare hash.
+		let mut without_root = header.clone();
+		without_root.set_final_sapling_root(None);
+		assert!(without_root.bare_hash() != header.bare_hash());
+	}
 }
@@ -70,6 +70,7 @@ pub mod block;
 pub mod block_status;
 pub mod blockchain_info;
 pub mod call_analytics;
+pub mod compact_block;
 pub mod encoded;
 pub mod engines;
 pub mod filter;
@@ -29,6 +29,8 @@ pub struct ManifestData {
 	pub state_hashes: Vec<H256>,
 	/// List of block chunk hashes.
 	pub block_hashes: Vec<H256>,
+	/// List of shielded pool chunk hashes.
+	pub shielded_hashes: Vec<H256>,
 	/// The final, expected state root.
 	pub state_root: H256,
 	/// Block number this snapshot was taken at.
@@ -40,13 +42,14 @@ pub struct ManifestData {
 impl ManifestData {
 	/// Encode the manifest data to rlp.
 	pub fn into_rlp(self) -> Bytes {
-		let mut stream = RlpStream::new_list(6);
+		let mut stream = RlpStream::new_list(7);
 		stream.append(&self.version);
 		stream.append_list(&self.state_hashes);
 		stream.append_list(&self.block_hashes);
 		stream.append(&self.state_root);
 		stream.append(&self.block_number);
 		stream.append(&self.block_hash);
+		stream.append_list(&self.shielded_hashes);
 
 		stream.out()
 	}
@@ -65,11 +68,18 @@ impl ManifestData {
 		let state_root: H256 = decoder.val_at(start + 2)?;
 		let block_number: u64 = decoder.val_at(start + 3)?;
 		let block_hash: H256 = decoder.val_at(start + 4)?;
+		// manifests written before shielded chunks existed end here.
+		let shielded_hashes: Vec<H256> = if decoder.item_count()? > start + 5 {
+			decoder.list_at(start + 5)?
+		} else {
+			Vec::new()
+		};
 
 		Ok(ManifestData {
 			version: version,
 			state_hashes: state_hashes,
 			block_hashes: block_hashes,
+			shielded_hashes: shielded_hashes,
 			state_root: state_root,
 			block_number: block_number,
 			block_hash: block_hash,
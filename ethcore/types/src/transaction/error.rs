@@ -102,6 +102,8 @@ pub enum PrivateTxError {
 	InvalidBalancingValue,
 	InvalidNumberOfSpends,
 	DuplicatedNullifier,
+	InvalidPublicOutputs,
+	InvalidCoinbase,
 }
 
 impl fmt::Display for PrivateTxError {
@@ -113,6 +115,8 @@ impl fmt::Display for PrivateTxError {
 			InvalidBalancingValue=> "Invalid balancing value.",
 			InvalidNumberOfSpends=> "Invalid number of spends.",
 			DuplicatedNullifier=> "Nullifiers duplicated within transaction.",
+			InvalidPublicOutputs => "Invalid public outputs.",
+			InvalidCoinbase => "Invalid coinbase.",
 		};
 		f.write_fmt(format_args!("Private transaction error: {}", msg))
 	}
@@ -34,6 +34,7 @@ use std::fmt;
 use zcash_primitives::transaction::components::{
 	OutputDescription, SpendDescription, GROTH_PROOF_SIZE,
 };
+use zcash_primitives::transaction::{origo_signature_hash, OrigoSigHashData};
 
 use ethkey::{Generator, Random};
 
@@ -67,6 +68,9 @@ pub const CONVERSION_FACTOR: U256 = U256([0x3B9ACA00, 0x0, 0x0, 0x0]);
 /// Max value allowed to send: (2^63-1) * (10^9)
 pub const MAX_VALUE_ALLOWED: U256 = U256([0xFFFFFFFFC4653600, 0x1DCD64FF, 0x0, 0x0]);
 
+/// Max number of public outputs of a private transaction, besides the recipient of its action.
+pub const MAX_PUBLIC_OUTPUTS: usize = 16;
+
 /// Transaction action type.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
@@ -173,13 +177,46 @@ pub mod signature {
 	}
 }
 
+/// Public value paid by a private transaction, besides the value sent to the recipient of its action.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublicOutput {
+	pub address: Address,
+	pub value: U256,
+}
+
+impl rlp::Decodable for PublicOutput {
+	fn decode(d: &Rlp) -> Result<Self, DecoderError> {
+		if d.item_count()? != 2 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+		Ok(PublicOutput {
+			address: d.val_at(0)?,
+			value: d.val_at(1)?,
+		})
+	}
+}
+
+impl rlp::Encodable for PublicOutput {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(2);
+		s.append(&self.address);
+		s.append(&self.value);
+	}
+}
+
 /// Private transaction data.
+///
+/// It is encoded as the list `[spends, outputs, balancing_value, binding_sig]`.
+/// A transaction with public outputs appends them as a fifth item, so that the encoding
+/// of the transactions without them is unchanged.
 #[derive(Clone)]
 pub struct PrivateTransaction {
 	pub spends: Vec<SpendDescription>,
 	pub outputs: Vec<OutputDescription>,
 	pub balancing_value: i64,
 	pub binding_sig: [u8; 64],
+	/// Only allowed with `Action::PrivateCall`.
+	pub public_outputs: Vec<PublicOutput>,
 }
 
 impl fmt::Debug for PrivateTransaction {
@@ -187,12 +224,13 @@ impl fmt::Debug for PrivateTransaction {
 		// fmt only supports array of max 32 length. So binding_sig is splitted into two parts.
 		write!(
 			f,
-			"spends: {:?}, outputs: {:?}, balancing_value: {:?}, binding_sig: {:?} {:?}",
+			"spends: {:?}, outputs: {:?}, balancing_value: {:?}, binding_sig: {:?} {:?}, public_outputs: {:?}",
 			self.spends,
 			self.outputs,
 			self.balancing_value,
 			&&self.binding_sig[..32],
 			&&self.binding_sig[32..],
+			self.public_outputs,
 		)
 	}
 }
@@ -203,12 +241,25 @@ impl PartialEq for PrivateTransaction {
 			&& self.outputs == other.outputs
 			&& self.balancing_value == other.balancing_value
 			&& &self.binding_sig[..] == &other.binding_sig[..]
+			&& self.public_outputs == other.public_outputs
 	}
 }
 impl Eq for PrivateTransaction {}
 
 impl rlp::Decodable for PrivateTransaction {
 	fn decode(d: &Rlp) -> Result<Self, DecoderError> {
+		let public_outputs: Vec<PublicOutput> = match d.item_count()? {
+			4 => vec![],
+			5 => {
+				let public_outputs = d.list_at(4)?;
+				// An empty list would give a second encoding of the same transaction.
+				if public_outputs.is_empty() {
+					return Err(DecoderError::Custom("Empty public outputs."));
+				}
+				public_outputs
+			}
+			_ => return Err(DecoderError::RlpIncorrectListLen),
+		};
 		let u_bv: u64 = d.val_at(2)?;
 		let binding_sig: H512 = d.val_at(3)?;
 		let spends_vec: Vec<SpendDescription> = d.list_at(0)?;
@@ -218,13 +269,14 @@ impl rlp::Decodable for PrivateTransaction {
 			outputs: outputs_vec,
 			balancing_value: u_bv as i64,
 			binding_sig: binding_sig.into(),
+			public_outputs,
 		})
 	}
 }
 
 impl rlp::Encodable for PrivateTransaction {
 	fn rlp_append(&self, s: &mut RlpStream) {
-		s.begin_list(4);
+		s.begin_list(self.rlp_item_count());
 		s.append_list(&self.spends);
 		s.append_list(&self.outputs);
 
@@ -234,12 +286,19 @@ impl rlp::Encodable for PrivateTransaction {
 
 		let sig_h512 = H512::from(self.binding_sig);
 		s.append(&sig_h512);
+		if !self.public_outputs.is_empty() {
+			s.append_list(&self.public_outputs);
+		}
 	}
 }
 
 impl PrivateTransaction {
+	fn rlp_item_count(&self) -> usize {
+		if self.public_outputs.is_empty() { 4 } else { 5 }
+	}
+
 	pub fn rlp_append_unsigned(&self, s: &mut RlpStream) {
-		s.begin_list(4);
+		s.begin_list(self.rlp_item_count());
 		s.begin_list(self.spends.len());
 		for spend in self.spends.iter() {
 			spend.rlp_append_unsigned(s);
@@ -248,6 +307,16 @@ impl PrivateTransaction {
 		let bv_u = self.balancing_value.clone() as u64;
 		s.append(&bv_u);
 		s.append_empty_data();
+		if !self.public_outputs.is_empty() {
+			s.append_list(&self.public_outputs);
+		}
+	}
+
+	/// Total value paid to the public outputs.
+	pub fn public_output_value(&self) -> U256 {
+		self.public_outputs
+			.iter()
+			.fold(U256::zero(), |total, output| total.saturating_add(output.value))
 	}
 	// Create a dummy PrivateTransaction for testing purpose.
 	pub fn create_private(nullifiers: Vec<u8>) -> Self {
@@ -271,6 +340,7 @@ impl PrivateTransaction {
 			outputs: vec![],
 			balancing_value: 10,
 			binding_sig: [1u8; 64],
+			public_outputs: vec![],
 		}
 	}
 }
@@ -342,6 +412,15 @@ impl Transaction {
 		}
 	}
 
+	/// If the transaction is a coinbase, paying the block author reward to a shielded address.
+	/// A coinbase is a private to private transaction with no spends.
+	pub fn is_coinbase(&self) -> bool {
+		match self.private {
+			Some(ref tx) => self.action == Action::PrivateToPrivate && tx.spends.is_empty(),
+			None => false,
+		}
+	}
+
 	/// Get all the nullifier for the private transaction.
 	pub fn get_nullifier_set(&self) -> HashSet<U256> {
 		if let Some(ref private) = self.private {
@@ -408,6 +487,13 @@ impl Transaction {
 			None => [0; 64],
 		}
 	}
+
+	pub fn public_outputs(&self) -> &[PublicOutput] {
+		match self.private {
+			Some(ref tx) => &tx.public_outputs,
+			None => &[],
+		}
+	}
 }
 
 impl HeapSizeOf for Transaction {
@@ -514,6 +600,7 @@ impl Transaction {
 				outputs: vec![output1, output2],
 				balancing_value: 0,
 				binding_sig: [1u8; 64],
+				public_outputs: vec![],
 			}),
 		}
 	}
@@ -554,6 +641,7 @@ impl Transaction {
 				outputs: vec![],
 				balancing_value: 10,
 				binding_sig: [1u8; 64],
+				public_outputs: vec![],
 			}),
 		}
 	}
@@ -565,6 +653,40 @@ impl Transaction {
 		keccak(stream.as_raw())
 	}
 
+	/// The hash signed by the spends and the binding signature of a private transaction.
+	/// With `shielded_sighash` it is the domain-separated Blake2b hash of the fields
+	/// of the transaction, otherwise its message hash.
+	pub fn sighash(&self, chain_id: Option<u64>, shielded_sighash: bool) -> H256 {
+		let private = match self.private {
+			Some(ref private) if shielded_sighash => private,
+			_ => return self.hash(chain_id),
+		};
+		let be = |value: &U256| {
+			let mut bytes = [0u8; 32];
+			value.to_big_endian(&mut bytes);
+			bytes
+		};
+		let action = rlp::encode(&self.action);
+		let public_outputs = private
+			.public_outputs
+			.iter()
+			.map(|output| (output.address.0, be(&output.value)))
+			.collect::<Vec<_>>();
+		H256::from(origo_signature_hash(&OrigoSigHashData {
+			chain_id: chain_id.unwrap_or(0),
+			action: &action[..],
+			nonce: be(&self.nonce),
+			gas_price: be(&self.gas_price),
+			gas: be(&self.gas),
+			value: be(&self.value),
+			data: &self.data,
+			shielded_spends: &private.spends,
+			shielded_outputs: &private.outputs,
+			public_outputs: &public_outputs,
+			value_balance: private.balancing_value,
+		}))
+	}
+
 	/// Signs the transaction as coming from `sender`.
 	pub fn sign(self, secret: &Secret, chain_id: Option<u64>) -> SignedTransaction {
 		let sig = ::ethkey::sign(secret, &self.hash(chain_id))
@@ -870,6 +992,33 @@ impl UnverifiedTransaction {
 			}
 		}
 
+		// Check public outputs.
+		if !private_tx.public_outputs.is_empty() {
+			match self.action {
+				Action::PrivateCall(_) => (),
+				_ => return Err(error::Error::InvalidPrivateTx(PrivateTxError::InvalidPublicOutputs)),
+			}
+			if private_tx.public_outputs.len() > MAX_PUBLIC_OUTPUTS
+				|| private_tx.public_outputs.iter().any(|output| output.value.is_zero() || output.value > MAX_VALUE_ALLOWED)
+			{
+				return Err(error::Error::InvalidPrivateTx(PrivateTxError::InvalidPublicOutputs));
+			}
+		}
+
+		// A coinbase only creates the note paying the block reward, its value is checked against
+		// the reward when the block is closed.
+		if self.is_coinbase() {
+			let minted = -(self.balancing_value() as i128);
+			if !self.gas.is_zero() || !self.gas_price.is_zero() || !self.value.is_zero() || !self.data.is_empty()
+				|| private_tx.outputs.len() != 1
+				|| minted <= 0
+				|| U256::from(minted as u64) * CONVERSION_FACTOR > MAX_VALUE_ALLOWED
+			{
+				return Err(error::Error::InvalidPrivateTx(PrivateTxError::InvalidCoinbase));
+			}
+			return Ok(());
+		}
+
 		// Check value.
 		if self.is_privacy() {
 			// Private to Private transaction shouldn't involve public balance transfer.
@@ -893,8 +1042,10 @@ impl UnverifiedTransaction {
 			}
 		} else {
 			// Transfer value from private, balancing_value should equal to the amount of value transfers to public,
-			// plus the gas fee(gas_price * gas).
-			if U256::from(self.balancing_value()) * CONVERSION_FACTOR != self.gas_price * self.gas + self.value {
+			// including the public outputs, plus the gas fee(gas_price * gas).
+			if U256::from(self.balancing_value()) * CONVERSION_FACTOR
+				!= self.gas_price * self.gas + self.value + private_tx.public_output_value()
+			{
 				return Err(error::Error::InvalidPrivateTx(
 					PrivateTxError::InvalidBalancingValue,
 				));
@@ -1058,9 +1209,8 @@ impl LocalizedTransaction {
 		}
 	}
 
-	//TODO
 	pub fn is_coin_base(&self) -> bool {
-		false
+		self.is_coinbase()
 	}
 
 	pub fn binding_sig(&self) -> [u8; 64] {
@@ -1118,9 +1268,8 @@ impl PendingTransaction {
 		}
 	}
 
-	//TODO
 	pub fn is_coin_base(&self) -> bool {
-		false
+		self.is_coinbase()
 	}
 }
 
@@ -1325,6 +1474,7 @@ mod tests {
 			outputs: vec![],
 			balancing_value: -10,
 			binding_sig: [1u8; 64],
+			public_outputs: vec![],
 		};
 		let encoded = ::rlp::encode(&ct);
 		let ct2: PrivateTransaction = rlp::decode(&encoded).unwrap();
@@ -1368,6 +1518,7 @@ mod tests {
 				outputs: vec![],
 				balancing_value: -10,
 				binding_sig: [1u8; 64],
+				public_outputs: vec![],
 			});
 			t = t.compute_hash();
 			// Adding private should change hash.
@@ -1410,6 +1561,7 @@ mod tests {
 			outputs: vec![],
 			balancing_value: -10,
 			binding_sig: [1u8; 64],
+			public_outputs: vec![],
 		});
 		t = t.compute_hash();
 		// Adding private should change hash.
@@ -1521,13 +1673,13 @@ mod tests {
 			))
 		);
 
-		// Create a private to private without spends.
+		// Create a private to private without spends, which is a coinbase paying gas.
 		let tx5 = Transaction::create_private(vec![], U256::zero(), Action::PrivateToPrivate)
 			.sign_for_private(TEST_CHAIN_ID);
 		assert_eq!(
 			tx5.transaction.verify_private_tx_basic(),
 			Err(error::Error::InvalidPrivateTx(
-				PrivateTxError::InvalidNumberOfSpends
+				PrivateTxError::InvalidCoinbase
 			))
 		);
 
@@ -1580,6 +1732,106 @@ mod tests {
 			.is_ok());
 	}
 
+	#[test]
+	fn encode_decode_public_outputs() {
+		let mut ct = PrivateTransaction::create_private(vec![0]);
+		let legacy = ::rlp::encode(&ct);
+		assert_eq!(Rlp::new(&legacy).item_count().unwrap(), 4);
+
+		ct.public_outputs = vec![
+			PublicOutput { address: Address::from(5), value: CONVERSION_FACTOR },
+			PublicOutput { address: Address::from(6), value: CONVERSION_FACTOR * 2 },
+		];
+		let encoded = ::rlp::encode(&ct);
+		assert_eq!(Rlp::new(&encoded).item_count().unwrap(), 5);
+		let ct2: PrivateTransaction = rlp::decode(&encoded).unwrap();
+		assert_eq!(ct2.public_outputs, ct.public_outputs);
+		assert_eq!(ct2.public_output_value(), CONVERSION_FACTOR * 3);
+
+		// The public outputs can't be an empty fifth item.
+		let mut s = RlpStream::new_list(5);
+		for item in Rlp::new(&legacy).iter() {
+			s.append_raw(item.as_raw(), 1);
+		}
+		s.begin_list(0);
+		assert!(rlp::decode::<PrivateTransaction>(&s.out()).is_err());
+	}
+
+	#[test]
+	fn test_verify_private_call_with_public_outputs() {
+		let mut tx = Transaction::create_private(
+			vec![0],
+			U256::zero(),
+			Action::PrivateCall(Address::from("0000000000000000000000000000000000000005")),
+		);
+		let hash = tx.hash(Some(TEST_CHAIN_ID));
+		tx.private.as_mut().unwrap().public_outputs = vec![
+			PublicOutput { address: Address::from(6), value: CONVERSION_FACTOR * 2 },
+		];
+		// The public outputs are signed.
+		assert_ne!(hash, tx.hash(Some(TEST_CHAIN_ID)));
+
+		// The balancing value doesn't cover the public outputs.
+		let tx1 = tx.clone().sign_for_private(TEST_CHAIN_ID);
+		assert_eq!(
+			tx1.transaction.verify_private_tx_basic(),
+			Err(error::Error::InvalidPrivateTx(PrivateTxError::InvalidBalancingValue))
+		);
+
+		tx.private.as_mut().unwrap().balancing_value = 2;
+		let tx2 = tx.clone().sign_for_private(TEST_CHAIN_ID);
+		assert!(tx2.transaction.verify_private_tx_basic().is_ok());
+		let encoded = ::rlp::encode(&tx2.transaction);
+		let decoded: UnverifiedTransaction = rlp::decode(&encoded).unwrap();
+		assert_eq!(decoded, tx2.transaction);
+
+		// Public outputs are only allowed with a private call.
+		let mut tx3 = tx.clone();
+		tx3.action = Action::PrivateToPrivate;
+		assert_eq!(
+			tx3.sign_for_private(TEST_CHAIN_ID).transaction.verify_private_tx_basic(),
+			Err(error::Error::InvalidPrivateTx(PrivateTxError::InvalidPublicOutputs))
+		);
+
+		let mut tx4 = tx.clone();
+		tx4.private.as_mut().unwrap().public_outputs.push(PublicOutput { address: Address::from(7), value: U256::zero() });
+		assert_eq!(
+			tx4.sign_for_private(TEST_CHAIN_ID).transaction.verify_private_tx_basic(),
+			Err(error::Error::InvalidPrivateTx(PrivateTxError::InvalidPublicOutputs))
+		);
+	}
+
+	#[test]
+	fn test_verify_coinbase() {
+		let mut tx = Transaction::create_private(vec![], U256::zero(), Action::PrivateToPrivate);
+		tx.gas = U256::zero();
+		tx.data = vec![];
+		tx.private.as_mut().unwrap().outputs.pop();
+		tx.private.as_mut().unwrap().balancing_value = -5;
+		assert!(tx.is_coinbase());
+		assert!(tx.clone().sign_for_private(TEST_CHAIN_ID).transaction.verify_private_tx_basic().is_ok());
+
+		// A coinbase creates value.
+		let mut tx1 = tx.clone();
+		tx1.private.as_mut().unwrap().balancing_value = 0;
+		assert_eq!(
+			tx1.sign_for_private(TEST_CHAIN_ID).transaction.verify_private_tx_basic(),
+			Err(error::Error::InvalidPrivateTx(PrivateTxError::InvalidCoinbase))
+		);
+
+		// A coinbase has a single output.
+		let mut tx2 = tx.clone();
+		let output = tx2.private.as_ref().unwrap().outputs[0].clone();
+		tx2.private.as_mut().unwrap().outputs.push(output);
+		assert_eq!(
+			tx2.sign_for_private(TEST_CHAIN_ID).transaction.verify_private_tx_basic(),
+			Err(error::Error::InvalidPrivateTx(PrivateTxError::InvalidCoinbase))
+		);
+
+		let tx3 = Transaction::create_private(vec![1], U256::zero(), Action::PrivateToPrivate);
+		assert!(!tx3.is_coinbase());
+	}
+
 	#[test]
 	fn test_verify_public_to_private_transaction() {
 		let key = Random.generate().unwrap();
@@ -1634,4 +1886,40 @@ mod tests {
 		let hash4 = tx1.hash(Some(TEST_CHAIN_ID));
 		assert_ne!(hash, hash4);
 	}
+
+	#[test]
+	fn test_shielded_sighash() {
+		let mut tx = Transaction::create_private(vec![1, 2], U256::zero(), Action::PrivateToPrivate);
+		let sighash = tx.sighash(Some(TEST_CHAIN_ID), true);
+		assert_eq!(tx.sighash(Some(TEST_CHAIN_ID), false), tx.hash(Some(TEST_CHAIN_ID)));
+		assert_ne!(sighash, tx.hash(Some(TEST_CHAIN_ID)));
+		assert_ne!(sighash, tx.sighash(Some(TEST_CHAIN_ID + 1), true));
+
+		// The signatures are not committed to.
+		tx.private.as_mut().unwrap().binding_sig = [2u8; 64];
+		tx.private.as_mut().unwrap().spends[0].spend_auth_sig = None;
+		assert_eq!(tx.sighash(Some(TEST_CHAIN_ID), true), sighash);
+
+		let mut tx1 = tx.clone();
+		tx1.gas = tx1.gas + 1;
+		assert_ne!(tx1.sighash(Some(TEST_CHAIN_ID), true), sighash);
+		let mut tx2 = tx.clone();
+		tx2.data = vec![1];
+		assert_ne!(tx2.sighash(Some(TEST_CHAIN_ID), true), sighash);
+		let mut tx3 = tx.clone();
+		tx3.private.as_mut().unwrap().spends[1].nullifier = [3u8; 32];
+		assert_ne!(tx3.sighash(Some(TEST_CHAIN_ID), true), sighash);
+		let mut tx4 = tx.clone();
+		tx4.private.as_mut().unwrap().outputs.pop();
+		assert_ne!(tx4.sighash(Some(TEST_CHAIN_ID), true), sighash);
+		let mut tx5 = tx.clone();
+		tx5.action = Action::PrivateCall(Address::from(1));
+		let call_sighash = tx5.sighash(Some(TEST_CHAIN_ID), true);
+		assert_ne!(call_sighash, sighash);
+		tx5.private.as_mut().unwrap().public_outputs.push(PublicOutput {
+			address: Address::from(2),
+			value: U256::from(1),
+		});
+		assert_ne!(tx5.sighash(Some(TEST_CHAIN_ID), true), call_sighash);
+	}
 }
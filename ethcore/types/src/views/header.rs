@@ -98,10 +98,20 @@ impl<'a> HeaderView<'a> {
 	/// Returns block extra data.
 	pub fn extra_data(&self) -> Bytes { self.rlp.val_at(12) }
 
+	/// Returns the root of the Sapling note commitment tree, if the header commits to one.
+	pub fn final_sapling_root(&self) -> Option<H256> {
+		if self.has_final_sapling_root() {
+			Some(self.rlp.at(13).val_at(0))
+		} else {
+			None
+		}
+	}
+
 	/// Returns a vector of post-RLP-encoded seal fields.
 	pub fn seal(&self) -> Vec<Bytes> {
+		let seal_start = if self.has_final_sapling_root() { 14 } else { 13 };
 		let mut seal = vec![];
-		for i in 13..self.rlp.item_count() {
+		for i in seal_start..self.rlp.item_count() {
 			seal.push(self.rlp.at(i).as_raw().to_vec());
 		}
 		seal
@@ -115,6 +125,10 @@ impl<'a> HeaderView<'a> {
 			.collect()
 	}
 
+	fn has_final_sapling_root(&self) -> bool {
+		::header::has_final_sapling_root(&self.rlp.rlp)
+			.expect("View rlp is trusted and should be valid; qed")
+	}
 }
 
 #[cfg(test)]
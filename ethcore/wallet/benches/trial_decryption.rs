@@ -0,0 +1,98 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Trial decryption of synthetic shielded outputs by a wallet with many viewing keys.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use ethcore_wallet::scanner::trial_decrypt;
+use ethcore_wallet::wallet_types::SaplingOutputDescription;
+use pairing::bls12_381::Bls12;
+use rand::{Rand, Rng, SeedableRng, XorShiftRng};
+use sapling_crypto::jubjub::{edwards, fs::Fs, Unknown};
+use sapling_crypto::primitives::{Diversifier, PaymentAddress};
+use zcash_primitives::keys::OutgoingViewingKey;
+use zcash_primitives::note_encryption::{try_sapling_note_decryption, Memo, SaplingNoteEncryption};
+use zcash_primitives::transaction::components::GROTH_PROOF_SIZE;
+use zcash_primitives::JUBJUB;
+
+const OUTPUTS: usize = 10_000;
+const IVKS: usize = 100;
+/// One output out of RECEIVED_EVERY is sent to one of the viewing keys.
+const RECEIVED_EVERY: usize = 100;
+
+// An output of a note sent to an address of `ivk`.
+fn synthetic_output<R: Rng>(rng: &mut R, ivk: &Fs) -> SaplingOutputDescription {
+	let (diversifier, g_d) = loop {
+		let diversifier = Diversifier(rng.gen());
+		if let Some(g_d) = diversifier.g_d::<Bls12>(&JUBJUB) {
+			break (diversifier, g_d);
+		}
+	};
+	let address = PaymentAddress { diversifier, pk_d: g_d.mul(*ivk, &JUBJUB) };
+	let note = address.create_note(rng.gen_range(1, 1_000_000), Fs::rand(rng), &JUBJUB)
+		.expect("the diversifier is valid; qed");
+	let cmu = note.cm(&JUBJUB);
+
+	let encryption = SaplingNoteEncryption::new(OutgoingViewingKey([0; 32]), note, address, Memo::default());
+	let cv: edwards::Point<Bls12, Unknown> = encryption.epk().into();
+	SaplingOutputDescription {
+		cv,
+		cmu,
+		ephemeral_key: encryption.epk().into(),
+		enc_ciphertext: encryption.encrypt_note_plaintext(),
+		out_ciphertext: encryption.encrypt_outgoing_plaintext(&cv, &cmu),
+		zkproof: [0; GROTH_PROOF_SIZE],
+	}
+}
+
+fn trial_decryption(c: &mut Criterion) {
+	let mut rng = XorShiftRng::from_seed([0x5dbe6259, 0x8d313d76, 0x3237db17, 0xe5bc0654]);
+	let ivks: Vec<Fs> = (0..IVKS).map(|_| Fs::rand(&mut rng)).collect();
+	let outputs: Vec<SaplingOutputDescription> = (0..OUTPUTS)
+		.map(|i| {
+			let ivk = match i % RECEIVED_EVERY {
+				0 => ivks[i / RECEIVED_EVERY % IVKS],
+				_ => Fs::rand(&mut rng),
+			};
+			synthetic_output(&mut rng, &ivk)
+		})
+		.collect();
+	let received = trial_decrypt(&outputs, &ivks).iter().filter(|output| output.is_some()).count();
+	assert_eq!(received, OUTPUTS / RECEIVED_EVERY);
+
+	let baseline_outputs = outputs.clone();
+	let baseline_ivks = ivks.clone();
+	c.bench_function("one by one, 10k outputs x 100 ivks", move |b| {
+		b.iter(|| {
+			baseline_outputs.iter().filter_map(|output| {
+				let epk = output.ephemeral_key.as_prime_order(&JUBJUB)?;
+				baseline_ivks.iter().filter_map(|ivk| {
+					try_sapling_note_decryption(ivk, &epk, &output.cmu, &output.enc_ciphertext)
+				}).next()
+			}).count()
+		})
+	});
+	c.bench_function("trial_decrypt, 10k outputs x 100 ivks", move |b| {
+		b.iter(|| trial_decrypt(&outputs, &ivks))
+	});
+}
+
+criterion_group! {
+	name = benches;
+	config = Criterion::default().sample_size(10);
+	targets = trial_decryption
+}
+criterion_main!(benches);
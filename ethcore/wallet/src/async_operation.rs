@@ -0,0 +1,358 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Queue of the wallet operations too slow to run in an RPC handler,
+//! such as proving shielded transactions, executed by a pool of worker threads.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use ethereum_types::H256;
+use parking_lot::Mutex;
+use rand::random;
+
+/// Default number of threads executing the operations.
+pub const DEFAULT_OPERATION_WORKERS: usize = 1;
+/// Number of finished operations kept until their result is taken, the oldest are dropped beyond.
+pub const MAX_FINISHED_OPERATIONS: usize = 1000;
+
+/// Hashes of the transactions sent by a successful operation, or its error.
+pub type OperationResult = Result<Vec<H256>, String>;
+
+type Job = Box<dyn FnOnce() -> OperationResult + Send>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OperationState {
+	Queued,
+	Executing,
+	Success,
+	Failed,
+}
+
+impl OperationState {
+	/// Whether the operation has completed, successfully or not.
+	pub fn is_finished(&self) -> bool {
+		match *self {
+			OperationState::Success | OperationState::Failed => true,
+			OperationState::Queued | OperationState::Executing => false,
+		}
+	}
+}
+
+impl fmt::Display for OperationState {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			OperationState::Queued => write!(f, "queued"),
+			OperationState::Executing => write!(f, "executing"),
+			OperationState::Success => write!(f, "success"),
+			OperationState::Failed => write!(f, "failed"),
+		}
+	}
+}
+
+impl FromStr for OperationState {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"queued" => Ok(OperationState::Queued),
+			"executing" => Ok(OperationState::Executing),
+			"success" => Ok(OperationState::Success),
+			"failed" => Ok(OperationState::Failed),
+			other => Err(format!("Invalid operation state {}.", other)),
+		}
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct OperationStatus {
+	pub id: String,
+	/// Name of the RPC method which queued the operation.
+	pub method: String,
+	pub state: OperationState,
+	/// Unix time in seconds the operation was queued at.
+	pub creation_time: u64,
+	/// Time spent executing the operation, once finished.
+	pub execution_time: Option<Duration>,
+	/// Hashes of the transactions sent by the operation, if successful.
+	pub result: Option<Vec<H256>>,
+	/// Error of the operation, if failed.
+	pub error: Option<String>,
+}
+
+struct Operations {
+	// Ids in the order the operations were queued.
+	ids: Vec<String>,
+	statuses: HashMap<String, OperationStatus>,
+	max_finished: usize,
+}
+
+impl Operations {
+	// Drop the oldest finished operations beyond `max_finished`.
+	fn prune_finished(&mut self) {
+		let finished = self.statuses.values().filter(|status| status.state.is_finished()).count();
+		if finished <= self.max_finished {
+			return;
+		}
+		let mut excess = finished - self.max_finished;
+		let Operations { ids, statuses, .. } = self;
+		ids.retain(|id| {
+			let finished = statuses.get(id).map_or(false, |status| status.state.is_finished());
+			if excess > 0 && finished {
+				statuses.remove(id);
+				excess -= 1;
+				return false;
+			}
+			true
+		});
+	}
+}
+
+/// Queue of the wallet operations. The finished operations are kept until their result is
+/// taken, up to `MAX_FINISHED_OPERATIONS` of them.
+pub struct OperationManager {
+	operations: Arc<Mutex<Operations>>,
+	sender: Option<Mutex<mpsc::Sender<(String, Job)>>>,
+	workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl OperationManager {
+	/// Start the manager with `workers` threads executing the operations.
+	pub fn new(workers: usize) -> Self {
+		Self::with_max_finished(workers, MAX_FINISHED_OPERATIONS)
+	}
+
+	fn with_max_finished(workers: usize, max_finished: usize) -> Self {
+		let operations = Arc::new(Mutex::new(Operations {
+			ids: Vec::new(),
+			statuses: HashMap::new(),
+			max_finished,
+		}));
+		let (sender, receiver) = mpsc::channel::<(String, Job)>();
+		let receiver = Arc::new(Mutex::new(receiver));
+
+		let workers = (0..workers.max(1))
+			.map(|i| {
+				let receiver = receiver.clone();
+				let operations = operations.clone();
+				thread::Builder::new()
+					.name(format!("Wallet operation #{}", i))
+					.spawn(move || loop {
+						let next = receiver.lock().recv();
+						let (id, job) = match next {
+							Ok(next) => next,
+							// The manager was dropped.
+							Err(_) => break,
+						};
+						Self::execute(&operations, id, job);
+					})
+					.expect("Error creating wallet operation thread")
+			})
+			.collect();
+
+		OperationManager {
+			operations,
+			sender: Some(Mutex::new(sender)),
+			workers,
+		}
+	}
+
+	fn execute(operations: &Mutex<Operations>, id: String, job: Job) {
+		if let Some(status) = operations.lock().statuses.get_mut(&id) {
+			status.state = OperationState::Executing;
+		}
+		let started = Instant::now();
+		let result = panic::catch_unwind(AssertUnwindSafe(job))
+			.unwrap_or_else(|_| Err(String::from("The operation panicked.")));
+		let mut operations = operations.lock();
+		if let Some(status) = operations.statuses.get_mut(&id) {
+			status.execution_time = Some(started.elapsed());
+			match result {
+				Ok(hashes) => {
+					status.state = OperationState::Success;
+					status.result = Some(hashes);
+				}
+				Err(error) => {
+					status.state = OperationState::Failed;
+					status.error = Some(error);
+				}
+			}
+		}
+		operations.prune_finished();
+	}
+
+	/// Queue an operation for `method` and return its id.
+	pub fn submit<F>(&self, method: &str, job: F) -> String
+	where
+		F: FnOnce() -> OperationResult + Send + 'static,
+	{
+		let id = format!("opid-{:016x}{:016x}", random::<u64>(), random::<u64>());
+		let creation_time = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|time| time.as_secs())
+			.unwrap_or(0);
+		{
+			let mut operations = self.operations.lock();
+			operations.ids.push(id.clone());
+			operations.statuses.insert(
+				id.clone(),
+				OperationStatus {
+					id: id.clone(),
+					method: method.into(),
+					state: OperationState::Queued,
+					creation_time,
+					execution_time: None,
+					result: None,
+					error: None,
+				},
+			);
+		}
+		self.sender
+			.as_ref()
+			.expect("The sender is only dropped with the manager; qed")
+			.lock()
+			.send((id.clone(), Box::new(job)))
+			.expect("The workers live as long as the manager; qed");
+		id
+	}
+
+	/// The statuses of the operations with the given ids, of all the operations if none is given.
+	pub fn statuses(&self, ids: &[String]) -> Vec<OperationStatus> {
+		let operations = self.operations.lock();
+		operations
+			.ids
+			.iter()
+			.filter(|id| ids.is_empty() || ids.contains(*id))
+			.filter_map(|id| operations.statuses.get(id).cloned())
+			.collect()
+	}
+
+	/// Remove and return the finished operations among the given ids,
+	/// among all the operations if none is given.
+	pub fn take_finished(&self, ids: &[String]) -> Vec<OperationStatus> {
+		let mut operations = self.operations.lock();
+		let finished = operations
+			.ids
+			.iter()
+			.filter(|id| ids.is_empty() || ids.contains(*id))
+			.filter_map(|id| operations.statuses.get(id))
+			.filter(|status| status.state.is_finished())
+			.cloned()
+			.collect::<Vec<_>>();
+		for status in finished.iter() {
+			operations.statuses.remove(&status.id);
+		}
+		let Operations { ids: queued_ids, statuses, .. } = &mut *operations;
+		queued_ids.retain(|id| statuses.contains_key(id));
+		finished
+	}
+
+	/// The ids of the operations, optionally only of those in `state`.
+	pub fn list_ids(&self, state: Option<OperationState>) -> Vec<String> {
+		let operations = self.operations.lock();
+		operations
+			.ids
+			.iter()
+			.filter(|id| {
+				state.map_or(true, |state| operations.statuses.get(*id).map(|status| status.state) == Some(state))
+			})
+			.cloned()
+			.collect()
+	}
+}
+
+impl Drop for OperationManager {
+	fn drop(&mut self) {
+		// Closing the channel stops the workers once the queued operations are executed.
+		self.sender.take();
+		for worker in self.workers.drain(..) {
+			let _ = worker.join();
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn wait_finished(manager: &OperationManager, id: &str) -> OperationStatus {
+		for _ in 0..500 {
+			let status = manager.statuses(&[id.to_owned()]).remove(0);
+			if status.state.is_finished() {
+				return status;
+			}
+			thread::sleep(Duration::from_millis(10));
+		}
+		panic!("operation {} did not finish", id);
+	}
+
+	#[test]
+	fn executes_queued_operations() {
+		let manager = OperationManager::new(1);
+		let (started, executing) = mpsc::channel::<()>();
+		let (release, blocked) = mpsc::channel::<()>();
+		let first = manager.submit("origo_sendMany", move || {
+			started.send(()).unwrap();
+			blocked.recv().unwrap();
+			Ok(vec![H256::from(1)])
+		});
+		let second = manager.submit("origo_sendMany", || Err(String::from("Insufficient Balance")));
+		executing.recv().unwrap();
+
+		// The single worker is busy with the first operation.
+		assert_eq!(manager.list_ids(None), vec![first.clone(), second.clone()]);
+		assert_eq!(manager.list_ids(Some(OperationState::Queued)), vec![second.clone()]);
+		assert!(manager.take_finished(&[]).is_empty());
+
+		release.send(()).unwrap();
+		let status = wait_finished(&manager, &first);
+		assert_eq!(status.state, OperationState::Success);
+		assert_eq!(status.result, Some(vec![H256::from(1)]));
+		assert!(status.execution_time.is_some());
+		let status = wait_finished(&manager, &second);
+		assert_eq!(status.state, OperationState::Failed);
+		assert_eq!(status.error, Some(String::from("Insufficient Balance")));
+
+		let finished = manager.take_finished(&[first.clone()]);
+		assert_eq!(finished.len(), 1);
+		assert_eq!(finished[0].method, "origo_sendMany");
+		assert_eq!(manager.list_ids(None), vec![second]);
+	}
+
+	#[test]
+	fn drops_oldest_finished_operations() {
+		let manager = OperationManager::with_max_finished(1, 2);
+		let ids: Vec<_> = (0..3).map(|_| manager.submit("origo_sendMany", || Ok(vec![]))).collect();
+		// The third one finishes last, once the other two are already finished.
+		wait_finished(&manager, &ids[2]);
+		assert_eq!(manager.list_ids(None), ids[1..].to_vec());
+	}
+
+	#[test]
+	fn reports_panicking_operations() {
+		let manager = OperationManager::new(2);
+		let id = manager.submit("origo_mergeToAddress", || panic!("proving failed"));
+		let status = wait_finished(&manager, &id);
+		assert_eq!(status.state, OperationState::Failed);
+		assert_eq!(status.error, Some(String::from("The operation panicked.")));
+		assert_eq!("executing".parse::<OperationState>(), Ok(OperationState::Executing));
+	}
+}
@@ -0,0 +1,218 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Selection of the notes spent by a transaction.
+
+use std::str::FromStr;
+
+use rand::{OsRng, Rng};
+
+use crate::transaction_builder::{Error, ErrorKind};
+
+/// Default maximum number of notes spent by a transaction.
+pub const MAX_SPENDS_PER_TX: usize = 50;
+
+/// Strategy choosing the notes spent to cover an amount.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CoinSelection {
+	/// The largest notes first.
+	LargestFirst,
+	/// As few notes as possible, leaving the smallest change.
+	FewestNotes,
+	/// Notes in random order, so that the spent notes don't reveal the wallet's other notes.
+	/// Falls back to the largest notes first if the random choice needs too many spends.
+	Random,
+}
+
+impl Default for CoinSelection {
+	fn default() -> Self {
+		CoinSelection::LargestFirst
+	}
+}
+
+impl FromStr for CoinSelection {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"largest-first" => Ok(CoinSelection::LargestFirst),
+			"fewest-notes" => Ok(CoinSelection::FewestNotes),
+			"random" => Ok(CoinSelection::Random),
+			other => Err(format!(
+				"Invalid coin selection {}, expected largest-first, fewest-notes or random.",
+				other
+			)),
+		}
+	}
+}
+
+/// Select notes of `values` adding up to at least `target`, spending at most `max_spends` of them.
+/// Returns the indexes of the selected notes.
+pub fn select_notes(
+	values: &[u64],
+	target: u64,
+	strategy: CoinSelection,
+	max_spends: usize,
+) -> Result<Vec<usize>, Error> {
+	let total = values.iter().fold(0u128, |sum, value| sum + *value as u128);
+	if total < target as u128 {
+		return Err(Error(ErrorKind::InsufficientBalance(total.to_string())));
+	}
+
+	let mut largest_first = (0..values.len()).collect::<Vec<_>>();
+	largest_first.sort_by(|a, b| values[*b].cmp(&values[*a]));
+	let fewest = take_until(values, &largest_first, target);
+	if fewest.len() > max_spends {
+		return Err(Error(ErrorKind::TooManySpends(max_spends)));
+	}
+
+	match strategy {
+		CoinSelection::LargestFirst => Ok(fewest),
+		CoinSelection::FewestNotes => Ok(fewest_notes(values, &largest_first, target, fewest.len())),
+		CoinSelection::Random => {
+			let mut order = (0..values.len()).collect::<Vec<_>>();
+			OsRng::new().expect("OS randomness is available").shuffle(&mut order);
+			let selected = take_until(values, &order, target);
+			if selected.len() > max_spends {
+				Ok(fewest)
+			} else {
+				Ok(selected)
+			}
+		}
+	}
+}
+
+// Take the notes in `order` until their sum reaches `target`.
+fn take_until(values: &[u64], order: &[usize], target: u64) -> Vec<usize> {
+	let mut selected = Vec::new();
+	let mut sum = 0u128;
+	for index in order.iter() {
+		if sum >= target as u128 && !selected.is_empty() {
+			break;
+		}
+		sum += values[*index] as u128;
+		selected.push(*index);
+	}
+	selected
+}
+
+// Choose `count` notes reaching `target`, taking for each of them the smallest note
+// with which the remaining largest notes still reach it. `largest_first` must reach
+// `target` within `count` notes.
+fn fewest_notes(values: &[u64], largest_first: &[usize], target: u64, count: usize) -> Vec<usize> {
+	let mut available = largest_first.to_vec();
+	// The sums of the largest available notes: `prefix[n]` adds up the first `n` of them.
+	let mut prefix = vec![0u128];
+	extend_prefix(&mut prefix, values, &available);
+	let mut selected = Vec::with_capacity(count);
+	let mut needed = target as u128;
+	for slot in 0..count {
+		let remaining = count - slot - 1;
+		// Candidates are visited from the smallest, the best other notes being
+		// the `remaining` largest ones without the candidate.
+		let position = (0..available.len())
+			.rev()
+			.find(|position| {
+				let value = values[available[*position]] as u128;
+				let best_others = if *position < remaining {
+					prefix[(remaining + 1).min(available.len())] - value
+				} else {
+					prefix[remaining]
+				};
+				value + best_others >= needed
+			})
+			.expect("the largest notes reach the target; qed");
+		let index = available.remove(position);
+		prefix.truncate(position + 1);
+		extend_prefix(&mut prefix, values, &available[position..]);
+		needed = needed.saturating_sub(values[index] as u128);
+		selected.push(index);
+	}
+	selected
+}
+
+// Append the running sums of the notes of `indexes` to `prefix`.
+fn extend_prefix(prefix: &mut Vec<u128>, values: &[u64], indexes: &[usize]) {
+	let mut sum = *prefix.last().expect("prefix starts with 0; qed");
+	for index in indexes.iter() {
+		sum += values[*index] as u128;
+		prefix.push(sum);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn selected_values(values: &[u64], selected: &[usize]) -> Vec<u64> {
+		let mut selected = selected.iter().map(|index| values[*index]).collect::<Vec<_>>();
+		selected.sort();
+		selected
+	}
+
+	#[test]
+	fn selects_largest_notes_first() {
+		let values = [5, 40, 10, 30, 20];
+		let selected = select_notes(&values, 55, CoinSelection::LargestFirst, 10).unwrap();
+		assert_eq!(selected_values(&values, &selected), vec![30, 40]);
+	}
+
+	#[test]
+	fn selects_fewest_notes_with_smallest_change() {
+		let values = [5, 40, 10, 30, 20];
+		let selected = select_notes(&values, 25, CoinSelection::FewestNotes, 10).unwrap();
+		assert_eq!(selected_values(&values, &selected), vec![30]);
+		let selected = select_notes(&values, 45, CoinSelection::FewestNotes, 10).unwrap();
+		assert_eq!(selected_values(&values, &selected), vec![5, 40]);
+		let selected = select_notes(&values, 75, CoinSelection::FewestNotes, 10).unwrap();
+		assert_eq!(selected_values(&values, &selected), vec![5, 30, 40]);
+	}
+
+	#[test]
+	fn selects_random_notes_reaching_target() {
+		let values = [5, 40, 10, 30, 20];
+		for _ in 0..20 {
+			let selected = select_notes(&values, 50, CoinSelection::Random, 10).unwrap();
+			assert!(selected_values(&values, &selected).iter().sum::<u64>() >= 50);
+		}
+		// Too many random spends fall back to the largest notes.
+		let selected = select_notes(&values, 70, CoinSelection::Random, 2).unwrap();
+		assert_eq!(selected_values(&values, &selected), vec![30, 40]);
+	}
+
+	#[test]
+	fn caps_spends_and_checks_balance() {
+		let values = [10; 10];
+		assert_eq!(
+			select_notes(&values, 100, CoinSelection::LargestFirst, 10).unwrap().len(),
+			10
+		);
+		assert_eq!(
+			select_notes(&values, 100, CoinSelection::LargestFirst, 9),
+			Err(Error(ErrorKind::TooManySpends(9)))
+		);
+		assert_eq!(
+			select_notes(&values, 101, CoinSelection::FewestNotes, 10),
+			Err(Error(ErrorKind::InsufficientBalance("100".into())))
+		);
+	}
+
+	#[test]
+	fn parses_strategies() {
+		assert_eq!("fewest-notes".parse::<CoinSelection>(), Ok(CoinSelection::FewestNotes));
+		assert!("smallest-first".parse::<CoinSelection>().is_err());
+	}
+}
@@ -0,0 +1,256 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! ZIP-32 master seed of the wallet and the accounts derived from it.
+
+use std::fs::{self, File};
+use std::io::ErrorKind;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use byteorder::{ByteOrder, LittleEndian};
+use ethstore::ethkey::Password;
+use ethstore::{Crypto, Error, JsonCrypto};
+use serde::{Deserialize, Serialize};
+use zcash_primitives::zip32::{ChildIndex, DiversifierIndex};
+
+use crate::mnemonic::Mnemonic;
+use crate::wallet_types::SaplingExtendedSpendingKey;
+
+/// ZIP-32 purpose of the account path.
+const ZIP32_PURPOSE: u32 = 32;
+
+/// SLIP-44 coin type of the account path `m/32'/coin_type'/account'`.
+pub const ORIGO_COIN_TYPE: u32 = 1;
+
+/// Name of the seed file in the wallet directory, after the wallet file prefix.
+pub const HD_SEED_FILE_NAME: &'static str = "hd_seed.json";
+
+/// Number of consecutive unused accounts derived ahead when restoring a seed,
+/// after which no more accounts are looked for.
+pub const ACCOUNT_GAP_LIMIT: u32 = 5;
+
+/// An account derived from the wallet seed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HdAccount {
+	/// Index of the account in the path `m/32'/coin_type'/account'`.
+	pub index: u32,
+	/// Default payment address of the account, naming its key file.
+	pub address: String,
+	/// Diversifier indexes of the addresses handed out for the account, oldest first.
+	pub diversifiers: Vec<u64>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct HdSeedFile {
+	crypto: JsonCrypto,
+	#[serde(default)]
+	mnemonic: Option<JsonCrypto>,
+	accounts: Vec<HdAccount>,
+	#[serde(default)]
+	restore_pending: bool,
+}
+
+/// The encrypted wallet seed together with the accounts derived from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HdSeed {
+	crypto: Crypto,
+	// The phrase the seed was created from, none for the seeds created from random bytes.
+	mnemonic: Option<Crypto>,
+	pub accounts: Vec<HdAccount>,
+	/// Whether the accounts were restored and the unused ones not pruned yet.
+	pub restore_pending: bool,
+}
+
+impl HdSeed {
+	/// Encrypt a new wallet seed with the password.
+	pub fn new(seed: &[u8], password: &Password, iterations: NonZeroU32) -> Result<Self, Error> {
+		Ok(HdSeed {
+			crypto: Crypto::with_plain(seed, password, iterations)?,
+			mnemonic: None,
+			accounts: Vec::new(),
+			restore_pending: false,
+		})
+	}
+
+	/// Encrypt the seed of a mnemonic phrase with the password, keeping the phrase to export it.
+	pub fn from_mnemonic(mnemonic: &Mnemonic, password: &Password, iterations: NonZeroU32) -> Result<Self, Error> {
+		let mut hd_seed = HdSeed::new(&mnemonic.to_seed("")[..], password, iterations)?;
+		hd_seed.mnemonic = Some(Crypto::with_plain(mnemonic.phrase().as_bytes(), password, iterations)?);
+		Ok(hd_seed)
+	}
+
+	/// Load the seed file, `None` if the wallet has no seed yet.
+	pub fn load(path: &Path) -> Result<Option<Self>, Error> {
+		let file = match File::open(path) {
+			Ok(file) => file,
+			Err(ref e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+			Err(e) => return Err(e.into()),
+		};
+		let seed_file: HdSeedFile = serde_json::from_reader(file)
+			.map_err(|e| Error::InvalidKeyFile(format!("{}", e)))?;
+
+		Ok(Some(HdSeed {
+			crypto: seed_file.crypto.into(),
+			mnemonic: seed_file.mnemonic.map(Into::into),
+			accounts: seed_file.accounts,
+			restore_pending: seed_file.restore_pending,
+		}))
+	}
+
+	/// Write the seed file, replacing the previous one only once fully written.
+	pub fn save(&self, path: &Path) -> Result<(), Error> {
+		let seed_file = HdSeedFile {
+			crypto: self.crypto.clone().into(),
+			mnemonic: self.mnemonic.clone().map(Into::into),
+			accounts: self.accounts.clone(),
+			restore_pending: self.restore_pending,
+		};
+
+		let tmp_path = path.with_extension("tmp");
+		{
+			let mut file = File::create(&tmp_path)?;
+			serde_json::to_writer(&mut file, &seed_file)
+				.map_err(|e| Error::Custom(format!("{}", e)))?;
+			file.sync_all()?;
+		}
+		fs::rename(&tmp_path, path)?;
+		Ok(())
+	}
+
+	/// Decrypt the seed.
+	pub fn decrypt(&self, password: &Password) -> Result<Vec<u8>, Error> {
+		self.crypto.decrypt(password)
+	}
+
+	/// Decrypt the mnemonic phrase of the seed, `None` if it wasn't created from one.
+	pub fn decrypt_mnemonic(&self, password: &Password) -> Result<Option<Mnemonic>, Error> {
+		let crypto = match self.mnemonic {
+			Some(ref crypto) => crypto,
+			None => return Ok(None),
+		};
+		let phrase = String::from_utf8(crypto.decrypt(password)?)
+			.map_err(|e| Error::Custom(format!("{}", e)))?;
+		Mnemonic::from_phrase(&phrase)
+			.map(Some)
+			.map_err(|e| Error::Custom(e.into()))
+	}
+
+	/// Index of the next account to derive.
+	pub fn next_account_index(&self) -> u32 {
+		self.accounts.len() as u32
+	}
+
+	/// Get the account at the given index.
+	pub fn account(&self, index: u32) -> Option<&HdAccount> {
+		self.accounts.iter().find(|account| account.index == index)
+	}
+
+	/// Get the account at the given index, mutably.
+	pub fn account_mut(&mut self, index: u32) -> Option<&mut HdAccount> {
+		self.accounts.iter_mut().find(|account| account.index == index)
+	}
+}
+
+/// Derive the spending key of the account `m/32'/coin_type'/account'`.
+pub fn derive_account_key(seed: &[u8], account: u32) -> SaplingExtendedSpendingKey {
+	let master = SaplingExtendedSpendingKey::master(seed);
+	SaplingExtendedSpendingKey::from_path(
+		&master,
+		&[
+			ChildIndex::Hardened(ZIP32_PURPOSE),
+			ChildIndex::Hardened(ORIGO_COIN_TYPE),
+			ChildIndex::Hardened(account),
+		],
+	)
+}
+
+/// Convert a diversifier index to the integer stored in the seed file.
+pub fn diversifier_to_u64(index: &DiversifierIndex) -> u64 {
+	LittleEndian::read_u64(&index.0[..8])
+}
+
+/// Convert an integer stored in the seed file to a diversifier index.
+pub fn diversifier_from_u64(index: u64) -> DiversifierIndex {
+	let mut bytes = [0u8; 11];
+	LittleEndian::write_u64(&mut bytes[..8], index);
+	DiversifierIndex(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::wallet_types::SaplingExtendedFullViewingKey;
+
+	#[test]
+	fn accounts_are_hardened_children() {
+		let seed = [7u8; 32];
+		let account0 = derive_account_key(&seed, 0);
+		let account1 = derive_account_key(&seed, 1);
+		assert_eq!(account0, derive_account_key(&seed, 0));
+		assert!(account0 != account1);
+		assert!(account0 != SaplingExtendedSpendingKey::master(&seed));
+	}
+
+	#[test]
+	fn diversified_addresses_are_distinct() {
+		let xfvk = SaplingExtendedFullViewingKey::from(&derive_account_key(&[1u8; 32], 0));
+		let (j0, address0) = xfvk.default_address().unwrap();
+		let (j1, address1) = xfvk.address(diversifier_from_u64(diversifier_to_u64(&j0) + 1)).unwrap();
+		assert!(diversifier_to_u64(&j1) > diversifier_to_u64(&j0));
+		assert!(address0 != address1);
+		assert_eq!(diversifier_from_u64(diversifier_to_u64(&j1)), j1);
+	}
+
+	#[test]
+	fn seed_file_roundtrip() {
+		let path = Path::new("./test_wallet_hd_seed");
+		let password = Password::from("pass");
+		let mut hd_seed = HdSeed::new(&[3u8; 32], &password, NonZeroU32::new(1024).unwrap()).unwrap();
+		hd_seed.accounts.push(HdAccount {
+			index: 0,
+			address: "ogo1".into(),
+			diversifiers: vec![0, 3],
+		});
+		hd_seed.restore_pending = true;
+		hd_seed.save(path).unwrap();
+
+		let loaded = HdSeed::load(path).unwrap().unwrap();
+		assert_eq!(loaded, hd_seed);
+		assert_eq!(loaded.decrypt(&password).unwrap(), vec![3u8; 32]);
+		assert!(loaded.decrypt(&Password::from("wrong")).is_err());
+		fs::remove_file(path).unwrap();
+		assert_eq!(HdSeed::load(path).unwrap(), None);
+	}
+
+	#[test]
+	fn mnemonic_is_kept_with_the_seed() {
+		let path = Path::new("./test_wallet_hd_seed_mnemonic");
+		let password = Password::from("pass");
+		let mnemonic = Mnemonic::from_entropy(&[9u8; 32]);
+		let hd_seed = HdSeed::from_mnemonic(&mnemonic, &password, NonZeroU32::new(1024).unwrap()).unwrap();
+		hd_seed.save(path).unwrap();
+
+		let loaded = HdSeed::load(path).unwrap().unwrap();
+		assert_eq!(loaded.decrypt(&password).unwrap(), mnemonic.to_seed("").to_vec());
+		assert_eq!(loaded.decrypt_mnemonic(&password).unwrap(), Some(mnemonic));
+		assert!(loaded.decrypt_mnemonic(&Password::from("wrong")).is_err());
+		fs::remove_file(path).unwrap();
+
+		let random = HdSeed::new(&[3u8; 32], &password, NonZeroU32::new(1024).unwrap()).unwrap();
+		assert_eq!(random.decrypt_mnemonic(&password).unwrap(), None);
+	}
+}
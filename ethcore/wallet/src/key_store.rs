@@ -0,0 +1,803 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::origo_account::OrigoAccount;
+use crate::origo_key_file::{OrigoKeyFile, OrigoKeyFileWhole};
+use crate::wallet_types::*;
+use ethstore::ethkey::Password;
+
+use crate::sendmany::{CAmount, SendManyRecipient, SpendKeys};
+use crate::hd_seed::diversifier_from_u64;
+use bech32::{u5, Bech32};
+use ff::{PrimeField, PrimeFieldRepr};
+use sapling_crypto::jubjub::fs::{Fs, FsRepr};
+use sapling_crypto::primitives::{Diversifier, ProofGenerationKey};
+use ethereum_types::H160;
+use ethereum_types::U256;
+use pairing::bls12_381::Bls12;
+use sapling_crypto::jubjub::{edwards, Unknown};
+use serde_json;
+use serde_json::{Result, Value};
+use std::collections::hash_map::HashMap;
+use std::io::{self, Read, Write};
+use std::str::FromStr;
+use zcash_primitives::JUBJUB;
+
+use byteorder::{BigEndian, ReadBytesExt};
+//use ethstore::crypto::KEY_ITERATIONS;
+use ethstore::Error;
+use std::fs::{self, File};
+use std::io::Cursor;
+use std::num::NonZeroU32;
+use dir::Directories;
+
+use backtrace::Backtrace;
+
+lazy_static! {
+	pub(crate) static ref KEY_ITERATIONS: NonZeroU32 =
+		NonZeroU32::new(10240).expect("KEY_ITERATIONS > 0; qed");
+}
+
+use std::collections::HashSet;
+// TODO(xin): Change to origo prefix.
+const ADDR_PREFIX: &'static str = "ogo";
+const EXTENDED_FULL_VIEWING_KEY_PREFIX: &'static str = "ogoxviews";
+const INCOMING_VIEWING_KEY_PREFIX: &'static str = "ogoivks";
+const EXTENDED_PROVING_KEY_PREFIX: &'static str = "ogoxprov";
+
+// Serialized lengths of the extended full viewing keys and incoming viewing keys.
+const EXTENDED_FULL_VIEWING_KEY_LENGTH: usize = 169;
+const INCOMING_VIEWING_KEY_LENGTH: usize = 32;
+// An extended full viewing key followed by the key deriving its nullifier key.
+const EXTENDED_PROVING_KEY_LENGTH: usize = EXTENDED_FULL_VIEWING_KEY_LENGTH + 32;
+
+// Number of diversifiers tried to find an address of an incoming viewing key.
+const MAX_DIVERSIFIER_TRIES: u64 = 256;
+
+// Struct used to covert between u5 vector and u8 vector.
+struct BitVec {
+	// TODO: Change to bit implementation.
+	pub vec_: Vec<u8>,
+}
+impl BitVec {
+	pub fn new() -> BitVec {
+		BitVec { vec_: Vec::new() }
+	}
+	pub fn from_u5_vec(u5_v: &[u5]) -> BitVec {
+		let mut vec = BitVec { vec_: Vec::new() };
+		for x in u5_v {
+			let mut x_u8 = x.to_u8();
+			let mut b5 = [0u8; 5];
+			for i in 0..5 {
+				b5[4 - i] = x_u8 % 2;
+				x_u8 = x_u8 / 2;
+			}
+			for x in b5.iter() {
+				vec.vec_.push(*x);
+			}
+		}
+		return vec;
+	}
+
+	pub fn attach_u8_vec(&mut self, u8_v: &[u8]) {
+		for x in u8_v {
+			let mut b8 = [0u8; 8];
+			let mut tmp = x.clone();
+			for i in 0..8 {
+				b8[7 - i] = tmp % 2;
+				tmp = tmp / 2;
+			}
+			for b in b8.iter() {
+				self.vec_.push(*b);
+			}
+		}
+	}
+
+	pub fn to_u5_vec(&mut self) -> Vec<u8> {
+		// Add additional 0 to bit vector such that the total lenght is multiplier of 5.
+		let mut u5_vec = Vec::new();
+		if self.vec_.len() % 5 != 0 {
+			for i in 0..(5 - (self.vec_.len() % 5)) {
+				self.vec_.push(0u8);
+			}
+		}
+
+		for i in 0..self.vec_.len() / 5 {
+			let mut num_u8 = 0;
+			for j in 0..5 {
+				num_u8 += 2_u8.pow(j as u32) * self.vec_[i * 5 + 4 - j];
+			}
+			u5_vec.push(num_u8);
+		}
+		return u5_vec;
+	}
+
+	pub fn to_u8(&self, start: usize, length: usize) -> Vec<u8> {
+		assert!(self.vec_.len() > start + length * 8);
+		let mut u8_vec = Vec::with_capacity(length);
+		for i in 0..length {
+			let mut num_u8 = 0;
+			for j in 0..8 {
+				num_u8 += 2_u8.pow(j as u32) * self.vec_[i * 8 + 7 - j];
+			}
+			u8_vec.push(num_u8);
+		}
+		return u8_vec;
+	}
+}
+
+pub fn decode_payment_address(address: &str) -> Option<SaplingPaymentAddress> {
+	let b32_parsed = match address.parse::<Bech32>() {
+		Ok(b) => b,
+		Err(_) => return None,
+	};
+	let u5_vec = b32_parsed.data();
+	if u5_vec.len() != (PAYMENT_ADDRESS_LENGTH * 8 - 1 + 5) / 5 {
+		return None;
+	}
+	let bit_vec = BitVec::from_u5_vec(u5_vec);
+	let u8_vec = bit_vec.to_u8(0, PAYMENT_ADDRESS_LENGTH);
+
+	let mut diversifier = [0u8; 11];
+	diversifier.copy_from_slice(&u8_vec[0..11]);
+	let pk_d = &u8_vec[11..PAYMENT_ADDRESS_LENGTH];
+	let pk_d = match edwards::Point::<Bls12, Unknown>::read(&mut pk_d.as_ref(), &JUBJUB) {
+		Ok(p) => p,
+		Err(_) => return None,
+	};
+	let pk_d = match pk_d.as_prime_order(&JUBJUB) {
+		Some(pk_d) => pk_d,
+		None => return None,
+	};
+	let payment_address = SaplingPaymentAddress {
+		diversifier: sapling_crypto::primitives::Diversifier(diversifier),
+		pk_d: pk_d,
+	};
+	Some(payment_address)
+}
+
+pub fn encode_payment_address(address: &SaplingPaymentAddress) -> String {
+	let mut pk_d_vec = [0u8; 32];
+	address.pk_d.write(&mut pk_d_vec[0..32]);
+	let mut bit_vec = BitVec::new();
+	bit_vec.attach_u8_vec(&address.diversifier.0);
+	bit_vec.attach_u8_vec(&pk_d_vec);
+	let b = Bech32::new_check_data(ADDR_PREFIX.into(), bit_vec.to_u5_vec());
+	assert!(b.is_ok());
+	let encoded = b.unwrap().to_string();
+	return encoded;
+}
+
+fn encode_bech32(prefix: &str, bytes: &[u8]) -> String {
+	let mut bit_vec = BitVec::new();
+	bit_vec.attach_u8_vec(bytes);
+	Bech32::new_check_data(prefix.into(), bit_vec.to_u5_vec())
+		.expect("prefix and data are valid bech32; qed")
+		.to_string()
+}
+
+fn decode_bech32(prefix: &str, encoded: &str, length: usize) -> Option<Vec<u8>> {
+	let b32_parsed = encoded.parse::<Bech32>().ok()?;
+	if b32_parsed.hrp() != prefix || b32_parsed.data().len() != (length * 8 - 1 + 5) / 5 {
+		return None;
+	}
+	Some(BitVec::from_u5_vec(b32_parsed.data()).to_u8(0, length))
+}
+
+pub fn encode_extended_full_viewing_key(xfvk: &SaplingExtendedFullViewingKey) -> String {
+	let mut bytes = Vec::with_capacity(EXTENDED_FULL_VIEWING_KEY_LENGTH);
+	xfvk.write(&mut bytes).expect("writing to a Vec never fails; qed");
+	encode_bech32(EXTENDED_FULL_VIEWING_KEY_PREFIX, &bytes)
+}
+
+pub fn decode_extended_full_viewing_key(encoded: &str) -> Option<SaplingExtendedFullViewingKey> {
+	let bytes = decode_bech32(EXTENDED_FULL_VIEWING_KEY_PREFIX, encoded, EXTENDED_FULL_VIEWING_KEY_LENGTH)?;
+	SaplingExtendedFullViewingKey::read(&bytes[..]).ok()
+}
+
+pub fn encode_incoming_viewing_key(ivk: &SaplingIncomingViewingKey) -> String {
+	let mut bytes = Vec::with_capacity(INCOMING_VIEWING_KEY_LENGTH);
+	ivk.into_repr().write_le(&mut bytes).expect("writing to a Vec never fails; qed");
+	encode_bech32(INCOMING_VIEWING_KEY_PREFIX, &bytes)
+}
+
+pub fn decode_incoming_viewing_key(encoded: &str) -> Option<SaplingIncomingViewingKey> {
+	let bytes = decode_bech32(INCOMING_VIEWING_KEY_PREFIX, encoded, INCOMING_VIEWING_KEY_LENGTH)?;
+	let mut repr = FsRepr::default();
+	repr.read_le(&bytes[..]).ok()?;
+	Fs::from_repr(repr).ok()
+}
+
+/// Encode the extended full viewing key with the key `nsk` deriving its nullifier key.
+/// Together they prove the spends of the notes of the key, without authorizing them.
+pub fn encode_extended_proving_key(xfvk: &SaplingExtendedFullViewingKey, nsk: &Fs) -> String {
+	let mut bytes = Vec::with_capacity(EXTENDED_PROVING_KEY_LENGTH);
+	xfvk.write(&mut bytes).expect("writing to a Vec never fails; qed");
+	nsk.into_repr().write_le(&mut bytes).expect("writing to a Vec never fails; qed");
+	encode_bech32(EXTENDED_PROVING_KEY_PREFIX, &bytes)
+}
+
+pub fn decode_extended_proving_key(encoded: &str) -> Option<(SaplingExtendedFullViewingKey, Fs)> {
+	let bytes = decode_bech32(EXTENDED_PROVING_KEY_PREFIX, encoded, EXTENDED_PROVING_KEY_LENGTH)?;
+	let xfvk = SaplingExtendedFullViewingKey::read(&bytes[..EXTENDED_FULL_VIEWING_KEY_LENGTH]).ok()?;
+	let mut repr = FsRepr::default();
+	repr.read_le(&bytes[EXTENDED_FULL_VIEWING_KEY_LENGTH..]).ok()?;
+	let nsk = Fs::from_repr(repr).ok()?;
+	// The key must derive the nullifier key of the viewing key.
+	let proof_generation_key = ProofGenerationKey { ak: xfvk.fvk.vk.ak.clone(), nsk };
+	if proof_generation_key.into_viewing_key(&JUBJUB).nk != xfvk.fvk.vk.nk {
+		return None;
+	}
+	Some((xfvk, nsk))
+}
+
+/// Find an address of the incoming viewing key, trying the diversifiers in order.
+pub fn incoming_viewing_key_address(ivk: &SaplingIncomingViewingKey) -> Option<SaplingPaymentAddress> {
+	(0..MAX_DIVERSIFIER_TRIES).filter_map(|index| {
+		let diversifier = Diversifier(diversifier_from_u64(index).0);
+		diversifier.g_d::<Bls12>(&JUBJUB).map(|g_d| SaplingPaymentAddress {
+			pk_d: g_d.mul(*ivk, &JUBJUB),
+			diversifier,
+		})
+	}).next()
+}
+
+pub type TxDestination = H160;
+
+pub fn decode_destination(address: &str) -> Option<TxDestination> {
+	match TxDestination::from_str(address.trim_start_matches("0x")) {
+		Ok(add) => Some(add),
+		Err(_) => None,
+	}
+}
+
+pub fn decode_transparent_destination(address: &str) -> bool {
+	match decode_destination(address) {
+		Some(_) => true,
+		None => false,
+	}
+}
+
+pub fn decode_outputs(
+	aoutputs_str: &str,
+) -> (Vec<SendManyRecipient>, Vec<SendManyRecipient>, U256) {
+	let v: Vec<Value> = serde_json::from_str(aoutputs_str).unwrap();
+	let mut total = U256::from(0);
+	let mut t_recipients = Vec::new();
+	let mut z_recipients = Vec::new();
+	for r in v {
+		let recipient = (
+			r["address"].as_str().unwrap().to_string(),
+			U256::from(r["amount"].as_u64().unwrap()),
+			"".to_string(),
+		);
+		total += recipient.1;
+		if decode_transparent_destination(&recipient.0) {
+			t_recipients.push(recipient);
+		} else {
+			z_recipients.push(recipient);
+		}
+	}
+	(z_recipients, t_recipients, total)
+}
+
+pub struct KeyStore {
+	map_incoming_view_keys: HashMap<SaplingPaymentAddress, SaplingIncomingViewingKey>,
+	map_full_viewing_keys: HashMap<SaplingIncomingViewingKey, SaplingFullViewingKey>,
+	// Extended full viewing keys by the default address of the key file.
+	map_extended_full_viewing_keys: HashMap<SaplingPaymentAddress, SaplingExtendedFullViewingKey>,
+	// Default address of the key file for every diversified address handed out.
+	map_diversified_addresses: HashMap<SaplingPaymentAddress, SaplingPaymentAddress>,
+	// Incoming viewing keys imported without their full viewing key.
+	incoming_only_viewing_keys: HashSet<SaplingIncomingViewingKey>,
+	// Imported viewing keys, without spending keys, with the block to scan the chain from.
+	watch_only_viewing_keys: HashMap<SaplingIncomingViewingKey, u64>,
+	// Keys deriving the nullifier keys of the viewing keys imported with their proving key.
+	watch_only_nullifier_keys: HashMap<SaplingIncomingViewingKey, Fs>,
+	file_path: String,
+	file_name_prefix: String,
+}
+
+impl KeyStore {
+	pub fn get_map_full_viewing_keys(
+		&self,
+	) -> &HashMap<SaplingIncomingViewingKey, SaplingFullViewingKey> {
+		&self.map_full_viewing_keys
+	}
+
+	// This function return the viewing keys not included in the handled_address_option,
+	// without full viewing key for the incoming viewing keys imported alone.
+	pub fn get_unhandled_viewing_keys(
+		&self,
+		handled_addresses_option: &Option<HashSet<String>>,
+	) -> HashMap<SaplingIncomingViewingKey, Option<SaplingFullViewingKey>> {
+		let mut handled_incoming_keys = HashSet::new();
+		if let Some(handled_addresses) = handled_addresses_option {
+			for address in handled_addresses.iter() {
+				if let Some(payment_address) = decode_payment_address(address) {
+					if let Some(incoming_key) = self.map_incoming_view_keys.get(&payment_address) {
+						handled_incoming_keys.insert(incoming_key);
+					}
+				}
+			}
+		}
+
+		let mut unhandled_viewing_keys = HashMap::new();
+		for (incoming_key, full_key) in self.map_full_viewing_keys.iter() {
+			if !handled_incoming_keys.contains(incoming_key) {
+				unhandled_viewing_keys.insert(incoming_key.clone(), Some(full_key.clone()));
+			}
+		}
+		for incoming_key in self.incoming_only_viewing_keys.iter() {
+			if !handled_incoming_keys.contains(incoming_key) {
+				unhandled_viewing_keys.insert(incoming_key.clone(), None);
+			}
+		}
+		unhandled_viewing_keys
+	}
+
+	// This function return the viewing keys of the given addresses, without full viewing key
+	// for the incoming viewing keys imported alone, or the first address not in the key store.
+	pub fn get_viewing_keys(
+		&self,
+		addresses: &[String],
+	) -> Result<HashMap<SaplingIncomingViewingKey, Option<SaplingFullViewingKey>>, String> {
+		let mut viewing_keys = HashMap::new();
+		for address in addresses.iter() {
+			let incoming_key = decode_payment_address(address)
+				.and_then(|payment_address| self.map_incoming_view_keys.get(&payment_address))
+				.ok_or_else(|| address.clone())?;
+			let full_key = self.map_full_viewing_keys.get(incoming_key).cloned();
+			viewing_keys.insert(incoming_key.clone(), full_key);
+		}
+		Ok(viewing_keys)
+	}
+
+	pub fn decrypt_key_file(
+		&self,
+		address: &str,
+		pass: String,
+	) -> Option<SaplingExtendedSpendingKey> {
+        let prefix = &self.file_name_prefix;
+		// Diversified addresses are spent with the key file of their default address.
+		let address = match decode_payment_address(address)
+			.and_then(|payment_address| self.map_diversified_addresses.get(&payment_address))
+		{
+			Some(default_address) => encode_payment_address(default_address),
+			None => address.to_string(),
+		};
+		let file_name = format!("{}/{}{}", self.file_path,prefix, address);
+
+		let mut file = File::open(file_name);
+		if file.is_err() {
+			return None;
+		}
+		let key_file = OrigoKeyFile::load(&file.unwrap()).unwrap();
+		let origo_account = OrigoAccount::from_file(key_file, None);
+
+		let origo_account = origo_account.unwrap();
+
+		if let Ok(secret) = origo_account.crypto.decrypt(&Password::from(pass)) {
+			let mut rdr = Cursor::new(secret);
+			let espk = SaplingExtendedSpendingKey::read(&mut rdr);
+			return espk.ok();
+		} else {
+			return None;
+		}
+	}
+
+	pub fn encrypt_key_file_and_add_viewing_key(
+		&mut self,
+		espk: &SaplingExtendedSpendingKey,
+		pass: String,
+	) -> io::Result<()> {
+		let pass = Password::from(pass);
+
+		let xfvk = SaplingExtendedFullViewingKey::from(espk);
+		let (_, address) = xfvk.default_address().unwrap();
+		let address_str = encode_payment_address(&address);
+
+		let origo_account = OrigoAccount::create(address_str.clone(), &xfvk, espk, &pass, *KEY_ITERATIONS);
+		let origo_key_file: OrigoKeyFile = origo_account.unwrap().into();
+
+        let file_name = format!("{}/{}{}", self.file_path, self.file_name_prefix, address_str);
+		let mut file = File::create(file_name)?;
+		origo_key_file.write(&mut file);
+
+		self.add_extended_full_viewing_key(xfvk, address);
+		Ok(())
+	}
+
+	//It should only used in test
+	pub fn new() -> Self {
+		KeyStore {
+			map_incoming_view_keys: HashMap::new(),
+			map_full_viewing_keys: HashMap::new(),
+			map_extended_full_viewing_keys: HashMap::new(),
+			map_diversified_addresses: HashMap::new(),
+			incoming_only_viewing_keys: HashSet::new(),
+			watch_only_viewing_keys: HashMap::new(),
+			watch_only_nullifier_keys: HashMap::new(),
+			file_path : "./".to_string(),
+			file_name_prefix: "wallet_".to_string(),
+		}
+	}
+
+	pub fn new_with_file_prefix(file_name_prefix: &str, file_path: &str) -> Self {
+		KeyStore {
+			map_incoming_view_keys: HashMap::new(),
+			map_full_viewing_keys: HashMap::new(),
+			map_extended_full_viewing_keys: HashMap::new(),
+			map_diversified_addresses: HashMap::new(),
+			incoming_only_viewing_keys: HashSet::new(),
+			watch_only_viewing_keys: HashMap::new(),
+			watch_only_nullifier_keys: HashMap::new(),
+			file_path: file_path.to_string(),
+			file_name_prefix: file_name_prefix.to_string(),
+		}
+	}
+
+	pub fn decode_z_destination(
+		&self,
+		address: &str,
+		pass: String,
+	) -> (
+		Option<SaplingPaymentAddress>,
+		Option<SaplingExtendedSpendingKey>,
+	) {
+		let payment_address = decode_payment_address(address);
+
+		match decode_payment_address(address) {
+			Some(a) => (Some(a), self.decrypt_key_file(address, pass)),
+			None => (None, None),
+		}
+	}
+
+
+	pub fn get_incoming_viewing_key(
+		&self,
+		address: &SaplingPaymentAddress,
+	) -> Option<SaplingIncomingViewingKey> {
+		match self.map_incoming_view_keys.get(address) {
+			Some(&v) => Some(v),
+			None => None,
+		}
+	}
+
+	pub fn get_full_viewing_key(
+		&self,
+		ivk: &SaplingIncomingViewingKey,
+	) -> Option<SaplingFullViewingKey> {
+		match self.map_full_viewing_keys.get(ivk) {
+			Some(&v) => Some(v),
+			None => None,
+		}
+	}
+
+	pub fn add_full_viewing_key(
+		&mut self,
+		fvk: SaplingFullViewingKey,
+		address: SaplingPaymentAddress,
+	) -> bool {
+		let ivk = fvk.vk.ivk();
+		self.map_full_viewing_keys.insert(ivk, fvk);
+		return self.add_incoming_viewing_key(ivk, address);
+	}
+
+	/// Add the extended full viewing key of a key file, with its default address.
+	pub fn add_extended_full_viewing_key(
+		&mut self,
+		xfvk: SaplingExtendedFullViewingKey,
+		address: SaplingPaymentAddress,
+	) -> bool {
+		let fvk = xfvk.fvk.clone();
+		self.map_extended_full_viewing_keys.insert(address.clone(), xfvk);
+		self.add_full_viewing_key(fvk, address)
+	}
+
+	/// Get the extended full viewing key of a default or diversified address.
+	pub fn get_extended_full_viewing_key(
+		&self,
+		address: &SaplingPaymentAddress,
+	) -> Option<&SaplingExtendedFullViewingKey> {
+		let address = self.map_diversified_addresses.get(address).unwrap_or(address);
+		self.map_extended_full_viewing_keys.get(address)
+	}
+
+	/// Get the default address of the extended full viewing key whose incoming viewing key is `ivk`.
+	pub fn get_default_address(
+		&self,
+		ivk: &SaplingIncomingViewingKey,
+	) -> Option<SaplingPaymentAddress> {
+		self.map_extended_full_viewing_keys
+			.iter()
+			.find(|(_, xfvk)| xfvk.fvk.vk.ivk() == *ivk)
+			.map(|(address, _)| address.clone())
+	}
+
+	/// Add an imported extended full viewing key, whose notes can't be spent.
+	/// The chain is scanned for it from `birthday`.
+	pub fn add_watch_only_extended_full_viewing_key(
+		&mut self,
+		xfvk: SaplingExtendedFullViewingKey,
+		birthday: u64,
+	) -> Option<SaplingPaymentAddress> {
+		let (_, address) = xfvk.default_address().ok()?;
+		self.watch_only_viewing_keys.insert(xfvk.fvk.vk.ivk(), birthday);
+		self.add_extended_full_viewing_key(xfvk, address.clone());
+		Some(address)
+	}
+
+	/// Add an imported extended full viewing key with the key `nsk` deriving its nullifier key.
+	/// The spends of its notes are proven by the wallet, to be signed offline.
+	/// The chain is scanned for it from `birthday`.
+	pub fn add_watch_only_proving_key(
+		&mut self,
+		xfvk: SaplingExtendedFullViewingKey,
+		nsk: Fs,
+		birthday: u64,
+	) -> Option<SaplingPaymentAddress> {
+		let ivk = xfvk.fvk.vk.ivk();
+		let address = self.add_watch_only_extended_full_viewing_key(xfvk, birthday)?;
+		self.watch_only_nullifier_keys.insert(ivk, nsk);
+		Some(address)
+	}
+
+	/// The keys proving the spends of the notes of a watch-only address imported with its
+	/// proving key, without the spend authorizing key.
+	pub fn get_spend_keys(&self, address: &SaplingPaymentAddress) -> Option<SpendKeys> {
+		let xfvk = self.get_extended_full_viewing_key(address)?;
+		let nsk = self.watch_only_nullifier_keys.get(&xfvk.fvk.vk.ivk())?;
+		Some(SpendKeys {
+			proof_generation_key: ProofGenerationKey { ak: xfvk.fvk.vk.ak.clone(), nsk: *nsk },
+			ovk: xfvk.fvk.ovk,
+			ask: None,
+		})
+	}
+
+	/// Add an imported incoming viewing key. Its notes can't be spent, and without
+	/// full viewing key their nullifiers are unknown: spending them isn't detected.
+	/// The chain is scanned for it from `birthday`.
+	pub fn add_watch_only_incoming_viewing_key(
+		&mut self,
+		ivk: SaplingIncomingViewingKey,
+		birthday: u64,
+	) -> Option<SaplingPaymentAddress> {
+		let address = incoming_viewing_key_address(&ivk)?;
+		self.watch_only_viewing_keys.insert(ivk, birthday);
+		self.incoming_only_viewing_keys.insert(ivk);
+		self.add_incoming_viewing_key(ivk, address.clone());
+		Some(address)
+	}
+
+	/// Whether the viewing key has been imported, with no spending key.
+	pub fn is_watch_only(&self, ivk: &SaplingIncomingViewingKey) -> bool {
+		self.watch_only_viewing_keys.contains_key(ivk)
+	}
+
+	/// Whether the viewing key is known, with or without spending key.
+	pub fn contains_viewing_key(&self, ivk: &SaplingIncomingViewingKey) -> bool {
+		self.map_full_viewing_keys.contains_key(ivk) || self.incoming_only_viewing_keys.contains(ivk)
+	}
+
+	/// The block to scan the chain from for the viewing key, 0 unless it was imported
+	/// with a later rescan height.
+	pub fn get_birthday(&self, ivk: &SaplingIncomingViewingKey) -> u64 {
+		self.watch_only_viewing_keys.get(ivk).cloned().unwrap_or(0)
+	}
+
+	/// Add a diversified address of the key file whose default address is `default_address`.
+	/// Returns false if there is no such key file.
+	pub fn add_diversified_address(
+		&mut self,
+		default_address: &SaplingPaymentAddress,
+		address: SaplingPaymentAddress,
+	) -> bool {
+		let fvk = match self.map_extended_full_viewing_keys.get(default_address) {
+			Some(xfvk) => xfvk.fvk.clone(),
+			None => return false,
+		};
+		if address != *default_address {
+			self.map_diversified_addresses.insert(address.clone(), default_address.clone());
+		}
+		self.add_full_viewing_key(fvk, address)
+	}
+
+	/// Delete the key file whose default address is `default_address`,
+	/// and forget its viewing key and diversified addresses.
+	pub fn remove_key_file(&mut self, default_address: &SaplingPaymentAddress) -> io::Result<()> {
+		let file_name = format!(
+			"{}/{}{}",
+			self.file_path,
+			self.file_name_prefix,
+			encode_payment_address(default_address)
+		);
+		fs::remove_file(file_name)?;
+
+		if let Some(xfvk) = self.map_extended_full_viewing_keys.remove(default_address) {
+			let ivk = xfvk.fvk.vk.ivk();
+			self.map_full_viewing_keys.remove(&ivk);
+			self.map_incoming_view_keys.retain(|_, address_ivk| *address_ivk != ivk);
+		}
+		self.map_diversified_addresses.retain(|_, address| address != default_address);
+		Ok(())
+	}
+
+	pub fn get_sapling_payment_addresses(&self) -> Vec<SaplingPaymentAddress> {
+		let mut set = Vec::new();
+		for (k, _) in &self.map_incoming_view_keys {
+			set.push(k.clone());
+		}
+		set
+	}
+
+	fn add_incoming_viewing_key(
+		&mut self,
+		ivk: SaplingIncomingViewingKey,
+		address: SaplingPaymentAddress,
+	) -> bool {
+		self.map_incoming_view_keys.insert(address, ivk);
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rand::{OsRng, Rand};
+
+	#[test]
+	fn test_decode_payment_address() {
+		let address =
+			"ogo14j53eenhdjp85dlfctsttmtgav8sqkkttsl6qxvpmn74jk7edsyzp08r550dzu96hu9gwtzk9w2";
+		let option = decode_payment_address(address);
+		match option {
+			Some(p) => assert_eq!(
+				address,
+				encode_payment_address(&p),
+				"encoded address doesn't equal."
+			),
+			None => panic!("Can't decode address"),
+		}
+	}
+
+	#[test]
+	fn test_decode_outputs() {
+		let output = r#"[{"address": "ztfaW34Gj9FrnGUEf833ywDVL62NWXBM81u6EQnM6VR45eYnXhwztecW1SjxA7JrmAXKJhxhj3vDNEpVCQoSvVoSpmbhtjf" ,"amount": 5},
+                         {"address": "0x793ea9692Ada1900fBd0B80FFFEc6E431fe8b391" ,"amount": 6}]"#;
+		let k = KeyStore::new();
+		let (zaddr_recipients, taddr_recipients, total_amount) = decode_outputs(output);
+		assert_eq!(total_amount, U256::from(11));
+		assert_eq!(zaddr_recipients.len(), 1);
+		assert_eq!(zaddr_recipients[0], ("ztfaW34Gj9FrnGUEf833ywDVL62NWXBM81u6EQnM6VR45eYnXhwztecW1SjxA7JrmAXKJhxhj3vDNEpVCQoSvVoSpmbhtjf".to_string(),
+                                        U256::from(5), "".to_string()));
+		assert_eq!(zaddr_recipients.len(), 1);
+		assert_eq!(taddr_recipients.len(), 1);
+		assert_eq!(
+			taddr_recipients[0],
+			(
+				"0x793ea9692Ada1900fBd0B80FFFEc6E431fe8b391".to_string(),
+				U256::from(6),
+				"".to_string()
+			)
+		);
+	}
+
+	#[test]
+	fn test_key_store_basic() {
+		let extsk = SaplingExtendedSpendingKey::master(&[]);
+		let mut key_store = KeyStore::new();
+		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		let ovk = extfvk.fvk.ovk;
+		let to = extfvk.default_address().unwrap().1;
+		let old_spending_key = extsk.clone();
+		let old_address = to.clone();
+
+		key_store.add_full_viewing_key(extfvk.fvk, to);
+		assert_eq!(key_store.map_full_viewing_keys.len(), 1);
+		assert_eq!(key_store.map_incoming_view_keys.len(), 1);
+
+		let addresses = key_store.get_sapling_payment_addresses();
+		assert_eq!(addresses.len(), 1);
+		assert_eq!(addresses[0], old_address);
+	}
+
+	#[test]
+	fn test_viewing_key_encodings() {
+		let extfvk = SaplingExtendedFullViewingKey::from(&SaplingExtendedSpendingKey::master(&[1u8; 32]));
+		let encoded = encode_extended_full_viewing_key(&extfvk);
+		assert_eq!(decode_extended_full_viewing_key(&encoded), Some(extfvk.clone()));
+		assert!(decode_incoming_viewing_key(&encoded).is_none());
+
+		let ivk = extfvk.fvk.vk.ivk();
+		let encoded = encode_incoming_viewing_key(&ivk);
+		assert_eq!(decode_incoming_viewing_key(&encoded), Some(ivk));
+		assert!(decode_extended_full_viewing_key(&encoded).is_none());
+
+		// The address found for the incoming viewing key is one of the full viewing key.
+		let address = incoming_viewing_key_address(&ivk).unwrap();
+		assert_eq!(extfvk.fvk.vk.into_payment_address(address.diversifier, &JUBJUB), Some(address));
+	}
+
+	#[test]
+	fn test_watch_only_viewing_keys() {
+		let mut key_store = KeyStore::new();
+		let extfvk = SaplingExtendedFullViewingKey::from(&SaplingExtendedSpendingKey::master(&[1u8; 32]));
+		let ivk = SaplingExtendedFullViewingKey::from(&SaplingExtendedSpendingKey::master(&[2u8; 32])).fvk.vk.ivk();
+		key_store.add_watch_only_extended_full_viewing_key(extfvk.clone(), 0).unwrap();
+		key_store.add_watch_only_incoming_viewing_key(ivk, 10).unwrap();
+
+		assert!(key_store.is_watch_only(&extfvk.fvk.vk.ivk()));
+		assert!(key_store.is_watch_only(&ivk));
+		assert_eq!(key_store.get_birthday(&ivk), 10);
+		let unhandled = key_store.get_unhandled_viewing_keys(&None);
+		assert_eq!(unhandled.len(), 2);
+		assert!(unhandled[&extfvk.fvk.vk.ivk()].is_some());
+		assert!(unhandled[&ivk].is_none());
+	}
+
+	#[test]
+	fn test_get_viewing_keys() {
+		let mut key_store = KeyStore::new();
+		let extfvk = SaplingExtendedFullViewingKey::from(&SaplingExtendedSpendingKey::master(&[1u8; 32]));
+		let ivk = SaplingExtendedFullViewingKey::from(&SaplingExtendedSpendingKey::master(&[2u8; 32])).fvk.vk.ivk();
+		let full_address = key_store.add_watch_only_extended_full_viewing_key(extfvk.clone(), 0).unwrap();
+		let incoming_address = key_store.add_watch_only_incoming_viewing_key(ivk, 0).unwrap();
+
+		let viewing_keys = key_store
+			.get_viewing_keys(&[encode_payment_address(&incoming_address)])
+			.unwrap();
+		assert_eq!(viewing_keys.len(), 1);
+		assert!(viewing_keys[&ivk].is_none());
+
+		let addresses = [encode_payment_address(&full_address), encode_payment_address(&incoming_address)];
+		let viewing_keys = key_store.get_viewing_keys(&addresses).unwrap();
+		assert_eq!(viewing_keys.len(), 2);
+		assert!(viewing_keys[&extfvk.fvk.vk.ivk()].is_some());
+
+		let unknown = SaplingExtendedFullViewingKey::from(&SaplingExtendedSpendingKey::master(&[3u8; 32]))
+			.default_address().unwrap().1;
+		let unknown = encode_payment_address(&unknown);
+		assert_eq!(key_store.get_viewing_keys(&[unknown.clone()]).err(), Some(unknown));
+	}
+
+	#[test]
+	fn test_get_unhandled_viewing_keys() {
+		let extsk = SaplingExtendedSpendingKey::master(&[]);
+		let mut key_store = KeyStore::new();
+		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		let ovk = extfvk.fvk.ovk;
+		let to = extfvk.default_address().unwrap().1;
+		let old_address = to.clone();
+		key_store.add_full_viewing_key(extfvk.fvk, to);
+
+		let seed = [0u8; 32];
+		let extsk = SaplingExtendedSpendingKey::master(&seed);
+		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		let ovk = extfvk.fvk.ovk;
+		let new_address_vk = extfvk.fvk.vk.ivk();
+		let to = extfvk.default_address().unwrap().1;
+		key_store.add_full_viewing_key(extfvk.fvk, to);
+
+		let filter_viewing_keys = key_store.get_unhandled_viewing_keys(&None);
+		assert_eq!(filter_viewing_keys.len(), 2);
+		let mut handled_addresses = HashSet::new();
+		handled_addresses.insert(encode_payment_address(&old_address));
+		let filter_viewing_keys = key_store.get_unhandled_viewing_keys(&Some(handled_addresses));
+		assert_eq!(filter_viewing_keys.len(), 1);
+		assert!(filter_viewing_keys.contains_key(&new_address_vk));
+	}
+}
@@ -0,0 +1,53 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shielded wallet.
+//!
+//! The wallet keeps the Sapling keys of the node and the notes they received. It is synced
+//! with the chain by `WalletSync`, on its own thread, after the blocks are imported.
+
+#[macro_use]
+extern crate lazy_static;
+#[macro_use]
+extern crate log;
+
+pub mod async_operation;
+pub mod coin_selection;
+pub mod hd_seed;
+pub mod key_store;
+pub mod mnemonic;
+pub mod offline;
+pub mod origo_account;
+pub mod origo_key_file;
+pub mod scanner;
+pub mod sendmany;
+pub mod sync;
+#[cfg(test)]
+mod test_helpers;
+pub mod transaction_builder;
+pub mod wallet;
+pub mod wallet_types;
+pub mod watch_only;
+
+pub use self::async_operation::{OperationManager, OperationState, OperationStatus, DEFAULT_OPERATION_WORKERS};
+pub use self::coin_selection::{CoinSelection, MAX_SPENDS_PER_TX};
+pub use self::key_store::{decode_payment_address, decode_transparent_destination, encode_payment_address};
+pub use self::offline::UnsignedTransaction;
+pub use self::sendmany::{MergeToAddressInputs, SendMany, SendManyInputs, SentNoteData};
+pub use self::sync::{RescanState, RescanStatus, WalletChain, WalletSync, WalletSyncStatus};
+pub use self::transaction_builder::ShieldedCoinbase;
+pub use self::wallet::{Wallet, DUMMY_WALLET_PASS};
+pub use self::wallet_types::SaplingPaymentAddress;
@@ -0,0 +1,184 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! BIP-39 mnemonic phrases backing up the wallet seed.
+
+use std::fmt;
+use std::num::NonZeroU32;
+
+use parity_crypto::{digest, pbkdf2};
+use rand::{OsRng, Rng};
+
+/// Number of words of the generated phrases.
+pub const MNEMONIC_WORDS: usize = 24;
+
+const PBKDF2_ROUNDS: u32 = 2048;
+
+lazy_static! {
+	static ref WORDLIST: Vec<&'static str> = include_str!("../res/bip39_english.txt")
+		.lines()
+		.collect();
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MnemonicError {
+	InvalidWordCount(usize),
+	UnknownWord(String),
+	InvalidChecksum,
+}
+
+impl fmt::Display for MnemonicError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		match *self {
+			MnemonicError::InvalidWordCount(count) =>
+				write!(f, "Invalid mnemonic length: {} words.", count),
+			MnemonicError::UnknownWord(ref word) => write!(f, "Unknown mnemonic word: {}.", word),
+			MnemonicError::InvalidChecksum => write!(f, "Invalid mnemonic checksum."),
+		}
+	}
+}
+
+impl Into<String> for MnemonicError {
+	fn into(self) -> String {
+		format!("{}", self)
+	}
+}
+
+/// A BIP-39 phrase of the english wordlist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mnemonic {
+	words: Vec<&'static str>,
+}
+
+impl Mnemonic {
+	/// Generate a phrase of `MNEMONIC_WORDS` words from fresh entropy.
+	pub fn generate() -> Self {
+		let mut entropy = [0u8; MNEMONIC_WORDS * 11 / 33 * 4];
+		OsRng::new().expect("OS randomness is available").fill_bytes(&mut entropy);
+		Mnemonic::from_entropy(&entropy)
+	}
+
+	/// Encode the entropy, a multiple of 4 bytes between 16 and 32 bytes.
+	pub fn from_entropy(entropy: &[u8]) -> Self {
+		assert!(entropy.len() % 4 == 0 && entropy.len() >= 16 && entropy.len() <= 32);
+
+		let checksum = digest::sha256(entropy)[0];
+		let bits = entropy.len() * 8 + entropy.len() / 4;
+		let bit = |i: usize| {
+			let byte = if i < entropy.len() * 8 { entropy[i / 8] } else { checksum };
+			(byte >> (7 - i % 8)) & 1
+		};
+
+		let words = (0..bits / 11)
+			.map(|w| {
+				let index = (0..11).fold(0usize, |acc, i| (acc << 1) | bit(w * 11 + i) as usize);
+				WORDLIST[index]
+			})
+			.collect();
+		Mnemonic { words }
+	}
+
+	/// Parse a phrase, checking its words and checksum.
+	pub fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
+		let words = phrase.split_whitespace().collect::<Vec<_>>();
+		if words.len() < 12 || words.len() > 24 || words.len() % 3 != 0 {
+			return Err(MnemonicError::InvalidWordCount(words.len()));
+		}
+
+		let mut bits = Vec::with_capacity(words.len() * 11);
+		for word in words.iter() {
+			let word = word.to_lowercase();
+			let index = WORDLIST
+				.binary_search_by(|probe| (*probe).cmp(word.as_str()))
+				.map_err(|_| MnemonicError::UnknownWord(word.clone()))?;
+			bits.extend((0..11).rev().map(|i| (index >> i) & 1 == 1));
+		}
+
+		let entropy_bits = bits.len() * 32 / 33;
+		let entropy = bits[..entropy_bits]
+			.chunks(8)
+			.map(|byte| byte.iter().fold(0u8, |acc, b| (acc << 1) | *b as u8))
+			.collect::<Vec<_>>();
+
+		let mnemonic = Mnemonic::from_entropy(&entropy);
+		if mnemonic.words.iter().zip(words.iter()).any(|(a, b)| *a != b.to_lowercase()) {
+			return Err(MnemonicError::InvalidChecksum);
+		}
+		Ok(mnemonic)
+	}
+
+	/// The words of the phrase separated by single spaces.
+	pub fn phrase(&self) -> String {
+		self.words.join(" ")
+	}
+
+	/// Derive the 64-byte BIP-39 seed with the optional passphrase.
+	pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+		let phrase = self.phrase();
+		let salt = format!("mnemonic{}", passphrase);
+		let mut seed = [0u8; 64];
+		let rounds = NonZeroU32::new(PBKDF2_ROUNDS).expect("PBKDF2_ROUNDS > 0; qed");
+		pbkdf2::sha512(rounds, pbkdf2::Salt(salt.as_bytes()), pbkdf2::Secret(phrase.as_bytes()), &mut seed);
+		seed
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rustc_hex::{FromHex, ToHex};
+
+	// From the reference BIP-39 test vectors, with the passphrase "TREZOR".
+	const VECTORS: &[(&str, &str, &str)] = &[
+		(
+			"00000000000000000000000000000000",
+			"abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+			"c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+		),
+		(
+			"8080808080808080808080808080808080808080808080808080808080808080",
+			"letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic avoid letter advice cage absurd amount doctor acoustic bless",
+			"c0c519bd0e91a2ed54357d9d1ebef6f5af218a153624cf4f2da911a0ed8f7a09e2ef61af0aca007096df430022f7a2b6fb91661a9589097069720d015e4e982f",
+		),
+	];
+
+	#[test]
+	fn encodes_reference_vectors() {
+		for &(entropy, phrase, seed) in VECTORS {
+			let mnemonic = Mnemonic::from_entropy(&entropy.from_hex::<Vec<u8>>().unwrap());
+			assert_eq!(mnemonic.phrase(), phrase);
+			assert_eq!(Mnemonic::from_phrase(phrase).unwrap(), mnemonic);
+			assert_eq!(mnemonic.to_seed("TREZOR").to_hex::<String>(), seed);
+		}
+	}
+
+	#[test]
+	fn generates_valid_phrases() {
+		let mnemonic = Mnemonic::generate();
+		assert_eq!(mnemonic.phrase().split(' ').count(), MNEMONIC_WORDS);
+		assert_eq!(Mnemonic::from_phrase(&mnemonic.phrase()).unwrap(), mnemonic);
+	}
+
+	#[test]
+	fn rejects_invalid_phrases() {
+		let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+		assert_eq!(Mnemonic::from_phrase(phrase), Err(MnemonicError::InvalidWordCount(11)));
+		let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+		assert_eq!(Mnemonic::from_phrase(phrase), Err(MnemonicError::InvalidChecksum));
+		let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon origo";
+		assert_eq!(Mnemonic::from_phrase(phrase), Err(MnemonicError::UnknownWord("origo".into())));
+	}
+}
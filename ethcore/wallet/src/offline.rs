@@ -0,0 +1,254 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shielded transactions proven by a watch-only wallet and signed offline.
+//!
+//! A wallet holding the proof generation key of an address proves the spends and outputs of
+//! a transaction. The spend authorizing key, kept offline, then signs the spends, and the sum
+//! of the value commitment randomness carried along signs the value balance.
+
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use sapling_crypto::jubjub::{fs::Fs, FixedGenerators};
+use sapling_crypto::redjubjub::{PrivateKey, PublicKey};
+use zcash_primitives::{
+	note_encryption::{try_sapling_output_recovery, Memo},
+	sapling::spend_sig,
+	JUBJUB,
+};
+use zcash_proofs::sapling::SaplingProvingContext;
+
+use crate::sendmany::{decode_fs, encode_fs};
+use crate::transaction_builder::{Error, ErrorKind};
+use crate::wallet_types::{SaplingOutgoingViewingKey, SaplingPaymentAddress};
+use common_types::transaction::{Transaction, UnverifiedTransaction};
+
+/// A private transaction with its proofs, whose spends and value balance are not signed yet.
+///
+/// It is encoded as the list `[transaction, shielded_sighash, alphas, bsk]`, the transaction
+/// being encoded as an unsigned private transaction of the chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsignedTransaction {
+	pub tx: Transaction,
+	pub chain_id: u64,
+	/// Whether the signatures sign the shielded signature hash.
+	pub shielded_sighash: bool,
+	/// The randomizers of the spend authorizing key, one per spend.
+	pub alphas: Vec<Fs>,
+	/// The value commitment randomness of the spends minus that of the outputs.
+	pub bsk: Fs,
+}
+
+impl Encodable for UnsignedTransaction {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(4);
+		s.append(&self.tx.clone().sign_for_private(self.chain_id).transaction);
+		s.append(&self.shielded_sighash);
+		s.begin_list(self.alphas.len());
+		for alpha in self.alphas.iter() {
+			s.append(&encode_fs(alpha));
+		}
+		s.append(&encode_fs(&self.bsk));
+	}
+}
+
+impl Decodable for UnsignedTransaction {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != 4 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+		let tx: UnverifiedTransaction = rlp.val_at(0)?;
+		if !tx.is_unsigned() || !tx.action.is_input_private() {
+			return Err(DecoderError::Custom("not an unsigned private transaction"));
+		}
+		let chain_id = tx.chain_id().ok_or(DecoderError::Custom("missing chain id"))?;
+		let mut alphas = Vec::new();
+		for alpha in rlp.at(2)?.iter() {
+			alphas.push(decode_fs(&alpha.as_val::<Vec<u8>>()?)?);
+		}
+		match tx.private {
+			Some(ref private) if private.spends.len() == alphas.len() => (),
+			_ => return Err(DecoderError::Custom("one randomizer per spend expected")),
+		}
+
+		Ok(UnsignedTransaction {
+			tx: tx.unsigned,
+			chain_id,
+			shielded_sighash: rlp.val_at(1)?,
+			alphas,
+			bsk: decode_fs(&rlp.val_at::<Vec<u8>>(3)?)?,
+		})
+	}
+}
+
+impl UnsignedTransaction {
+	/// Signs the spends with the spend authorizing key `ask` of the spent notes,
+	/// and the value balance of the transaction.
+	pub fn sign(&self, ask: &Fs) -> Result<Transaction, Error> {
+		self.sign_spends(&vec![*ask; self.alphas.len()])
+	}
+
+	/// Signs every spend with its own spend authorizing key in `asks`.
+	pub(crate) fn sign_spends(&self, asks: &[Fs]) -> Result<Transaction, Error> {
+		let mut tx = self.tx.clone();
+		let sighash: [u8; 32] = tx.sighash(Some(self.chain_id), self.shielded_sighash).into();
+		{
+			let private = tx.private.as_mut().ok_or(Error(ErrorKind::InvalidUnsignedTransaction))?;
+			if private.spends.len() != self.alphas.len() || asks.len() != self.alphas.len() {
+				return Err(Error(ErrorKind::InvalidUnsignedTransaction));
+			}
+			for ((spend, alpha), ask) in private.spends.iter_mut().zip(self.alphas.iter()).zip(asks) {
+				// The spend is authorized by the key only if it randomizes to the key of the spend.
+				let rsk = PrivateKey(*ask).randomize(*alpha);
+				let rk = PublicKey::from_private(&rsk, FixedGenerators::SpendingKeyGenerator, &JUBJUB);
+				if rk.0 != spend.rk.0 {
+					return Err(Error(ErrorKind::WrongSpendingKey));
+				}
+				spend.spend_auth_sig = Some(spend_sig(PrivateKey(*ask), *alpha, &sighash, &JUBJUB));
+			}
+
+			let ctx = SaplingProvingContext::from_value_commitments(
+				self.bsk,
+				private.spends.iter().map(|spend| &spend.cv),
+				private.outputs.iter().map(|output| &output.cv),
+				&JUBJUB,
+			);
+			let binding_sig = ctx
+				.binding_sig(private.balancing_value, &sighash, &JUBJUB)
+				.map_err(|_| Error(ErrorKind::BindingSig))?;
+			binding_sig
+				.write(&mut private.binding_sig[..])
+				.expect("binding signature is 64 bytes; qed");
+		}
+		Ok(tx)
+	}
+
+	/// The shielded outputs of the transaction recovered with the outgoing viewing key
+	/// of the spent notes: their recipients, values and memos, or None if not sent with the key.
+	pub fn recover_outputs(
+		&self,
+		ovk: &SaplingOutgoingViewingKey,
+	) -> Vec<Option<(SaplingPaymentAddress, u64, Memo)>> {
+		self.tx
+			.v_shielded_output()
+			.iter()
+			.map(|output| {
+				let epk = output.ephemeral_key.as_prime_order(&JUBJUB)?;
+				try_sapling_output_recovery(
+					ovk,
+					&output.cv,
+					&output.cmu,
+					&epk,
+					&output.enc_ciphertext,
+					&output.out_ciphertext,
+				)
+				.map(|(note, address, memo)| (address, note.value, memo))
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::sendmany::SpendKeys;
+	use crate::transaction_builder::TransactionBuilder;
+	use crate::wallet_types::{SaplingExtendedFullViewingKey, SaplingExtendedSpendingKey};
+	use ethcore_blockchain::zkp::{OUTPUT_VK, SPEND_VK};
+
+	use bellman::groth16::Proof;
+	use ethereum_types::U256;
+	use ff::PrimeField;
+	use pairing::bls12_381::Bls12;
+	use rand::{OsRng, Rand};
+	use sapling_crypto::redjubjub::Signature;
+	use zcash_primitives::{
+		merkle_tree::{CommitmentTree, IncrementalWitness},
+		sapling::Node,
+	};
+	use zcash_proofs::sapling::SaplingVerificationContext;
+
+	use common_types::transaction::CONVERSION_FACTOR;
+
+	const TEST_CHAIN_ID: u64 = 2;
+
+	#[test]
+	fn sign_unsigned_transaction() {
+		let mut rng = OsRng::new().expect("should be able to construct RNG");
+		let extsk = SaplingExtendedSpendingKey::master(&[]);
+		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		let to = extfvk.default_address().unwrap().1;
+		// The keys of a watch-only wallet.
+		let keys = SpendKeys { ask: None, ..SpendKeys::from(&extsk.expsk) };
+
+		let mut builder = TransactionBuilder::new(0.into(), U256::from(0), U256::from(21000), vec![], TEST_CHAIN_ID);
+		builder.set_shielded_sighash(true);
+		let note = to.create_note(300, Fs::rand(&mut rng), &JUBJUB).unwrap();
+		let mut tree = CommitmentTree::new();
+		tree.append(Node::new(note.cm(&JUBJUB).into_repr())).unwrap();
+		let inc_tree = IncrementalWitness::from_tree(&tree);
+		let witness = inc_tree.path().unwrap();
+		assert!(builder
+			.add_sapling_spend_with_keys(keys, to.diversifier, note, inc_tree.root().into(), witness)
+			.is_ok());
+		assert!(builder
+			.add_sapling_output(extfvk.fvk.ovk, to.clone(), &(U256::from(200) * CONVERSION_FACTOR), "")
+			.is_ok());
+		assert_eq!(builder.build(), Err(Error(ErrorKind::NoPrivateKey)));
+
+		let unsigned = builder.build_unsigned().unwrap();
+		let decoded: UnsignedTransaction = ::rlp::decode(&::rlp::encode(&unsigned)).unwrap();
+		assert_eq!(decoded, unsigned);
+		let outputs = unsigned.recover_outputs(&extfvk.fvk.ovk);
+		assert_eq!(outputs.len(), 2);
+		assert_eq!(outputs[0].as_ref().map(|output| (output.0.clone(), output.1)), Some((to, 200)));
+
+		let wrong_key = SaplingExtendedSpendingKey::master(&[1]);
+		assert_eq!(unsigned.sign(&wrong_key.expsk.ask), Err(Error(ErrorKind::WrongSpendingKey)));
+		let tx = unsigned.sign(&extsk.expsk.ask).unwrap();
+
+		let sighash = tx.sighash(Some(TEST_CHAIN_ID), true).into();
+		let mut ctx = SaplingVerificationContext::new();
+		let spend_desc = tx.v_shielded_spend()[0].clone();
+		assert!(ctx.check_spend(
+			spend_desc.cv,
+			spend_desc.anchor,
+			&spend_desc.nullifier,
+			spend_desc.rk,
+			&sighash,
+			spend_desc.spend_auth_sig.unwrap(),
+			Proof::<Bls12>::read(&spend_desc.zkproof[..]).unwrap(),
+			&SPEND_VK,
+			&JUBJUB
+		));
+		// The binding signature is checked against the value commitments of the outputs too.
+		for output_desc in tx.v_shielded_output() {
+			assert!(ctx.check_output(
+				output_desc.cv,
+				output_desc.cmu,
+				output_desc.ephemeral_key,
+				Proof::<Bls12>::read(&output_desc.zkproof[..]).unwrap(),
+				&OUTPUT_VK,
+				&JUBJUB,
+			));
+		}
+		assert!(ctx.final_check(
+			tx.balancing_value(),
+			&sighash,
+			Signature::read(&tx.binding_sig()[..]).unwrap(),
+			&JUBJUB,
+		));
+	}
+}
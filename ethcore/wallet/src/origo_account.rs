@@ -14,8 +14,8 @@
 // You should have received a copy of the GNU General Public License
 // along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::wallet::origo_key_file::OrigoKeyFile;
-use crate::wallet::wallet_types::*;
+use crate::origo_key_file::OrigoKeyFile;
+use crate::wallet_types::*;
 use ethstore::ethkey::{Password, Secret};
 use ethstore::Crypto;
 //use ethstore::crypto;
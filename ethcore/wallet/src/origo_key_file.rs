@@ -14,9 +14,9 @@
 // You should have received a copy of the GNU General Public License
 // along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::wallet::wallet_types::*;
+use crate::wallet_types::*;
 
-use crate::wallet::origo_account::OrigoAccount;
+use crate::origo_account::OrigoAccount;
 use ethstore::ethkey::Password;
 use ethstore::JsonCrypto;
 use serde::de::{DeserializeOwned, Error, MapAccess, Visitor};
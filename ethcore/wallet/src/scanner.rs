@@ -0,0 +1,177 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Parallel trial decryption of the shielded outputs of blocks.
+//!
+//! The outputs of a batch of blocks are all decrypted at once, across the cores, before
+//! the wallet is updated with the notes found. The viewing keys tried on an output share
+//! the multiples of its ephemeral key, computed once, for their key agreements.
+
+use std::collections::HashMap;
+
+use common_types::encoded;
+use common_types::transaction::LocalizedTransaction;
+use rayon::prelude::*;
+use zcash_primitives::note_encryption::{try_sapling_note_decryption_batch, Memo};
+use zcash_primitives::JUBJUB;
+
+use crate::wallet_types::{
+	SaplingFullViewingKey, SaplingIncomingViewingKey, SaplingNote, SaplingOutputDescription,
+	SaplingPaymentAddress,
+};
+
+/// The viewing keys the blocks are scanned for, with the full viewing key of their account
+/// if the wallet has it.
+pub type ViewingKeys = HashMap<SaplingIncomingViewingKey, Option<SaplingFullViewingKey>>;
+
+/// A shielded output decrypted by one of the viewing keys.
+#[derive(Clone)]
+pub struct DecryptedOutput {
+	/// The incoming viewing key the output was decrypted with.
+	pub ivk: SaplingIncomingViewingKey,
+	pub note: SaplingNote,
+	pub address: SaplingPaymentAddress,
+	pub memo: Memo,
+}
+
+/// A block whose shielded outputs were trial decrypted.
+pub struct DecryptedBlock {
+	pub block: encoded::Block,
+	/// The private transactions of the block.
+	pub txs: Vec<LocalizedTransaction>,
+	/// The outputs decrypted, by index of their transaction in `txs` and of the output in it.
+	pub outputs: HashMap<(usize, usize), DecryptedOutput>,
+}
+
+/// Trial decrypt the outputs with the incoming viewing keys, in parallel. Returns, for each
+/// output, the index of the key which decrypted it along with the note.
+pub fn trial_decrypt(
+	outputs: &[SaplingOutputDescription],
+	ivks: &[SaplingIncomingViewingKey],
+) -> Vec<Option<(usize, DecryptedOutput)>> {
+	outputs
+		.par_iter()
+		.map(|output| {
+			let epk = output.ephemeral_key.as_prime_order(&JUBJUB)?;
+			try_sapling_note_decryption_batch(ivks, &epk, &output.cmu, &output.enc_ciphertext)
+				.map(|(index, note, address, memo)| {
+					(index, DecryptedOutput { ivk: ivks[index], note, address, memo })
+				})
+		})
+		.collect()
+}
+
+/// Trial decrypt the shielded outputs of all the blocks with the viewing keys at once.
+pub fn decrypt_blocks(blocks: Vec<encoded::Block>, viewing_keys: &ViewingKeys) -> Vec<DecryptedBlock> {
+	let mut decrypted: Vec<DecryptedBlock> = blocks
+		.into_iter()
+		.map(|block| DecryptedBlock {
+			txs: block.view().localized_private_txs(),
+			block,
+			outputs: HashMap::new(),
+		})
+		.collect();
+	if viewing_keys.is_empty() {
+		return decrypted;
+	}
+
+	let mut positions = Vec::new();
+	let mut outputs = Vec::new();
+	for (block_index, block) in decrypted.iter().enumerate() {
+		for (tx_index, tx) in block.txs.iter().enumerate() {
+			for (output_index, output) in tx.v_shielded_output().into_iter().enumerate() {
+				positions.push((block_index, tx_index, output_index));
+				outputs.push(output);
+			}
+		}
+	}
+
+	let ivks: Vec<SaplingIncomingViewingKey> = viewing_keys.keys().cloned().collect();
+	let results = trial_decrypt(&outputs, &ivks);
+	for ((block_index, tx_index, output_index), result) in positions.into_iter().zip(results) {
+		if let Some((_, output)) = result {
+			decrypted[block_index].outputs.insert((tx_index, output_index), output);
+		}
+	}
+	decrypted
+}
+
+/// Trial decrypt the shielded outputs of the block with the viewing keys.
+pub fn decrypt_block(block: &encoded::Block, viewing_keys: &ViewingKeys) -> DecryptedBlock {
+	decrypt_blocks(vec![block.clone()], viewing_keys)
+		.pop()
+		.expect("one block is decrypted; qed")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use pairing::bls12_381::Bls12;
+	use rand::{thread_rng, Rand};
+	use sapling_crypto::jubjub::{edwards, fs::Fs, Unknown};
+	use sapling_crypto::primitives::{Diversifier, PaymentAddress};
+	use zcash_primitives::keys::OutgoingViewingKey;
+	use zcash_primitives::note_encryption::SaplingNoteEncryption;
+	use zcash_primitives::transaction::components::GROTH_PROOF_SIZE;
+
+	fn output_to(ivk: &Fs, value: u64) -> SaplingOutputDescription {
+		let diversifier = Diversifier([0; 11]);
+		let g_d = diversifier.g_d::<Bls12>(&JUBJUB).unwrap();
+		let address = PaymentAddress { diversifier, pk_d: g_d.mul(*ivk, &JUBJUB) };
+		let note = address.create_note(value, Fs::rand(&mut thread_rng()), &JUBJUB).unwrap();
+		let cmu = note.cm(&JUBJUB);
+
+		let encryption = SaplingNoteEncryption::new(OutgoingViewingKey([0; 32]), note, address, Memo::default());
+		let cv: edwards::Point<Bls12, Unknown> = encryption.epk().into();
+		SaplingOutputDescription {
+			cv,
+			cmu,
+			ephemeral_key: encryption.epk().into(),
+			enc_ciphertext: encryption.encrypt_note_plaintext(),
+			out_ciphertext: encryption.encrypt_outgoing_plaintext(&cv, &cmu),
+			zkproof: [0; GROTH_PROOF_SIZE],
+		}
+	}
+
+	#[test]
+	fn trial_decrypt_finds_the_outputs_of_the_keys() {
+		let mut rng = thread_rng();
+		let ivks: Vec<Fs> = (0..4).map(|_| Fs::rand(&mut rng)).collect();
+		let outputs = vec![
+			output_to(&Fs::rand(&mut rng), 1),
+			output_to(&ivks[2], 2),
+			output_to(&Fs::rand(&mut rng), 3),
+			output_to(&ivks[0], 4),
+		];
+
+		let decrypted = trial_decrypt(&outputs, &ivks);
+		assert_eq!(decrypted.len(), outputs.len());
+		assert!(decrypted[0].is_none());
+		assert!(decrypted[2].is_none());
+
+		let (index, output) = decrypted[1].clone().unwrap();
+		assert_eq!(index, 2);
+		assert_eq!(output.ivk, ivks[2]);
+		assert_eq!(output.note.value, 2);
+
+		let (index, output) = decrypted[3].clone().unwrap();
+		assert_eq!(index, 0);
+		assert_eq!(output.note.value, 4);
+
+		assert!(trial_decrypt(&outputs, &[]).iter().all(Option::is_none));
+	}
+}
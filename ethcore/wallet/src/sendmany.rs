@@ -0,0 +1,665 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+use pairing::bls12_381::{Bls12, Fr, FrRepr};
+
+use ff::{PrimeField, PrimeFieldRepr};
+
+use ethereum_types::U256;
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+use sapling_crypto::{
+	jubjub::fs::{Fs, FsRepr},
+	primitives::{Diversifier, Note, PaymentAddress, ProofGenerationKey},
+};
+use std::cmp::Eq;
+use std::collections::LinkedList;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use zcash_primitives::{
+	merkle_tree::{CommitmentTree, CommitmentTreeWitness},
+	note_encryption::Memo,
+	sapling::Node,
+};
+
+use crate::coin_selection::{select_notes, CoinSelection};
+use crate::key_store::{
+	decode_destination, decode_payment_address, encode_payment_address, KeyStore, TxDestination,
+};
+use crate::offline::UnsignedTransaction;
+use crate::transaction_builder::{Error, ErrorKind, TransactionBuilder};
+use crate::wallet::Wallet;
+use crate::wallet_types::{
+	SaplingCommitmentTreeWitness, SaplingExpandedSpendingKey, SaplingExtendedSpendingKey,
+	SaplingIncomingViewingKey, SaplingNote, SaplingOutgoingViewingKey, SaplingPaymentAddress,
+	SaplingWitness, TxHash,
+};
+use common_types::transaction::{Transaction as CommonTransaction, CONVERSION_FACTOR};
+use parking_lot::RwLock;
+use zcash_primitives::JUBJUB;
+
+/// The keys spending the notes of an address. A watch-only wallet holding the proof
+/// generation key proves the spends, which are signed offline with the spend authorizing key.
+#[derive(Clone)]
+pub struct SpendKeys {
+	pub proof_generation_key: ProofGenerationKey<Bls12>,
+	pub ovk: SaplingOutgoingViewingKey,
+	/// The spend authorizing key, unknown to the watch-only wallets.
+	pub ask: Option<Fs>,
+}
+
+impl<'a> From<&'a SaplingExpandedSpendingKey> for SpendKeys {
+	fn from(expsk: &'a SaplingExpandedSpendingKey) -> Self {
+		SpendKeys {
+			proof_generation_key: expsk.proof_generation_key(&JUBJUB),
+			ovk: expsk.ovk,
+			ask: Some(expsk.ask),
+		}
+	}
+}
+
+/// SpendDescriptionInfo contains all the information required to generate a SpendDescription.
+pub struct SpendDescriptionInfo {
+	pub keys: SpendKeys,
+	pub diversifier: Diversifier,
+	pub note: SaplingNote,
+	pub alpha: Fs,
+	/// Anchor refers to the output Sapling treestate of a previous block.
+	pub anchor: Fr,
+	pub witness: CommitmentTreeWitness<Node>,
+}
+
+/// OutputDescriptionInfo contains all the required information to create a OutputDescription.
+pub struct OutputDescriptionInfo {
+	pub ovk: SaplingOutgoingViewingKey,
+	pub to: PaymentAddress<Bls12>,
+	pub note: SaplingNote,
+	/// Memo represents a 512-byte memo field associated with note.
+	/// It is used by agreement between the sender and recipient of the note.
+	pub memo: Memo,
+}
+
+#[derive(Clone)]
+pub struct SaplingNoteData {
+	// The address for the note.
+	pub address: SaplingPaymentAddress,
+	// The decryption note data.
+	pub note: SaplingNote,
+	// The decryption note memo.
+	pub memo: Memo,
+	// The note witness for the note.
+	pub witnesses: LinkedList<SaplingWitness>,
+	// The block number for the block the note was mined in.
+	pub createHeight: u64,
+	// The current block number when create the transaction using the note,
+	// the note is locked until the transaction is mined or the lock expires.
+	pub spendHeight: Option<u64>,
+	// The block number for the block to confirm the transaction using the note.
+	pub confirmHeight: Option<u64>,
+	pub ivk: SaplingIncomingViewingKey,
+	pub nullifier: Option<U256>,
+	// The hash for the transaction to create the note.
+	pub hash: TxHash,
+	// The note index in the transaction's output.
+	pub index: usize,
+	// Whether the note pays a block reward, it can't be spent before COINBASE_MATURITY confirmations.
+	pub coinbase: bool,
+}
+
+impl SaplingNoteData {
+	pub fn new(
+		address: SaplingPaymentAddress,
+		note: SaplingNote,
+		memo: Memo,
+		block_number: u64,
+		ivk: SaplingIncomingViewingKey,
+		nullifier: U256,
+		hash: TxHash,
+		number: usize,
+	) -> Self {
+		SaplingNoteData {
+			address,
+			note,
+			memo,
+			witnesses: LinkedList::new(),
+			createHeight: block_number,
+			spendHeight: None,
+			confirmHeight: None,
+			ivk: ivk,
+			nullifier: Some(nullifier),
+			hash,
+			index: number,
+			coinbase: false,
+		}
+	}
+	pub fn push_front(&mut self, witness: SaplingWitness) {
+		self.witnesses.push_front(witness);
+	}
+
+	pub fn pop_back(&mut self) {
+		self.witnesses.pop_back();
+	}
+
+	pub fn pop_front(&mut self) {
+		self.witnesses.pop_front();
+	}
+
+	pub fn front(&self) -> Option<SaplingWitness> {
+		self.witnesses.front().map(|head| head.clone())
+	}
+}
+
+pub(crate) fn encode_fs(fs: &Fs) -> Vec<u8> {
+	let mut bytes = vec![];
+	fs.into_repr()
+		.write_le(&mut bytes)
+		.expect("writing to a Vec never fails; qed");
+	bytes
+}
+
+pub(crate) fn decode_fs(bytes: &[u8]) -> Result<Fs, DecoderError> {
+	let mut repr = FsRepr::default();
+	repr.read_le(bytes)
+		.map_err(|_| DecoderError::Custom("invalid scalar length"))?;
+	Fs::from_repr(repr).map_err(|_| DecoderError::Custom("invalid scalar"))
+}
+
+fn append_optional<T: Encodable>(s: &mut RlpStream, value: &Option<T>) {
+	match *value {
+		Some(ref v) => {
+			s.begin_list(1);
+			s.append(v);
+		}
+		None => {
+			s.begin_list(0);
+		}
+	}
+}
+
+fn decode_optional<T: Decodable>(rlp: &Rlp) -> Result<Option<T>, DecoderError> {
+	match rlp.item_count()? {
+		0 => Ok(None),
+		1 => rlp.val_at(0).map(Some),
+		_ => Err(DecoderError::RlpIncorrectListLen),
+	}
+}
+
+impl Encodable for SaplingNoteData {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(13);
+		s.append(&encode_payment_address(&self.address));
+		s.append(&self.note.value);
+		s.append(&encode_fs(&self.note.r));
+		s.append(&self.memo.as_bytes().to_vec());
+		s.begin_list(self.witnesses.len());
+		for witness in self.witnesses.iter() {
+			let mut bytes = vec![];
+			witness
+				.write(&mut bytes)
+				.expect("writing to a Vec never fails; qed");
+			s.append(&bytes);
+		}
+		s.append(&self.createHeight);
+		append_optional(s, &self.spendHeight);
+		append_optional(s, &self.confirmHeight);
+		s.append(&encode_fs(&self.ivk));
+		append_optional(s, &self.nullifier);
+		s.append(&self.hash);
+		s.append(&(self.index as u64));
+		s.append(&self.coinbase);
+	}
+}
+
+impl Decodable for SaplingNoteData {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		// Notes stored before coinbase notes were introduced have no coinbase flag.
+		let coinbase = match rlp.item_count()? {
+			12 => false,
+			13 => rlp.val_at(12)?,
+			_ => return Err(DecoderError::RlpIncorrectListLen),
+		};
+
+		let address: String = rlp.val_at(0)?;
+		let address = decode_payment_address(&address)
+			.ok_or(DecoderError::Custom("invalid payment address"))?;
+		let value: u64 = rlp.val_at(1)?;
+		let r = decode_fs(&rlp.val_at::<Vec<u8>>(2)?)?;
+		let note = address
+			.create_note(value, r, &JUBJUB)
+			.ok_or(DecoderError::Custom("invalid note diversifier"))?;
+		let memo = Memo::from_bytes(&rlp.val_at::<Vec<u8>>(3)?)
+			.ok_or(DecoderError::Custom("invalid memo length"))?;
+
+		let mut witnesses = LinkedList::new();
+		for witness in rlp.at(4)?.iter() {
+			let bytes: Vec<u8> = witness.as_val()?;
+			let witness = SaplingWitness::read(&bytes[..])
+				.map_err(|_| DecoderError::Custom("invalid witness"))?;
+			witnesses.push_back(witness);
+		}
+
+		Ok(SaplingNoteData {
+			address,
+			note,
+			memo,
+			witnesses,
+			createHeight: rlp.val_at(5)?,
+			spendHeight: decode_optional(&rlp.at(6)?)?,
+			confirmHeight: decode_optional(&rlp.at(7)?)?,
+			ivk: decode_fs(&rlp.val_at::<Vec<u8>>(8)?)?,
+			nullifier: decode_optional(&rlp.at(9)?)?,
+			hash: rlp.val_at(10)?,
+			index: rlp.val_at::<u64>(11)? as usize,
+			coinbase,
+		})
+	}
+}
+
+/// A note sent by a wallet account, recovered with the outgoing viewing key of the account.
+#[derive(Clone)]
+pub struct SentNoteData {
+	// The recipient address of the note.
+	pub address: SaplingPaymentAddress,
+	pub value: u64,
+	pub memo: Memo,
+	// The incoming viewing key of the sending account.
+	pub ivk: SaplingIncomingViewingKey,
+	// The block number for the block including the transaction.
+	pub createHeight: u64,
+	// The hash for the transaction to send the note.
+	pub hash: TxHash,
+	// The note index in the transaction's output.
+	pub index: usize,
+}
+
+impl Encodable for SentNoteData {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(7);
+		s.append(&encode_payment_address(&self.address));
+		s.append(&self.value);
+		s.append(&self.memo.as_bytes().to_vec());
+		s.append(&encode_fs(&self.ivk));
+		s.append(&self.createHeight);
+		s.append(&self.hash);
+		s.append(&(self.index as u64));
+	}
+}
+
+impl Decodable for SentNoteData {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		if rlp.item_count()? != 7 {
+			return Err(DecoderError::RlpIncorrectListLen);
+		}
+
+		let address: String = rlp.val_at(0)?;
+		let address = decode_payment_address(&address)
+			.ok_or(DecoderError::Custom("invalid payment address"))?;
+		let memo = Memo::from_bytes(&rlp.val_at::<Vec<u8>>(2)?)
+			.ok_or(DecoderError::Custom("invalid memo length"))?;
+
+		Ok(SentNoteData {
+			address,
+			value: rlp.val_at(1)?,
+			memo,
+			ivk: decode_fs(&rlp.val_at::<Vec<u8>>(3)?)?,
+			createHeight: rlp.val_at(4)?,
+			hash: rlp.val_at(5)?,
+			index: rlp.val_at::<u64>(6)? as usize,
+		})
+	}
+}
+
+#[derive(Clone)]
+pub struct SaplingNoteEntry {
+	pub hash: TxHash,
+	pub index: usize,
+	/// PaymentAddress not sure is Bls12
+	pub address: PaymentAddress<Bls12>,
+	pub witness: SaplingWitness,
+	pub note: Note<Bls12>,
+	pub memo: Memo,
+	/// Number of blocks mined on top of the note, including its own block.
+	pub confirmation: i64,
+	/// False if the note was received by an imported viewing key or is an immature coinbase.
+	pub spendable: bool,
+	/// True if the note is spent by a transaction not mined yet.
+	pub locked: bool,
+}
+
+pub type CAmount = u64;
+
+pub type SendManyRecipient = (String, U256, String);
+
+pub struct SendMany {
+	pub main_wallet: Arc<RwLock<Wallet>>,
+}
+
+pub struct SendManyInputs {
+	/// Could be private or public address.
+	pub from: String,
+	// The amount of value the comes from public account.
+	pub value_from_public: U256,
+	/// Private Recipients.
+	pub shield_to: Vec<SendManyRecipient>,
+	/// Public recipients. The first one is called with `data`, the others only receive value.
+	pub to: Vec<SendManyRecipient>,
+	pub min_conf: u32,
+	/// Strategy choosing the spent notes.
+	pub coin_selection: CoinSelection,
+	/// Maximum number of notes spent by the transaction.
+	pub max_spends: usize,
+	pub gas_price: U256,
+	pub nonce: U256,
+	pub gas: U256,
+	pub data: Vec<u8>,
+	pub chain_id: u64,
+	/// Whether the transaction signs the shielded signature hash.
+	pub shielded_sighash: bool,
+}
+
+pub struct MergeToAddressInputs {
+	/// Private addresses whose notes are merged.
+	pub from: Vec<String>,
+	/// Private address receiving the merged notes.
+	pub to: String,
+	pub memo: String,
+	pub min_conf: u32,
+	/// Maximum number of notes spent by each transaction.
+	pub max_spends: usize,
+	pub gas_price: U256,
+	pub gas: U256,
+	pub chain_id: u64,
+	/// Whether the transactions sign the shielded signature hash.
+	pub shielded_sighash: bool,
+}
+
+impl SendMany {
+	pub fn new(wallet: Arc<RwLock<Wallet>>) -> SendMany {
+		SendMany {
+			main_wallet: wallet,
+		}
+	}
+
+	pub fn pre_send_many(
+		&mut self,
+		inputs: &SendManyInputs,
+		pass: String,
+	) -> Result<CommonTransaction, Error> {
+		let (payment_address, spending_key_option) = self
+			.main_wallet
+			.read()
+			.unwrap()
+			.key_store
+			.decode_z_destination(&inputs.from, pass);
+
+		//TODO
+		let next_block_height = 0;
+		{
+			let mut builder = TransactionBuilder::new(
+				inputs.gas_price,
+				inputs.nonce,
+				inputs.gas,
+				inputs.data.clone(),
+				inputs.chain_id,
+			);
+			builder.set_shielded_sighash(inputs.shielded_sighash);
+			let expsk: Option<SaplingExpandedSpendingKey> = spending_key_option
+				.and_then(|spending_key: SaplingExtendedSpendingKey| Some(spending_key.expsk));
+
+			if (expsk.is_none()) {
+				return Err(Error(ErrorKind::NoPrivateKey));
+			}
+
+			let mut sendmany_operation = SendManyOperation::new(
+				inputs.value_from_public,
+				builder,
+				self.main_wallet.clone(),
+				inputs.from.clone(),
+				inputs.to.clone(),
+				inputs.shield_to.clone(),
+				inputs.min_conf,
+				inputs.coin_selection,
+				inputs.max_spends,
+				SpendKeys::from(&expsk.unwrap()),
+			);
+
+			sendmany_operation.main_impl(inputs.gas, inputs.gas_price)
+		}
+	}
+
+	/// Build the transaction spending the notes of a watch-only address imported with its
+	/// proving key. Its spends are proven but not signed, they are signed offline
+	/// with the spend authorizing key of the address.
+	pub fn create_unsigned(&mut self, inputs: &SendManyInputs) -> Result<UnsignedTransaction, Error> {
+		let keys = decode_payment_address(&inputs.from)
+			.and_then(|address| self.main_wallet.read().key_store.get_spend_keys(&address))
+			.ok_or(Error(ErrorKind::NoProvingKey))?;
+		let mut builder = TransactionBuilder::new(
+			inputs.gas_price,
+			inputs.nonce,
+			inputs.gas,
+			inputs.data.clone(),
+			inputs.chain_id,
+		);
+		builder.set_shielded_sighash(inputs.shielded_sighash);
+		let mut sendmany_operation = SendManyOperation::new(
+			U256::from(0),
+			builder,
+			self.main_wallet.clone(),
+			inputs.from.clone(),
+			inputs.to.clone(),
+			inputs.shield_to.clone(),
+			inputs.min_conf,
+			inputs.coin_selection,
+			inputs.max_spends,
+			keys,
+		);
+		sendmany_operation.unsigned_impl(inputs.gas, inputs.gas_price)
+	}
+
+	/// Build the transactions merging the notes of the `from` addresses into notes sent to
+	/// the `to` address, each spending at most `max_spends` notes of one address. Every
+	/// transaction creates its own note: an address with more than `max_spends` notes is
+	/// merged into several notes, to merge again once the transactions are mined.
+	/// The smallest notes are merged first, each transaction paying its own fee.
+	pub fn merge_to_address(
+		&mut self,
+		inputs: &MergeToAddressInputs,
+		pass: String,
+	) -> Result<Vec<CommonTransaction>, Error> {
+		let to = decode_payment_address(&inputs.to).ok_or(Error(ErrorKind::InvalidAddress))?;
+		let fee = inputs.gas * inputs.gas_price;
+		let mut txs = Vec::new();
+		for from in inputs.from.iter() {
+			let keys = SpendKeys::from(&self
+				.main_wallet
+				.read()
+				.unwrap()
+				.key_store
+				.decode_z_destination(from, pass.clone())
+				.1
+				.ok_or(Error(ErrorKind::NoPrivateKey))?
+				.expsk);
+			let mut entries = self.main_wallet.read().get_filtered_address_notes(
+				from,
+				inputs.min_conf,
+				u32::max_value(),
+				true,
+			);
+			entries.sort_by_key(|entry| entry.note.value);
+
+			for chunk in entries.chunks(inputs.max_spends) {
+				let value = chunk.iter().fold(U256::from(0), |sum, entry| {
+					sum + U256::from(entry.note.value) * CONVERSION_FACTOR
+				});
+				// A single note is already merged.
+				if chunk.len() < 2 || value <= fee {
+					continue;
+				}
+				let mut builder = TransactionBuilder::new(
+					inputs.gas_price,
+					U256::from(0),
+					inputs.gas,
+					vec![],
+					inputs.chain_id,
+				);
+				builder.set_shielded_sighash(inputs.shielded_sighash);
+				for entry in chunk.iter() {
+					add_note_spend(&mut builder, &keys, entry)?;
+				}
+				builder.add_sapling_output(keys.ovk.clone(), to.clone(), &(value - fee), &inputs.memo)?;
+				let tx = builder.build()?;
+				self.main_wallet.write().lock_spent_notes(&tx);
+				txs.push(tx);
+			}
+		}
+		if txs.is_empty() {
+			return Err(Error(ErrorKind::InsufficientBalance(String::from("no notes to merge"))));
+		}
+		Ok(txs)
+	}
+}
+
+fn add_note_spend(
+	builder: &mut TransactionBuilder,
+	keys: &SpendKeys,
+	entry: &SaplingNoteEntry,
+) -> Result<(), Error> {
+	let anchor = entry.witness.root();
+	let witness = entry.witness.path().ok_or(Error(ErrorKind::InvalidWitness))?;
+	builder.add_sapling_spend_with_keys(
+		keys.clone(),
+		entry.address.diversifier.clone(),
+		entry.note.clone(),
+		Fr::from(anchor),
+		witness,
+	)
+}
+
+pub struct SendManyOperation {
+	/// The amount of value the comes from public account.
+	value_from_public: U256,
+	t_outputs_: Vec<SendManyRecipient>,
+	z_outputs_: Vec<SendManyRecipient>,
+	transaction_builder_: TransactionBuilder,
+	wallet: Arc<RwLock<Wallet>>,
+	spendingkey_: SpendKeys,
+	mindepth: u32,
+	coin_selection: CoinSelection,
+	max_spends: usize,
+	fromaddress_: String,
+}
+
+impl SendManyOperation {
+	fn new(
+		value_from_public: U256,
+		builder: TransactionBuilder,
+		wallet: Arc<RwLock<Wallet>>,
+		fromaddress: String,
+		t_outputs: Vec<SendManyRecipient>,
+		z_outputs: Vec<SendManyRecipient>,
+		min_depth: u32,
+		coin_selection: CoinSelection,
+		max_spends: usize,
+		spendingkey_: SpendKeys,
+	) -> Self {
+		SendManyOperation {
+			value_from_public,
+			transaction_builder_: builder,
+			wallet,
+			fromaddress_: fromaddress,
+			t_outputs_: t_outputs,
+			z_outputs_: z_outputs,
+			mindepth: min_depth,
+			coin_selection,
+			max_spends,
+			spendingkey_: spendingkey_,
+		}
+	}
+
+	fn find_unspent_notes(&mut self, target_amout: U256) -> Result<Vec<SaplingNoteEntry>, Error> {
+		let wallet = self.wallet.read();
+		let sapling_entries =
+			wallet.get_filtered_address_notes(&self.fromaddress_, self.mindepth, u32::max_value(), true);
+		// The target in note values, rounded up.
+		let target = (target_amout + CONVERSION_FACTOR - U256::from(1)) / CONVERSION_FACTOR;
+		if target > U256::from(u64::max_value()) {
+			return Err(Error(ErrorKind::InvalidAmount(target_amout.to_string())));
+		}
+		let values = sapling_entries.iter().map(|entry| entry.note.value).collect::<Vec<_>>();
+		let selected = select_notes(&values, target.low_u64(), self.coin_selection, self.max_spends)?;
+		Ok(selected.into_iter().map(|index| sapling_entries[index].clone()).collect())
+	}
+
+	pub fn main_impl(&mut self, gas: U256, gas_price: U256) -> Result<CommonTransaction, Error> {
+		self.add_inputs_and_outputs(gas, gas_price)?;
+		let tx = self.transaction_builder_.build()?;
+		self.wallet.write().lock_spent_notes(&tx);
+		Ok(tx)
+	}
+
+	/// Build the transaction without signing its spends, locking its notes like `main_impl`.
+	pub fn unsigned_impl(&mut self, gas: U256, gas_price: U256) -> Result<UnsignedTransaction, Error> {
+		self.add_inputs_and_outputs(gas, gas_price)?;
+		let unsigned = self.transaction_builder_.build_unsigned()?;
+		self.wallet.write().lock_spent_notes(&unsigned.tx);
+		Ok(unsigned)
+	}
+
+	fn add_inputs_and_outputs(&mut self, gas: U256, gas_price: U256) -> Result<(), Error> {
+		let mut target_amount = self.z_outputs_.iter().fold(U256::from(0), |mut total: U256, recipient| {
+			total += recipient.1;
+			total
+		});
+		target_amount += self.t_outputs_.iter().fold(U256::from(0), |mut total: U256, recipient| {
+			total += recipient.1;
+			total
+		});
+
+		// Add transparent inputs.
+		if !self.value_from_public.is_zero() {
+			self.transaction_builder_
+				.set_public_input(self.value_from_public);
+		} else {
+			// Add sapling spends.
+			let input_sapling_notes = self.find_unspent_notes(target_amount + gas * gas_price)?;
+			for saplingNoteEntry in input_sapling_notes.iter() {
+				add_note_spend(&mut self.transaction_builder_, &self.spendingkey_, saplingNoteEntry)?;
+			}
+		}
+
+		let ovk = self.spendingkey_.ovk;
+		for (address, value, memo) in self.z_outputs_.iter() {
+			let to = decode_payment_address(address);
+			if to.is_some() {
+				self.transaction_builder_.add_sapling_output(
+					ovk.clone(),
+					to.unwrap(),
+					&value,
+					&memo,
+				)?;
+			} else {
+				println!("decode_payment_address failed, address = {0}", address);
+			}
+		}
+
+		// Add transparent outputs, the first one is the recipient of the public call.
+		for (address, amount, _) in self.t_outputs_.iter() {
+			let addr = decode_destination(address).ok_or(Error(ErrorKind::InvalidAddress))?;
+			self.transaction_builder_.add_public_output(addr, &amount)?;
+		}
+		Ok(())
+	}
+}
@@ -0,0 +1,1076 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Wallet syncing with the imported blocks.
+//!
+//! The wallet is no longer updated while the blocks are inserted in the chain. Once the
+//! blocks are committed, `WalletSync` is notified and scans them on a thread of its own:
+//! the blocks the wallet scanned which are no longer canonical are rewound first, then
+//! the canonical blocks are scanned up to the best block. Their outputs are trial decrypted
+//! by batches of blocks, in parallel, before the wallet is locked to add the notes found.
+//!
+//! Rescans of a range of blocks for some of the keys are requested with `WalletSync::rescan`
+//! and run on the same thread, between two syncs.
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use common_types::encoded;
+use ethcore::client::{ChainNotify, Client, NewBlocks};
+use ethcore_blockchain::{BlockChain, BlockProvider};
+use ethereum_types::H256;
+use kvdb::{DBTransaction, KeyValueDB};
+use parking_lot::{Mutex, RwLock};
+use zcash_primitives::merkle_tree::CommitmentTree;
+use zcash_primitives::sapling::Node;
+
+use crate::scanner::{decrypt_blocks, ViewingKeys};
+use crate::wallet::{RescannedNotes, Wallet};
+
+/// Maximum number of blocks whose outputs are trial decrypted at once.
+const SCAN_BATCH_BLOCKS: u64 = 200;
+/// Minimum time between two logs of the progress of the wallet catching up.
+const PROGRESS_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The chain a wallet is synced with.
+pub trait WalletChain: Send + Sync {
+	/// The blockchain the wallet scans.
+	fn chain(&self) -> Arc<BlockChain>;
+
+	/// The database the wallet data is stored in, along with the chain.
+	fn wallet_db(&self) -> Arc<KeyValueDB>;
+}
+
+impl WalletChain for Client {
+	fn chain(&self) -> Arc<BlockChain> {
+		Client::chain(self)
+	}
+
+	fn wallet_db(&self) -> Arc<KeyValueDB> {
+		self.db().key_value().clone()
+	}
+}
+
+/// How far the wallet is synced with the chain.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct WalletSyncStatus {
+	/// The number of the last block scanned by the wallet.
+	pub scanned_block: u64,
+	/// The number of the best block of the chain.
+	pub best_block: u64,
+}
+
+impl WalletSyncStatus {
+	/// The number of blocks the wallet is behind the chain.
+	pub fn lag(&self) -> u64 {
+		self.best_block.saturating_sub(self.scanned_block)
+	}
+
+	/// Whether the wallet has scanned the best block.
+	pub fn is_synced(&self) -> bool {
+		self.lag() == 0
+	}
+}
+
+/// The state of a rescan of the chain.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RescanState {
+	/// Waiting for the sync thread.
+	Queued,
+	/// The blocks are being rescanned.
+	Running,
+	/// The notes of the blocks were rebuilt.
+	Finished,
+	/// Cancelled before the end, the notes found so far were dropped.
+	Cancelled,
+	/// Stopped by an error, the notes found so far were dropped.
+	Failed,
+}
+
+impl RescanState {
+	/// Whether the rescan is over.
+	pub fn is_done(&self) -> bool {
+		match *self {
+			RescanState::Queued | RescanState::Running => false,
+			RescanState::Finished | RescanState::Cancelled | RescanState::Failed => true,
+		}
+	}
+}
+
+impl fmt::Display for RescanState {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let state = match *self {
+			RescanState::Queued => "queued",
+			RescanState::Running => "running",
+			RescanState::Finished => "finished",
+			RescanState::Cancelled => "cancelled",
+			RescanState::Failed => "failed",
+		};
+		write!(f, "{}", state)
+	}
+}
+
+/// The progress of a rescan of the chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RescanStatus {
+	/// The first block rescanned.
+	pub from_block: u64,
+	/// The last block rescanned for notes.
+	pub to_block: u64,
+	/// The last block processed: the witnesses of the notes found are brought up
+	/// to the last block scanned by the wallet.
+	pub end_block: u64,
+	/// The last block processed so far.
+	pub scanned_block: u64,
+	/// The addresses rescanned for.
+	pub addresses: Vec<String>,
+	pub state: RescanState,
+	/// The time spent rescanning so far.
+	pub elapsed: Duration,
+	/// The error which stopped the rescan.
+	pub error: Option<String>,
+}
+
+impl RescanStatus {
+	/// The fraction of the blocks processed, from 0 to 1.
+	pub fn progress(&self) -> f64 {
+		let total = self.end_block + 1 - self.from_block;
+		self.processed_blocks() as f64 / total as f64
+	}
+
+	/// The time left, estimated from the pace of the rescan so far.
+	pub fn eta(&self) -> Option<Duration> {
+		let processed = self.processed_blocks();
+		if self.state != RescanState::Running || processed == 0 {
+			return None;
+		}
+		let elapsed = self.elapsed.as_secs() as f64 + self.elapsed.subsec_nanos() as f64 / 1e9;
+		let remaining = (self.end_block - self.scanned_block) as f64;
+		Some(Duration::from_millis((elapsed * remaining / processed as f64 * 1000.0) as u64))
+	}
+
+	fn processed_blocks(&self) -> u64 {
+		(self.scanned_block + 1).saturating_sub(self.from_block)
+	}
+}
+
+// A rescan requested, shared by `WalletSync` and the sync thread.
+struct Rescan {
+	status: RescanStatus,
+	viewing_keys: ViewingKeys,
+	started: Option<Instant>,
+	cancelled: bool,
+}
+
+impl Rescan {
+	fn status(&self) -> RescanStatus {
+		let mut status = self.status.clone();
+		if status.state == RescanState::Running {
+			status.elapsed = self.started.map_or(status.elapsed, |started| started.elapsed());
+		}
+		status
+	}
+}
+
+type SharedRescan = Arc<Mutex<Option<Rescan>>>;
+
+/// Keeps a wallet synced with the canonical chain on a thread of its own.
+/// The wallet data is written after each batch of blocks scanned, and once the sync is dropped.
+pub struct WalletSync {
+	wallet: Arc<RwLock<Wallet>>,
+	status: Arc<RwLock<WalletSyncStatus>>,
+	// The last rescan requested.
+	rescan: SharedRescan,
+	// Wakes the sync thread up, which stops once it is dropped.
+	sender: Option<Mutex<mpsc::Sender<()>>>,
+	// Interrupts the scan running when the sync is dropped.
+	stopping: Arc<AtomicBool>,
+	thread: Option<thread::JoinHandle<()>>,
+}
+
+impl WalletSync {
+	/// Loads the wallet data stored with the chain and starts syncing the wallet
+	/// with the blocks imported since it was last written.
+	pub fn start(wallet: Arc<RwLock<Wallet>>, chain: Arc<WalletChain>) -> Self {
+		let mut syncer = Syncer::new(wallet.clone());
+		syncer.load(&*chain);
+		let status = syncer.status.clone();
+		let rescan = syncer.rescan.clone();
+		let stopping = syncer.stopping.clone();
+		let chain: Weak<WalletChain> = Arc::downgrade(&chain);
+
+		let (sender, receiver) = mpsc::channel();
+		let thread = thread::Builder::new()
+			.name("wallet-sync".into())
+			.spawn(move || {
+				while receiver.recv().is_ok() && !syncer.is_stopping() {
+					// The blocks imported meanwhile are all scanned at once.
+					while receiver.try_recv().is_ok() {}
+					match chain.upgrade() {
+						Some(chain) => syncer.sync(&*chain),
+						None => break,
+					}
+				}
+				// Keep the blocks scanned since the last write for the next start.
+				if let Some(chain) = chain.upgrade() {
+					syncer.write(&*chain.wallet_db());
+				}
+			})
+			.expect("Failed to spawn the wallet sync thread");
+		// Catch up with the blocks imported while the node was stopped.
+		let _ = sender.send(());
+
+		WalletSync {
+			wallet,
+			status,
+			rescan,
+			sender: Some(Mutex::new(sender)),
+			stopping,
+			thread: Some(thread),
+		}
+	}
+
+	/// Schedules a scan of the blocks imported since the last one.
+	pub fn notify(&self) {
+		if let Some(ref sender) = self.sender {
+			let _ = sender.lock().send(());
+		}
+	}
+
+	/// The wallet being synced.
+	pub fn wallet(&self) -> Arc<RwLock<Wallet>> {
+		self.wallet.clone()
+	}
+
+	/// How far the wallet is synced with the chain.
+	pub fn status(&self) -> WalletSyncStatus {
+		*self.status.read()
+	}
+
+	/// Schedules a rescan of the blocks `from_block` to `to_block`, the last scanned block
+	/// by default, for the given addresses or all of them. The notes the keys received and
+	/// sent in these blocks are rebuilt from the commitment tree of the parent of `from_block`,
+	/// and replace the notes of the wallet once the rescan finishes. The new blocks are
+	/// scanned after the rescan.
+	pub fn rescan(
+		&self,
+		from_block: u64,
+		to_block: Option<u64>,
+		addresses: Option<Vec<String>>,
+	) -> Result<RescanStatus, String> {
+		let status = queue_rescan(&self.rescan, &self.wallet, from_block, to_block, addresses)?;
+		self.notify();
+		Ok(status)
+	}
+
+	/// The progress of the last rescan requested, if any.
+	pub fn rescan_status(&self) -> Option<RescanStatus> {
+		self.rescan.lock().as_ref().map(Rescan::status)
+	}
+
+	/// Cancels the rescan queued or running. Returns whether there was one.
+	pub fn cancel_rescan(&self) -> bool {
+		match *self.rescan.lock() {
+			Some(ref mut rescan) if !rescan.status.state.is_done() => {
+				rescan.cancelled = true;
+				true
+			}
+			_ => false,
+		}
+	}
+}
+
+impl Drop for WalletSync {
+	fn drop(&mut self) {
+		// Stop the scan running and wait for the wallet data to be written.
+		self.stopping.store(true, Ordering::SeqCst);
+		self.sender = None;
+		if let Some(thread) = self.thread.take() {
+			if thread.join().is_err() {
+				warn!(target: "wallet", "The wallet sync thread panicked, the last blocks scanned are scanned again on restart.");
+			}
+		}
+	}
+}
+
+impl ChainNotify for WalletSync {
+	fn new_blocks(&self, new_blocks: NewBlocks) {
+		if new_blocks.route.enacted().is_empty() && new_blocks.route.retracted().is_empty() {
+			return;
+		}
+		self.notify();
+	}
+}
+
+// Check the rescan requested and queue it for the sync thread.
+fn queue_rescan(
+	rescan: &Mutex<Option<Rescan>>,
+	wallet: &RwLock<Wallet>,
+	from_block: u64,
+	to_block: Option<u64>,
+	addresses: Option<Vec<String>>,
+) -> Result<RescanStatus, String> {
+	let mut rescan = rescan.lock();
+	if rescan.as_ref().map_or(false, |running| !running.status.state.is_done()) {
+		return Err("A rescan is already running.".into());
+	}
+
+	let (addresses, viewing_keys, last_scanned) = {
+		let wallet = wallet.read();
+		let addresses = addresses.unwrap_or_else(|| wallet.list_addresses());
+		let viewing_keys = wallet.key_store.get_viewing_keys(&addresses)
+			.map_err(|address| format!("Address {} is not in the wallet.", address))?;
+		(addresses, viewing_keys, wallet.last_scanned_number)
+	};
+	if viewing_keys.is_empty() {
+		return Err("No address to rescan for.".into());
+	}
+	let from_block = from_block.max(1);
+	let to_block = to_block.unwrap_or(last_scanned);
+	if to_block > last_scanned {
+		return Err(format!("Block #{} is not scanned by the wallet yet, the last scanned block is #{}.", to_block, last_scanned));
+	}
+	if from_block > to_block {
+		return Err(format!("No block to rescan from #{} to #{}.", from_block, to_block));
+	}
+
+	let status = RescanStatus {
+		from_block,
+		to_block,
+		end_block: last_scanned,
+		scanned_block: from_block - 1,
+		addresses,
+		state: RescanState::Queued,
+		elapsed: Duration::from_secs(0),
+		error: None,
+	};
+	*rescan = Some(Rescan {
+		status: status.clone(),
+		viewing_keys,
+		started: None,
+		cancelled: false,
+	});
+	Ok(status)
+}
+
+// The state of the sync thread.
+struct Syncer {
+	wallet: Arc<RwLock<Wallet>>,
+	status: Arc<RwLock<WalletSyncStatus>>,
+	rescan: SharedRescan,
+	// The addresses whose notes are scanned up to the last scanned block.
+	handled_addresses: HashSet<String>,
+	// Whether the accounts restored from a seed which received no notes are to be pruned
+	// once the wallet is synced.
+	restoring: bool,
+	stopping: Arc<AtomicBool>,
+	last_progress_log: Instant,
+}
+
+impl Syncer {
+	fn new(wallet: Arc<RwLock<Wallet>>) -> Self {
+		Syncer {
+			wallet,
+			status: Arc::new(RwLock::new(WalletSyncStatus::default())),
+			rescan: Arc::new(Mutex::new(None)),
+			handled_addresses: HashSet::new(),
+			restoring: false,
+			stopping: Arc::new(AtomicBool::new(false)),
+			last_progress_log: Instant::now(),
+		}
+	}
+
+	fn is_stopping(&self) -> bool {
+		self.stopping.load(Ordering::SeqCst)
+	}
+
+	// Reload the notes stored in db, or rebuild them from the genesis if the block
+	// they were scanned up to is unknown.
+	fn load(&mut self, chain: &WalletChain) {
+		let bc = chain.chain();
+		let mut wallet = self.wallet.write();
+		let stored_addresses = wallet.load_wallet_data(&*chain.wallet_db());
+
+		match wallet.last_scanned_block.and_then(|hash| bc.block_number(&hash)) {
+			Some(number) => {
+				wallet.last_scanned_number = number;
+				self.handled_addresses = match stored_addresses {
+					Some(addresses) => addresses.addresses.into_iter().collect(),
+					None => wallet.list_addresses().into_iter().collect(),
+				};
+			}
+			None => {
+				if let Some(hash) = wallet.last_scanned_block {
+					warn!(target: "wallet", "Wallet last scanned block {} is unknown, rescanning the chain.", hash);
+				}
+				wallet.clear_wallet_data();
+				if wallet.list_addresses().is_empty() {
+					// Nothing to look for in the chain.
+					wallet.last_scanned_block = Some(bc.best_block_hash());
+					wallet.last_scanned_number = bc.best_block_number();
+				} else {
+					wallet.last_scanned_block = None;
+					wallet.last_scanned_number = 0;
+					self.restoring = true;
+				}
+				self.handled_addresses = wallet.list_addresses().into_iter().collect();
+			}
+		}
+		self.update_status(wallet.last_scanned_number, bc.best_block_number());
+	}
+
+	// Bring the wallet up to date with the canonical chain, and store it.
+	fn sync(&mut self, chain: &WalletChain) {
+		let bc = chain.chain();
+		let db = chain.wallet_db();
+		self.rewind_retracted(&bc);
+		self.scan_new_addresses(&bc);
+		self.scan_enacted(&bc, &*db);
+		self.run_rescan(&bc, &*db);
+
+		while self.restoring && self.status.read().is_synced() {
+			self.restoring = false;
+			let result = self.wallet.write().finish_mnemonic_restore();
+			match result {
+				// Restoring again once the chain is scanned for the new accounts.
+				Ok(ref addresses) if !addresses.is_empty() => self.scan_new_addresses(&bc),
+				Ok(_) => {}
+				Err(e) => warn!(target: "wallet", "Failed to restore the wallet accounts: {}", e),
+			}
+		}
+		self.write(&*db);
+	}
+
+	// Undo the blocks scanned by the wallet which were retracted from the canonical chain,
+	// down to their common ancestor with it.
+	fn rewind_retracted(&mut self, bc: &BlockChain) {
+		loop {
+			let hash = match self.wallet.read().last_scanned_block {
+				Some(hash) if !bc.is_canon(&hash) => hash,
+				_ => return,
+			};
+			let block = match bc.block(&hash) {
+				Some(block) => block,
+				None => {
+					warn!(target: "wallet", "Retracted block {} is unknown, rescanning the chain.", hash);
+					let mut wallet = self.wallet.write();
+					wallet.clear_wallet_data();
+					wallet.last_scanned_block = None;
+					wallet.last_scanned_number = 0;
+					return;
+				}
+			};
+			let retracted_tx_hashes = block.view()
+				.localized_private_txs()
+				.iter()
+				.map(|tx| tx.hash())
+				.collect();
+
+			let mut wallet = self.wallet.write();
+			wallet.remove_retracted_txs(&retracted_tx_hashes);
+			wallet.rewind_retracted_blocks(1, block.number().saturating_sub(1));
+			wallet.last_scanned_block = Some(block.parent_hash());
+		}
+	}
+
+	// Scan the blocks already scanned for the keys added to the wallet since.
+	fn scan_new_addresses(&mut self, bc: &BlockChain) {
+		let handled_addresses = Some(self.handled_addresses.clone());
+		let (addresses, viewing_keys, first, last) = {
+			let wallet = self.wallet.read();
+			// Diversified addresses share the viewing key of an account already scanned for.
+			let viewing_keys = wallet.key_store.get_unhandled_viewing_keys(&handled_addresses);
+			let first = wallet.rescan_height(&handled_addresses).max(1);
+			(wallet.list_addresses(), viewing_keys, first, wallet.last_scanned_number)
+		};
+
+		if !viewing_keys.is_empty() {
+			if first <= last {
+				info!(target: "wallet", "Scanning blocks #{} to #{} for the new wallet keys", first, last);
+			}
+			let mut number = first;
+			while number <= last {
+				// The keys are scanned for again on the next start.
+				if self.is_stopping() {
+					return;
+				}
+				let blocks = canonical_blocks(bc, number, last);
+				if blocks.is_empty() {
+					break;
+				}
+				for block in decrypt_blocks(blocks, &viewing_keys) {
+					number = block.block.number() + 1;
+					if let Some(parent_commitment_tree_ser) = bc.block_commitment_ser(&block.block.parent_hash()) {
+						let mut parent_commitment_tree = parent_commitment_tree_ser.get_commitment_tree();
+						self.wallet.write()
+							.scan_decrypted_block(&mut parent_commitment_tree, &block, &viewing_keys);
+					}
+				}
+			}
+			self.restoring = true;
+		}
+		self.handled_addresses = addresses.into_iter().collect();
+	}
+
+	// Scan the canonical blocks following the last scanned one, up to the best block.
+	fn scan_enacted(&mut self, bc: &BlockChain, db: &KeyValueDB) {
+		let best = bc.best_block_number();
+		'batches: loop {
+			let (first, viewing_keys) = {
+				let wallet = self.wallet.read();
+				(wallet.last_scanned_number + 1, wallet.key_store.get_unhandled_viewing_keys(&None))
+			};
+			if first > best || self.is_stopping() {
+				break;
+			}
+			let blocks = canonical_blocks(bc, first, best);
+			if blocks.is_empty() {
+				break;
+			}
+
+			for block in decrypt_blocks(blocks, &viewing_keys) {
+				let number = block.block.number();
+				{
+					let mut wallet = self.wallet.write();
+					// The chain was reorganized meanwhile, it is rewound on the next sync.
+					if wallet.last_scanned_block.map_or(false, |hash| hash != block.block.parent_hash()) {
+						break 'batches;
+					}
+					match bc.block_commitment_ser(&block.block.parent_hash()) {
+						Some(parent_commitment_tree_ser) => {
+							let mut parent_commitment_tree = parent_commitment_tree_ser.get_commitment_tree();
+							wallet.sync_decrypted_block(&mut parent_commitment_tree, &block, &viewing_keys);
+						}
+						None => {
+							// Its notes can't be witnessed, the block is scanned again on the next sync.
+							warn!(target: "wallet", "No commitment tree for the parent of block #{}, the wallet sync is stalled", number);
+							break 'batches;
+						}
+					}
+				}
+				self.update_status(number, best);
+
+				if number < best && self.last_progress_log.elapsed() >= PROGRESS_LOG_INTERVAL {
+					self.last_progress_log = Instant::now();
+					info!(target: "wallet", "Syncing shielded wallet #{}/{} ({} blocks behind)", number, best, best - number);
+				}
+			}
+			// A restart resumes from the last batch scanned.
+			self.write(db);
+		}
+		let scanned = self.wallet.read().last_scanned_number;
+		self.update_status(scanned, best);
+	}
+
+	// Run the rescan queued, if any, and store its result.
+	fn run_rescan(&mut self, bc: &BlockChain, db: &KeyValueDB) {
+		let (from, to, viewing_keys) = {
+			let mut queued = self.rescan.lock();
+			let rescan = match *queued {
+				Some(ref mut rescan) if rescan.status.state == RescanState::Queued => rescan,
+				_ => return,
+			};
+			if rescan.cancelled {
+				rescan.status.state = RescanState::Cancelled;
+				return;
+			}
+			rescan.status.state = RescanState::Running;
+			rescan.started = Some(Instant::now());
+			(rescan.status.from_block, rescan.status.to_block, rescan.viewing_keys.clone())
+		};
+
+		info!(target: "wallet", "Rescanning blocks #{} to #{} for {} wallet keys", from, to, viewing_keys.len());
+		let result = self.rescan_blocks(bc, from, to, &viewing_keys);
+		self.write(db);
+
+		let mut rescan = self.rescan.lock();
+		if let Some(ref mut rescan) = *rescan {
+			rescan.status.elapsed = rescan.started.map_or(rescan.status.elapsed, |started| started.elapsed());
+			match result {
+				Ok(state) => rescan.status.state = state,
+				Err(e) => {
+					warn!(target: "wallet", "Rescan failed: {}", e);
+					rescan.status.state = RescanState::Failed;
+					rescan.status.error = Some(e);
+				}
+			}
+		}
+	}
+
+	// Rebuild the notes of `viewing_keys` in the blocks `from` to `to`, from the commitment tree
+	// of the parent of `from`. The notes are rebuilt apart from the wallet, which keeps its notes
+	// if the rescan doesn't finish.
+	fn rescan_blocks(
+		&mut self,
+		bc: &BlockChain,
+		from: u64,
+		to: u64,
+		viewing_keys: &ViewingKeys,
+	) -> Result<RescanState, String> {
+		let last = self.wallet.read().last_scanned_number;
+		if from > last {
+			return Err(format!("Block #{} is no longer scanned by the wallet.", from));
+		}
+		let rescanned = from..=to.min(last);
+		let parent_hash = bc.block_hash(from - 1)
+			.ok_or_else(|| format!("Block #{} is unknown.", from - 1))?;
+		let commitment_tree = bc.block_commitment_ser(&parent_hash)
+			.ok_or_else(|| format!("No commitment tree is stored for block #{}.", from - 1))?
+			.get_commitment_tree();
+		if let Some(ref mut rescan) = *self.rescan.lock() {
+			rescan.status.end_block = last;
+		}
+
+		let mut notes = self.wallet.write().start_rescan(viewing_keys, &rescanned);
+		let result = self.rebuild_notes(bc, &mut notes, commitment_tree, parent_hash, viewing_keys, &rescanned, last);
+		if result == Ok(RescanState::Finished) {
+			self.wallet.write().finish_rescan(notes);
+		}
+		result
+	}
+
+	// Add to `notes` the notes of `viewing_keys` in the `rescanned` blocks carrying `commitment_tree`,
+	// the tree of the block `parent_hash`, forward, then bring their witnesses up to the block `last`.
+	// The blocks enacted meanwhile are only scanned once the rescan is over.
+	fn rebuild_notes(
+		&mut self,
+		bc: &BlockChain,
+		notes: &mut RescannedNotes,
+		mut commitment_tree: CommitmentTree<Node>,
+		mut parent_hash: H256,
+		viewing_keys: &ViewingKeys,
+		rescanned: &RangeInclusive<u64>,
+		last: u64,
+	) -> Result<RescanState, String> {
+		let to = *rescanned.end();
+		// Past `to`, the blocks are only processed for the witnesses and spends of the notes found.
+		let no_viewing_keys = ViewingKeys::new();
+		let mut number = *rescanned.start();
+		while number <= last {
+			let (decrypted_keys, batch_last) = if number <= to {
+				(viewing_keys, to)
+			} else {
+				(&no_viewing_keys, last)
+			};
+			let blocks = canonical_blocks(bc, number, batch_last);
+			if blocks.is_empty() {
+				return Err(format!("Block #{} is unknown.", number));
+			}
+			for block in decrypt_blocks(blocks, decrypted_keys) {
+				if block.block.parent_hash() != parent_hash {
+					return Err("The chain was reorganized during the rescan.".into());
+				}
+				self.wallet.write()
+					.rescan_decrypted_block(notes, &mut commitment_tree, &block, viewing_keys, rescanned);
+				parent_hash = block.block.hash();
+				number = block.block.number() + 1;
+
+				if self.rescan_progress(number - 1) {
+					return Ok(RescanState::Cancelled);
+				}
+				if number <= last && self.last_progress_log.elapsed() >= PROGRESS_LOG_INTERVAL {
+					self.last_progress_log = Instant::now();
+					info!(target: "wallet", "Rescanning shielded wallet #{}/{}", number - 1, last);
+				}
+			}
+		}
+		if self.wallet.read().last_scanned_block != Some(parent_hash) {
+			return Err("The chain was reorganized during the rescan.".into());
+		}
+		Ok(RescanState::Finished)
+	}
+
+	// Record the last block rescanned, returns whether the rescan was cancelled, or the sync stopped.
+	fn rescan_progress(&self, scanned_block: u64) -> bool {
+		match *self.rescan.lock() {
+			Some(ref mut rescan) => {
+				rescan.status.scanned_block = scanned_block;
+				rescan.cancelled || self.is_stopping()
+			}
+			None => true,
+		}
+	}
+
+	fn write(&self, db: &KeyValueDB) {
+		let mut batch = DBTransaction::new();
+		self.wallet.write().write_wallet_data(&mut batch);
+		db.write(batch).expect("Low level database error when writing wallet data. Some issue with disk?");
+	}
+
+	fn update_status(&self, scanned_block: u64, best_block: u64) {
+		*self.status.write() = WalletSyncStatus { scanned_block, best_block };
+	}
+}
+
+// The canonical blocks from `first` to `last`, at most SCAN_BATCH_BLOCKS of them.
+fn canonical_blocks(bc: &BlockChain, first: u64, last: u64) -> Vec<encoded::Block> {
+	let mut blocks = Vec::new();
+	for number in first..=last.min(first + SCAN_BATCH_BLOCKS - 1) {
+		match bc.block_hash(number).and_then(|hash| bc.block(&hash)) {
+			Some(block) => blocks.push(block),
+			None => break,
+		}
+	}
+	blocks
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	use ethcore_blockchain::generator::BlockBuilder;
+	use ethcore_blockchain::BlockChainDB;
+	use ethereum_types::U256;
+	use tempdir::TempDir;
+
+	use crate::test_helpers::{create_private_transaction, TestChain, TEST_CHAIN_ID};
+
+	fn new_wallet(dir: &TempDir) -> Arc<RwLock<Wallet>> {
+		Arc::new(RwLock::new(Wallet::new_from_file("wallet_", dir.path().to_str().unwrap())))
+	}
+
+	fn new_syncer(wallet: &Arc<RwLock<Wallet>>, chain: &TestChain) -> Syncer {
+		let mut syncer = Syncer::new(wallet.clone());
+		syncer.load(chain);
+		syncer
+	}
+
+	#[test]
+	fn syncs_enacted_blocks_and_reports_lag() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let b2 = b1.add_block();
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+
+		chain.insert_block(b1.last().encoded());
+		chain.insert_block(b2.last().encoded());
+		assert_eq!(wallet.read().nullifier_notes.len(), 0);
+
+		syncer.sync(&chain);
+		let status = *syncer.status.read();
+		assert_eq!(status, WalletSyncStatus { scanned_block: 2, best_block: 2 });
+		assert!(status.is_synced());
+		let wallet = wallet.read();
+		assert_eq!(wallet.last_scanned_block, Some(b2.last().hash()));
+		assert_eq!(wallet.tx_nullifiers.len(), 1);
+		assert_eq!(wallet.nullifier_notes.len(), 2);
+		assert_eq!(wallet.address_nullifiers.len(), 1);
+		for note in wallet.nullifier_notes.values() {
+			assert_eq!(note.witnesses.len(), 2);
+		}
+	}
+
+	#[test]
+	fn scans_imported_addresses() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+		chain.insert_block(b1.last().encoded());
+		syncer.sync(&chain);
+
+		// Pretend the address was imported after the block was scanned.
+		wallet.write().clear_wallet_data();
+		syncer.handled_addresses.clear();
+		syncer.sync(&chain);
+		assert_eq!(wallet.read().tx_nullifiers.len(), 1);
+		assert_eq!(wallet.read().nullifier_notes.len(), 2);
+		assert_eq!(syncer.handled_addresses.len(), 1);
+
+		wallet.write().create_new_private_address("".into()).unwrap();
+		syncer.sync(&chain);
+		assert_eq!(syncer.handled_addresses.len(), 2);
+		assert_eq!(wallet.read().nullifier_notes.len(), 2);
+	}
+
+	#[test]
+	fn resumes_from_last_written_block() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let b2 = b1.add_block();
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+		chain.insert_block(b1.last().encoded());
+		syncer.sync(&chain);
+		chain.insert_block(b2.last().encoded());
+
+		// Notes and witnesses are restored from db, and the blocks imported since are scanned.
+		let reloaded = new_wallet(&dir);
+		let mut syncer = new_syncer(&reloaded, &chain);
+		assert_eq!(reloaded.read().last_scanned_block, Some(b1.last().hash()));
+		assert_eq!(reloaded.read().nullifier_notes.len(), 2);
+		assert_eq!(syncer.status.read().lag(), 1);
+		syncer.sync(&chain);
+		let reloaded = reloaded.read();
+		assert_eq!(reloaded.last_scanned_block, Some(b2.last().hash()));
+		assert_eq!(reloaded.tx_nullifiers.len(), 1);
+		for note in reloaded.nullifier_notes.values() {
+			assert_eq!(note.witnesses.len(), 2);
+		}
+	}
+
+	#[test]
+	fn resyncs_from_older_written_block() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let b2 = b1.add_block();
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+		chain.insert_block(b1.last().encoded());
+		chain.insert_block(b2.last().encoded());
+		syncer.sync(&chain);
+
+		// Pretend the wallet was last written at the genesis.
+		{
+			let mut wallet = wallet.write();
+			wallet.clear_wallet_data();
+			wallet.last_scanned_block = Some(genesis.last().hash());
+			let mut batch = DBTransaction::new();
+			wallet.write_wallet_data(&mut batch);
+			chain.db.key_value().write(batch).unwrap();
+		}
+
+		let reloaded = new_wallet(&dir);
+		let mut syncer = new_syncer(&reloaded, &chain);
+		assert_eq!(reloaded.read().nullifier_notes.len(), 0);
+		assert_eq!(syncer.status.read().lag(), 2);
+		syncer.sync(&chain);
+		let reloaded = reloaded.read();
+		assert_eq!(reloaded.last_scanned_block, Some(b2.last().hash()));
+		assert_eq!(reloaded.tx_nullifiers.len(), 1);
+		assert_eq!(reloaded.nullifier_notes.len(), 2);
+		for note in reloaded.nullifier_notes.values() {
+			assert_eq!(note.witnesses.len(), 2);
+		}
+	}
+
+	#[test]
+	fn writes_each_scanned_batch() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+		chain.insert_block(b1.last().encoded());
+		syncer.scan_enacted(&chain.chain, &**chain.db.key_value());
+
+		let reloaded = new_wallet(&dir);
+		new_syncer(&reloaded, &chain);
+		assert_eq!(reloaded.read().last_scanned_block, Some(b1.last().hash()));
+		assert_eq!(reloaded.read().nullifier_notes.len(), 2);
+	}
+
+	#[test]
+	fn rewinds_retracted_blocks() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1a = genesis.add_block_with_transactions(vec![t1]);
+		let b2a = b1a.add_block();
+		let b1b = genesis.add_block_with_difficulty(9);
+		let b2b = b1b.add_block();
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+
+		chain.insert_block(b1a.last().encoded());
+		chain.insert_block(b1b.last().encoded());
+		syncer.sync(&chain);
+		assert_eq!(wallet.read().nullifier_notes.len(), 2);
+
+		chain.insert_block(b2b.last().encoded());
+		syncer.sync(&chain);
+		assert_eq!(wallet.read().last_scanned_block, Some(b2b.last().hash()));
+		assert_eq!(wallet.read().tx_nullifiers.len(), 0);
+		assert_eq!(wallet.read().nullifier_notes.len(), 0);
+		assert_eq!(wallet.read().address_nullifiers.len(), 0);
+
+		chain.insert_block(b2a.last().encoded());
+		syncer.sync(&chain);
+		assert_eq!(wallet.read().last_scanned_block, Some(b2a.last().hash()));
+		assert_eq!(wallet.read().tx_nullifiers.len(), 1);
+		assert_eq!(wallet.read().nullifier_notes.len(), 2);
+		assert_eq!(wallet.read().address_nullifiers.len(), 1);
+	}
+
+	#[test]
+	fn rewinds_blocks_retracted_while_stopped() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1a = genesis.add_block_with_transactions(vec![t1]);
+		let b2a = b1a.add_block();
+		let b1b = genesis.add_block_with_difficulty(9);
+		let b2b = b1b.add_block();
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+		chain.insert_block(b1a.last().encoded());
+		syncer.sync(&chain);
+
+		// The chain is reorganised while the wallet is not running.
+		chain.insert_block(b1b.last().encoded());
+		chain.insert_block(b2b.last().encoded());
+		let reloaded = new_wallet(&dir);
+		let mut syncer = new_syncer(&reloaded, &chain);
+		assert_eq!(reloaded.read().last_scanned_block, Some(b1a.last().hash()));
+		assert_eq!(reloaded.read().nullifier_notes.len(), 2);
+		syncer.sync(&chain);
+		assert_eq!(reloaded.read().last_scanned_block, Some(b2b.last().hash()));
+		assert_eq!(reloaded.read().tx_nullifiers.len(), 0);
+		assert_eq!(reloaded.read().nullifier_notes.len(), 0);
+
+		chain.insert_block(b2a.last().encoded());
+		syncer.sync(&chain);
+		let reloaded = reloaded.read();
+		assert_eq!(reloaded.last_scanned_block, Some(b2a.last().hash()));
+		assert_eq!(reloaded.tx_nullifiers.len(), 1);
+		assert_eq!(reloaded.nullifier_notes.len(), 2);
+		for note in reloaded.nullifier_notes.values() {
+			assert_eq!(note.witnesses.len(), 2);
+		}
+	}
+
+	#[test]
+	fn rescans_blocks_for_addresses() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let b2 = b1.add_block();
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+		chain.insert_block(b1.last().encoded());
+		chain.insert_block(b2.last().encoded());
+		syncer.sync(&chain);
+		let nullifiers: HashSet<U256> = wallet.read().nullifier_notes.keys().cloned().collect();
+		assert_eq!(nullifiers.len(), 2);
+
+		// Rescanning notes already found rebuilds the same ones.
+		let status = queue_rescan(&syncer.rescan, &wallet, 0, None, None).unwrap();
+		assert_eq!(status.from_block, 1);
+		assert_eq!(status.to_block, 2);
+		assert_eq!(status.state, RescanState::Queued);
+		syncer.sync(&chain);
+		assert_eq!(wallet.read().nullifier_notes.keys().cloned().collect::<HashSet<_>>(), nullifiers);
+
+		// The notes lost are found again, with their witnesses up to the last scanned block.
+		wallet.write().clear_wallet_data();
+		let addresses = wallet.read().list_addresses();
+		queue_rescan(&syncer.rescan, &wallet, 1, Some(1), Some(addresses.clone())).unwrap();
+		syncer.sync(&chain);
+		let status = syncer.rescan.lock().as_ref().unwrap().status();
+		assert_eq!(status.state, RescanState::Finished);
+		assert_eq!(status.scanned_block, 2);
+		assert_eq!(status.end_block, 2);
+		assert_eq!(status.addresses, addresses);
+		assert_eq!(status.progress(), 1.0);
+		assert_eq!(status.eta(), None);
+		let wallet = wallet.read();
+		assert_eq!(wallet.tx_nullifiers.len(), 1);
+		assert_eq!(wallet.address_nullifiers.len(), 1);
+		assert_eq!(wallet.nullifier_notes.keys().cloned().collect::<HashSet<_>>(), nullifiers);
+		for note in wallet.nullifier_notes.values() {
+			assert_eq!(note.witnesses.len(), 2);
+		}
+	}
+
+	#[test]
+	fn checks_and_cancels_rescans() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+		chain.insert_block(b1.last().encoded());
+		syncer.sync(&chain);
+
+		assert!(queue_rescan(&syncer.rescan, &wallet, 1, Some(2), None).is_err());
+		assert!(queue_rescan(&syncer.rescan, &wallet, 2, None, None).is_err());
+		assert!(queue_rescan(&syncer.rescan, &wallet, 1, None, Some(vec!["unknown".into()])).is_err());
+		assert!(queue_rescan(&syncer.rescan, &wallet, 1, None, Some(vec![])).is_err());
+
+		wallet.write().clear_wallet_data();
+		queue_rescan(&syncer.rescan, &wallet, 1, None, None).unwrap();
+		assert!(queue_rescan(&syncer.rescan, &wallet, 1, None, None).is_err());
+		syncer.rescan.lock().as_mut().unwrap().cancelled = true;
+		syncer.sync(&chain);
+		assert_eq!(syncer.rescan.lock().as_ref().unwrap().status.state, RescanState::Cancelled);
+		assert_eq!(wallet.read().nullifier_notes.len(), 0);
+
+		// A new rescan can be queued once the last one is over.
+		queue_rescan(&syncer.rescan, &wallet, 1, None, None).unwrap();
+		syncer.sync(&chain);
+		assert_eq!(syncer.rescan.lock().as_ref().unwrap().status.state, RescanState::Finished);
+		assert_eq!(wallet.read().nullifier_notes.len(), 2);
+	}
+
+	#[test]
+	fn keeps_notes_of_stopped_rescans() {
+		let dir = TempDir::new("").unwrap();
+		let wallet = new_wallet(&dir);
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let b2 = b1.add_block();
+		let chain = TestChain::new(genesis.last().encoded());
+		let mut syncer = new_syncer(&wallet, &chain);
+		chain.insert_block(b1.last().encoded());
+		chain.insert_block(b2.last().encoded());
+		syncer.sync(&chain);
+		let nullifiers: HashSet<U256> = wallet.read().nullifier_notes.keys().cloned().collect();
+		assert_eq!(nullifiers.len(), 2);
+
+		// The rescan is cancelled after its first block, before the witnesses are up to date.
+		queue_rescan(&syncer.rescan, &wallet, 1, None, None).unwrap();
+		let viewing_keys = {
+			let mut rescan = syncer.rescan.lock();
+			let rescan = rescan.as_mut().unwrap();
+			rescan.cancelled = true;
+			rescan.viewing_keys.clone()
+		};
+		assert_eq!(syncer.rescan_blocks(&chain.chain, 1, 2, &viewing_keys), Ok(RescanState::Cancelled));
+		let wallet = wallet.read();
+		assert_eq!(wallet.tx_nullifiers.len(), 1);
+		assert_eq!(wallet.nullifier_notes.keys().cloned().collect::<HashSet<_>>(), nullifiers);
+		for note in wallet.nullifier_notes.values() {
+			assert_eq!(note.witnesses.len(), 2);
+		}
+	}
+}
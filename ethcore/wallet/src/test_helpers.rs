@@ -0,0 +1,104 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+
+//! Fixtures shared by the wallet tests.
+
+use std::sync::Arc;
+
+use common_types::encoded;
+use common_types::engines::ForkChoice;
+use common_types::transaction::{Transaction, CONVERSION_FACTOR};
+use ethcore::test_helpers::new_db;
+use ethcore_blockchain::{BlockChain, BlockChainDB, BlockProvider, Config, ExtrasInsert};
+use ethereum_types::U256;
+use ff::PrimeField;
+use kvdb::KeyValueDB;
+use rand::{OsRng, Rand};
+use sapling_crypto::jubjub::fs::Fs;
+use zcash_primitives::{
+	merkle_tree::{CommitmentTree, IncrementalWitness},
+	sapling::Node,
+	JUBJUB,
+};
+
+use crate::sync::WalletChain;
+use crate::transaction_builder::TransactionBuilder;
+use crate::wallet::Wallet;
+use crate::wallet_types::SaplingExtendedFullViewingKey;
+
+pub const TEST_CHAIN_ID: u64 = 0;
+
+/// A chain the blocks are inserted into directly, without being verified.
+pub struct TestChain {
+	pub chain: Arc<BlockChain>,
+	pub db: Arc<BlockChainDB>,
+}
+
+impl TestChain {
+	pub fn new(genesis: encoded::Block) -> Self {
+		let db = new_db();
+		let chain = Arc::new(BlockChain::new(Config::default(), genesis.raw(), db.clone(), None));
+		TestChain { chain, db }
+	}
+
+	pub fn insert_block(&self, block: encoded::Block) {
+		let parent_details = self.chain.block_details(&block.parent_hash()).unwrap();
+		let fork_choice = if parent_details.total_difficulty + block.difficulty() > self.chain.best_block_total_difficulty() {
+			ForkChoice::New
+		} else {
+			ForkChoice::Old
+		};
+		let mut batch = self.db.key_value().transaction();
+		self.chain.insert_block(&mut batch, block, vec![], ExtrasInsert { fork_choice, is_finalized: false });
+		self.db.key_value().write(batch).unwrap();
+		self.chain.commit();
+	}
+}
+
+impl WalletChain for TestChain {
+	fn chain(&self) -> Arc<BlockChain> {
+		self.chain.clone()
+	}
+
+	fn wallet_db(&self) -> Arc<KeyValueDB> {
+		self.db.key_value().clone()
+	}
+}
+
+/// A transaction paying 200 to the wallet, spending a note of 400 of it which is not in the chain.
+pub fn create_private_transaction(wallet: &mut Wallet) -> Transaction {
+	let mut rng = OsRng::new().expect("should be able to construct RNG");
+
+	let (_, extsk) = wallet.add_address_from_seed([0u8; 32], "".to_string());
+	let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+	let ovk = extfvk.fvk.ovk;
+	let to = extfvk.default_address().unwrap().1;
+
+	let mut builder = TransactionBuilder::new(0.into(), U256::from(0), U256::from(21000), vec![], TEST_CHAIN_ID);
+	let note = to.create_note(400, Fs::rand(&mut rng), &JUBJUB).unwrap();
+	let mut tree = CommitmentTree::new();
+	tree.append(Node::new(note.cm(&JUBJUB).into_repr())).unwrap();
+	let inc_tree = IncrementalWitness::from_tree(&tree);
+	let witness = inc_tree.path().unwrap();
+	assert!(builder
+		.add_sapling_spend(extsk.expsk, to.diversifier, note, inc_tree.root().into(), witness)
+		.is_ok());
+	assert!(builder
+		.add_sapling_output(ovk, to, &(U256::from(200) * CONVERSION_FACTOR), "haha")
+		.is_ok());
+	builder.build().unwrap()
+}
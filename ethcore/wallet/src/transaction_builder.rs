@@ -1,29 +1,34 @@
 use ethereum_types::{Address, U256};
 use pairing::bls12_381::{Bls12, Fr};
-use rand::{OsRng, Rand};
-use sapling_crypto::{jubjub::fs::Fs, primitives::Diversifier, redjubjub::PrivateKey};
+use rand::{random, OsRng, Rand};
+use sapling_crypto::{jubjub::fs::Fs, primitives::Diversifier};
 use std::fmt;
+use std::sync::Arc;
 use zcash_primitives::{
+	keys::OutgoingViewingKey,
 	merkle_tree::{CommitmentTreeWitness, IncrementalWitness},
 	note_encryption::{Memo, SaplingNoteEncryption},
-	sapling::{spend_sig, Node},
-	transaction::components::GROTH_PROOF_SIZE,
+	sapling::Node,
 	JUBJUB,
 };
 use zcash_proofs::sapling::SaplingProvingContext;
 
-use crate::wallet::sendmany::{CAmount, OutputDescriptionInfo, SpendDescriptionInfo};
-use crate::wallet::wallet_types::{
+use crate::key_store::encode_payment_address;
+use crate::offline::UnsignedTransaction;
+use crate::sendmany::{CAmount, OutputDescriptionInfo, SpendDescriptionInfo, SpendKeys};
+use crate::wallet_types::{
 	SaplingCommitmentTreeWitness, SaplingExpandedSpendingKey, SaplingNote,
 	SaplingOutgoingViewingKey, SaplingOutputDescription, SaplingPaymentAddress,
 	SaplingSpendDescription,
 };
-use crate::wallet::zkp::{OUTPUT_PARAM, SPEND_PARAM, SPEND_VK};
+use ethcore::miner::ShieldedAuthor;
+use ethcore_blockchain::zkp::{tx_prover, TxProver};
 use common_types::transaction::{
-	Action, PrivateTransaction, Transaction, CONVERSION_FACTOR, MAX_VALUE_ALLOWED,
+	Action, PrivateTransaction, PublicOutput, Transaction, CONVERSION_FACTOR, MAX_PUBLIC_OUTPUTS,
+	MAX_VALUE_ALLOWED,
 };
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq)]
 pub struct Error(pub ErrorKind);
 
 impl Error {
@@ -44,8 +49,14 @@ pub enum ErrorKind {
 	InvalidWitness,
 	NoChangeAddress,
 	NoPrivateKey,
-	SpendProof,
 	InvalidGasMulGasPrice(String),
+	TooManySpends(usize),
+	TooManyPublicOutputs(usize),
+	SaplingParams(String),
+	Prover(String),
+	NoProvingKey,
+	WrongSpendingKey,
+	InvalidUnsignedTransaction,
 }
 
 impl fmt::Display for ErrorKind {
@@ -62,9 +73,19 @@ impl fmt::Display for ErrorKind {
 			ErrorKind::InvalidWitness => write!(f, "Invalid Witness."),
 			ErrorKind::NoChangeAddress => write!(f, "No Change Address."),
 			ErrorKind::NoPrivateKey => write!(f, "No Private Key."),
-			ErrorKind::SpendProof => write!(f, "Spend Proof."),
 			ErrorKind::InvalidGasMulGasPrice(ref e) =>
 				write!(f, "Invalid Product of Gas and Gas_price:{}", e),
+			ErrorKind::TooManySpends(max) =>
+				write!(f, "Too many notes to spend, at most {} per transaction.", max),
+			ErrorKind::TooManyPublicOutputs(max) =>
+				write!(f, "Too many public outputs, at most {} besides the call.", max),
+			ErrorKind::SaplingParams(ref e) => write!(f, "{}", e),
+			ErrorKind::Prover(ref e) => write!(f, "Proving failed: {}", e),
+			ErrorKind::NoProvingKey => write!(f, "No Proving Key."),
+			ErrorKind::WrongSpendingKey =>
+				write!(f, "The spending key does not authorize the spends of the transaction."),
+			ErrorKind::InvalidUnsignedTransaction =>
+				write!(f, "Invalid unsigned transaction, one randomizer per spend expected."),
 		}
 	}
 }
@@ -89,6 +110,10 @@ pub struct TransactionBuilder {
 	public_output_amount: U256,
 	// Chain ID
 	pub chain_id: u64,
+	// Sign the shielded signature hash instead of the transaction hash.
+	shielded_sighash: bool,
+	// Prover of the spends and outputs, the wallet's prover if none.
+	prover: Option<Arc<dyn TxProver>>,
 }
 
 impl TransactionBuilder {
@@ -109,14 +134,28 @@ impl TransactionBuilder {
 					outputs: vec![],
 					balancing_value: 0,
 					binding_sig: [0u8; 64],
+					public_outputs: vec![],
 				}),
 			},
 			public_input_amount: U256::from(0),
 			public_output_amount: U256::from(0),
 			chain_id,
+			shielded_sighash: false,
+			prover: None,
 		}
 	}
 
+	/// Proves the transaction with `prover` instead of the wallet's prover.
+	pub fn set_prover(&mut self, prover: Arc<dyn TxProver>) {
+		self.prover = Some(prover);
+	}
+
+	/// Makes the spends and the binding signature sign the shielded signature hash,
+	/// required once the shielded sighash transition of the chain is reached.
+	pub fn set_shielded_sighash(&mut self, shielded_sighash: bool) {
+		self.shielded_sighash = shielded_sighash;
+	}
+
 	pub fn add_sapling_spend(
 		&mut self,
 		expsk: SaplingExpandedSpendingKey,
@@ -124,12 +163,25 @@ impl TransactionBuilder {
 		note: SaplingNote,
 		anchor: Fr,
 		witness: SaplingCommitmentTreeWitness,
+	) -> Result<(), Error> {
+		self.add_sapling_spend_with_keys(SpendKeys::from(&expsk), diversifier, note, anchor, witness)
+	}
+
+	/// Adds a spend proven with the proof generation key of `keys`. The transaction is
+	/// only built unsigned if the spend authorizing key is unknown.
+	pub fn add_sapling_spend_with_keys(
+		&mut self,
+		keys: SpendKeys,
+		diversifier: Diversifier,
+		note: SaplingNote,
+		anchor: Fr,
+		witness: SaplingCommitmentTreeWitness,
 	) -> Result<(), Error> {
 		let alpha = Fs::rand(&mut self.rng);
 		// tx.private is initialized so calling unwrap should be safe.
 		self.tx.private.as_mut().unwrap().balancing_value += note.value as i64;
 		self.spends.push(SpendDescriptionInfo {
-			expsk,
+			keys,
 			diversifier,
 			note,
 			alpha,
@@ -198,12 +250,43 @@ impl TransactionBuilder {
 		self.public_input_amount = value;
 	}
 
+	/// Makes the transaction a coinbase, paying `value` of the block reward to its outputs.
+	/// The transaction has no spends, gas or public value.
+	pub fn set_coinbase_value(&mut self, value: U256) {
+		self.public_input_amount = value;
+	}
+
+	/// Sets the public call of the transaction, which spends private inputs.
 	pub fn set_public_output(&mut self, address: Address, value: &U256) {
+		if let Action::PrivateCall(_) = self.tx.action {
+			// Replace the previous call.
+			self.public_output_amount -= self.tx.value;
+		}
 		self.tx.action = Action::PrivateCall(address);
 		self.tx.value = *value;
 		self.public_output_amount += *value;
 	}
 
+	/// Adds a public value recipient. The first one is the recipient of the public call,
+	/// the next ones are paid by the public outputs of the private transaction.
+	pub fn add_public_output(&mut self, address: Address, value: &U256) -> Result<(), Error> {
+		if value.is_zero() || *value > MAX_VALUE_ALLOWED {
+			return Err(Error(ErrorKind::InvalidAmount(value.to_string())));
+		}
+		match self.tx.action {
+			Action::PrivateCall(_) => {
+				let private = self.tx.private.as_mut().unwrap();
+				if private.public_outputs.len() >= MAX_PUBLIC_OUTPUTS {
+					return Err(Error(ErrorKind::TooManyPublicOutputs(MAX_PUBLIC_OUTPUTS)));
+				}
+				private.public_outputs.push(PublicOutput { address, value: *value });
+				self.public_output_amount += *value;
+			}
+			_ => self.set_public_output(address, value),
+		}
+		Ok(())
+	}
+
 	fn compute_change(&self) -> Result<U256, Error> {
 		let balancing_value = self.tx.balancing_value();
 		if balancing_value.is_negative() {
@@ -243,6 +326,17 @@ impl TransactionBuilder {
 	}
 
 	pub fn build(&mut self) -> Result<Transaction, Error> {
+		let asks = self
+			.spends
+			.iter()
+			.map(|spend| spend.keys.ask.ok_or(Error(ErrorKind::NoPrivateKey)))
+			.collect::<Result<Vec<_>, _>>()?;
+		self.build_unsigned()?.sign_spends(&asks)
+	}
+
+	/// Builds the transaction with its proofs, leaving its spends and value balance to be
+	/// signed with the spend authorizing key of the spent notes.
+	pub fn build_unsigned(&mut self) -> Result<UnsignedTransaction, Error> {
 		if self.tx.value % CONVERSION_FACTOR != U256::from(0) {
 			return Err(Error(ErrorKind::ConversionFactorError));
 		}
@@ -262,7 +356,7 @@ impl TransactionBuilder {
 			if !self.spends.is_empty() {
 				// Send change to the address of the first shield spend.
 				self.add_sapling_output(
-					self.spends[0].expsk.ovk.clone(),
+					self.spends[0].keys.ovk.clone(),
 					SaplingPaymentAddress {
 						diversifier: self.spends[0].diversifier,
 						pk_d: self.spends[0].note.pk_d.clone(),
@@ -277,10 +371,14 @@ impl TransactionBuilder {
 		} else if !change.is_zero() {
 			return Err(Error(ErrorKind::InvalidChange(change.to_string())));
 		}
+		let prover = match self.prover {
+			Some(ref prover) => prover.clone(),
+			None => tx_prover().map_err(|e| Error(ErrorKind::SaplingParams(e)))?,
+		};
 		let mut ctx = SaplingProvingContext::new();
 
 		for spend in self.spends.iter() {
-			let proof_generation_key = spend.expsk.proof_generation_key(&JUBJUB);
+			let proof_generation_key = spend.keys.proof_generation_key.clone();
 
 			let mut nullifier = [0u8; 32];
 			nullifier.copy_from_slice(&spend.note.nf(
@@ -288,7 +386,7 @@ impl TransactionBuilder {
 				spend.witness.position,
 				&JUBJUB,
 			));
-			let (proof, cv, rk) = ctx
+			let proof = prover
 				.spend_proof(
 					proof_generation_key,
 					spend.diversifier,
@@ -297,27 +395,20 @@ impl TransactionBuilder {
 					spend.note.value,
 					spend.anchor,
 					spend.witness.clone(),
-					&SPEND_PARAM,
-					&SPEND_VK,
-					&JUBJUB,
 				)
-				.map_err(|()| Error(ErrorKind::SpendProof))?;
-
-			let mut v = vec![];
-			proof.write(&mut v).unwrap();
-			let mut zkproof = [0u8; GROTH_PROOF_SIZE];
-			zkproof.copy_from_slice(v.as_slice());
+				.map_err(|e| Error(ErrorKind::Prover(e)))?;
+			ctx.add_spend(proof.rcv, &proof.cv, &JUBJUB);
 			self.tx
 				.private
 				.as_mut()
 				.unwrap()
 				.spends
 				.push(SaplingSpendDescription {
-					cv,
+					cv: proof.cv,
 					anchor: spend.anchor,
 					nullifier,
-					rk,
-					zkproof,
+					rk: proof.rk,
+					zkproof: proof.zkproof,
 					spend_auth_sig: None,
 				});
 		}
@@ -335,18 +426,12 @@ impl TransactionBuilder {
 			let xxx = output.note.value;
 			// println!("In build 2.1, {0}", xxx);
 			// Generate a proof ZKOutput for the Output statement
-			let (proof, cv) = ctx.output_proof(
-				*encryptor.esk(),
-				output.to,
-				output.note.r,
-				output.note.value,
-				&OUTPUT_PARAM,
-				&JUBJUB,
-			);
-			let mut v = vec![];
-			proof.write(&mut v).unwrap();
-			let mut zkproof = [0u8; GROTH_PROOF_SIZE];
-			zkproof.copy_from_slice(v.as_slice());
+			let proof = prover
+				.output_proof(*encryptor.esk(), output.to.clone(), output.note.r, output.note.value)
+				.map_err(|e| Error(ErrorKind::Prover(e)))?;
+			ctx.add_output(proof.rcv, &proof.cv, &JUBJUB);
+			let cv = proof.cv;
+			let zkproof = proof.zkproof;
 
 			let cmu = output.note.cm(&JUBJUB);
 
@@ -365,36 +450,54 @@ impl TransactionBuilder {
 			};
 			self.tx.private.as_mut().unwrap().outputs.push(output_desc);
 		}
-		// Signature
-		// TODO(xin): Compute real sig_hash.
-		let sighash = self.tx.hash(Some(self.chain_id)).into();
-		for (i, spend) in self.spends.iter().enumerate() {
-			self.tx.private.as_mut().unwrap().spends[i].spend_auth_sig = Some(spend_sig(
-				PrivateKey(spend.expsk.ask),
-				spend.alpha,
-				&sighash,
-				&JUBJUB,
-			));
-		}
+		Ok(UnsignedTransaction {
+			tx: self.tx.clone(),
+			chain_id: self.chain_id,
+			shielded_sighash: self.shielded_sighash,
+			alphas: self.spends.iter().map(|spend| spend.alpha).collect(),
+			bsk: ctx.bsk(),
+		})
+	}
+}
 
-		let binding_sig = match ctx.binding_sig(self.tx.balancing_value(), &sighash, &JUBJUB) {
-			Ok(sig) => sig,
-			Err(_) => return Err(Error(ErrorKind::BindingSig)),
-		};
+/// Build the coinbase transaction paying `value` of the block reward to `to`.
+/// The note is encrypted with a random outgoing viewing key, only the recipient can recover it.
+pub fn build_coinbase(
+	to: SaplingPaymentAddress,
+	value: &U256,
+	chain_id: u64,
+	shielded_sighash: bool,
+) -> Result<Transaction, Error> {
+	let mut builder = TransactionBuilder::new(U256::from(0), U256::from(0), U256::from(0), vec![], chain_id);
+	builder.set_shielded_sighash(shielded_sighash);
+	builder.set_coinbase_value(*value);
+	builder.add_sapling_output(OutgoingViewingKey(random()), to, value, "")?;
+	builder.build()
+}
+
+/// Shielded address paid the author reward of the blocks mined by the node.
+#[derive(Clone)]
+pub struct ShieldedCoinbase(pub SaplingPaymentAddress);
 
-		binding_sig.write(&mut self.tx.private.as_mut().unwrap().binding_sig[..]);
+impl fmt::Debug for ShieldedCoinbase {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "ShieldedCoinbase({})", encode_payment_address(&self.0))
+	}
+}
 
-		Ok(self.tx.clone())
+impl ShieldedAuthor for ShieldedCoinbase {
+	fn build_coinbase(&self, value: &U256, chain_id: u64, shielded_sighash: bool) -> Result<Transaction, String> {
+		build_coinbase(self.0.clone(), value, chain_id, shielded_sighash).map_err(|e| e.0.into())
 	}
 }
 
 mod tests {
 	use super::*;
-	use crate::wallet::wallet_types::{
+	use crate::wallet_types::{
 		SaplingExtendedFullViewingKey, SaplingExtendedSpendingKey, SaplingMerkleTree,
 		SaplingWitness,
 	};
-	use crate::wallet::zkp::{OUTPUT_VK, SPEND_VK};
+	use ethcore_blockchain::zkp::{OUTPUT_VK, SPEND_VK};
 
 	use bellman::groth16::Proof;
 	use ff::PrimeField;
@@ -404,6 +507,8 @@ mod tests {
 		note_encryption::try_sapling_note_decryption,
 		sapling::Node,
 	};
+	use sapling_crypto::primitives::{PaymentAddress, ProofGenerationKey};
+	use zcash_proofs::prover::{OutputProof, SpendProof};
 	use zcash_proofs::sapling::SaplingVerificationContext;
 	// TODO(xin): Add tests for failure cases.
 	const TEST_CHAIN_ID: u64 = 2;
@@ -450,7 +555,7 @@ mod tests {
 		let result = builder.build();
 		assert!(result.is_ok());
 		let tx = result.unwrap();
-		let sighash = tx.hash(Some(TEST_CHAIN_ID)).into();
+		let sighash = tx.sighash(Some(TEST_CHAIN_ID), false).into();
 		// Verify input.
 		let mut ctx = SaplingVerificationContext::new();
 		// tx.get_nullifier_set()
@@ -463,7 +568,6 @@ mod tests {
 			spend_desc.anchor,
 			&spend_desc.nullifier,
 			spend_desc.rk,
-			// TODO(xin): Change to real sighash.
 			&sighash,
 			spend_desc.spend_auth_sig.unwrap(),
 			spend_proof,
@@ -585,6 +689,60 @@ mod tests {
 			.is_ok());
 	}
 
+	#[test]
+	fn build_transaction_with_mixed_outputs() {
+		let mut rng = OsRng::new().expect("should be able to construct RNG");
+
+		let extsk = SaplingExtendedSpendingKey::master(&[]);
+		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		let ovk = extfvk.fvk.ovk;
+		let to = extfvk.default_address().unwrap().1;
+
+		let mut builder = TransactionBuilder::new(
+			0.into(),
+			U256::from(0),
+			U256::from(21000),
+			vec![],
+			TEST_CHAIN_ID,
+		);
+
+		let note1 = to.create_note(1000, Fs::rand(&mut rng), &JUBJUB).unwrap();
+		let mut tree = CommitmentTree::new();
+		tree.append(Node::new(note1.cm(&JUBJUB).into_repr())).unwrap();
+		let inc_tree = IncrementalWitness::from_tree(&tree);
+		let witness1 = inc_tree.path().unwrap();
+		assert!(builder
+			.add_sapling_spend(extsk.expsk, to.diversifier, note1, inc_tree.root().into(), witness1)
+			.is_ok());
+
+		let call = Address::from("0000000000000000000000000000000000000005");
+		let payee = Address::from("0000000000000000000000000000000000000006");
+		assert!(builder
+			.add_sapling_output(ovk, to, &(U256::from(200) * CONVERSION_FACTOR), "")
+			.is_ok());
+		assert!(builder.add_public_output(call, &(U256::from(300) * CONVERSION_FACTOR)).is_ok());
+		assert!(builder.add_public_output(payee, &(U256::from(100) * CONVERSION_FACTOR)).is_ok());
+		assert_eq!(
+			builder.add_public_output(payee, &U256::zero()),
+			Err(Error(ErrorKind::InvalidAmount("0".into())))
+		);
+
+		let tx = builder.build().unwrap();
+		assert_eq!(tx.action, Action::PrivateCall(call));
+		assert_eq!(tx.value, U256::from(300) * CONVERSION_FACTOR);
+		assert_eq!(
+			tx.public_outputs(),
+			&[PublicOutput { address: payee, value: U256::from(100) * CONVERSION_FACTOR }][..]
+		);
+		// The recipient and the change.
+		assert_eq!(tx.v_shielded_output().len(), 2);
+		assert_eq!(tx.balancing_value(), 400);
+		assert!(tx
+			.sign_for_private(TEST_CHAIN_ID)
+			.verify_private_tx_basic()
+			.is_ok());
+	}
+
 	#[test]
 	fn build_transaction_with_public_input() {
 		let extsk = SaplingExtendedSpendingKey::master(&[]);
@@ -615,4 +773,114 @@ mod tests {
 		// Build failed because change is positive but there's no private input.
 		assert!(builder.build().is_err());
 	}
+
+	struct UnreachableProver;
+
+	impl TxProver for UnreachableProver {
+		fn spend_proof(
+			&self,
+			_proof_generation_key: ProofGenerationKey<Bls12>,
+			_diversifier: Diversifier,
+			_rcm: Fs,
+			_ar: Fs,
+			_value: u64,
+			_anchor: Fr,
+			_witness: CommitmentTreeWitness<Node>,
+		) -> Result<SpendProof, String> {
+			Err("Prover unreachable.".into())
+		}
+
+		fn output_proof(
+			&self,
+			_esk: Fs,
+			_payment_address: PaymentAddress<Bls12>,
+			_rcm: Fs,
+			_value: u64,
+		) -> Result<OutputProof, String> {
+			Err("Prover unreachable.".into())
+		}
+	}
+
+	#[test]
+	fn build_transaction_with_prover() {
+		let extsk = SaplingExtendedSpendingKey::master(&[]);
+		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		let to = extfvk.default_address().unwrap().1;
+
+		let mut builder = TransactionBuilder::new(0.into(), U256::from(0), U256::from(21000), vec![], TEST_CHAIN_ID);
+		builder.set_prover(Arc::new(UnreachableProver));
+		builder.set_public_input(U256::from(200) * CONVERSION_FACTOR);
+		assert!(builder
+			.add_sapling_output(extfvk.fvk.ovk, to, &(U256::from(200) * CONVERSION_FACTOR), "")
+			.is_ok());
+		assert_eq!(
+			builder.build().unwrap_err(),
+			Error(ErrorKind::Prover("Prover unreachable.".into()))
+		);
+	}
+
+	#[test]
+	fn build_coinbase_transaction() {
+		let extsk = SaplingExtendedSpendingKey::master(&[]);
+		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		let to = extfvk.default_address().unwrap().1;
+
+		let tx = build_coinbase(to.clone(), &(U256::from(500) * CONVERSION_FACTOR), TEST_CHAIN_ID, false).unwrap();
+		assert!(tx.is_coinbase());
+		assert_eq!(tx.balancing_value(), -500);
+		assert!(tx
+			.clone()
+			.sign_for_private(TEST_CHAIN_ID)
+			.verify_private_tx_basic()
+			.is_ok());
+
+		let outputs = tx.v_shielded_output();
+		let output_desc = &outputs[0];
+		let epk = output_desc.ephemeral_key.as_prime_order(&JUBJUB).unwrap();
+		let (note, address, _) = try_sapling_note_decryption(
+			&extfvk.fvk.vk.ivk(),
+			&epk,
+			&output_desc.cmu,
+			&output_desc.enc_ciphertext,
+		)
+		.unwrap();
+		assert_eq!(address, to);
+		assert_eq!(note.value, 500);
+
+		assert_eq!(
+			build_coinbase(to, &(CONVERSION_FACTOR + U256::from(1)), TEST_CHAIN_ID, false),
+			Err(Error(ErrorKind::ConversionFactorError))
+		);
+	}
+
+	#[test]
+	fn build_transaction_with_shielded_sighash() {
+		let extsk = SaplingExtendedSpendingKey::master(&[]);
+		let extfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		let to = extfvk.default_address().unwrap().1;
+
+		let tx = build_coinbase(to, &(U256::from(500) * CONVERSION_FACTOR), TEST_CHAIN_ID, true).unwrap();
+		let binding_sig_valid = |sighash: [u8; 32]| {
+			let mut ctx = SaplingVerificationContext::new();
+			for output_desc in tx.v_shielded_output() {
+				let zkproof = Proof::<Bls12>::read(&output_desc.zkproof[..]).unwrap();
+				assert!(ctx.check_output(
+					output_desc.cv,
+					output_desc.cmu,
+					output_desc.ephemeral_key,
+					zkproof,
+					&OUTPUT_VK,
+					&JUBJUB,
+				));
+			}
+			ctx.final_check(
+				tx.balancing_value(),
+				&sighash,
+				Signature::read(&tx.binding_sig()[..]).unwrap(),
+				&JUBJUB,
+			)
+		};
+		assert!(binding_sig_valid(tx.sighash(Some(TEST_CHAIN_ID), true).into()));
+		assert!(!binding_sig_valid(tx.hash(Some(TEST_CHAIN_ID)).into()));
+	}
 }
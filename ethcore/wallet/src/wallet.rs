@@ -0,0 +1,2217 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+use dir::Directories;
+use ethereum_types::{H160, H256, U256};
+use ff::{PrimeField, PrimeFieldRepr};
+use pairing::bls12_381::{Bls12, Fr, FrRepr};
+use parking_lot::Mutex as parking_Mutex;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{prelude::*, BufReader};
+use std::mem;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use ethcore_db as db;
+use kvdb::{DBTransaction, KeyValueDB};
+use rlp::{Decodable, DecoderError, Encodable, Rlp, RlpStream};
+
+use zcash_primitives::merkle_tree::CommitmentTree;
+use zcash_primitives::sapling::Node;
+use zcash_primitives::{
+	note_encryption::{try_sapling_output_recovery, Memo},
+	JUBJUB,
+};
+
+use crate::hd_seed::{
+	derive_account_key, diversifier_from_u64, diversifier_to_u64, HdAccount, HdSeed,
+	ACCOUNT_GAP_LIMIT, HD_SEED_FILE_NAME,
+};
+use crate::mnemonic::Mnemonic;
+use crate::scanner::{decrypt_block, DecryptedBlock, DecryptedOutput, ViewingKeys};
+use crate::key_store::{
+	decode_destination, decode_extended_full_viewing_key, decode_extended_proving_key,
+	decode_incoming_viewing_key, decode_payment_address, encode_extended_full_viewing_key,
+	encode_extended_proving_key, encode_incoming_viewing_key, encode_payment_address, KeyStore,
+	KEY_ITERATIONS,
+};
+use crate::sendmany::{SaplingNoteData, SaplingNoteEntry, SentNoteData};
+use crate::watch_only::{
+	load_watch_only_keys, save_watch_only_keys, WatchOnlyKey, WATCH_ONLY_FILE_NAME,
+};
+use crate::wallet_types::{
+	SaplingExtendedFullViewingKey, SaplingExtendedSpendingKey,
+	SaplingIncomingViewingKey, SaplingMerkleTree, SaplingOutputDescription, SaplingPaymentAddress,
+	SaplingSpendDescription, SaplingWitness, TxHash,
+};
+
+use crate::origo_account::OrigoAccount;
+use crate::origo_key_file::OrigoKeyFile;
+use common_types::encoded;
+use common_types::transaction::{
+	LocalizedTransaction, SyncTransaction, Transaction, UnverifiedTransaction,
+};
+use ethstore::ethkey::Password;
+use ethstore::{Error as KeyError, JsonCrypto};
+use std::collections::hash_set::HashSet;
+use std::fs;
+use std::io::Cursor;
+
+use backtrace::Backtrace;
+
+type SaplingIncomingViewingKeyMap = HashMap<SaplingPaymentAddress, SaplingIncomingViewingKey>;
+
+pub const WITNESS_CACHE_SIZE: usize = 100;
+/// Number of blocks after which a note stays locked by a transaction which is not mined.
+pub const LOCKED_NOTE_EXPIRY: u64 = 20;
+/// Number of confirmations after which a coinbase note can be spent.
+pub const COINBASE_MATURITY: u64 = 100;
+pub const DUMMY_WALLET_PASS: &'static str = "dummy_wallet_pass";
+
+// Keys of the wallet column. Notes are stored under the prefix followed by their nullifier,
+// sent notes under the prefix followed by their transaction hash.
+const WALLET_NOTE_KEY_PREFIX: u8 = 0;
+const WALLET_SENT_NOTES_KEY_PREFIX: u8 = 1;
+const WALLET_LAST_SCANNED_KEY: &'static [u8] = b"last_scanned";
+const WALLET_ADDRESSES_KEY: &'static [u8] = b"addresses";
+// Keys of the extra column the wallet was stored under before it had a column of its own.
+const LEGACY_WALLET_KEYS: [&'static [u8]; 2] = [b"wallet_txs", b"wallet_addresses"];
+
+fn note_key(nullifier: &U256) -> [u8; 33] {
+	let mut key = [0u8; 33];
+	key[0] = WALLET_NOTE_KEY_PREFIX;
+	nullifier.to_big_endian(&mut key[1..]);
+	key
+}
+
+fn sent_notes_key(hash: &TxHash) -> [u8; 33] {
+	let mut key = [0u8; 33];
+	key[0] = WALLET_SENT_NOTES_KEY_PREFIX;
+	key[1..].copy_from_slice(&hash[..]);
+	key
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WalletTxHashes {
+	// All the related transaction hashes in the wallet.
+	pub hashes: Vec<TxHash>,
+}
+
+impl WalletTxAddresses {
+	pub fn new() -> Self {
+		WalletTxAddresses { addresses: vec![] }
+	}
+}
+
+impl rlp::Encodable for WalletTxHashes {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(1);
+		s.append_list(&self.hashes);
+	}
+}
+
+impl rlp::Decodable for WalletTxHashes {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		Ok(WalletTxHashes {
+			hashes: rlp.list_at(0)?,
+		})
+	}
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct WalletTxAddresses {
+	// All the related transaction hashes in the wallet.
+	pub addresses: Vec<String>,
+}
+
+impl rlp::Encodable for WalletTxAddresses {
+	fn rlp_append(&self, s: &mut RlpStream) {
+		s.begin_list(self.addresses.len());
+		for b in &self.addresses {
+			s.append(b);
+		}
+	}
+}
+
+impl rlp::Decodable for WalletTxAddresses {
+	fn decode(rlp: &Rlp) -> Result<Self, DecoderError> {
+		let mut addresses = vec![];
+		for i in 0..rlp.item_count()? {
+			addresses.push(rlp.val_at(i)?);
+		}
+		Ok(WalletTxAddresses { addresses })
+	}
+}
+
+/// The addresses handed out for an account derived from the wallet seed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountAddresses {
+	pub index: u32,
+	/// The default address first, then the diversified ones.
+	pub addresses: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReloadBlockInfo {
+	pub block_hash: H256,
+	pub block: encoded::Block,
+	pub tx_hashes: HashSet<TxHash>,
+}
+
+/// A transaction receiving notes of the wallet, spending them, or sending notes from its accounts.
+#[derive(Clone)]
+pub struct WalletTransaction {
+	pub hash: TxHash,
+	/// The number of the block including the transaction.
+	pub block_number: u64,
+	/// The value of the notes received by the wallet.
+	pub received: u64,
+	/// The value of the notes of the wallet spent.
+	pub spent: u64,
+	/// The memos of the notes received, ordered by output.
+	pub memos: Vec<Memo>,
+	/// The notes sent by the wallet accounts, including the change.
+	pub sent: Vec<SentNoteData>,
+	/// Whether the transaction pays a block reward.
+	pub coinbase: bool,
+}
+
+impl WalletTransaction {
+	fn new(hash: TxHash, block_number: u64) -> Self {
+		WalletTransaction {
+			hash,
+			block_number,
+			received: 0,
+			spent: 0,
+			memos: Vec::new(),
+			sent: Vec::new(),
+			coinbase: false,
+		}
+	}
+
+	/// The change of the shielded balance of the wallet made by the transaction.
+	pub fn value_change(&self) -> i64 {
+		self.received as i64 - self.spent as i64
+	}
+}
+
+/// The notes of the wallet being rebuilt by a rescan, apart from the wallet.
+pub struct RescannedNotes {
+	nullifier_notes: HashMap<U256, SaplingNoteData>,
+	address_nullifiers: HashMap<SaplingPaymentAddress, Vec<U256>>,
+	tx_nullifiers: HashMap<H256, Vec<U256>>,
+	tx_sent_notes: HashMap<H256, Vec<SentNoteData>>,
+	changed_nullifiers: HashSet<U256>,
+	changed_sent_txs: HashSet<H256>,
+}
+
+pub struct Wallet {
+	pub nullifier_notes: HashMap<U256, SaplingNoteData>,
+	pub address_nullifiers: HashMap<SaplingPaymentAddress, Vec<U256>>,
+	pub tx_nullifiers: HashMap<H256, Vec<U256>>,
+	// The last canonical block the wallet has been synced with.
+	pub last_scanned_block: Option<H256>,
+	// The number of the last scanned block, the confirmations of the notes are counted from it.
+	pub last_scanned_number: u64,
+	// The notes sent by the wallet accounts, by transaction.
+	pub tx_sent_notes: HashMap<H256, Vec<SentNoteData>>,
+	// Notes added, updated or dropped since the last write.
+	changed_nullifiers: HashSet<U256>,
+	// Transactions whose sent notes were added or dropped since the last write.
+	changed_sent_txs: HashSet<H256>,
+	// Whether the keys of the legacy wallet data have been deleted from the db.
+	legacy_data_removed: bool,
+
+	pub key_store: KeyStore,
+	// The ZIP-32 seed accounts are derived from, created with the first account.
+	hd_seed: Option<HdSeed>,
+	// The password of the accounts being restored from a mnemonic phrase, to derive more of them
+	// while the last ones are used. Only kept in memory.
+	restore_password: Option<String>,
+	// The viewing keys imported without spending keys.
+	watch_only_keys: Vec<WatchOnlyKey>,
+	file_path: String,
+	file_name_prefix: String,
+}
+
+impl Wallet {
+	pub fn new_from_default_dir() -> Self {
+		let file_path = format!("{}", &Directories::default().wallet);
+		Wallet::new_from_dir(&file_path[..])
+	}
+
+	pub fn new_from_dir(file_path: &str) -> Self {
+		Wallet::new_from_file("wallet_", file_path)
+	}
+
+	pub fn new_from_file(file_name_prefix: &str, file_path: &str) -> Self {
+		let mut wallet = Wallet {
+			nullifier_notes: HashMap::new(),
+			address_nullifiers: HashMap::new(),
+			tx_nullifiers: HashMap::new(),
+			last_scanned_block: None,
+			last_scanned_number: 0,
+			tx_sent_notes: HashMap::new(),
+			changed_nullifiers: HashSet::new(),
+			changed_sent_txs: HashSet::new(),
+			legacy_data_removed: false,
+			key_store: KeyStore::new_with_file_prefix(file_name_prefix, file_path),
+			hd_seed: None,
+			restore_password: None,
+			watch_only_keys: Vec::new(),
+			file_path: file_path.into(),
+			file_name_prefix: file_name_prefix.into(),
+		};
+		wallet.load_from_encrypted_files();
+		wallet.hd_seed = HdSeed::load(Path::new(&wallet.hd_seed_path()))
+			.expect("Failed to load the wallet seed file");
+		wallet.load_diversified_addresses();
+		let watch_only_keys = load_watch_only_keys(Path::new(&wallet.watch_only_keys_path()))
+			.expect("Failed to load the wallet viewing keys file");
+		for key in watch_only_keys.iter() {
+			if wallet.add_watch_only_key(key).is_none() {
+				warn!(target: "wallet", "Invalid imported viewing key {}", key.key);
+			}
+		}
+		wallet.watch_only_keys = watch_only_keys;
+		wallet
+	}
+
+	fn hd_seed_path(&self) -> String {
+		format!("{}/{}{}", self.file_path, self.file_name_prefix, HD_SEED_FILE_NAME)
+	}
+
+	fn watch_only_keys_path(&self) -> String {
+		format!("{}/{}{}", self.file_path, self.file_name_prefix, WATCH_ONLY_FILE_NAME)
+	}
+
+	fn add_watch_only_key(&mut self, key: &WatchOnlyKey) -> Option<SaplingPaymentAddress> {
+		if let Some((xfvk, nsk)) = decode_extended_proving_key(&key.key) {
+			return self.key_store.add_watch_only_proving_key(xfvk, nsk, key.birthday);
+		}
+		match decode_extended_full_viewing_key(&key.key) {
+			Some(xfvk) => self.key_store.add_watch_only_extended_full_viewing_key(xfvk, key.birthday),
+			None => {
+				let ivk = decode_incoming_viewing_key(&key.key)?;
+				self.key_store.add_watch_only_incoming_viewing_key(ivk, key.birthday)
+			}
+		}
+	}
+
+	/// Import a Bech32 extended full viewing key or incoming viewing key, to watch
+	/// the notes it receives without being able to spend them. Returns an address of the key.
+	/// An extended proving key also lets the wallet prove the transactions spending the notes,
+	/// to be signed offline. The chain is scanned for its notes from `rescan_from`, or from the genesis.
+	pub fn import_viewing_key(&mut self, key: &str, rescan_from: Option<u64>) -> Result<String, KeyError> {
+		let ivk = decode_extended_proving_key(key)
+			.map(|(xfvk, _)| xfvk.fvk.vk.ivk())
+			.or_else(|| decode_extended_full_viewing_key(key).map(|xfvk| xfvk.fvk.vk.ivk()))
+			.or_else(|| decode_incoming_viewing_key(key))
+			.ok_or_else(|| KeyError::Custom("Invalid viewing key".into()))?;
+		if self.key_store.contains_viewing_key(&ivk) {
+			return Err(KeyError::Custom("The viewing key is already in the wallet".into()));
+		}
+
+		let key = WatchOnlyKey {
+			key: key.to_string(),
+			birthday: rescan_from.unwrap_or(0),
+		};
+		let address = self.add_watch_only_key(&key)
+			.ok_or_else(|| KeyError::Custom("No valid address for the viewing key".into()))?;
+		self.watch_only_keys.push(key);
+		save_watch_only_keys(Path::new(&self.watch_only_keys_path()), &self.watch_only_keys)?;
+		Ok(encode_payment_address(&address))
+	}
+
+	/// Export the Bech32 viewing key of a wallet address: its extended full viewing key,
+	/// or its incoming viewing key only if `incoming_only` is set.
+	pub fn export_viewing_key(&self, address: &str, incoming_only: bool) -> Result<String, KeyError> {
+		let address = decode_payment_address(address).ok_or(KeyError::InvalidAccount)?;
+		if incoming_only {
+			return self.key_store.get_incoming_viewing_key(&address)
+				.map(|ivk| encode_incoming_viewing_key(&ivk))
+				.ok_or(KeyError::InvalidAccount);
+		}
+		match self.key_store.get_extended_full_viewing_key(&address) {
+			Some(xfvk) => Ok(encode_extended_full_viewing_key(xfvk)),
+			None if self.key_store.get_incoming_viewing_key(&address).is_some() =>
+				Err(KeyError::Custom("Only the incoming viewing key of the address is known".into())),
+			None => Err(KeyError::InvalidAccount),
+		}
+	}
+
+	/// Export the Bech32 extended proving key of a wallet address, unlocked with `pass`.
+	/// Imported into a watch-only wallet, it proves the transactions spending the notes
+	/// of the address, which are signed offline with its spending key.
+	pub fn export_proving_key(&self, address: &str, pass: String) -> Result<String, KeyError> {
+		let payment_address = decode_payment_address(address).ok_or(KeyError::InvalidAccount)?;
+		if self.key_store.get_extended_full_viewing_key(&payment_address).is_none() {
+			return Err(KeyError::InvalidAccount);
+		}
+		let extsk = self.key_store.decrypt_key_file(address, pass).ok_or(KeyError::InvalidPassword)?;
+		let xfvk = SaplingExtendedFullViewingKey::from(&extsk);
+		Ok(encode_extended_proving_key(&xfvk, &extsk.expsk.nsk))
+	}
+
+	/// The block to scan the chain from for the viewing keys not included in `handled_addresses`.
+	pub fn rescan_height(&self, handled_addresses: &Option<HashSet<String>>) -> u64 {
+		self.key_store
+			.get_unhandled_viewing_keys(handled_addresses)
+			.keys()
+			.map(|ivk| self.key_store.get_birthday(ivk))
+			.min()
+			.unwrap_or(0)
+	}
+
+	// Register the diversified addresses handed out for the seed accounts.
+	fn load_diversified_addresses(&mut self) {
+		let accounts = match self.hd_seed {
+			Some(ref hd_seed) => hd_seed.accounts.clone(),
+			None => return,
+		};
+		for account in accounts.iter() {
+			let default_address = match decode_payment_address(&account.address) {
+				Some(address) => address,
+				None => continue,
+			};
+			let xfvk = match self.key_store.get_extended_full_viewing_key(&default_address) {
+				Some(xfvk) => xfvk.clone(),
+				None => {
+					warn!(target: "wallet", "Missing key file of account {}", account.index);
+					continue;
+				}
+			};
+			for index in account.diversifiers.iter() {
+				if let Ok((_, address)) = xfvk.address(diversifier_from_u64(*index)) {
+					self.key_store.add_diversified_address(&default_address, address);
+				}
+			}
+		}
+	}
+
+	/// Save internal state to file.
+	fn save_key_to_file(
+		&mut self,
+		espk: &SaplingExtendedSpendingKey,
+		pass: String,
+	) -> std::io::Result<()> {
+		self.key_store
+			.encrypt_key_file_and_add_viewing_key(espk, pass)?;
+		Ok(())
+	}
+
+	pub fn load_from_encrypted_files(&mut self) -> std::io::Result<()> {
+		let dir = format!("{}", self.file_path);
+
+		let paths = fs::read_dir(dir)?;
+
+		let mut key_store = KeyStore::new_with_file_prefix(&self.file_name_prefix[..], &self.file_path[..]);
+
+		for path in paths {
+			let mut file_name = format!("{}", path.unwrap().path().display());
+			file_name = file_name.as_str()[0..].to_string();
+
+			if file_name.ends_with(HD_SEED_FILE_NAME)
+				|| file_name.ends_with(WATCH_ONLY_FILE_NAME)
+				|| file_name.ends_with(".tmp")
+			{
+				continue;
+			}
+
+			if file_name.contains(&self.file_name_prefix[..]) {
+				//println!("{}", file_name);
+				let mut file = File::open(file_name);
+				let key_file = OrigoKeyFile::load(&file.unwrap()).unwrap();
+				let origo_account = OrigoAccount::from_file(key_file, None);
+
+				let origo_account = origo_account.unwrap();
+				//Only decode full viewing keys using blank password
+				let none_pass = Password::from("");
+				let secret_efvk = origo_account.efvk.decrypt(&none_pass).unwrap();
+				let mut rdr_efvk = Cursor::new(secret_efvk);
+				let efvk = SaplingExtendedFullViewingKey::read(&mut rdr_efvk).unwrap();
+
+				let (_, address) = efvk.default_address().unwrap();
+
+				key_store.add_extended_full_viewing_key(efvk, address);
+			}
+		}
+		self.key_store = key_store;
+		Ok(())
+	}
+
+	pub fn add_address_from_seed(&mut self, seed: [u8; 32], pass: String) -> (String, SaplingExtendedSpendingKey)  {
+		let xsk = SaplingExtendedSpendingKey::master(&seed);
+
+		// Store the keys into key_store and serialize.
+		let xfvk = SaplingExtendedFullViewingKey::from(&xsk);
+		let (_, address) = xfvk.default_address().unwrap();
+
+		self.save_key_to_file(&xsk, pass);
+
+		(encode_payment_address(&address), xsk)
+	}
+
+	pub fn list_addresses(&self) -> Vec<String> {
+		self.key_store
+			.get_sapling_payment_addresses()
+			.iter()
+			.map(|payment_address| encode_payment_address(payment_address))
+			.collect()
+	}
+
+	/// Clear the notes data in wallet.
+	pub fn clear_wallet_data(&mut self) {
+		self.changed_nullifiers.extend(self.nullifier_notes.keys().cloned());
+		self.nullifier_notes.clear();
+		self.tx_nullifiers.clear();
+		self.address_nullifiers.clear();
+		self.changed_sent_txs.extend(self.tx_sent_notes.keys().cloned());
+		self.tx_sent_notes.clear();
+	}
+
+	/// Write the notes changed since the last write, the handled addresses and the last
+	/// scanned block into db. The batch is expected to be committed together with the block
+	/// it was synced to, so that the stored wallet always matches the stored chain.
+	pub fn write_wallet_data(&mut self, batch: &mut DBTransaction) {
+		for nullifier in self.changed_nullifiers.drain() {
+			match self.nullifier_notes.get(&nullifier) {
+				Some(note) => batch.put(db::COL_WALLET, &note_key(&nullifier), &::rlp::encode(note)),
+				None => batch.delete(db::COL_WALLET, &note_key(&nullifier)),
+			}
+		}
+
+		for hash in self.changed_sent_txs.drain() {
+			match self.tx_sent_notes.get(&hash) {
+				Some(notes) => batch.put(db::COL_WALLET, &sent_notes_key(&hash), &::rlp::encode_list(notes)),
+				None => batch.delete(db::COL_WALLET, &sent_notes_key(&hash)),
+			}
+		}
+
+		if !self.legacy_data_removed {
+			for key in LEGACY_WALLET_KEYS.iter() {
+				batch.delete(db::COL_EXTRA, key);
+			}
+			self.legacy_data_removed = true;
+		}
+
+		if let Some(ref hash) = self.last_scanned_block {
+			batch.put(db::COL_WALLET, WALLET_LAST_SCANNED_KEY, &::rlp::encode(hash));
+		}
+
+		let encoded = ::rlp::encode(&self.get_wallet_addresses());
+		batch.put(db::COL_WALLET, WALLET_ADDRESSES_KEY, &encoded);
+	}
+
+	/// Replace the notes and the last scanned block with the ones stored in db.
+	/// Returns the addresses handled by the stored notes, if any.
+	pub fn load_wallet_data(&mut self, db: &KeyValueDB) -> Option<WalletTxAddresses> {
+		let prefix = [WALLET_NOTE_KEY_PREFIX];
+		self.nullifier_notes = db
+			.iter_from_prefix(db::COL_WALLET, &prefix)
+			// iterator may continue beyond values beginning with this prefix.
+			.take_while(|(key, _)| key.starts_with(&prefix))
+			.filter(|(key, _)| key.len() == 33)
+			.map(|(key, val)| {
+				let note: SaplingNoteData = ::rlp::decode(&val)
+					.expect("decode error: the db is corrupted or the data structure has changed");
+				(U256::from(&key[1..]), note)
+			})
+			.collect();
+		self.changed_nullifiers.clear();
+		self.rebuild_nullifier_indexes();
+
+		let prefix = [WALLET_SENT_NOTES_KEY_PREFIX];
+		self.tx_sent_notes = db
+			.iter_from_prefix(db::COL_WALLET, &prefix)
+			.take_while(|(key, _)| key.starts_with(&prefix))
+			.filter(|(key, _)| key.len() == 33)
+			.map(|(key, val)| {
+				let notes: Vec<SentNoteData> = ::rlp::decode_list(&val);
+				(H256::from(&key[1..]), notes)
+			})
+			.collect();
+		self.changed_sent_txs.clear();
+
+		self.last_scanned_block = db
+			.get(db::COL_WALLET, WALLET_LAST_SCANNED_KEY)
+			.expect("Low-level database error when fetching 'last_scanned'. Some issue with disk?")
+			.map(|hash| ::rlp::decode(&hash).expect("decode error: the db is corrupted or the data structure has changed"));
+
+		db.get(db::COL_WALLET, WALLET_ADDRESSES_KEY)
+			.expect("Low-level database error when fetching wallet addresses. Some issue with disk?")
+			.map(|addresses| ::rlp::decode(&addresses).expect("decode error: the db is corrupted or the data structure has changed"))
+	}
+
+	/// Get all the addresses in wallet.
+	pub fn get_wallet_addresses(&self) -> WalletTxAddresses {
+		WalletTxAddresses {
+			addresses: self.list_addresses(),
+		}
+	}
+
+	/// Add the SaplingNote on the new block.
+	pub fn sync_new_block(
+		&mut self,
+		mut parent_commitment_tree: &mut CommitmentTree<Node>,
+		pblock: &encoded::Block,
+	) {
+		let viewing_keys = self.key_store.get_unhandled_viewing_keys(&None);
+		let block = decrypt_block(pblock, &viewing_keys);
+		self.sync_decrypted_block(parent_commitment_tree, &block, &viewing_keys);
+	}
+
+	/// Add the notes of a new block decrypted with `viewing_keys`, the keys of all the addresses.
+	pub fn sync_decrypted_block(
+		&mut self,
+		mut parent_commitment_tree: &mut CommitmentTree<Node>,
+		block: &DecryptedBlock,
+		viewing_keys: &ViewingKeys,
+	) {
+		self.process_new_block(parent_commitment_tree, block, viewing_keys, &None, &(0..=u64::max_value()));
+		self.last_scanned_block = Some(block.block.hash());
+		self.last_scanned_number = block.block.view().header().number();
+		self.release_expired_locks();
+	}
+
+	/// Reload the SaplingNote on the block for filtered_txs.
+	pub fn reload_new_block(
+		&mut self,
+		mut parent_commitment_tree: &mut CommitmentTree<Node>,
+		pblock: &encoded::Block,
+		filtered_tx_hashes: &Option<HashSet<TxHash>>,
+	) {
+		let viewing_keys = self.key_store.get_unhandled_viewing_keys(&None);
+		let block = decrypt_block(pblock, &viewing_keys);
+		self.process_new_block(
+			parent_commitment_tree,
+			&block,
+			&viewing_keys,
+			filtered_tx_hashes,
+			&(0..=u64::max_value()),
+		);
+	}
+
+	/// Scan the blockchain for new imported users.
+	pub fn scan_new_block(
+		&mut self,
+		mut parent_commitment_tree: &mut CommitmentTree<Node>,
+		pblock: &encoded::Block,
+		handled_addresses: &Option<HashSet<String>>,
+	) {
+		let viewing_keys = self.key_store.get_unhandled_viewing_keys(handled_addresses);
+		let block = decrypt_block(pblock, &viewing_keys);
+		self.scan_decrypted_block(parent_commitment_tree, &block, &viewing_keys);
+	}
+
+	/// Add the notes of a block already scanned, decrypted with `viewing_keys`, the keys
+	/// of the new imported users.
+	pub fn scan_decrypted_block(
+		&mut self,
+		mut parent_commitment_tree: &mut CommitmentTree<Node>,
+		block: &DecryptedBlock,
+		viewing_keys: &ViewingKeys,
+	) {
+		self.process_new_block(parent_commitment_tree, block, viewing_keys, &None, &(0..=u64::max_value()));
+	}
+
+	/// A copy of the notes of the wallet to rescan the blocks of `heights` for `viewing_keys`,
+	/// without the notes they received and sent there and the spends of their notes from
+	/// the start of `heights`. The wallet itself is left untouched until `finish_rescan`.
+	pub fn start_rescan(&mut self, viewing_keys: &ViewingKeys, heights: &RangeInclusive<u64>) -> RescannedNotes {
+		let mut notes = RescannedNotes {
+			nullifier_notes: self.nullifier_notes.clone(),
+			address_nullifiers: HashMap::new(),
+			tx_nullifiers: HashMap::new(),
+			tx_sent_notes: self.tx_sent_notes.clone(),
+			changed_nullifiers: HashSet::new(),
+			changed_sent_txs: HashSet::new(),
+		};
+		self.swap_notes(&mut notes);
+		self.clear_notes(viewing_keys, heights);
+		self.swap_notes(&mut notes);
+		notes
+	}
+
+	/// Add to `notes` the notes of a block rescanned for `viewing_keys`. Blocks after the
+	/// rescanned heights are expected to be decrypted with no keys, only the witnesses of
+	/// the notes created in `rescanned` are advanced and the spends marked on them.
+	pub fn rescan_decrypted_block(
+		&mut self,
+		notes: &mut RescannedNotes,
+		mut parent_commitment_tree: &mut CommitmentTree<Node>,
+		block: &DecryptedBlock,
+		viewing_keys: &ViewingKeys,
+		rescanned: &RangeInclusive<u64>,
+	) {
+		self.swap_notes(notes);
+		self.process_new_block(parent_commitment_tree, block, viewing_keys, &None, rescanned);
+		self.swap_notes(notes);
+	}
+
+	/// Replace the notes of the wallet with the rescanned ones. The notes locked or
+	/// unlocked while the rescan was running keep their lock.
+	pub fn finish_rescan(&mut self, mut notes: RescannedNotes) {
+		for (nullifier, note) in notes.nullifier_notes.iter_mut() {
+			if let Some(current) = self.nullifier_notes.get(nullifier) {
+				note.spendHeight = current.spendHeight;
+			}
+		}
+		self.nullifier_notes = notes.nullifier_notes;
+		self.tx_sent_notes = notes.tx_sent_notes;
+		self.changed_nullifiers.extend(notes.changed_nullifiers);
+		self.changed_sent_txs.extend(notes.changed_sent_txs);
+		self.rebuild_nullifier_indexes();
+	}
+
+	// Exchange the notes of the wallet and their indexes with `notes`.
+	fn swap_notes(&mut self, notes: &mut RescannedNotes) {
+		mem::swap(&mut self.nullifier_notes, &mut notes.nullifier_notes);
+		mem::swap(&mut self.address_nullifiers, &mut notes.address_nullifiers);
+		mem::swap(&mut self.tx_nullifiers, &mut notes.tx_nullifiers);
+		mem::swap(&mut self.tx_sent_notes, &mut notes.tx_sent_notes);
+		mem::swap(&mut self.changed_nullifiers, &mut notes.changed_nullifiers);
+		mem::swap(&mut self.changed_sent_txs, &mut notes.changed_sent_txs);
+	}
+
+	// Drop the notes received and sent by `viewing_keys` in the blocks of `heights`,
+	// and forget the spends of their notes from the start of `heights`.
+	fn clear_notes(&mut self, viewing_keys: &ViewingKeys, heights: &RangeInclusive<u64>) {
+		let removed: Vec<U256> = self.nullifier_notes
+			.iter()
+			.filter(|(_, note)| viewing_keys.contains_key(&note.ivk) && heights.contains(&note.createHeight))
+			.map(|(nullifier, _)| nullifier.clone())
+			.collect();
+		for nullifier in removed {
+			self.nullifier_notes.remove(&nullifier);
+			self.changed_nullifiers.insert(nullifier);
+		}
+		for (nullifier, note) in self.nullifier_notes.iter_mut() {
+			if !viewing_keys.contains_key(&note.ivk) {
+				continue;
+			}
+			if note.confirmHeight.map_or(false, |height| height >= *heights.start()) {
+				note.confirmHeight = None;
+				self.changed_nullifiers.insert(nullifier.clone());
+			}
+		}
+
+		let changed_sent_txs = &mut self.changed_sent_txs;
+		self.tx_sent_notes.retain(|hash, notes| {
+			let count = notes.len();
+			notes.retain(|note| !(viewing_keys.contains_key(&note.ivk) && heights.contains(&note.createHeight)));
+			if notes.len() != count {
+				changed_sent_txs.insert(hash.clone());
+			}
+			!notes.is_empty()
+		});
+
+		self.rebuild_nullifier_indexes();
+	}
+
+	/// This function process the transaction in block to receive the sapling note into wallet.
+	fn process_new_block(
+		&mut self,
+		mut parent_commitment_tree: &mut CommitmentTree<Node>,
+		block: &DecryptedBlock,
+		viewing_keys: &ViewingKeys,
+		filtered_tx_hashes: &Option<HashSet<TxHash>>,
+		witnessed_heights: &RangeInclusive<u64>,
+	) {
+		let block_height = block.block.view().header().number();
+		let mut note_commitment_index: usize = 0;
+		let mut note_commitment_list = vec![];
+
+		for tx in block.txs.iter() {
+			for item in tx.v_shielded_output().iter() {
+				note_commitment_list.push(Node::new(item.cmu.into_repr()));
+			}
+		}
+		if filtered_tx_hashes.is_none() {
+			self.advance_witnesses(viewing_keys, &note_commitment_list, witnessed_heights);
+		}
+		for (tx_index, tx) in block.txs.iter().enumerate() {
+			self.receive_spling_notes_into_wallet(
+				&block_height,
+				tx,
+				tx_index,
+				&block.outputs,
+				&mut parent_commitment_tree,
+				viewing_keys,
+				filtered_tx_hashes,
+				&note_commitment_list,
+				&mut note_commitment_index,
+			);
+		}
+	}
+
+	/// Append the note commitments of a new block to the witnesses of the notes
+	/// received by the given viewing keys in `created_heights`, keeping the last
+	/// WITNESS_CACHE_SIZE of them.
+	fn advance_witnesses(
+		&mut self,
+		viewing_keys: &ViewingKeys,
+		note_commitment_list: &Vec<Node>,
+		created_heights: &RangeInclusive<u64>,
+	) {
+		for (nullifier, note_data) in self.nullifier_notes.iter_mut() {
+			if !viewing_keys.contains_key(&note_data.ivk) || !created_heights.contains(&note_data.createHeight) {
+				continue;
+			}
+			if let Some(mut witness) = note_data.front() {
+				for cm in note_commitment_list.iter() {
+					witness.append(cm.clone()).expect("failed to append note");
+				}
+				note_data.push_front(witness);
+				if note_data.witnesses.len() > WITNESS_CACHE_SIZE {
+					note_data.pop_back();
+				}
+				self.changed_nullifiers.insert(nullifier.clone());
+			}
+		}
+	}
+
+	/// Undo the witness updates and spends of the blocks retracted down to the
+	/// common ancestor at `ancestor_number`. The notes locked while a retracted block was
+	/// the last scanned stay locked, from the ancestor.
+	pub fn rewind_retracted_blocks(&mut self, retracted: usize, ancestor_number: u64) {
+		for (nullifier, note_data) in self.nullifier_notes.iter_mut() {
+			let mut changed = false;
+			for _ in 0..retracted {
+				// the oldest witness cached is kept when the reorg is deeper than the cache.
+				if note_data.witnesses.len() > 1 {
+					note_data.pop_front();
+					changed = true;
+				}
+			}
+			if note_data.confirmHeight.map_or(false, |height| height > ancestor_number) {
+				note_data.confirmHeight = None;
+				changed = true;
+			}
+			if note_data.spendHeight.map_or(false, |height| height > ancestor_number) {
+				note_data.spendHeight = Some(ancestor_number);
+				changed = true;
+			}
+			if changed {
+				self.changed_nullifiers.insert(nullifier.clone());
+			}
+		}
+		self.last_scanned_number = ancestor_number;
+	}
+
+	/// Lock the notes spent by a transaction created by the wallet,
+	/// so that they aren't selected again while the transaction is not mined.
+	pub fn lock_spent_notes(&mut self, tx: &Transaction) {
+		let last_scanned_number = self.last_scanned_number;
+		for spend in tx.v_shielded_spend().iter() {
+			let nullifier = U256::from(spend.nullifier);
+			if let Some(note) = self.nullifier_notes.get_mut(&nullifier) {
+				note.spendHeight = Some(last_scanned_number);
+				self.changed_nullifiers.insert(nullifier);
+			}
+		}
+	}
+
+	/// Unlock the notes spent by a transaction which couldn't be sent.
+	pub fn unlock_spent_notes(&mut self, tx: &Transaction) {
+		for spend in tx.v_shielded_spend().iter() {
+			let nullifier = U256::from(spend.nullifier);
+			if let Some(note) = self.nullifier_notes.get_mut(&nullifier) {
+				note.spendHeight = None;
+				self.changed_nullifiers.insert(nullifier);
+			}
+		}
+	}
+
+	// Unlock the notes whose spending transaction hasn't been mined within LOCKED_NOTE_EXPIRY blocks.
+	fn release_expired_locks(&mut self) {
+		let last_scanned_number = self.last_scanned_number;
+		for (nullifier, note) in self.nullifier_notes.iter_mut() {
+			let expired = note.spendHeight
+				.map_or(false, |height| height + LOCKED_NOTE_EXPIRY < last_scanned_number);
+			if expired && note.confirmHeight.is_none() {
+				note.spendHeight = None;
+				self.changed_nullifiers.insert(nullifier.clone());
+			}
+		}
+	}
+
+	/// The number of blocks mined on top of the note, including its own block.
+	pub fn confirmations(&self, note: &SaplingNoteData) -> u64 {
+		self.block_confirmations(note.createHeight)
+	}
+
+	/// The number of blocks scanned on top of the block of the given number, including itself.
+	pub fn block_confirmations(&self, block_number: u64) -> u64 {
+		if self.last_scanned_number < block_number {
+			return 0;
+		}
+		self.last_scanned_number - block_number + 1
+	}
+
+	/// Delete the SaplingNote on the retracted txs.
+	pub fn remove_retracted_txs(&mut self, retracted_tx_hashes: &Vec<H256>) {
+		for hash in retracted_tx_hashes.iter() {
+			if let Some(nullifiers) = self.tx_nullifiers.get(hash) {
+				for nullifier in nullifiers.iter() {
+					self.nullifier_notes.remove(nullifier);
+					self.changed_nullifiers.insert(nullifier.clone());
+				}
+			}
+			if self.tx_sent_notes.remove(hash).is_some() {
+				self.changed_sent_txs.insert(hash.clone());
+			}
+		}
+
+		self.rebuild_nullifier_indexes();
+	}
+
+	/// Reconstruct the tx_nullifiers and address_nullifiers data from the notes.
+	fn rebuild_nullifier_indexes(&mut self) {
+		self.tx_nullifiers.clear();
+		self.address_nullifiers.clear();
+		for (nullifier, salingNote) in self.nullifier_notes.iter() {
+			match self.tx_nullifiers.get_mut(&salingNote.hash) {
+				None => {
+					let mut nullifiers = Vec::new();
+					nullifiers.push(nullifier.clone());
+					self.tx_nullifiers
+						.insert(salingNote.hash.clone(), nullifiers);
+				}
+				Some(mut v) => {
+					v.push(nullifier.clone());
+				}
+			}
+			match self.address_nullifiers.get_mut(&salingNote.address) {
+				None => {
+					let mut nullifiers = Vec::new();
+					nullifiers.push(nullifier.clone());
+					self.address_nullifiers
+						.insert(salingNote.address.clone(), nullifiers);
+				}
+				Some(mut v) => {
+					v.push(nullifier.clone());
+				}
+			}
+		}
+	}
+
+	/// This function process the private transaction, receiving
+	/// the sapling notes decrypted from its outputs into the wallet.
+	fn receive_spling_notes_into_wallet(
+		&mut self,
+		block_height: &u64,
+		tx: &LocalizedTransaction,
+		tx_index: usize,
+		decrypted_outputs: &HashMap<(usize, usize), DecryptedOutput>,
+		mut commitment_tree: &mut CommitmentTree<Node>,
+		handled_viewing_keys: &ViewingKeys,
+		filtered_tx_hashes_option: &Option<HashSet<TxHash>>,
+		note_commitment_list: &Vec<Node>,
+		commitment_index: &mut usize,
+	) {
+		let hash = tx.hash();
+		for (i, output) in tx.v_shielded_output().iter().enumerate() {
+			let current_commitment_index = *commitment_index;
+			commitment_tree.append(note_commitment_list[current_commitment_index].clone());
+			*commitment_index += 1;
+			if let Some(filtered_tx_hashes) = filtered_tx_hashes_option {
+				if !filtered_tx_hashes.contains(&hash) {
+					return;
+				}
+			}
+			let decrypted = decrypted_outputs.get(&(tx_index, i));
+			if let Some((decrypted, fvk)) = decrypted.and_then(|decrypted| {
+				handled_viewing_keys.get(&decrypted.ivk).map(|fvk| (decrypted, fvk))
+			}) {
+				// Only the witnesses of the notes received are built.
+				let mut front_op = SaplingWitness::from_tree(&commitment_tree);
+				for successor_index in *commitment_index..note_commitment_list.len() {
+					front_op.append(note_commitment_list[successor_index].clone());
+				}
+				// Notes of incoming viewing keys are keyed by their commitment,
+				// their nullifier being unknown.
+				let nullifier = match fvk {
+					Some(fvk) => {
+						let position = front_op.position();
+						let nullifier_vec = decrypted.note.clone().nf(&fvk.vk, position as u64, &JUBJUB);
+						let mut nullifier_arr = [0u8; 32];
+						nullifier_arr.copy_from_slice(&nullifier_vec);
+						U256::from(nullifier_arr)
+					}
+					None => {
+						let mut cm_arr = [0u8; 32];
+						output.cmu.into_repr().write_be(&mut cm_arr[..])
+							.expect("32 bytes fit a commitment; qed");
+						U256::from(cm_arr)
+					}
+				};
+
+				let address = decrypted.address.clone();
+				let mut note_data = SaplingNoteData::new(
+					address.clone(),
+					decrypted.note.clone(),
+					decrypted.memo.clone(),
+					block_height.clone(),
+					decrypted.ivk.clone(),
+					nullifier.clone(),
+					hash.clone(),
+					i,
+				);
+				if fvk.is_none() {
+					note_data.nullifier = None;
+				}
+				note_data.coinbase = tx.is_coinbase();
+				note_data.push_front(front_op);
+
+				match self.tx_nullifiers.get_mut(&hash) {
+					None => {
+						let mut nullifiers = Vec::new();
+						nullifiers.push(nullifier.clone());
+						self.tx_nullifiers.insert(hash.clone(), nullifiers);
+					}
+					Some(mut v) => {
+						v.push(nullifier);
+					}
+				}
+
+				match self.address_nullifiers.get_mut(&address) {
+					None => {
+						let mut nullifiers = Vec::new();
+						nullifiers.push(nullifier.clone());
+						self.address_nullifiers.insert(address.clone(), nullifiers);
+					}
+					Some(mut v) => {
+						v.push(nullifier.clone());
+					}
+				}
+
+				assert!(!self.nullifier_notes.contains_key(&nullifier));
+				self.nullifier_notes.insert(nullifier.clone(), note_data);
+				self.changed_nullifiers.insert(nullifier.clone());
+			}
+			self.recover_sent_note(block_height, &hash, i, output, handled_viewing_keys);
+		}
+
+		// Mark the spend notes as confirmed.
+		for spend in tx.v_shielded_spend().iter() {
+			let nullifier = U256::from(spend.nullifier);
+			match self.nullifier_notes.get_mut(&nullifier) {
+				Some(mut note) => {
+					note.confirmHeight = Some(block_height.clone());
+					self.changed_nullifiers.insert(nullifier);
+				}
+				None => {}
+			}
+		}
+	}
+
+	/// Record the output as a sent note if it can be recovered with the outgoing viewing key
+	/// of one of the accounts, which is the case for the outputs created by the account.
+	fn recover_sent_note(
+		&mut self,
+		block_height: &u64,
+		hash: &TxHash,
+		index: usize,
+		output: &SaplingOutputDescription,
+		handled_viewing_keys: &ViewingKeys,
+	) {
+		let epk = match output.ephemeral_key.as_prime_order(&JUBJUB) {
+			Some(epk) => epk,
+			None => return,
+		};
+		if self
+			.tx_sent_notes
+			.get(hash)
+			.map_or(false, |notes| notes.iter().any(|note| note.index == index))
+		{
+			return;
+		}
+
+		for (ivk, fvk) in handled_viewing_keys.iter() {
+			let fvk = match fvk {
+				Some(fvk) => fvk,
+				None => continue,
+			};
+			if let Some((note, address, memo)) = try_sapling_output_recovery(
+				&fvk.ovk,
+				&output.cv,
+				&output.cmu,
+				&epk,
+				&output.enc_ciphertext,
+				&output.out_ciphertext,
+			) {
+				self.changed_sent_txs.insert(hash.clone());
+				self.tx_sent_notes
+					.entry(hash.clone())
+					.or_insert_with(Vec::new)
+					.push(SentNoteData {
+						address,
+						value: note.value,
+						memo,
+						ivk: ivk.clone(),
+						createHeight: block_height.clone(),
+						hash: hash.clone(),
+						index,
+					});
+				break;
+			}
+		}
+	}
+
+	/// The notes sent by the account of `ivk`, or by all the accounts, ordered by block.
+	pub fn get_sent_notes(&self, ivk: Option<&SaplingIncomingViewingKey>) -> Vec<&SentNoteData> {
+		let mut notes = self
+			.tx_sent_notes
+			.values()
+			.flat_map(|notes| notes.iter())
+			.filter(|note| ivk.map_or(true, |ivk| note.ivk == *ivk))
+			.collect::<Vec<_>>();
+		notes.sort_by_key(|note| (note.createHeight, note.hash, note.index));
+		notes
+	}
+
+	/// The notes received and sent by the wallet in the transaction.
+	pub fn get_transaction_notes(&self, hash: &TxHash) -> (Vec<&SaplingNoteData>, Vec<&SentNoteData>) {
+		let mut received = self
+			.tx_nullifiers
+			.get(hash)
+			.map(|nullifiers| {
+				nullifiers
+					.iter()
+					.filter_map(|nullifier| self.nullifier_notes.get(nullifier))
+					.collect::<Vec<_>>()
+			})
+			.unwrap_or_default();
+		received.sort_by_key(|note| note.index);
+		let sent = self
+			.tx_sent_notes
+			.get(hash)
+			.map(|notes| notes.iter().collect())
+			.unwrap_or_default();
+		(received, sent)
+	}
+
+	/// The transactions of the wallet, or of the account of `ivk`, ordered by block.
+	/// The transaction spending a note is the one revealing its nullifier on the chain,
+	/// as given by `nullifier_transaction`. The spends of the notes of incoming viewing
+	/// keys are unknown.
+	pub fn list_transactions<F>(
+		&self,
+		ivk: Option<&SaplingIncomingViewingKey>,
+		nullifier_transaction: F,
+	) -> Vec<WalletTransaction>
+	where
+		F: Fn(&U256) -> Option<TxHash>,
+	{
+		let mut transactions: HashMap<TxHash, WalletTransaction> = HashMap::new();
+		let mut received = self
+			.nullifier_notes
+			.values()
+			.filter(|note| ivk.map_or(true, |ivk| note.ivk == *ivk))
+			.collect::<Vec<_>>();
+		received.sort_by_key(|note| note.index);
+		for note in received {
+			{
+				let tx = transactions
+					.entry(note.hash.clone())
+					.or_insert_with(|| WalletTransaction::new(note.hash.clone(), note.createHeight));
+				tx.received += note.note.value;
+				tx.memos.push(note.memo.clone());
+				tx.coinbase |= note.coinbase;
+			}
+			if let (Some(height), Some(nullifier)) = (note.confirmHeight, note.nullifier) {
+				if let Some(hash) = nullifier_transaction(&nullifier) {
+					transactions
+						.entry(hash.clone())
+						.or_insert_with(|| WalletTransaction::new(hash, height))
+						.spent += note.note.value;
+				}
+			}
+		}
+		for note in self.get_sent_notes(ivk) {
+			transactions
+				.entry(note.hash.clone())
+				.or_insert_with(|| WalletTransaction::new(note.hash.clone(), note.createHeight))
+				.sent
+				.push(note.clone());
+		}
+
+		let mut transactions = transactions.into_iter().map(|(_, tx)| tx).collect::<Vec<_>>();
+		transactions.sort_by_key(|tx| (tx.block_number, tx.hash));
+		transactions
+	}
+
+	/// Derive the next account from the wallet seed and return its default address.
+	/// The seed is created with the first account from a new mnemonic phrase, encrypted
+	/// with its password. All the accounts of the wallet share that password: any other
+	/// one is rejected.
+	pub fn create_new_private_address(&mut self, pass: String) -> Result<String, KeyError> {
+		let mut hd_seed = match self.hd_seed.take() {
+			Some(hd_seed) => hd_seed,
+			None => HdSeed::from_mnemonic(&Mnemonic::generate(), &Password::from(pass.clone()), *KEY_ITERATIONS)?,
+		};
+		let result = self.add_hd_account(&mut hd_seed, pass);
+		self.hd_seed = Some(hd_seed);
+		result
+	}
+
+	/// Return the mnemonic phrase of the wallet seed, the only backup of the seed. The seed
+	/// is created from a new phrase with its first account if the wallet has none yet.
+	pub fn new_mnemonic(&mut self, password: String) -> Result<String, KeyError> {
+		if self.hd_seed.is_none() {
+			self.create_new_private_address(password.clone())?;
+		}
+		let hd_seed = self.hd_seed.as_ref().expect("the seed is created with the first account; qed");
+		let mnemonic = hd_seed.decrypt_mnemonic(&Password::from(password))
+			.map_err(|_| KeyError::Custom("Wrong wallet password, all the accounts share the password of the wallet seed".into()))?
+			.ok_or_else(|| KeyError::Custom("The wallet seed wasn't created from a mnemonic phrase".into()))?;
+		Ok(mnemonic.phrase())
+	}
+
+	/// Restore the wallet seed from a mnemonic phrase and return the derived accounts.
+	/// The first `ACCOUNT_GAP_LIMIT` accounts are derived, more are derived by
+	/// `finish_mnemonic_restore` while the last ones received notes.
+	pub fn restore_mnemonic(&mut self, phrase: &str, password: String) -> Result<Vec<String>, KeyError> {
+		let mnemonic = Mnemonic::from_phrase(phrase).map_err(|e| KeyError::Custom(e.into()))?;
+		if self.hd_seed.is_some() {
+			return Err(KeyError::Custom("The wallet already has a seed".into()));
+		}
+
+		let mut hd_seed = HdSeed::from_mnemonic(&mnemonic, &Password::from(password.clone()), *KEY_ITERATIONS)?;
+		hd_seed.restore_pending = true;
+		let mut addresses = Vec::new();
+		for _ in 0..ACCOUNT_GAP_LIMIT {
+			addresses.push(self.add_hd_account(&mut hd_seed, password.clone())?);
+		}
+		self.hd_seed = Some(hd_seed);
+		self.restore_password = Some(password);
+		Ok(addresses)
+	}
+
+	/// Once the chain has been scanned for the accounts restored from a mnemonic phrase,
+	/// derive more of them until the last `ACCOUNT_GAP_LIMIT` ones received no notes, and
+	/// return the new addresses, to scan the chain for. Without new addresses, the unused
+	/// accounts after the last used one are pruned and the restore is over.
+	pub fn finish_mnemonic_restore(&mut self) -> Result<Vec<String>, KeyError> {
+		let hd_seed_path = self.hd_seed_path();
+		let mut hd_seed = match self.hd_seed.take() {
+			Some(hd_seed) => hd_seed,
+			None => return Ok(Vec::new()),
+		};
+		let result = self.restore_accounts(&mut hd_seed, &hd_seed_path);
+		self.hd_seed = Some(hd_seed);
+		result
+	}
+
+	fn restore_accounts(&mut self, hd_seed: &mut HdSeed, hd_seed_path: &str) -> Result<Vec<String>, KeyError> {
+		if !hd_seed.restore_pending {
+			return Ok(Vec::new());
+		}
+
+		let used_ivks = self.nullifier_notes.values().map(|note| note.ivk).collect::<HashSet<_>>();
+		let key_store = &self.key_store;
+		let account_address = |account: &HdAccount| decode_payment_address(&account.address);
+		let last_used = hd_seed.accounts.iter()
+			.filter(|account| {
+				account_address(account)
+					.and_then(|address| key_store.get_extended_full_viewing_key(&address))
+					.map_or(false, |xfvk| used_ivks.contains(&xfvk.fvk.vk.ivk()))
+			})
+			.map(|account| account.index)
+			.max();
+
+		let unused = last_used.map_or(hd_seed.next_account_index(), |index| hd_seed.next_account_index() - index - 1);
+		if unused < ACCOUNT_GAP_LIMIT {
+			match self.restore_password.clone() {
+				Some(pass) => {
+					let mut addresses = Vec::new();
+					for _ in unused..ACCOUNT_GAP_LIMIT {
+						addresses.push(self.add_hd_account(hd_seed, pass.clone())?);
+					}
+					return Ok(addresses);
+				}
+				None => warn!(target: "wallet", "The last restored accounts received notes, more of them may be in use: derive them with origo_getNewAddress."),
+			}
+		}
+
+		let keep = last_used.unwrap_or(0);
+		for account in hd_seed.accounts.iter().filter(|account| account.index > keep) {
+			if let Some(address) = account_address(account) {
+				self.key_store.remove_key_file(&address)?;
+			}
+		}
+		hd_seed.accounts.retain(|account| account.index <= keep);
+		hd_seed.restore_pending = false;
+		self.restore_password = None;
+		hd_seed.save(Path::new(hd_seed_path))?;
+		Ok(Vec::new())
+	}
+
+	fn add_hd_account(&mut self, hd_seed: &mut HdSeed, pass: String) -> Result<String, KeyError> {
+		let seed = hd_seed.decrypt(&Password::from(pass.clone()))
+			.map_err(|_| KeyError::Custom("Wrong wallet password, all the accounts share the password of the wallet seed".into()))?;
+		let index = hd_seed.next_account_index();
+		let xsk = derive_account_key(&seed, index);
+		let xfvk = SaplingExtendedFullViewingKey::from(&xsk);
+		let (diversifier, address) = xfvk.default_address()
+			.map_err(|_| KeyError::Custom("No valid diversifier for the account".into()))?;
+
+		self.save_key_to_file(&xsk, pass)?;
+		let address = encode_payment_address(&address);
+		hd_seed.accounts.push(HdAccount {
+			index,
+			address: address.clone(),
+			diversifiers: vec![diversifier_to_u64(&diversifier)],
+		});
+		hd_seed.save(Path::new(&self.hd_seed_path()))?;
+		Ok(address)
+	}
+
+	/// Hand out a new diversified address of the seed account `index`.
+	/// It shares the viewing keys and spending key of the account.
+	pub fn get_new_diversified_address(&mut self, index: u32) -> Result<String, KeyError> {
+		let hd_seed_path = self.hd_seed_path();
+		let hd_seed = self.hd_seed.as_mut().ok_or(KeyError::InvalidAccount)?;
+		let account = hd_seed.account_mut(index).ok_or(KeyError::InvalidAccount)?;
+		let default_address = decode_payment_address(&account.address).ok_or(KeyError::InvalidAccount)?;
+		let xfvk = self.key_store
+			.get_extended_full_viewing_key(&default_address)
+			.ok_or(KeyError::InvalidAccount)?
+			.clone();
+
+		let next = account.diversifiers.last().map_or(0, |last| last + 1);
+		let (diversifier, address) = xfvk.address(diversifier_from_u64(next))
+			.map_err(|_| KeyError::Custom("Diversifier space of the account is exhausted".into()))?;
+		account.diversifiers.push(diversifier_to_u64(&diversifier));
+		hd_seed.save(Path::new(&hd_seed_path))?;
+
+		self.key_store.add_diversified_address(&default_address, address.clone());
+		Ok(encode_payment_address(&address))
+	}
+
+	/// List the accounts derived from the wallet seed with their addresses.
+	pub fn list_accounts(&self) -> Vec<AccountAddresses> {
+		let hd_seed = match self.hd_seed {
+			Some(ref hd_seed) => hd_seed,
+			None => return Vec::new(),
+		};
+		hd_seed.accounts.iter().map(|account| {
+			let xfvk = decode_payment_address(&account.address)
+				.and_then(|address| self.key_store.get_extended_full_viewing_key(&address));
+			let addresses = match xfvk {
+				Some(xfvk) => account.diversifiers.iter()
+					.filter_map(|index| xfvk.address(diversifier_from_u64(*index)).ok())
+					.map(|(_, address)| encode_payment_address(&address))
+					.collect(),
+				None => vec![account.address.clone()],
+			};
+			AccountAddresses {
+				index: account.index,
+				addresses,
+			}
+		}).collect()
+	}
+
+	// Get the SaplingNoteEntry for the specific filtered address.
+	/// Get the unspent notes of the address with between `min_depth` and `max_depth` (inclusive)
+	/// confirmations. The notes locked by a transaction not mined yet and the immature coinbase
+	/// notes are skipped if `ignore_locked`.
+	pub fn get_filtered_address_notes(
+		&self,
+		address: &String,
+		min_depth: u32,
+		max_depth: u32,
+		ignore_locked: bool,
+	) -> Vec<SaplingNoteEntry> {
+		let mut filtered_addresses = HashSet::new();
+		if address.len() > 0 {
+			let payment_address = decode_payment_address(&address);
+			if payment_address.is_some() {
+				filtered_addresses.insert(payment_address.unwrap());
+			}
+		}
+		self.get_filtered_notes(&filtered_addresses, min_depth, max_depth, true, ignore_locked)
+	}
+
+	fn get_filtered_notes(
+		&self,
+		filtered_addresses: &HashSet<SaplingPaymentAddress>,
+		min_depth: u32,
+		max_depth: u32,
+		ignore_spent: bool,
+		ignore_locked: bool,
+	) -> Vec<SaplingNoteEntry> {
+		let mut filter_notes: Vec<SaplingNoteEntry> = Vec::new();
+		for address in filtered_addresses.iter() {
+
+			if let Some(nullifiers) = self.address_nullifiers.get(address) {
+				for nullifier in nullifiers.iter() {
+					if let Some(saplingNote) = self.nullifier_notes.get(nullifier) {
+						if ignore_spent && saplingNote.confirmHeight.is_some() {
+							continue;
+						}
+						let locked = saplingNote.spendHeight.is_some();
+						if ignore_locked && locked {
+							continue;
+						}
+						let confirmation = self.confirmations(saplingNote);
+						if confirmation < min_depth as u64 || confirmation > max_depth as u64 {
+							continue;
+						}
+						let immature = saplingNote.coinbase && confirmation < COINBASE_MATURITY;
+						if ignore_locked && immature {
+							continue;
+						}
+
+						let note = SaplingNoteEntry {
+							hash: saplingNote.hash.clone(),
+							index: saplingNote.index.clone(),
+							address: address.clone(),
+							witness: saplingNote.front().unwrap(),
+							note: saplingNote.note.clone(),
+							memo: saplingNote.memo.clone(),
+							confirmation: confirmation as i64,
+							spendable: !self.key_store.is_watch_only(&saplingNote.ivk) && !immature,
+							locked,
+						};
+						filter_notes.push(note)
+					}
+				}
+			}
+		}
+		filter_notes
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use std::str::FromStr;
+
+	use crate::coin_selection::{CoinSelection, MAX_SPENDS_PER_TX};
+	use crate::offline::UnsignedTransaction;
+	use crate::sendmany::{SendMany, SendManyInputs};
+	use crate::test_helpers::{create_private_transaction, TEST_CHAIN_ID};
+	use crate::transaction_builder::{build_coinbase, Error, ErrorKind};
+	use crate::wallet_types::{SaplingExtendedFullViewingKey, SaplingExtendedSpendingKey};
+	use ethcore_blockchain::generator::{BlockBuilder, BlockGenerator, BlockOptions};
+	use common_types::ids::BlockId::Hash;
+	use common_types::transaction::{Action, Transaction, CONVERSION_FACTOR};
+	use sapling_crypto::redjubjub::Signature;
+	use std::collections::btree_map::BTreeMap;
+	use parking_lot::RwLock;
+	use rand::{OsRng, Rand};
+	use sapling_crypto::jubjub::fs::Fs;
+	use zcash_primitives::{merkle_tree::CommitmentTree, sapling::Node};
+
+	fn remove_old_key_files(file_name_prefix_op: &str, file_path: &str) {
+		//Remove old key files
+		let mut dir = format!("{}", file_path);
+		if file_path == "" {
+			dir = "./".to_string();
+		}
+
+		let paths = fs::read_dir(dir).unwrap();
+
+		let mut file_name_prefix = "wallet";
+		if file_name_prefix_op != "" {
+			file_name_prefix = file_name_prefix_op;
+		}
+
+		for path in paths {
+			let mut file_name = format!("{}", path.unwrap().path().display());
+			file_name = file_name.as_str()[2..].to_string();
+			if file_name.starts_with(file_name_prefix) {
+				fs::remove_file(file_name);
+			}
+		}
+	}
+
+	#[test]
+	fn test_save_load_wallet() {
+		let file_name_prefix = "test_wallet_save";
+
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+		let add1 = wallet.create_new_private_address("".into()).unwrap();
+		let add2 = wallet.create_new_private_address("".into()).unwrap();
+
+		let addresses1 = wallet.list_addresses();
+		assert_eq!(addresses1.len(), 2);
+
+		let mut wallet2 = Wallet::new_from_file(file_name_prefix, "./");
+
+		// Test addresses are the same from loaded wallet.
+		let addresses2 = wallet2.list_addresses();
+		assert_eq!(addresses2.len(), 2);
+		assert!(addresses2.contains(&add1));
+		assert!(addresses2.contains(&add2));
+		// Remove temp file.
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_hd_accounts_and_diversified_addresses() {
+		let file_name_prefix = "test_wallet_hd";
+
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+		let add1 = wallet.create_new_private_address("pass".into()).unwrap();
+		let add2 = wallet.create_new_private_address("pass".into()).unwrap();
+		match wallet.create_new_private_address("wrong".into()) {
+			Err(KeyError::Custom(ref e)) => assert!(e.starts_with("Wrong wallet password")),
+			other => panic!("Unexpected result {:?}", other),
+		}
+		let div1 = wallet.get_new_diversified_address(0).unwrap();
+		assert!(div1 != add1);
+		assert!(wallet.get_new_diversified_address(2).is_err());
+
+		let accounts = wallet.list_accounts();
+		assert_eq!(accounts.len(), 2);
+		assert_eq!(accounts[0], AccountAddresses { index: 0, addresses: vec![add1.clone(), div1.clone()] });
+		assert_eq!(accounts[1], AccountAddresses { index: 1, addresses: vec![add2] });
+
+		// Accounts and diversified addresses are restored from the seed file.
+		let wallet2 = Wallet::new_from_file(file_name_prefix, "./");
+		assert_eq!(wallet2.list_accounts(), accounts);
+		let default_address = decode_payment_address(&add1).unwrap();
+		let diversified_address = decode_payment_address(&div1).unwrap();
+		let xfvk = wallet2.key_store.get_extended_full_viewing_key(&default_address).unwrap();
+		assert_eq!(xfvk.fvk.vk.ivk(), wallet2.key_store.get_extended_full_viewing_key(&diversified_address).unwrap().fvk.vk.ivk());
+
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_mnemonic_backup_and_restore() {
+		let file_name_prefix = "test_wallet_mnemonic";
+		let restored_prefix = "test_wallet_restored";
+
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+		let phrase = wallet.new_mnemonic("pass".into()).unwrap();
+		assert_eq!(wallet.new_mnemonic("pass".into()).unwrap(), phrase);
+		assert!(wallet.new_mnemonic("wrong".into()).is_err());
+		let address = wallet.list_addresses()[0].clone();
+
+		let mut restored = Wallet::new_from_file(restored_prefix, "./");
+		assert!(restored.restore_mnemonic("abandon about", "pass".into()).is_err());
+		let addresses = restored.restore_mnemonic(&phrase, "pass".into()).unwrap();
+		assert_eq!(addresses.len(), ACCOUNT_GAP_LIMIT as usize);
+		assert_eq!(addresses[0], address);
+		assert!(restored.restore_mnemonic(&phrase, "pass".into()).is_err());
+
+		// No account received notes, only the first one is kept.
+		assert!(restored.finish_mnemonic_restore().unwrap().is_empty());
+		assert_eq!(restored.list_addresses(), vec![address.clone()]);
+		let reloaded = Wallet::new_from_file(restored_prefix, "./");
+		assert_eq!(reloaded.list_accounts(), vec![AccountAddresses { index: 0, addresses: vec![address] }]);
+
+		remove_old_key_files(file_name_prefix, "./");
+		remove_old_key_files(restored_prefix, "./");
+	}
+
+	#[test]
+	fn test_mnemonic_of_first_address() {
+		let file_name_prefix = "test_wallet_mnemonic_first";
+		let restored_prefix = "test_wallet_mnemonic_first_restored";
+
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+		let address = wallet.create_new_private_address("pass".into()).unwrap();
+		let phrase = wallet.new_mnemonic("pass".into()).unwrap();
+		assert_eq!(wallet.list_addresses(), vec![address.clone()]);
+		let reloaded = Wallet::new_from_file(file_name_prefix, "./");
+		assert_eq!(reloaded.hd_seed.as_ref().unwrap().decrypt_mnemonic(&Password::from("pass")).unwrap().unwrap().phrase(), phrase);
+
+		let mut restored = Wallet::new_from_file(restored_prefix, "./");
+		assert_eq!(restored.restore_mnemonic(&phrase, "pass".into()).unwrap()[0], address);
+
+		remove_old_key_files(file_name_prefix, "./");
+		remove_old_key_files(restored_prefix, "./");
+	}
+
+	#[test]
+	fn test_mnemonic_restore_gap_limit() {
+		let file_name_prefix = "test_wallet_mnemonic_gap";
+		let restored_prefix = "test_wallet_mnemonic_gap_restored";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+		let phrase = wallet.new_mnemonic("pass".into()).unwrap();
+
+		let mut restored = Wallet::new_from_file(restored_prefix, "./");
+		let addresses = restored.restore_mnemonic(&phrase, "pass".into()).unwrap();
+		// The last account derived received a note.
+		let last = decode_payment_address(addresses.last().unwrap()).unwrap();
+		let ivk = restored.key_store.get_extended_full_viewing_key(&last).unwrap().fvk.vk.ivk();
+		let note = last.create_note(1, Fs::rand(&mut OsRng::new().unwrap()), &JUBJUB).unwrap();
+		let nullifier = U256::from(1);
+		restored.nullifier_notes.insert(
+			nullifier,
+			SaplingNoteData::new(last, note, Memo::default(), 1, ivk, nullifier, H256::zero(), 0),
+		);
+
+		// Accounts are derived until ACCOUNT_GAP_LIMIT unused ones follow the last used one.
+		let more = restored.finish_mnemonic_restore().unwrap();
+		assert_eq!(more.len(), ACCOUNT_GAP_LIMIT as usize);
+		assert_eq!(restored.list_accounts().len(), 2 * ACCOUNT_GAP_LIMIT as usize);
+		assert!(restored.finish_mnemonic_restore().unwrap().is_empty());
+		let accounts = restored.list_accounts();
+		assert_eq!(accounts.len(), ACCOUNT_GAP_LIMIT as usize);
+		assert_eq!(accounts.last().unwrap().addresses[0], *addresses.last().unwrap());
+
+		remove_old_key_files(file_name_prefix, "./");
+		remove_old_key_files(restored_prefix, "./");
+	}
+
+	#[test]
+	fn test_watch_only_viewing_keys() {
+		let source_prefix = "test_wallet_watch_source";
+		let fvk_prefix = "test_wallet_watch_fvk";
+		let ivk_prefix = "test_wallet_watch_ivk";
+		let mut source = Wallet::new_from_file(source_prefix, "./");
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut source).sign_for_private(0);
+		let b1a = genesis.add_block_with_transactions(vec![t1.clone()]);
+		let address = source.list_addresses()[0].clone();
+		let xfvk = source.export_viewing_key(&address, false).unwrap();
+		let ivk = source.export_viewing_key(&address, true).unwrap();
+		assert!(xfvk.starts_with("ogoxviews1"));
+		assert!(ivk.starts_with("ogoivks1"));
+
+		let mut fvk_wallet = Wallet::new_from_file(fvk_prefix, "./");
+		assert_eq!(fvk_wallet.import_viewing_key(&xfvk, None).unwrap(), address);
+		assert!(fvk_wallet.import_viewing_key(&xfvk, None).is_err());
+		assert!(fvk_wallet.import_viewing_key("ogoxviews1invalid", None).is_err());
+		let mut ivk_wallet = Wallet::new_from_file(ivk_prefix, "./");
+		let ivk_address = ivk_wallet.import_viewing_key(&ivk, Some(1)).unwrap();
+		assert_eq!(ivk_wallet.rescan_height(&None), 1);
+		assert_eq!(ivk_wallet.export_viewing_key(&ivk_address, true).unwrap(), ivk);
+		assert!(ivk_wallet.export_viewing_key(&ivk_address, false).is_err());
+
+		for block in BlockGenerator::new(vec![b1a]) {
+			let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+			fvk_wallet.sync_new_block(&mut parent_commitment_tree, &block.encoded());
+			let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+			ivk_wallet.sync_new_block(&mut parent_commitment_tree, &block.encoded());
+		}
+
+		// Both find the notes but can't spend them, only the full viewing key knows the nullifiers.
+		let notes = fvk_wallet.get_filtered_address_notes(&address, 0, u32::max_value(), false);
+		assert_eq!(notes.iter().map(|entry| entry.note.value).sum::<u64>(), 400);
+		assert!(notes.iter().all(|entry| !entry.spendable));
+		assert!(fvk_wallet.nullifier_notes.values().all(|note| note.nullifier.is_some()));
+		let notes = ivk_wallet.get_filtered_address_notes(&address, 0, u32::max_value(), false);
+		assert_eq!(notes.iter().map(|entry| entry.note.value).sum::<u64>(), 400);
+		assert!(notes.iter().all(|entry| !entry.spendable));
+		assert!(ivk_wallet.nullifier_notes.values().all(|note| note.nullifier.is_none()));
+
+		// The imported keys are reloaded.
+		let reloaded = Wallet::new_from_file(ivk_prefix, "./");
+		assert_eq!(reloaded.list_addresses(), vec![ivk_address]);
+		assert_eq!(reloaded.rescan_height(&None), 1);
+
+		remove_old_key_files(source_prefix, "./");
+		remove_old_key_files(fvk_prefix, "./");
+		remove_old_key_files(ivk_prefix, "./");
+	}
+
+	#[test]
+	fn test_offline_signing() {
+		let source_prefix = "test_wallet_offline_source";
+		let watch_prefix = "test_wallet_offline_watch";
+		let mut source = Wallet::new_from_file(source_prefix, "./");
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut source).sign_for_private(0);
+		let b1a = genesis.add_block_with_transactions(vec![t1.clone()]);
+		let address = source.list_addresses()[0].clone();
+		assert!(source.export_proving_key(&address, "wrong".into()).is_err());
+		let proving_key = source.export_proving_key(&address, "".into()).unwrap();
+		assert!(proving_key.starts_with("ogoxprov1"));
+
+		let watch = Arc::new(RwLock::new(Wallet::new_from_file(watch_prefix, "./")));
+		assert_eq!(watch.write().import_viewing_key(&proving_key, None).unwrap(), address);
+		for block in BlockGenerator::new(vec![b1a]) {
+			let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+			watch.write().sync_new_block(&mut parent_commitment_tree, &block.encoded());
+		}
+
+		let inputs = SendManyInputs {
+			from: address.clone(),
+			value_from_public: U256::from(0),
+			shield_to: vec![(address.clone(), U256::from(100) * CONVERSION_FACTOR, "".into())],
+			to: vec![],
+			min_conf: 1,
+			coin_selection: CoinSelection::default(),
+			max_spends: MAX_SPENDS_PER_TX,
+			gas_price: U256::from(1000000),
+			nonce: U256::from(0),
+			gas: U256::from(21000),
+			data: vec![],
+			chain_id: TEST_CHAIN_ID,
+			shielded_sighash: true,
+		};
+		// The watch-only wallet can't sign, only prove.
+		assert_eq!(
+			SendMany::new(watch.clone()).pre_send_many(&inputs, "".into()).unwrap_err(),
+			Error(ErrorKind::NoPrivateKey)
+		);
+		let unsigned = SendMany::new(watch.clone()).create_unsigned(&inputs).unwrap();
+		assert!(unsigned.tx.v_shielded_spend().iter().all(|spend| spend.spend_auth_sig.is_none()));
+
+		// The unsigned transaction is carried to the offline signer.
+		let unsigned: UnsignedTransaction = ::rlp::decode(&::rlp::encode(&unsigned)).unwrap();
+		let extsk = source.key_store.decrypt_key_file(&address, "".into()).unwrap();
+		let recovered = unsigned.recover_outputs(&extsk.expsk.ovk);
+		assert!(recovered.iter().all(Option::is_some));
+		assert_eq!(recovered.iter().map(|output| output.as_ref().unwrap().1).sum::<u64>(), 200 - 21);
+
+		let other = SaplingExtendedSpendingKey::master(&[1]);
+		assert_eq!(unsigned.sign(&other.expsk.ask), Err(Error(ErrorKind::WrongSpendingKey)));
+		let tx = unsigned.sign(&extsk.expsk.ask).unwrap();
+		assert!(tx.v_shielded_spend().iter().all(|spend| spend.spend_auth_sig.is_some()));
+		assert!(tx.sign_for_private(TEST_CHAIN_ID).verify_private_tx_basic().is_ok());
+
+		// The proving key is reloaded with the watch-only keys.
+		let reloaded = Wallet::new_from_file(watch_prefix, "./");
+		assert!(reloaded.key_store.get_spend_keys(&decode_payment_address(&address).unwrap()).is_some());
+
+		remove_old_key_files(source_prefix, "./");
+		remove_old_key_files(watch_prefix, "./");
+	}
+
+	#[test]
+	fn test_sync_and_retract_block() {
+		let file_name_path = "test_wallet_sync";
+		let mut wallet = Wallet::new_from_file(file_name_path, "./");
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet).sign_for_private(0);
+		let b1a = genesis.add_block_with_transactions(vec![t1.clone()]);
+		let generator = BlockGenerator::new(vec![b1a]);
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+
+		let mut retract_tx_hashes = vec![];
+		for block in generator {
+			wallet.sync_new_block(&mut parent_commitment_tree, &block.encoded());
+			for tx in block.encoded().view().localized_private_txs().iter() {
+				retract_tx_hashes.push(tx.hash().clone());
+			}
+		}
+
+		assert_eq!(wallet.tx_nullifiers.len(), 1);
+		assert_eq!(wallet.address_nullifiers.len(), 1);
+		assert_eq!(wallet.nullifier_notes.len(), 2);
+		for (nullifier, note) in wallet.nullifier_notes.iter() {
+			assert_eq!(note.createHeight, 1);
+			assert_eq!(note.confirmHeight, None);
+			assert_eq!(note.spendHeight, None);
+		}
+
+		let addresses = wallet.list_addresses();
+		assert_eq!(addresses.len(), 1);
+		let saplingNotes = wallet.get_filtered_address_notes(&addresses[0], 0, u32::max_value(), false);
+		let mut sum = 0;
+		for entry in saplingNotes.iter() {
+			sum = sum + entry.note.value;
+		}
+		assert_eq!(sum, 400);
+
+		wallet.remove_retracted_txs(&retract_tx_hashes);
+		assert_eq!(wallet.tx_nullifiers.len(), 0);
+		assert_eq!(wallet.address_nullifiers.len(), 0);
+		assert_eq!(wallet.nullifier_notes.len(), 0);
+		remove_old_key_files(file_name_path, "./");
+	}
+
+	#[test]
+	fn test_write_reload_scan_wallet_data() {
+		let file_name_prefix = "test_wallet_reload";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet).sign_for_private(TEST_CHAIN_ID);
+		let t1_hash = t1.hash();
+		let mut t2 = create_private_transaction(&mut wallet);
+		t2.value = 100.into();
+		t2.nonce = 2.into();
+		let signed_t2 = t2.sign_for_private(TEST_CHAIN_ID);
+		assert_ne!(t1.hash(), signed_t2.hash());
+		let b1a = genesis.add_block_with_transactions(vec![t1.clone(), signed_t2]);
+		let block = b1a.last().encoded();
+
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+		wallet.sync_new_block(&mut parent_commitment_tree, &block);
+		let wallet_hashes = WalletTxHashes {
+			hashes: wallet
+				.tx_nullifiers
+				.iter()
+				.map(|(hash, _)| hash.clone())
+				.collect::<Vec<TxHash>>(),
+		};
+		let encoded = ::rlp::encode(&wallet_hashes);
+		let decode_hashes: WalletTxHashes = rlp::decode(&encoded).unwrap();
+		assert_eq!(wallet_hashes, decode_hashes);
+		assert_eq!(wallet_hashes.hashes.len(), 2);
+		let wallet_addresses = WalletTxAddresses {
+			addresses: wallet
+				.address_nullifiers
+				.iter()
+				.map(|(address, _)| encode_payment_address(address))
+				.collect(),
+		};
+		let encoded = ::rlp::encode(&wallet_addresses);
+		let decoded_addresses: WalletTxAddresses = rlp::decode(&encoded).unwrap();
+		assert_eq!(wallet_addresses, decoded_addresses);
+		assert_eq!(wallet_addresses.addresses.len(), 1);
+
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+
+		let handled_addresses = wallet_addresses
+			.addresses
+			.iter()
+			.cloned()
+			.collect::<HashSet<_>>();
+		wallet.scan_new_block(
+			&mut parent_commitment_tree,
+			&block,
+			&Some(handled_addresses),
+		);
+		assert_eq!(wallet.tx_nullifiers.len(), 0);
+
+		let mut tx_hashes: HashSet<TxHash> = HashSet::new();
+		tx_hashes.insert(t1_hash);
+		let block_height = block.number();
+		wallet.reload_new_block(&mut parent_commitment_tree, &block, &Some(tx_hashes));
+		assert_eq!(wallet.tx_nullifiers.len(), 1);
+		assert_eq!(wallet.address_nullifiers.len(), 1);
+		assert_eq!(wallet.nullifier_notes.len(), 2);
+
+		let addresses = wallet.list_addresses();
+		let saplingNotes = wallet.get_filtered_address_notes(&addresses[0], 0, u32::max_value(), false);
+		let mut sum = 0;
+		for entry in saplingNotes.iter() {
+			sum = sum + entry.note.value;
+		}
+		assert_eq!(sum, 400);
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_reload_block_ordered() {
+		let file_name_prefix = "test_wallet_ordered";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet).sign_for_private(TEST_CHAIN_ID);
+		let b1a = genesis.add_block_with_transactions(vec![t1.clone()]);
+		let block = b1a.last().encoded();
+		let mut reload_blocks: BTreeMap<u64, ReloadBlockInfo> = BTreeMap::new();
+		let reload_block = ReloadBlockInfo {
+			block_hash: block.hash(),
+			block,
+			tx_hashes: HashSet::new(),
+		};
+		reload_blocks.insert(10, reload_block.clone());
+		reload_blocks.insert(2, reload_block.clone());
+		reload_blocks.insert(5, reload_block.clone());
+
+		// The reload blocks must in ordered by block_height.
+		assert_eq!(
+			vec![2, 5, 10],
+			reload_blocks
+				.iter()
+				.map(|(key, value)| { (*key).clone() })
+				.collect::<Vec<u64>>()
+		);
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_note_data_rlp() {
+		let file_name_prefix = "test_wallet_note_rlp";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet).sign_for_private(TEST_CHAIN_ID);
+		let b1a = genesis.add_block_with_transactions(vec![t1]);
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+		wallet.sync_new_block(&mut parent_commitment_tree, &b1a.last().encoded());
+
+		assert!(!wallet.nullifier_notes.is_empty());
+		for (nullifier, note_data) in wallet.nullifier_notes.iter() {
+			let decoded: SaplingNoteData = rlp::decode(&rlp::encode(note_data)).unwrap();
+			assert_eq!(decoded.address, note_data.address);
+			assert_eq!(decoded.note, note_data.note);
+			assert_eq!(decoded.memo.as_bytes(), note_data.memo.as_bytes());
+			assert_eq!(decoded.witnesses.len(), note_data.witnesses.len());
+			assert_eq!(decoded.front().unwrap().root(), note_data.front().unwrap().root());
+			assert_eq!(decoded.createHeight, note_data.createHeight);
+			assert_eq!(decoded.spendHeight, note_data.spendHeight);
+			assert_eq!(decoded.confirmHeight, note_data.confirmHeight);
+			assert_eq!(decoded.ivk, note_data.ivk);
+			assert_eq!(decoded.nullifier, Some(nullifier.clone()));
+			assert_eq!(decoded.hash, note_data.hash);
+			assert_eq!(decoded.index, note_data.index);
+			assert_eq!(decoded.coinbase, note_data.coinbase);
+		}
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_witnesses_follow_new_blocks() {
+		let file_name_prefix = "test_wallet_witness";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet).sign_for_private(TEST_CHAIN_ID);
+		let mut t2 = create_private_transaction(&mut wallet);
+		t2.nonce = 2.into();
+		let b1a = genesis.add_block_with_transactions(vec![t1]);
+		let b2a = b1a.add_block_with_transactions(vec![t2.sign_for_private(TEST_CHAIN_ID)]);
+		let b3a = b2a.add_block();
+
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+		for block in BlockGenerator::new(vec![b1a, b2a, b3a]) {
+			wallet.sync_new_block(&mut parent_commitment_tree, &block.encoded());
+		}
+
+		assert_eq!(wallet.nullifier_notes.len(), 4);
+		for note_data in wallet.nullifier_notes.values() {
+			// one witness per block since the note was received.
+			assert_eq!(note_data.witnesses.len() as u64, 4 - note_data.createHeight);
+			assert_eq!(note_data.front().unwrap().root(), parent_commitment_tree.root());
+		}
+
+		// Rewinding the last block drops its witness only.
+		wallet.rewind_retracted_blocks(1, 2);
+		for note_data in wallet.nullifier_notes.values() {
+			assert_eq!(note_data.witnesses.len() as u64, 3 - note_data.createHeight);
+		}
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_note_confirmations_and_locks() {
+		let file_name_prefix = "test_wallet_confirmations";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet).sign_for_private(TEST_CHAIN_ID);
+		let b1a = genesis.add_block_with_transactions(vec![t1]);
+		let next_blocks = b1a.add_blocks(LOCKED_NOTE_EXPIRY as usize + 1);
+		let mut blocks = BlockGenerator::new(vec![b1a, next_blocks]);
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+		wallet.sync_new_block(&mut parent_commitment_tree, &blocks.next().unwrap().encoded());
+
+		let address = wallet.list_addresses()[0].clone();
+		let notes = wallet.get_filtered_address_notes(&address, 1, 1, true);
+		assert_eq!(notes.len(), 2);
+		assert!(notes.iter().all(|entry| entry.confirmation == 1 && !entry.locked));
+		assert!(wallet.get_filtered_address_notes(&address, 2, u32::max_value(), true).is_empty());
+
+		// A note spent by a transaction not mined yet is locked.
+		let locked = wallet.nullifier_notes.keys().next().unwrap().clone();
+		wallet.nullifier_notes.get_mut(&locked).unwrap().spendHeight = Some(1);
+		assert_eq!(wallet.get_filtered_address_notes(&address, 1, u32::max_value(), true).len(), 1);
+		let notes = wallet.get_filtered_address_notes(&address, 1, u32::max_value(), false);
+		assert_eq!(notes.iter().filter(|entry| entry.locked).count(), 1);
+
+		for block in blocks {
+			wallet.sync_new_block(&mut parent_commitment_tree, &block.encoded());
+		}
+		assert_eq!(wallet.last_scanned_number, LOCKED_NOTE_EXPIRY + 2);
+		let notes = wallet.get_filtered_address_notes(&address, 1, u32::max_value(), true);
+		assert_eq!(notes.len(), 2);
+		assert!(notes.iter().all(|entry| entry.confirmation == LOCKED_NOTE_EXPIRY as i64 + 2));
+
+		wallet.rewind_retracted_blocks(1, LOCKED_NOTE_EXPIRY + 1);
+		assert!(wallet.get_filtered_address_notes(&address, LOCKED_NOTE_EXPIRY as u32 + 2, u32::max_value(), true).is_empty());
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_coinbase_note_maturity() {
+		let file_name_prefix = "test_wallet_coinbase";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+
+		let (address, extsk) = wallet.add_address_from_seed([0u8; 32], "".to_string());
+		let to = SaplingExtendedFullViewingKey::from(&extsk).default_address().unwrap().1;
+		let coinbase = build_coinbase(to, &(U256::from(500) * CONVERSION_FACTOR), TEST_CHAIN_ID, false)
+			.unwrap()
+			.sign_for_private(TEST_CHAIN_ID);
+
+		let genesis = BlockBuilder::genesis();
+		let b1 = genesis.add_block_with_transactions(vec![coinbase]);
+		let next_blocks = b1.add_blocks(COINBASE_MATURITY as usize - 1);
+		let mut blocks = BlockGenerator::new(vec![b1, next_blocks]);
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+		wallet.sync_new_block(&mut parent_commitment_tree, &blocks.next().unwrap().encoded());
+
+		// The coinbase note is listed but can't be selected before it matures.
+		let notes = wallet.get_filtered_address_notes(&address, 1, u32::max_value(), false);
+		assert_eq!(notes.len(), 1);
+		assert_eq!(notes[0].note.value, 500);
+		assert!(!notes[0].spendable);
+		assert!(wallet.nullifier_notes.values().all(|note_data| note_data.coinbase));
+		assert!(wallet.get_filtered_address_notes(&address, 1, u32::max_value(), true).is_empty());
+
+		for block in blocks {
+			wallet.sync_new_block(&mut parent_commitment_tree, &block.encoded());
+		}
+		let notes = wallet.get_filtered_address_notes(&address, 1, u32::max_value(), true);
+		assert_eq!(notes.len(), 1);
+		assert_eq!(notes[0].confirmation, COINBASE_MATURITY as i64);
+		assert!(notes[0].spendable);
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_write_load_wallet_db() {
+		let file_name_prefix = "test_wallet_db";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+		let db = kvdb_memorydb::create(db::NUM_COLUMNS.unwrap());
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet).sign_for_private(TEST_CHAIN_ID);
+		let t1_hash = t1.hash();
+		let b1a = genesis.add_block_with_transactions(vec![t1]);
+		let block = b1a.last().encoded();
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+		wallet.sync_new_block(&mut parent_commitment_tree, &block);
+
+		let mut batch = DBTransaction::new();
+		batch.put(db::COL_EXTRA, LEGACY_WALLET_KEYS[0], b"legacy");
+		db.write(batch).unwrap();
+		let mut batch = DBTransaction::new();
+		wallet.write_wallet_data(&mut batch);
+		db.write(batch).unwrap();
+		assert!(db.get(db::COL_EXTRA, LEGACY_WALLET_KEYS[0]).unwrap().is_none());
+
+		// Only the last scanned block and the addresses are written when no note changed.
+		let mut batch = DBTransaction::new();
+		wallet.write_wallet_data(&mut batch);
+		assert_eq!(batch.ops.len(), 2);
+
+		let mut reloaded = Wallet::new_from_file(file_name_prefix, "./");
+		let addresses = reloaded.load_wallet_data(&db).unwrap();
+		assert_eq!(addresses, wallet.get_wallet_addresses());
+		assert_eq!(reloaded.last_scanned_block, Some(block.hash()));
+		assert_eq!(reloaded.nullifier_notes.len(), 2);
+		assert_eq!(reloaded.tx_nullifiers.len(), 1);
+		assert_eq!(reloaded.address_nullifiers.len(), 1);
+		let notes = reloaded.get_filtered_address_notes(&reloaded.list_addresses()[0], 0, u32::max_value(), false);
+		assert_eq!(notes.iter().map(|entry| entry.note.value).sum::<u64>(), 400);
+
+		// The spends of retracted blocks are undone in db.
+		let spent = reloaded.nullifier_notes.keys().next().unwrap().clone();
+		{
+			let note = reloaded.nullifier_notes.get_mut(&spent).unwrap();
+			note.spendHeight = Some(1);
+			note.confirmHeight = Some(1);
+		}
+		reloaded.changed_nullifiers.insert(spent);
+		reloaded.rewind_retracted_blocks(1, 0);
+		let mut batch = DBTransaction::new();
+		reloaded.write_wallet_data(&mut batch);
+		db.write(batch).unwrap();
+		wallet.load_wallet_data(&db);
+		assert_eq!(wallet.nullifier_notes[&spent].confirmHeight, None);
+		assert_eq!(wallet.nullifier_notes[&spent].spendHeight, Some(0));
+
+		// Retracted notes are deleted from db.
+		reloaded.remove_retracted_txs(&vec![t1_hash]);
+		let mut batch = DBTransaction::new();
+		reloaded.write_wallet_data(&mut batch);
+		db.write(batch).unwrap();
+		wallet.load_wallet_data(&db);
+		assert_eq!(wallet.nullifier_notes.len(), 0);
+		assert_eq!(wallet.tx_nullifiers.len(), 0);
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_recover_sent_notes() {
+		let file_name_prefix = "test_wallet_sent_notes";
+		let mut wallet = Wallet::new_from_file(file_name_prefix, "./");
+		let db = kvdb_memorydb::create(db::NUM_COLUMNS.unwrap());
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet).sign_for_private(TEST_CHAIN_ID);
+		let t1_hash = t1.hash();
+		let b1a = genesis.add_block_with_transactions(vec![t1]);
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+		wallet.sync_new_block(&mut parent_commitment_tree, &b1a.last().encoded());
+
+		// Both the payment and the change are recovered with the outgoing viewing key.
+		let address = decode_payment_address(&wallet.list_addresses()[0]).unwrap();
+		let ivk = wallet.key_store.get_incoming_viewing_key(&address).unwrap();
+		let sent = wallet.get_sent_notes(Some(&ivk));
+		assert_eq!(sent.len(), 2);
+		assert!(sent.iter().all(|note| note.hash == t1_hash && note.createHeight == 1));
+		assert!(sent.iter().all(|note| note.address == address && note.value == 200));
+		assert!(sent.iter().any(|note| note.memo.to_utf8() == Some(Ok("haha".into()))));
+		assert_eq!(wallet.key_store.get_default_address(&ivk), Some(address.clone()));
+
+		let (received, sent) = wallet.get_transaction_notes(&t1_hash);
+		assert_eq!(received.len(), 2);
+		assert_eq!(sent.len(), 2);
+
+		for note in sent.iter() {
+			let decoded: SentNoteData = rlp::decode(&rlp::encode(*note)).unwrap();
+			assert_eq!(decoded.address, note.address);
+			assert_eq!(decoded.value, note.value);
+			assert_eq!(decoded.memo.as_bytes(), note.memo.as_bytes());
+			assert_eq!(decoded.ivk, note.ivk);
+			assert_eq!(decoded.createHeight, note.createHeight);
+			assert_eq!(decoded.hash, note.hash);
+			assert_eq!(decoded.index, note.index);
+		}
+
+		let mut batch = DBTransaction::new();
+		wallet.write_wallet_data(&mut batch);
+		db.write(batch).unwrap();
+		let mut reloaded = Wallet::new_from_file(file_name_prefix, "./");
+		reloaded.load_wallet_data(&db);
+		assert_eq!(reloaded.get_sent_notes(None).len(), 2);
+
+		// Sent notes of retracted transactions are deleted from db.
+		reloaded.remove_retracted_txs(&vec![t1_hash]);
+		let mut batch = DBTransaction::new();
+		reloaded.write_wallet_data(&mut batch);
+		db.write(batch).unwrap();
+		wallet.load_wallet_data(&db);
+		assert!(wallet.get_sent_notes(None).is_empty());
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_list_transactions() {
+		let file_name_prefix = "test_wallet_list_transactions";
+		let wallet = Arc::new(RwLock::new(Wallet::new_from_file(file_name_prefix, "./")));
+
+		let genesis = BlockBuilder::genesis();
+		let t1 = create_private_transaction(&mut wallet.write()).sign_for_private(TEST_CHAIN_ID);
+		let t1_hash = t1.hash();
+		let b1 = genesis.add_block_with_transactions(vec![t1]);
+		let mut parent_commitment_tree = CommitmentTree::<Node>::new();
+		wallet.write().sync_new_block(&mut parent_commitment_tree, &b1.last().encoded());
+
+		let address = wallet.read().list_addresses()[0].clone();
+		let inputs = SendManyInputs {
+			from: address.clone(),
+			value_from_public: U256::from(0),
+			shield_to: vec![(address.clone(), U256::from(100) * CONVERSION_FACTOR, "".into())],
+			to: vec![],
+			min_conf: 1,
+			coin_selection: CoinSelection::default(),
+			max_spends: MAX_SPENDS_PER_TX,
+			gas_price: U256::from(1000000),
+			nonce: U256::from(0),
+			gas: U256::from(21000),
+			data: vec![],
+			chain_id: TEST_CHAIN_ID,
+			shielded_sighash: true,
+		};
+		let t2 = SendMany::new(wallet.clone())
+			.pre_send_many(&inputs, "".into())
+			.unwrap()
+			.sign_for_private(TEST_CHAIN_ID);
+		let t2_hash = t2.hash();
+		let spent: HashSet<U256> = t2.v_shielded_spend().iter().map(|spend| U256::from(spend.nullifier)).collect();
+		let b2 = b1.add_block_with_transactions(vec![t2]);
+		wallet.write().sync_new_block(&mut parent_commitment_tree, &b2.last().encoded());
+
+		let nullifier_transaction = |nullifier: &U256| if spent.contains(nullifier) { Some(t2_hash) } else { None };
+		let txs = wallet.read().list_transactions(None, nullifier_transaction);
+		assert_eq!(txs.len(), 2);
+		assert_eq!((txs[0].hash, txs[0].block_number, txs[0].value_change()), (t1_hash, 1, 400));
+		assert!(txs[0].memos.iter().any(|memo| memo.to_utf8() == Some(Ok("haha".into()))));
+		assert_eq!(txs[0].sent.len(), 2);
+		// The fee is the only value leaving the wallet.
+		assert_eq!((txs[1].hash, txs[1].block_number, txs[1].value_change()), (t2_hash, 2, -21));
+		assert_eq!(txs[1].spent, 200);
+		assert_eq!(txs[1].sent.iter().map(|note| note.value).sum::<u64>(), 179);
+
+		// The spends are unknown to the wallet without the chain.
+		let txs = wallet.read().list_transactions(None, |_| None);
+		assert_eq!(txs[1].value_change(), 179);
+		let other = SaplingExtendedFullViewingKey::from(&SaplingExtendedSpendingKey::master(&[1]));
+		assert!(wallet.read().list_transactions(Some(&other.fvk.vk.ivk()), nullifier_transaction).is_empty());
+		remove_old_key_files(file_name_prefix, "./");
+	}
+
+	#[test]
+	fn test_save_load_file() {
+		let wallet_file_prefix = "test_wallet_new";
+		let mut wallet = Wallet::new_from_file(wallet_file_prefix, "./");
+
+		let seed = [0u8; 32];
+		let address_str = wallet.add_address_from_seed(seed, String::from("pass_for_test"));
+		let wallet_new = Wallet::new_from_file(wallet_file_prefix, "./");
+
+		let addresses = wallet.key_store.get_sapling_payment_addresses();
+		let reload_addresses = wallet_new.key_store.get_sapling_payment_addresses();
+
+		assert_eq!(addresses.len(), 1);
+		for index in 0..addresses.len() {
+			let address = addresses[index];
+			let reload_address = reload_addresses[index];
+			assert_eq!(address.clone(), reload_address.clone());
+
+			let fs = wallet.key_store.get_incoming_viewing_key(&address).expect("");
+			let reload_fs = wallet_new.key_store.get_incoming_viewing_key(&reload_address).expect("");
+
+			let fvks = wallet.key_store.get_full_viewing_key(&fs).expect("");
+			let reload_fvks = wallet_new.key_store.get_full_viewing_key(&reload_fs).expect("");
+			assert_eq!(fvks.vk.ak, reload_fvks.vk.ak);
+			assert_eq!(fvks.vk.nk, reload_fvks.vk.nk);
+			assert_eq!(fvks.ovk, reload_fvks.ovk);
+		}
+		remove_old_key_files(wallet_file_prefix, "");
+	}
+
+	#[test]
+	fn test_encrypt_key_file() {
+		let wallet_file_prefix = "test_wallet_new";
+		let mut wallet = Wallet::new_from_file(wallet_file_prefix, "./");
+
+		let seed = [0u8; 32];
+		wallet.add_address_from_seed(seed, String::from("pass_for_test"));
+
+		let x = "ogo180m058urhazk8j98zvz9fsq5zd0vd9dpsc8c6ednwd2xkc3l8z9thmxsezepzx4aascp6nrlkd6";
+		let path = format!("{}{}", wallet_file_prefix, x);
+
+		//Just for showing example of encryted file
+		let expected_simuler_result = r#"
+			{
+				"address":"ogo180m058urhazk8j98zvz9fsq5zd0vd9dpsc8c6ednwd2xkc3l8z9thmxsezepzx4aascp6nrlkd6",
+				"crypto":
+					{
+						"cipher":"aes-128-ctr",
+						"cipherparams":{"iv":"734243b9ecf79c2f3d30ea9b6099dee0"},
+						"ciphertext":"15c8fba8f02476a47f890a7bd71adbd99dd74219859f144af574c0516b13b69ca24d9d6da162c25e60e506d4f563d61c2a967cd2023fa08e4c6c19e3dd5d555aeb5ff7faae52bb45e14e594d57ea6c256af2c347e2a28ee11583384da6211a53eebcb8edea0464fc26013df9767499f6da75abe7d5f5e96b04bfd1ec0dad7f1defeffdfaea8edf49e2f248fbe29f4f952d03b3761e9f5fd9ecfbbd786589dc9f320f0ac53b0b5114e2",
+						"kdf":"pbkdf2",
+						"kdfparams":{"c":10240,"dklen":32,"prf":"hmac-sha256","salt":"d93cd65787d722f51a37023d1c65387bed382b6d909bee2bc4558bd26bde491a"},
+						"mac":"cbf9a0770c3a7f8d8d39251b24084d716e123138f72576976d22f0a49e0d12a0"},
+						"efvk":{"cipher":"aes-128-ctr","cipherparams":{"iv":"8ad6a7b91e2fd0c64449ebc978f61280"},"ciphertext":"d77b706e12e81d345d57b113a94de7bc7c1cbe9f054b6440c0ce9cefea1b607ddb6d85c542343e45c0837f1d46d3a28d6b9618dd851b855d4b97d18d54e81988d393e6162d1829901157597b54467573bff8f76a8073566693fd0479fe250b1c8bd46b7ac85e5d9af044d572538e7a6b146e52da87f34750829b2827a9e5a2bc5b16c06c01cc3bec3aacb7c7571a5e922c950d4b6fef668162bb08328d8c61eecbe926a5a2ff40faa2","kdf":"pbkdf2","kdfparams":{"c":10240,"dklen":32,"prf":"hmac-sha256","salt":"3a8cc7393a6cd567cb915616c7ee620045f4150cc6dfe4ca0b667a6ba76d582a"},"mac":"dc3c4e331ab109b30cdcf80bdc045ea97299366fecb524f79142a5c0ba22dbd9"}
+					}"#;
+		let contents = fs::read_to_string(path);
+	}
+}
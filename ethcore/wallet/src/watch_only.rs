@@ -0,0 +1,58 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Viewing keys imported into the wallet without their spending keys.
+
+use std::fs::{self, File};
+use std::io::ErrorKind;
+use std::path::Path;
+
+use ethstore::Error;
+use serde::{Deserialize, Serialize};
+
+/// Name of the imported viewing keys file in the wallet directory, after the wallet file prefix.
+pub const WATCH_ONLY_FILE_NAME: &'static str = "viewing_keys.json";
+
+/// An imported viewing key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WatchOnlyKey {
+	/// Bech32 encoding of the extended full viewing key or of the incoming viewing key.
+	pub key: String,
+	/// Block the chain is scanned from for the key.
+	pub birthday: u64,
+}
+
+/// Load the imported viewing keys, none if the file doesn't exist.
+pub fn load_watch_only_keys(path: &Path) -> Result<Vec<WatchOnlyKey>, Error> {
+	let file = match File::open(path) {
+		Ok(file) => file,
+		Err(ref e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+		Err(e) => return Err(e.into()),
+	};
+	serde_json::from_reader(file).map_err(|e| Error::InvalidKeyFile(format!("{}", e)))
+}
+
+/// Write the imported viewing keys, replacing the previous file only once fully written.
+pub fn save_watch_only_keys(path: &Path, keys: &[WatchOnlyKey]) -> Result<(), Error> {
+	let tmp_path = path.with_extension("tmp");
+	{
+		let mut file = File::create(&tmp_path)?;
+		serde_json::to_writer(&mut file, keys).map_err(|e| Error::Custom(format!("{}", e)))?;
+		file.sync_all()?;
+	}
+	fs::rename(&tmp_path, path)?;
+	Ok(())
+}
@@ -124,6 +124,16 @@ pub struct Params {
 	pub kip4_transition: Option<Uint>,
 	/// KIP6 activiation block height.
 	pub kip6_transition: Option<Uint>,
+	/// Block at which headers start committing to the final Sapling root.
+	pub final_sapling_root_transition: Option<Uint>,
+	/// Block at which the author reward can be paid to a shielded address by a coinbase transaction.
+	/// Only supported by the Ethash engine, which checks the coinbase value against the reward.
+	pub shielded_coinbase_transition: Option<Uint>,
+	/// Block at which spends and binding signatures sign the Blake2b shielded signature hash
+	/// instead of the transaction hash.
+	pub shielded_sighash_transition: Option<Uint>,
+	/// Block at which private calls can pay several public recipients.
+	pub public_outputs_transition: Option<Uint>,
 	/// Total balance that can been mined
 	pub total_reward: Option<Uint>,
 	/// Block count of each era
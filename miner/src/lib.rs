@@ -60,6 +60,8 @@ extern crate rustc_hex;
 extern crate ethkey;
 #[cfg(test)]
 extern crate env_logger;
+#[cfg(test)]
+extern crate ethcore_wallet;
 
 pub mod external;
 #[cfg(feature = "price-info")]
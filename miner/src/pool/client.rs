@@ -56,6 +56,9 @@ pub trait Client: fmt::Debug + Sync {
 		t: &transaction::UnverifiedTransaction,
 	) -> Result<(), transaction::Error>;
 
+	/// Whether the private transactions included in the next block sign the shielded signature hash.
+	fn shielded_sighash(&self) -> bool;
+
 	/// Structurarily verify given transaction.
 	fn verify_transaction(&self, tx: transaction::UnverifiedTransaction)
 		-> Result<transaction::SignedTransaction, transaction::Error>;
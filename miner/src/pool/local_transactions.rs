@@ -356,7 +356,8 @@ mod tests {
 				spends: vec!{},
 				outputs: vec!{},
 				balancing_value: -10,
-				binding_sig: [1u8; 64]}),
+				binding_sig: [1u8; 64],
+				public_outputs: vec!{}}),
 		}.sign_for_private(0);
 
 		let mut tx = Transaction::from_pending_block_transaction(signed);
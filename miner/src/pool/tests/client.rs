@@ -36,6 +36,7 @@ pub struct TestClient {
 	local_address: Address,
 	max_transaction_size: usize,
 	nullifier_set: HashSet<U256>,
+	shielded_sighash: bool,
 }
 
 impl Default for TestClient {
@@ -52,6 +53,7 @@ impl Default for TestClient {
 			local_address: Default::default(),
 			max_transaction_size: MAX_TRANSACTION_SIZE,
 			nullifier_set: HashSet::new(),
+			shielded_sighash: false,
 		}
 	}
 }
@@ -91,6 +93,11 @@ impl TestClient {
 		self
 	}
 
+	pub fn with_shielded_sighash(mut self) -> Self {
+		self.shielded_sighash = true;
+		self
+	}
+
 	pub fn verify<T: Into<transaction::PendingTransaction>>(&self, tx: T) -> pool::VerifiedTransaction {
 		let tx = tx.into();
 		pool::VerifiedTransaction {
@@ -119,6 +126,10 @@ impl pool::client::Client for TestClient {
 		Ok(())
 	}
 
+	fn shielded_sighash(&self) -> bool {
+		self.shielded_sighash
+	}
+
 	fn verify_transaction(&self, tx: UnverifiedTransaction)
 		-> Result<SignedTransaction, transaction::Error>
 	{
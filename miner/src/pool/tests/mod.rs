@@ -27,10 +27,10 @@ use self::tx::{Tx, TxExt, PairExt, PrivateTx};
 use self::client::TestClient;
 
 use rand::{OsRng, Rand};
-use ethcore_blockchain::wallet::wallet_types::{
+use ethcore_wallet::wallet_types::{
 	SaplingExtendedFullViewingKey, SaplingExtendedSpendingKey,
 };
-use ethcore_blockchain::wallet::transaction_builder::TransactionBuilder;
+use ethcore_wallet::transaction_builder::TransactionBuilder;
 use sapling_crypto::{
 	jubjub::fs::Fs,
 	redjubjub::Signature,
@@ -127,6 +127,10 @@ fn private_to_public_transaction(gas_price: U256, value: u64) -> SignedTransacti
 }
 
 fn create_private(gas_price: u64) -> SignedTransaction {
+	create_private_with_sighash(gas_price, false)
+}
+
+fn create_private_with_sighash(gas_price: u64, shielded_sighash: bool) -> SignedTransaction {
 	let mut rng = OsRng::new().expect("should be able to construct RNG");
 
 	let extsk = SaplingExtendedSpendingKey::master(&[]);
@@ -135,6 +139,7 @@ fn create_private(gas_price: u64) -> SignedTransaction {
 	let to = extfvk.default_address().unwrap().1;
 
 	let mut builder = TransactionBuilder::new(gas_price.into(), U256::from(0), U256::from(21000), vec![], TEST_CHAIN_ID);
+	builder.set_shielded_sighash(shielded_sighash);
 	add_private_input(&mut builder, 300 + 21000 * gas_price / CONVERSION_FACTOR.low_u64());
 
 	// Add output
@@ -306,6 +311,21 @@ fn test_invalid_balancing_value_or_binding_sig() {
 	assert_eq!(r, vec![Err(transaction::Error::InvalidBalancingValueOrBindingSig)]);
 }
 
+#[test]
+fn test_shielded_sighash_transition() {
+	// Signed with the transaction hash.
+	let tx = create_private(2_000_000);
+	let r = new_queue().import(TestClient::new().with_shielded_sighash(), vec![tx].local());
+	assert_eq!(r, vec![Err(transaction::Error::InvalidSpendDescription)]);
+
+	// Signed with the shielded signature hash.
+	let tx = create_private_with_sighash(2_000_000, true);
+	let r = new_queue().import(TestClient::new(), vec![tx.clone()].local());
+	assert_eq!(r, vec![Err(transaction::Error::InvalidSpendDescription)]);
+	let r = new_queue().import(TestClient::new().with_shielded_sighash(), vec![tx].local());
+	assert_eq!(r, vec![Ok(())]);
+}
+
 #[test]
 fn should_return_correct_nonces_when_dropped_because_of_limit() {
 	// given
@@ -194,7 +194,8 @@ impl PrivateTx {
 				spends: spends,
 				outputs: vec!{},
 				balancing_value: self.balance,
-				binding_sig: [1u8; 64]}),
+				binding_sig: [1u8; 64],
+				public_outputs: vec!{}}),
 		}
 	}
 }
@@ -30,9 +30,9 @@ use ethereum_types::{U256, H256};
 use rlp::Encodable;
 use txpool;
 use types::transaction;
-use zcash_proofs::sapling::SaplingVerificationContext;
+use zcash_proofs::sapling::{SaplingBatchVerifier, SaplingVerificationContext};
 use bellman::groth16::Proof;
-use ethcore_blockchain::wallet::zkp::{OUTPUT_VK, SPEND_VK};
+use ethcore_blockchain::zkp::{OUTPUT_VK, SPEND_VK};
 use sapling_crypto::redjubjub::Signature;
 use zcash_primitives::{
 	merkle_tree::{CommitmentTreeWitness, IncrementalWitness},
@@ -311,7 +311,7 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C, ::pool::scoring::N
 
 
 		if transaction.is_private() {
-			verify_private_transaction(&transaction.transaction)?;
+			verify_private_transaction(&transaction.transaction, self.client.shielded_sighash())?;
 			self.client.verify_private_transaction_basic(&transaction)?;
 		}
 		// For PrivateToPrivate and PrivateToPublic transaction without public sender,
@@ -360,20 +360,117 @@ impl<C: Client> txpool::Verifier<Transaction> for Verifier<C, ::pool::scoring::N
 	}
 }
 
-pub fn verify_private_transaction(transaction: &transaction::SignedTransaction) -> Result<(), transaction::Error> {
+/// Verify the Sapling part of a private transaction: spend and output Groth16 proofs,
+/// spend authorization signatures and the binding signature.
+///
+/// The proofs of the transaction are checked in a single batch. Malformed proofs or
+/// signatures are rejected instead of panicking. The signatures are checked against
+/// the shielded signature hash if `shielded_sighash`, against the transaction hash otherwise.
+pub fn verify_private_transaction(
+	transaction: &transaction::SignedTransaction,
+	shielded_sighash: bool,
+) -> Result<(), transaction::Error> {
+	verify_private_transactions(Some(transaction), shielded_sighash).map_err(|(_, err)| err)
+}
+
+/// Verify the Sapling part of many private transactions, e.g. all private transactions
+/// of a block, with a single batched proof verification.
+///
+/// If the batch fails, every transaction is verified on its own to find the offending
+/// one, whose hash is returned together with the error.
+pub fn verify_private_transactions<'a, I>(
+	transactions: I,
+	shielded_sighash: bool,
+) -> Result<(), (H256, transaction::Error)>
+	where I: IntoIterator<Item = &'a transaction::SignedTransaction>
+{
+	let transactions: Vec<_> = transactions.into_iter().collect();
+	let mut batch = SaplingBatchVerifier::new();
+	for transaction in &transactions {
+		queue_private_transaction(transaction, shielded_sighash, &mut batch).map_err(|err| (transaction.hash(), err))?;
+	}
+
+	if batch.verify(&SPEND_VK, &OUTPUT_VK) {
+		return Ok(());
+	}
+
+	debug!(target: "txqueue", "Batched verification of {} shielded proofs failed, verifying one by one", batch.len());
+	for transaction in &transactions {
+		verify_private_transaction_proofs(transaction, shielded_sighash).map_err(|err| (transaction.hash(), err))?;
+	}
+
+	// Only reachable if the batch failed while all proofs verify one by one,
+	// which happens with negligible probability.
+	Ok(())
+}
+
+/// Check everything but the Groth16 proofs of a private transaction and queue its proofs into `batch`.
+fn queue_private_transaction(
+	transaction: &transaction::SignedTransaction,
+	shielded_sighash: bool,
+	batch: &mut SaplingBatchVerifier,
+) -> Result<(), transaction::Error> {
 	let mut ctx = SaplingVerificationContext::new();
 	let chain_id = transaction.transaction.chain_id();
-	let sighash = transaction.transaction.unsigned.hash(chain_id).into();
-	let spends = transaction.v_shielded_spend();
-	for spend in spends.iter() {
-		let spend_proof = Proof::<Bls12>::read(&spend.zkproof[..]).unwrap();
+	let sighash = transaction.transaction.unsigned.sighash(chain_id, shielded_sighash).into();
+	for spend in transaction.v_shielded_spend().iter() {
+		let spend_proof = Proof::<Bls12>::read(&spend.zkproof[..])
+			.map_err(|_| transaction::Error::InvalidSpendDescription)?;
+		let spend_auth_sig = spend.spend_auth_sig
+			.ok_or(transaction::Error::InvalidSpendDescription)?;
+		if !(ctx.check_spend_batched(
+			spend.cv,
+			spend.anchor,
+			&spend.nullifier,
+			spend.rk.clone(),
+			&sighash,
+			spend_auth_sig,
+			spend_proof,
+			batch,
+			&JUBJUB,
+		)) {
+			bail!(transaction::Error::InvalidSpendDescription);
+		}
+	}
+
+	for output in transaction.v_shielded_output().iter() {
+		let zkproof = Proof::<Bls12>::read(&output.zkproof[..])
+			.map_err(|_| transaction::Error::InvalidOutputDescription)?;
+		if !(ctx.check_output_batched(
+			output.cv,
+			output.cmu,
+			output.ephemeral_key,
+			zkproof,
+			batch,
+			&JUBJUB,
+		)) {
+			bail!(transaction::Error::InvalidOutputDescription);
+		}
+	}
+
+	final_check(&ctx, transaction, &sighash)
+}
+
+/// Verify a private transaction with a separate pairing check for every proof.
+fn verify_private_transaction_proofs(
+	transaction: &transaction::SignedTransaction,
+	shielded_sighash: bool,
+) -> Result<(), transaction::Error> {
+	let mut ctx = SaplingVerificationContext::new();
+	let chain_id = transaction.transaction.chain_id();
+	let sighash = transaction.transaction.unsigned.sighash(chain_id, shielded_sighash).into();
+	for spend in transaction.v_shielded_spend().iter() {
+		let spend_proof = Proof::<Bls12>::read(&spend.zkproof[..])
+			.map_err(|_| transaction::Error::InvalidSpendDescription)?;
+		let spend_auth_sig = spend.spend_auth_sig
+			.ok_or(transaction::Error::InvalidSpendDescription)?;
 		if !(ctx.check_spend(
 			spend.cv,
 			spend.anchor,
 			&spend.nullifier,
 			spend.rk.clone(),
 			&sighash,
-			spend.spend_auth_sig.unwrap(),
+			spend_auth_sig,
 			spend_proof,
 			&SPEND_VK,
 			&JUBJUB,
@@ -382,9 +479,9 @@ pub fn verify_private_transaction(transaction: &transaction::SignedTransaction)
 		}
 	}
 
-	let outputs = transaction.v_shielded_output();
-	for output in outputs.iter() {
-		let zkproof = Proof::<Bls12>::read(&output.zkproof[..]).unwrap();
+	for output in transaction.v_shielded_output().iter() {
+		let zkproof = Proof::<Bls12>::read(&output.zkproof[..])
+			.map_err(|_| transaction::Error::InvalidOutputDescription)?;
 		if !(ctx.check_output(
 			output.cv,
 			output.cmu,
@@ -397,10 +494,21 @@ pub fn verify_private_transaction(transaction: &transaction::SignedTransaction)
 		}
 	}
 
+	final_check(&ctx, transaction, &sighash)
+}
+
+/// Check the balancing value and the binding signature once all spends and outputs are accumulated in `ctx`.
+fn final_check(
+	ctx: &SaplingVerificationContext,
+	transaction: &transaction::SignedTransaction,
+	sighash: &[u8; 32],
+) -> Result<(), transaction::Error> {
+	let binding_sig = Signature::read(&transaction.binding_sig()[..])
+		.map_err(|_| transaction::Error::InvalidBalancingValueOrBindingSig)?;
 	if !(ctx.final_check(
 		transaction.balancing_value(),
-		&sighash,
-		Signature::read(&transaction.binding_sig()[..]).unwrap(),
+		sighash,
+		binding_sig,
 		&JUBJUB,
 	)) {
 		bail!(transaction::Error::InvalidBalancingValueOrBindingSig);
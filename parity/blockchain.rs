@@ -429,6 +429,7 @@ fn execute_import(cmd: ImportBlockchain) -> Result<(), String> {
 			client: client.clone(),
 			sync: None,
 			net: None,
+			wallet_sync: None,
 		},
 		None,
 		None,
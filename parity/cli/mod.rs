@@ -72,6 +72,42 @@ usage! {
 				"<PATH>",
 				"Path to the wallet",
 			}
+
+			CMD cmd_wallet_new_mnemonic
+			{
+				"Print the mnemonic phrase of the shielded wallet seed, created from a new phrase if the wallet has none",
+			}
+
+			CMD cmd_wallet_restore_mnemonic
+			{
+				"Restore the shielded wallet seed and its accounts from a mnemonic phrase",
+
+				ARG arg_wallet_restore_mnemonic_file: (Option<String>) = None,
+				"[FILE]",
+				"File holding the mnemonic phrase, read from the standard input if not given",
+			}
+
+			CMD cmd_wallet_export_proving_key
+			{
+				"Print the proving key of a shielded address, to import into a watch-only node building its transactions",
+
+				ARG arg_wallet_export_proving_key_address: (Option<String>) = None,
+				"<ADDRESS>",
+				"Shielded address of the wallet",
+			}
+
+			CMD cmd_wallet_sign_offline
+			{
+				"Sign a transaction built by origo_createUnsignedTransaction with the spending key of a shielded address",
+
+				ARG arg_wallet_sign_offline_address: (Option<String>) = None,
+				"<ADDRESS>",
+				"Shielded address whose notes the transaction spends",
+
+				ARG arg_wallet_sign_offline_file: (Option<String>) = None,
+				"[FILE]",
+				"File holding the hex of the unsigned transaction, read from the standard input if not given",
+			}
 		}
 
 		CMD cmd_import
@@ -310,6 +346,18 @@ usage! {
 			"--wallet-path=[PATH]",
 			"Specify the path for wallet files to be found",
 
+			ARG arg_sapling_params_dir: (String) = "$BASE/sapling-params", or |c: &Config| c.parity.as_ref()?.sapling_params_dir.clone(),
+			"--sapling-params-dir=[PATH]",
+			"Specify the directory of the Sapling proving parameters sapling-spend.params and sapling-output.params, checked against their pinned hashes at startup",
+
+			ARG arg_shielded_prover: (Option<String>) = None, or |c: &Config| c.parity.as_ref()?.shielded_prover.clone(),
+			"--shielded-prover=[ADDRESS]",
+			"Prove the shielded transactions with the origo-prover service at ADDRESS, either unix:<path> or <host>:<port>, instead of in-process. The service learns the values and nullifiers of the transactions, only use a trusted one.",
+
+			FLAG flag_no_shielded_wallet: (bool) = false, or |c: &Config| c.parity.as_ref()?.no_shielded_wallet,
+			"--no-shielded-wallet",
+			"Don't run the shielded wallet. The wallet isn't synced with the chain and the origo wallet RPCs are disabled.",
+
 			ARG arg_identity: (String) = "", or |c: &Config| c.parity.as_ref()?.identity.clone(),
 			"--identity=[NAME]",
 			"Specify your node's name.",
@@ -775,7 +823,7 @@ usage! {
 
 			ARG arg_author: (Option<String>) = None, or |c: &Config| c.mining.as_ref()?.author.clone(),
 			"--author=[ADDRESS]",
-			"Specify the block author (aka \"coinbase\") address for sending block rewards from sealed blocks. NOTE: MINING WILL NOT WORK WITHOUT THIS OPTION.", // Sealing/Mining Option
+			"Specify the block author (aka \"coinbase\") address for sending block rewards from sealed blocks. A shielded address is paid the block rewards by coinbase transactions once the chain enables them. NOTE: MINING WILL NOT WORK WITHOUT THIS OPTION.", // Sealing/Mining Option
 
 			ARG arg_engine_signer: (Option<String>) = None, or |c: &Config| c.mining.as_ref()?.engine_signer.clone(),
 			"--engine-signer=[ADDRESS]",
@@ -805,6 +853,10 @@ usage! {
 			"--max-round-blocks-to-import=[S]",
 			"Maximal number of blocks to import for each import round.",
 
+			ARG arg_internal_miner_threads: (Option<usize>) = None, or |c: &Config| c.mining.as_ref()?.internal_miner_threads.clone(),
+			"--internal-miner-threads=[N]",
+			"Seal PoW blocks with the built-in CPU equihash solver using N threads. Intended for dev and test chains; needs a few GB of memory.",
+
 		["Internal Options"]
 			FLAG flag_can_restart: (bool) = false, or |_| None,
 			"--can-restart",
@@ -1152,6 +1204,9 @@ struct Operating {
 	db_path: Option<String>,
 	keys_path: Option<String>,
 	wallet_path: Option<String>,
+	sapling_params_dir: Option<String>,
+	shielded_prover: Option<String>,
+	no_shielded_wallet: Option<bool>,
 	identity: Option<String>,
 	light: Option<bool>,
 	no_persistent_txqueue: Option<bool>,
@@ -1351,6 +1406,7 @@ struct Mining {
 	refuse_service_transactions: Option<bool>,
 	infinite_pending_block: Option<bool>,
 	max_round_blocks_to_import: Option<usize>,
+	internal_miner_threads: Option<usize>,
 }
 
 #[derive(Default, Debug, PartialEq, Deserialize)]
@@ -1669,6 +1725,10 @@ mod tests {
 			cmd_account_import: false,
 			cmd_wallet: false,
 			cmd_wallet_import: false,
+			cmd_wallet_new_mnemonic: false,
+			cmd_wallet_restore_mnemonic: false,
+			cmd_wallet_export_proving_key: false,
+			cmd_wallet_sign_offline: false,
 			cmd_import: false,
 			cmd_export: false,
 			cmd_export_blocks: false,
@@ -1704,6 +1764,10 @@ mod tests {
 			arg_dapp_path: None,
 			arg_account_import_path: None,
 			arg_wallet_import_path: None,
+			arg_wallet_restore_mnemonic_file: None,
+			arg_wallet_export_proving_key_address: None,
+			arg_wallet_sign_offline_address: None,
+			arg_wallet_sign_offline_file: None,
 			arg_db_reset_num: 10,
 
 			// -- Operating Options
@@ -1722,6 +1786,9 @@ mod tests {
 			arg_db_path: Some("$HOME/.origo/chains".into()),
 			arg_keys_path: "$HOME/.origo/keys".into(),
 			arg_wallet_path: "$HOME/.origo/wallet".into(),
+			arg_sapling_params_dir: "$HOME/.origo/sapling-params".into(),
+			arg_shielded_prover: None,
+			flag_no_shielded_wallet: false,
 			arg_identity: "".into(),
 			flag_light: false,
 			flag_no_hardcoded_sync: false,
@@ -1863,6 +1930,7 @@ mod tests {
 			flag_refuse_service_transactions: false,
 			flag_infinite_pending_block: false,
 			arg_max_round_blocks_to_import: 12usize,
+			arg_internal_miner_threads: None,
 
 			flag_stratum: false,
 			arg_stratum_interface: "local".to_owned(),
@@ -2001,6 +2069,9 @@ mod tests {
 				db_path: None,
 				keys_path: None,
 				wallet_path: None,
+				sapling_params_dir: None,
+				shielded_prover: None,
+				no_shielded_wallet: None,
 				identity: None,
 				light: None,
 				no_hardcoded_sync: None,
@@ -2142,6 +2213,7 @@ mod tests {
 				refuse_service_transactions: None,
 				infinite_pending_block: None,
 				max_round_blocks_to_import: None,
+				internal_miner_threads: None,
 			}),
 			footprint: Some(Footprint {
 				tracing: Some("on".into()),
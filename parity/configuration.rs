@@ -18,9 +18,11 @@ use ansi_term::Colour;
 use bytes::Bytes;
 use cli::{Args, ArgsError};
 use ethcore::client::VMType;
+use ethcore::ProverAddress;
 use ethcore::miner::{stratum, MinerOptions};
 use ethcore::snapshot::SnapshotConfiguration;
 use ethcore::verification::queue::VerifierSettings;
+use ethcore_wallet::{decode_payment_address, SaplingPaymentAddress};
 use ethereum_types::{Address, H256, U256};
 use ethkey::{Public, Secret};
 use hash::keccak;
@@ -65,6 +67,7 @@ use secretstore::{
 };
 use snapshot::{self, SnapshotCommand};
 use updater::{ReleaseTrack, UpdateFilter, UpdatePolicy};
+use wallet::{ExportProvingKey, NewMnemonic, RestoreMnemonic, SignOffline, WalletCmd};
 
 const DEFAULT_MAX_PEERS: u16 = 50;
 const DEFAULT_MIN_PEERS: u16 = 25;
@@ -75,6 +78,7 @@ pub enum Cmd {
 	Version,
 	Account(AccountCmd),
 	ImportPresaleWallet(ImportWallet),
+	Wallet(WalletCmd),
 	Blockchain(BlockchainCmd),
 	SignerToken(WsConfiguration, LogConfig),
 	SignerSign {
@@ -259,18 +263,47 @@ impl Configuration {
 			});
 			Cmd::Account(account_cmd)
 		} else if self.args.cmd_wallet {
-			let presale_cmd = ImportWallet {
-				iterations: keys_iterations,
-				path: dirs.keys,
-				spec: spec,
-				wallet_path: self.args.arg_wallet_import_path.clone().unwrap(),
-				password_file: self
-					.accounts_config()?
-					.password_files
-					.first()
-					.map(|x| x.to_owned()),
-			};
-			Cmd::ImportPresaleWallet(presale_cmd)
+			let password_file = self
+				.accounts_config()?
+				.password_files
+				.first()
+				.map(|x| x.to_owned());
+			if self.args.cmd_wallet_import {
+				let presale_cmd = ImportWallet {
+					iterations: keys_iterations,
+					path: dirs.keys,
+					spec: spec,
+					wallet_path: self.args.arg_wallet_import_path.clone().unwrap(),
+					password_file: password_file,
+				};
+				Cmd::ImportPresaleWallet(presale_cmd)
+			} else if self.args.cmd_wallet_new_mnemonic {
+				Cmd::Wallet(WalletCmd::NewMnemonic(NewMnemonic {
+					path: dirs.wallet,
+					password_file: password_file,
+				}))
+			} else if self.args.cmd_wallet_restore_mnemonic {
+				Cmd::Wallet(WalletCmd::RestoreMnemonic(RestoreMnemonic {
+					path: dirs.wallet,
+					password_file: password_file,
+					mnemonic_file: self.args.arg_wallet_restore_mnemonic_file.clone(),
+				}))
+			} else if self.args.cmd_wallet_export_proving_key {
+				Cmd::Wallet(WalletCmd::ExportProvingKey(ExportProvingKey {
+					path: dirs.wallet,
+					password_file: password_file,
+					address: self.args.arg_wallet_export_proving_key_address.clone().unwrap(),
+				}))
+			} else if self.args.cmd_wallet_sign_offline {
+				Cmd::Wallet(WalletCmd::SignOffline(SignOffline {
+					path: dirs.wallet,
+					password_file: password_file,
+					address: self.args.arg_wallet_sign_offline_address.clone().unwrap(),
+					transaction_file: self.args.arg_wallet_sign_offline_file.clone(),
+				}))
+			} else {
+				unreachable!();
+			}
 		} else if self.args.cmd_import {
 			let import_cmd = ImportBlockchain {
 				spec: spec,
@@ -423,7 +456,10 @@ impl Configuration {
 				acc_conf: self.accounts_config()?,
 				gas_pricer_conf: self.gas_pricer_config()?,
 				miner_extras: self.miner_extras()?,
+				shielded_prover: self.shielded_prover()?,
+				shielded_wallet: !self.args.flag_no_shielded_wallet,
 				stratum: self.stratum_options()?,
+				internal_miner_threads: self.internal_miner_threads()?,
 				update_policy: update_policy,
 				allow_missing_blocks: self.args.flag_jsonrpc_allow_missing_blocks,
 				mode: mode,
@@ -475,8 +511,13 @@ impl Configuration {
 	fn miner_extras(&self) -> Result<MinerExtras, String> {
 		let floor = to_u256(&self.args.arg_gas_floor_target)?;
 		let ceil = to_u256(&self.args.arg_gas_cap)?;
+		let shielded_author = self.shielded_author();
 		let extras = MinerExtras {
-			author: self.author()?,
+			author: match shielded_author {
+				Some(_) => Address::default(),
+				None => self.author()?,
+			},
+			shielded_author,
 			extra_data: self.extra_data()?,
 			gas_range_target: (floor, ceil),
 			engine_signer: self.engine_signer()?,
@@ -498,6 +539,23 @@ impl Configuration {
 		)
 	}
 
+	// The author, if it is a shielded address.
+	fn shielded_author(&self) -> Option<SaplingPaymentAddress> {
+		self.args
+			.arg_etherbase
+			.as_ref()
+			.or(self.args.arg_author.as_ref())
+			.and_then(|author| decode_payment_address(author))
+	}
+
+	// The address of the service proving the shielded transactions, if not proven in-process.
+	fn shielded_prover(&self) -> Result<Option<ProverAddress>, String> {
+		match self.args.arg_shielded_prover {
+			Some(ref address) => address.parse().map(Some),
+			None => Ok(None),
+		}
+	}
+
 	fn engine_signer(&self) -> Result<Address, String> {
 		to_address(self.args.arg_engine_signer.clone())
 	}
@@ -611,6 +669,13 @@ impl Configuration {
 		Ok(cfg)
 	}
 
+	fn internal_miner_threads(&self) -> Result<Option<usize>, String> {
+		match self.args.arg_internal_miner_threads {
+			Some(0) => Err("--internal-miner-threads must be at least 1".into()),
+			threads => Ok(threads),
+		}
+	}
+
 	fn stratum_options(&self) -> Result<Option<stratum::Options>, String> {
 		if self.args.flag_stratum {
 			Ok(Some(stratum::Options {
@@ -1128,6 +1193,7 @@ impl Configuration {
 		let cache_path = replace_home_and_local(&data_path, &local_path, cache_path);
 		let keys_path = replace_home(&data_path, &self.args.arg_keys_path);
 		let wallet_path = replace_home(&data_path, &self.args.arg_wallet_path);
+		let sapling_params_path = replace_home(&data_path, &self.args.arg_sapling_params_dir);
 		let secretstore_path = replace_home(&data_path, &self.args.arg_secretstore_path);
 		let ui_path = replace_home(&data_path, &self.args.arg_ui_path);
 
@@ -1139,6 +1205,7 @@ impl Configuration {
 			signer: ui_path,
 			secretstore: secretstore_path,
 			wallet: wallet_path,
+			sapling_params: sapling_params_path,
 		}
 	}
 
@@ -1384,6 +1451,7 @@ mod tests {
 	use rpc::WsConfiguration;
 	use rpc_apis::ApiSet;
 	use run::RunCmd;
+	use wallet::{RestoreMnemonic, SignOffline, WalletCmd};
 
 	use network::{AllowIP, IpFilter};
 
@@ -1477,6 +1545,50 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn test_command_wallet_restore_mnemonic() {
+		let args = vec![
+			"parity",
+			"wallet",
+			"restore-mnemonic",
+			"phrase.txt",
+			"--password",
+			"pwd",
+		];
+		let conf = parse(&args);
+		assert_eq!(
+			conf.into_command().unwrap().cmd,
+			Cmd::Wallet(WalletCmd::RestoreMnemonic(RestoreMnemonic {
+				path: Directories::default().wallet,
+				password_file: Some("pwd".into()),
+				mnemonic_file: Some("phrase.txt".into()),
+			}))
+		);
+	}
+
+	#[test]
+	fn test_command_wallet_sign_offline() {
+		let args = vec![
+			"parity",
+			"wallet",
+			"sign-offline",
+			"ogo1address",
+			"unsigned.hex",
+			"--password",
+			"pwd",
+		];
+		let conf = parse(&args);
+		assert_eq!(
+			conf.into_command().unwrap().cmd,
+			Cmd::Wallet(WalletCmd::SignOffline(SignOffline {
+				path: Directories::default().wallet,
+				password_file: Some("pwd".into()),
+				address: "ogo1address".into(),
+				transaction_file: Some("unsigned.hex".into()),
+			}))
+		);
+	}
+
 	#[test]
 	fn test_command_blockchain_import() {
 		let args = vec!["parity", "import", "blockchain.json"];
@@ -1689,6 +1801,9 @@ mod tests {
 			fat_db: Default::default(),
 			snapshot_conf: Default::default(),
 			stratum: None,
+			internal_miner_threads: None,
+			shielded_prover: None,
+			shielded_wallet: true,
 			check_seal: true,
 			download_old_blocks: true,
 			verifier_settings: Default::default(),
@@ -2141,6 +2256,21 @@ mod tests {
 		assert_eq!(conf1.ipfs_config().port, 5002);
 	}
 
+	#[test]
+	fn should_parse_internal_miner_threads() {
+		// given
+
+		// when
+		let conf0 = parse(&["parity"]);
+		let conf1 = parse(&["parity", "--internal-miner-threads", "4"]);
+		let conf2 = parse(&["parity", "--internal-miner-threads", "0"]);
+
+		// then
+		assert_eq!(conf0.internal_miner_threads(), Ok(None));
+		assert_eq!(conf1.internal_miner_threads(), Ok(Some(4)));
+		assert!(conf2.internal_miner_threads().is_err());
+	}
+
 	#[test]
 	fn should_expose_all_servers() {
 		// given
@@ -30,6 +30,7 @@ use ethcore::client::{
 use types::BlockNumber;
 use ethcore::snapshot::{RestorationStatus, SnapshotService as SS};
 use ethcore::snapshot::service::Service as SnapshotService;
+use ethcore_wallet::{WalletSync, WalletSyncStatus};
 use sync::{LightSyncProvider, LightSync, SyncProvider, ManageNetwork};
 use io::{TimerToken, IoContext, IoHandler};
 use light::Cache as LightDataCache;
@@ -101,6 +102,7 @@ pub struct Report {
 	queue_info: BlockQueueInfo,
 	cache_sizes: CacheSizes,
 	sync_info: Option<SyncInfo>,
+	wallet_sync: Option<WalletSyncStatus>,
 }
 
 /// Something which can provide data to the informant.
@@ -120,6 +122,7 @@ pub struct FullNodeInformantData {
 	pub client: Arc<Client>,
 	pub sync: Option<Arc<SyncProvider>>,
 	pub net: Option<Arc<ManageNetwork>>,
+	pub wallet_sync: Option<Arc<WalletSync>>,
 }
 
 impl InformantData for FullNodeInformantData {
@@ -168,6 +171,7 @@ impl InformantData for FullNodeInformantData {
 			queue_info,
 			cache_sizes,
 			sync_info,
+			wallet_sync: self.wallet_sync.as_ref().map(|wallet_sync| wallet_sync.status()),
 		}
 	}
 }
@@ -210,6 +214,7 @@ impl InformantData for LightNodeInformantData {
 			queue_info,
 			cache_sizes,
 			sync_info,
+			wallet_sync: None,
 		}
 	}
 }
@@ -271,6 +276,7 @@ impl<T: InformantData> Informant<T> {
 			queue_info,
 			cache_sizes,
 			sync_info,
+			wallet_sync,
 			..
 		} = full_report;
 
@@ -281,7 +287,8 @@ impl<T: InformantData> Informant<T> {
 				_ => false,
 			}
 		);
-		if !importing && !snapshot_sync && elapsed < Duration::from_secs(30) {
+		let wallet_syncing = wallet_sync.map_or(false, |status| !status.is_synced());
+		if !importing && !snapshot_sync && !wallet_syncing && elapsed < Duration::from_secs(30) {
 			return;
 		}
 
@@ -293,7 +300,7 @@ impl<T: InformantData> Informant<T> {
 			false => t,
 		};
 
-		info!(target: "import", "{}  {}  {}  {}",
+		info!(target: "import", "{}  {}{}  {}  {}",
 			match importing {
 				true => match snapshot_sync {
 					false => format!("Syncing {} {}  {}  {}+{} Qed",
@@ -349,6 +356,13 @@ impl<T: InformantData> Informant<T> {
 				),
 				_ => String::new(),
 			},
+			match wallet_sync {
+				Some(status) if wallet_syncing => format!("  Wallet {} {} behind",
+					paint(Green.bold(), format!("#{}", status.scanned_block)),
+					paint(Yellow.bold(), format!("{}", status.lag())),
+				),
+				_ => String::new(),
+			},
 			cache_sizes.display(Blue.bold(), &paint),
 			match rpc_stats {
 				Some(ref rpc_stats) => format!(
@@ -53,6 +53,7 @@ extern crate ethcore_miner as miner;
 extern crate ethcore_network as network;
 extern crate ethcore_service;
 extern crate ethcore_sync as sync;
+extern crate ethcore_wallet;
 extern crate ethereum_types;
 extern crate ethkey;
 extern crate ethstore;
@@ -71,6 +72,7 @@ extern crate parity_updater as updater;
 extern crate parity_version;
 extern crate parity_whisper;
 extern crate registrar;
+extern crate zcash_primitives;
 
 #[macro_use]
 extern crate log as rlog;
@@ -115,6 +117,7 @@ mod signer;
 mod snapshot;
 mod upgrade;
 mod user_defaults;
+mod wallet;
 mod whisper;
 mod db;
 
@@ -209,6 +212,7 @@ fn execute<Cr, Rr>(
 		Cmd::Hash(maybe_file) => print_hash_of(maybe_file).map(|s| ExecutionAction::Instant(Some(s))),
 		Cmd::Account(account_cmd) => account::execute(account_cmd).map(|s| ExecutionAction::Instant(Some(s))),
 		Cmd::ImportPresaleWallet(presale_cmd) => presale::execute(presale_cmd).map(|s| ExecutionAction::Instant(Some(s))),
+		Cmd::Wallet(wallet_cmd) => wallet::execute(wallet_cmd).map(|s| ExecutionAction::Instant(Some(s))),
 		Cmd::Blockchain(blockchain_cmd) => blockchain::execute(blockchain_cmd).map(|_| ExecutionAction::Instant(None)),
 		Cmd::SignerToken(ws_conf, logger_config) => signer::execute(ws_conf, logger_config).map(|s| ExecutionAction::Instant(Some(s))),
 		Cmd::SignerSign { id, pwfile, port, authfile } => cli_signer::signer_sign(id, pwfile, port, authfile).map(|s| ExecutionAction::Instant(Some(s))),
@@ -45,7 +45,8 @@ pub struct EpochFetch {
 }
 
 impl EpochFetch {
-	fn request<T>(&self, req: T) -> BoxFuture<T::Out, &'static str>
+	/// Make an on-demand request through the sync service.
+	pub fn request<T>(&self, req: T) -> BoxFuture<T::Out, &'static str>
 		where T: Send + request::RequestAdapter + 'static, T::Out: Send + 'static
 	{
 		Box::new(match self.sync.read().upgrade() {
@@ -17,5 +17,7 @@
 //! Utilities and helpers for the light client.
 
 mod epoch_fetch;
+mod wallet_sync;
 
 pub use self::epoch_fetch::EpochFetch;
+pub use self::wallet_sync::LightWalletSync;
@@ -0,0 +1,373 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shielded wallet sync for the light client.
+//!
+//! The light client only stores headers, the blocks the wallet scans are fetched on demand.
+//! The bodies of each batch of blocks are fetched, checked against the transactions roots of
+//! the headers, then trial decrypted.
+//!
+//! Unlike the full node, the wallet of a light client can't be rescanned: keys added to it
+//! are only scanned for from the last scanned block on.
+
+use std::cmp;
+use std::collections::VecDeque;
+use std::sync::{mpsc, Arc, Weak};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ethcore_wallet::Wallet;
+use ethcore_wallet::scanner::decrypt_blocks;
+use ethcore_wallet::wallet::WITNESS_CACHE_SIZE;
+use ethereum_types::H256;
+use futures::{future, Future};
+use kvdb::{DBTransaction, KeyValueDB};
+use light::cht;
+use light::client::{LightChainClient, LightChainNotify};
+use light::on_demand::request;
+use light::MAX_SHIELDED_BLOCKS_PER_REQUEST;
+use parking_lot::{Mutex, RwLock};
+use types::encoded;
+use types::ids::BlockId;
+use zcash_primitives::merkle_tree::CommitmentTree;
+use zcash_primitives::sapling::Node;
+
+use super::EpochFetch;
+
+/// Minimum time between two logs of the progress of the wallet catching up.
+const PROGRESS_LOG_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Keeps the shielded wallet of a light client synced with the canonical headers,
+/// on a thread of its own.
+pub struct LightWalletSync {
+	wallet: Arc<RwLock<Wallet>>,
+	// Wakes the sync thread up, which stops once it is dropped.
+	sender: Mutex<mpsc::Sender<()>>,
+}
+
+impl LightWalletSync {
+	/// Loads the wallet data stored with the headers and starts syncing the wallet
+	/// with the headers imported since it was last written.
+	pub fn start(
+		wallet: Arc<RwLock<Wallet>>,
+		client: Arc<LightChainClient>,
+		db: Arc<KeyValueDB>,
+		fetch: EpochFetch,
+	) -> Self {
+		let mut syncer = Syncer {
+			wallet: wallet.clone(),
+			db,
+			fetch,
+			tree: None,
+			recent: VecDeque::new(),
+			restoring: false,
+			last_progress_log: Instant::now(),
+		};
+		syncer.load(&*client);
+		let client: Weak<LightChainClient> = Arc::downgrade(&client);
+
+		let (sender, receiver) = mpsc::channel();
+		thread::Builder::new()
+			.name("light-wallet-sync".into())
+			.spawn(move || {
+				while receiver.recv().is_ok() {
+					// The headers imported meanwhile are all scanned at once.
+					while receiver.try_recv().is_ok() {}
+					match client.upgrade() {
+						Some(client) => syncer.sync(&*client),
+						None => break,
+					}
+				}
+			})
+			.expect("Failed to spawn the light wallet sync thread");
+		// Catch up with the headers imported while the node was stopped.
+		let _ = sender.send(());
+
+		LightWalletSync {
+			wallet,
+			sender: Mutex::new(sender),
+		}
+	}
+
+	/// Schedules a scan of the blocks imported since the last one.
+	pub fn notify(&self) {
+		let _ = self.sender.lock().send(());
+	}
+
+	/// The wallet being synced.
+	pub fn wallet(&self) -> Arc<RwLock<Wallet>> {
+		self.wallet.clone()
+	}
+}
+
+impl LightChainNotify for LightWalletSync {
+	fn new_headers(&self, good: &[H256]) {
+		if !good.is_empty() {
+			self.notify();
+		}
+	}
+}
+
+// A block scanned by the wallet, kept to rewind it once retracted.
+struct ScannedBlock {
+	hash: H256,
+	number: u64,
+	parent_hash: H256,
+	private_txs: Vec<H256>,
+}
+
+// The state of the sync thread.
+struct Syncer {
+	wallet: Arc<RwLock<Wallet>>,
+	db: Arc<KeyValueDB>,
+	fetch: EpochFetch,
+	// The commitment tree as of the end of the last scanned block, once known.
+	tree: Option<(H256, CommitmentTree<Node>)>,
+	// The last blocks scanned, oldest first, at most as many as the witnesses cached.
+	recent: VecDeque<ScannedBlock>,
+	// Whether the accounts restored from a seed which received no notes are to be pruned
+	// once the wallet is synced.
+	restoring: bool,
+	last_progress_log: Instant,
+}
+
+impl Syncer {
+	// Reload the notes stored in db, or rebuild them from the genesis if the block
+	// they were scanned up to is unknown.
+	fn load(&mut self, client: &LightChainClient) {
+		let mut wallet = self.wallet.write();
+		wallet.load_wallet_data(&*self.db);
+
+		match wallet.last_scanned_block.and_then(|hash| client.block_header(BlockId::Hash(hash))) {
+			Some(header) => wallet.last_scanned_number = header.number(),
+			None => {
+				if let Some(hash) = wallet.last_scanned_block {
+					warn!(target: "wallet", "Wallet last scanned block {} is unknown, rescanning the chain.", hash);
+				}
+				wallet.clear_wallet_data();
+				if wallet.list_addresses().is_empty() {
+					// Nothing to look for in the chain.
+					let info = client.chain_info();
+					wallet.last_scanned_block = Some(info.best_block_hash);
+					wallet.last_scanned_number = info.best_block_number;
+				} else {
+					wallet.last_scanned_block = None;
+					wallet.last_scanned_number = 0;
+					self.restoring = true;
+				}
+			}
+		}
+	}
+
+	// Bring the wallet up to date with the canonical headers, and store it.
+	fn sync(&mut self, client: &LightChainClient) {
+		self.rewind_retracted(client);
+		if let Err(e) = self.scan_enacted(client) {
+			// Retried once new headers are imported.
+			debug!(target: "wallet", "Light wallet sync interrupted: {}", e);
+		}
+
+		let best = client.chain_info().best_block_number;
+		if self.restoring && self.wallet.read().last_scanned_number >= best {
+			self.restoring = false;
+			let mut wallet = self.wallet.write();
+			match wallet.finish_mnemonic_restore() {
+				Ok(ref addresses) if !addresses.is_empty() => {
+					// The chain is scanned again from the genesis for the new accounts.
+					self.recent.clear();
+					self.tree = None;
+					wallet.clear_wallet_data();
+					wallet.last_scanned_block = None;
+					wallet.last_scanned_number = 0;
+					self.restoring = true;
+				}
+				Ok(_) => {}
+				Err(e) => warn!(target: "wallet", "Failed to restore the wallet accounts: {}", e),
+			}
+		}
+		self.write();
+	}
+
+	// Undo the blocks scanned by the wallet which were retracted from the canonical chain,
+	// down to their common ancestor with it.
+	fn rewind_retracted(&mut self, client: &LightChainClient) {
+		loop {
+			let (hash, number) = {
+				let wallet = self.wallet.read();
+				match wallet.last_scanned_block {
+					Some(hash) => (hash, wallet.last_scanned_number),
+					None => return,
+				}
+			};
+			if client.block_hash(BlockId::Number(number)).map_or(true, |canonical| canonical == hash) {
+				return;
+			}
+
+			let mut wallet = self.wallet.write();
+			self.tree = None;
+			match self.recent.pop_back() {
+				Some(ref block) if block.hash == hash => {
+					wallet.remove_retracted_txs(&block.private_txs);
+					wallet.rewind_retracted_blocks(1, block.number - 1);
+					wallet.last_scanned_block = Some(block.parent_hash);
+				}
+				_ => {
+					warn!(target: "wallet", "Retracted block {} is unknown, rescanning the chain.", hash);
+					self.recent.clear();
+					wallet.clear_wallet_data();
+					wallet.last_scanned_block = None;
+					wallet.last_scanned_number = 0;
+					return;
+				}
+			}
+		}
+	}
+
+	// Scan the canonical blocks following the last scanned one, up to the best block.
+	fn scan_enacted(&mut self, client: &LightChainClient) -> Result<(), String> {
+		let best = client.chain_info().best_block_number;
+		loop {
+			let (first, last_scanned, viewing_keys) = {
+				let wallet = self.wallet.read();
+				(wallet.last_scanned_number + 1, wallet.last_scanned_block, wallet.key_store.get_unhandled_viewing_keys(&None))
+			};
+			if first > best {
+				return Ok(());
+			}
+			let last = cmp::min(best, first + MAX_SHIELDED_BLOCKS_PER_REQUEST - 1);
+
+			let parent = match last_scanned {
+				Some(hash) => self.header(client, BlockId::Hash(hash))?,
+				None => self.header(client, BlockId::Earliest)?,
+			};
+			let headers = self.canonical_headers(client, first, last)?;
+			let mut parent_hash = parent.hash();
+			for header in &headers {
+				if header.parent_hash() != parent_hash {
+					return Err("The chain was reorganized during the sync.".into());
+				}
+				parent_hash = header.hash();
+			}
+
+			let mut tree = match self.commitment_tree(&parent)? {
+				Some(tree) => tree,
+				None => {
+					// The tree of a block from before the sapling roots can only be rebuilt
+					// from the genesis.
+					warn!(target: "wallet", "No commitment tree for block #{}, rescanning the chain.", parent.number());
+					let mut wallet = self.wallet.write();
+					self.recent.clear();
+					wallet.clear_wallet_data();
+					wallet.last_scanned_block = None;
+					wallet.last_scanned_number = 0;
+					continue;
+				}
+			};
+			let blocks = self.blocks(headers)?;
+
+			for block in decrypt_blocks(blocks, &viewing_keys) {
+				self.wallet.write().sync_decrypted_block(&mut tree, &block, &viewing_keys);
+				self.recent.push_back(ScannedBlock {
+					hash: block.block.hash(),
+					number: block.block.number(),
+					parent_hash: block.block.parent_hash(),
+					private_txs: block.txs.iter().map(|tx| tx.hash()).collect(),
+				});
+				if self.recent.len() > WITNESS_CACHE_SIZE {
+					self.recent.pop_front();
+				}
+			}
+			self.tree = Some((parent_hash, tree));
+
+			if last < best && self.last_progress_log.elapsed() >= PROGRESS_LOG_INTERVAL {
+				self.last_progress_log = Instant::now();
+				info!(target: "wallet", "Syncing shielded wallet #{}/{} ({} blocks behind)", last, best, best - last);
+				self.write();
+			}
+		}
+	}
+
+	// The header of the given block, fetched from the network when it is no longer stored.
+	fn header(&self, client: &LightChainClient, id: BlockId) -> Result<encoded::Header, String> {
+		match client.block_header(id) {
+			Some(header) => Ok(header),
+			None => match id {
+				BlockId::Hash(hash) => Ok(self.fetch.request(request::HeaderByHash(hash.into())).wait()?),
+				_ => Err(format!("Block {:?} is unknown.", id)),
+			},
+		}
+	}
+
+	// The canonical headers of the blocks `first` to `last`. The headers pruned from the header
+	// chain are fetched from the network, against the CHT roots.
+	fn canonical_headers(&self, client: &LightChainClient, first: u64, last: u64) -> Result<Vec<encoded::Header>, String> {
+		let stored: Vec<_> = (first..=last)
+			.map(|number| (number, client.block_header(BlockId::Number(number))))
+			.collect();
+
+		let mut proofs = Vec::new();
+		for &(number, ref header) in &stored {
+			if header.is_some() {
+				continue;
+			}
+			let cht_root = cht::block_to_cht_number(number)
+				.and_then(|cht_num| client.cht_root(cht_num as usize))
+				.ok_or_else(|| format!("Block #{} is unknown.", number))?;
+			let req = request::HeaderProof::new(number, cht_root)
+				.expect("only fails for 0; the scanned blocks follow the genesis; qed");
+			proofs.push(self.fetch.request(req));
+		}
+		let hashes = future::join_all(proofs).wait()?;
+		let fetched = future::join_all(hashes.into_iter()
+			.map(|(hash, _)| self.fetch.request(request::HeaderByHash(hash.into())))
+		).wait()?;
+
+		let mut fetched = fetched.into_iter();
+		Ok(stored.into_iter()
+			.map(|(_, header)| header.unwrap_or_else(|| fetched.next().expect("a header is fetched for each one missing; qed")))
+			.collect())
+	}
+
+	// The commitment tree as of the end of the block `header`, `None` if it can't be checked.
+	fn commitment_tree(&mut self, header: &encoded::Header) -> Result<Option<CommitmentTree<Node>>, String> {
+		match self.tree.take() {
+			Some((hash, tree)) if hash == header.hash() => return Ok(Some(tree)),
+			_ => {}
+		}
+		if header.number() == 0 {
+			return Ok(Some(CommitmentTree::new()));
+		}
+		if header.final_sapling_root().is_none() {
+			return Ok(None);
+		}
+		Ok(Some(self.fetch.request(request::CommitmentTree(header.clone().into())).wait()?))
+	}
+
+	// The blocks of the given headers. The body of every block is fetched: unlike the shielded
+	// outputs listed by a peer, it is checked against the transactions root of the header.
+	fn blocks(&self, headers: Vec<encoded::Header>) -> Result<Vec<encoded::Block>, String> {
+		Ok(future::join_all(headers.into_iter()
+			.map(|header| self.fetch.request(request::Body(header.into())))
+		).wait()?)
+	}
+
+	fn write(&self) {
+		let mut batch = DBTransaction::new();
+		self.wallet.write().write_wallet_data(&mut batch);
+		self.db.write(batch).expect("Low level database error when writing wallet data. Some issue with disk?");
+	}
+}
+
@@ -22,6 +22,7 @@ use std::num::NonZeroU32;
 use ethcore::client::Mode;
 use ethcore::ethereum;
 use ethcore::spec::{Spec, SpecParams};
+use ethcore_wallet::SaplingPaymentAddress;
 use ethereum_types::{U256, Address};
 use parity_runtime::Executor;
 use hash_fetch::fetch::Client as FetchClient;
@@ -293,6 +294,8 @@ impl GasPricerConfig {
 #[derive(Debug, PartialEq)]
 pub struct MinerExtras {
 	pub author: Address,
+	/// Shielded address paid the block rewards. The transaction fees are paid to `author`, the zero address.
+	pub shielded_author: Option<SaplingPaymentAddress>,
 	pub engine_signer: Address,
 	pub extra_data: Vec<u8>,
 	pub gas_range_target: (U256, U256),
@@ -304,6 +307,7 @@ impl Default for MinerExtras {
 	fn default() -> Self {
 		MinerExtras {
 			author: Default::default(),
+			shielded_author: None,
 			engine_signer: Default::default(),
 			extra_data: version_data(),
 			gas_range_target: (8_000_000.into(), 10_000_000.into()),
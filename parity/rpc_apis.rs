@@ -26,12 +26,14 @@ use ethcore::client::Client;
 use ethcore::miner::Miner;
 use ethcore::snapshot::SnapshotService;
 use ethcore::client::BlockChainClient;
+use ethcore_wallet::{OperationManager, WalletSync};
 use sync::SyncState;
 use ethcore_logger::RotatingLogger;
 use hash_fetch::fetch::Client as FetchClient;
 use jsonrpc_core::{self as core, MetaIoHandler};
 use light::client::LightChainClient;
 use light::{Cache as LightDataCache, TransactionQueue as LightTransactionQueue};
+use light_helpers::LightWalletSync;
 use miner::external::ExternalMiner;
 use parity_rpc::dispatch::{FullDispatcher, LightDispatcher};
 use parity_rpc::informant::{ActivityNotifier, ClientNotifier};
@@ -251,6 +253,9 @@ pub struct FullDependencies {
 	pub gas_price_percentile: usize,
 	pub poll_lifetime: u32,
 	pub allow_missing_blocks: bool,
+	pub operations: Arc<OperationManager>,
+	/// The shielded wallet sync, `None` if the wallet is disabled.
+	pub wallet_sync: Option<Arc<WalletSync>>,
 }
 
 impl FullDependencies {
@@ -414,8 +419,13 @@ impl FullDependencies {
 				}
 				Api::Origo => {
 					#[cfg(feature = "accounts")]
-					handler.extend_with(OrigoAccountsInfo::to_delegate(OrigoAccountsClient::new(&self.accounts, dispatcher.clone(), &self.client.wallet() )));
+					{
+						if let Some(ref wallet_sync) = self.wallet_sync {
+							handler.extend_with(OrigoAccountsInfo::to_delegate(OrigoAccountsClient::new(&self.accounts, dispatcher.clone(), &wallet_sync.wallet(), Some(wallet_sync.clone()), &self.operations)));
+						}
+					}
 					handler.extend_with(OrigoRewardsInfo::to_delegate(OrigoRewardsClient::new(&self.client)));
+					handler.extend_with(OrigoCompactBlocks::to_delegate(OrigoCompactBlocksClient::new(&self.client)));
 				}
 				Api::ParitySet => {
 					handler.extend_with(
@@ -509,6 +519,9 @@ pub struct LightDependencies<T> {
 	pub whisper_rpc: Option<::whisper::RpcFactory>,
 	pub gas_price_percentile: usize,
 	pub poll_lifetime: u32,
+	pub operations: Arc<OperationManager>,
+	/// The shielded wallet sync, `None` if the wallet is disabled.
+	pub wallet_sync: Option<Arc<LightWalletSync>>,
 }
 
 impl<C: LightChainClient + 'static> LightDependencies<C> {
@@ -660,8 +673,13 @@ impl<C: LightChainClient + 'static> LightDependencies<C> {
 					handler.extend_with(ParityAccounts::to_delegate(ParityAccountsClient::new(&self.accounts)));
 				}
 				Api::Origo => {
+					// The rewards need the full chain, and the light wallet can't be rescanned.
 					#[cfg(feature = "accounts")]
-					warn!(target: "rpc", "Origo API is not available in light client mode.");
+					{
+						if let Some(ref wallet_sync) = self.wallet_sync {
+							handler.extend_with(OrigoAccountsInfo::to_delegate(OrigoAccountsClient::new(&self.accounts, dispatcher.clone(), &wallet_sync.wallet(), None, &self.operations)));
+						}
+					}
 				}
 				Api::ParitySet => handler.extend_with(
 					light::ParitySetClient::new(self.client.clone(), self.sync.clone(), self.fetch.clone())
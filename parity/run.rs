@@ -19,17 +19,19 @@ use std::sync::{Arc, Weak, atomic};
 use std::time::{Duration, Instant};
 use std::thread;
 
-use parking_lot::{Mutex};
+use parking_lot::{Mutex, RwLock};
 use ansi_term::Colour;
 use bytes::Bytes;
 use call_contract::CallContract;
 use ethcore::client::{BlockId, Client, Mode, DatabaseCompactionProfile, VMType, BlockChainClient, BlockInfo};
-use ethcore::miner::{self, stratum, Miner, MinerService, MinerOptions};
+use ethcore::miner::{self, internal_miner, stratum, Miner, MinerService, MinerOptions};
 use ethcore::snapshot::{self, SnapshotConfiguration};
 use ethcore::spec::SpecParams;
 use ethcore::verification::queue::VerifierSettings;
+use ethcore::{check_sapling_params, set_sapling_params_dir, set_tx_prover, ProverAddress, RemoteTxProver};
 use ethcore_logger::{Config as LogConfig, RotatingLogger};
 use ethcore_service::ClientService;
+use ethcore_wallet::{OperationManager, ShieldedCoinbase, Wallet, WalletSync, DEFAULT_OPERATION_WORKERS};
 use ethereum_types::Address;
 use futures::IntoFuture;
 use hash_fetch::{self, fetch};
@@ -107,6 +109,10 @@ pub struct RunCmd {
 	pub acc_conf: AccountsConfig,
 	pub gas_pricer_conf: GasPricerConfig,
 	pub miner_extras: MinerExtras,
+	/// Address of the service proving the shielded transactions, proven in-process if none.
+	pub shielded_prover: Option<ProverAddress>,
+	/// Whether to run the shielded wallet and sync it with the chain.
+	pub shielded_wallet: bool,
 	pub update_policy: UpdatePolicy,
 	pub mode: Option<Mode>,
 	pub tracing: Switch,
@@ -121,6 +127,7 @@ pub struct RunCmd {
 	pub name: String,
 	pub custom_bootnodes: bool,
 	pub stratum: Option<stratum::Options>,
+	pub internal_miner_threads: Option<usize>,
 	pub snapshot_conf: SnapshotConfiguration,
 	pub check_seal: bool,
 	pub allow_missing_blocks: bool,
@@ -161,6 +168,24 @@ impl ::local_store::NodeInfo for FullNodeInfo {
 	}
 }
 
+// Set the prover of the shielded transactions, checking the Sapling parameters when they are proven locally.
+fn set_shielded_prover(cmd: &RunCmd) -> Result<(), String> {
+	match cmd.shielded_prover {
+		Some(ref address) => {
+			info!("Proving shielded transactions with {}", Colour::White.bold().paint(address.to_string()));
+			set_tx_prover(Arc::new(RemoteTxProver::new(address.clone())));
+		}
+		None => {
+			set_sapling_params_dir(&cmd.dirs.sapling_params);
+			check_sapling_params().map_err(|e| format!(
+				"{} Download sapling-spend.params and sapling-output.params into {} or point --sapling-params-dir to them.",
+				e, cmd.dirs.sapling_params,
+			))?;
+		}
+	}
+	Ok(())
+}
+
 type LightClient = ::light::client::Client<::light_helpers::EpochFetch>;
 
 // helper for light execution.
@@ -195,6 +220,11 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 	// create dirs used by parity
 	cmd.dirs.create_dirs(cmd.acc_conf.unlocked_accounts.len() == 0, cmd.secretstore_conf.enabled)?;
 
+	// the shielded wallet proves transactions with the Sapling parameters, fail early if they are unusable
+	if cmd.shielded_wallet {
+		set_shielded_prover(&cmd)?;
+	}
+
 	//print out running parity environment
 	print_running_environment(&spec.data_dir, &cmd.dirs, &db_dirs);
 
@@ -254,6 +284,7 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 	let db = db::open_db(&db_dirs.client_path(algorithm).to_str().expect("DB path could not be converted to string."),
 						 &cmd.cache_config,
 						 &cmd.compaction).map_err(|e| format!("Failed to open database {:?}", e))?;
+	let wallet_db = db.key_value().clone();
     //Mark 1
 	let service = light_client::Service::start(config, &spec, fetch, db, cache.clone())
 		.map_err(|e| format!("Error starting light client: {}", e))?;
@@ -291,6 +322,21 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 	let light_sync = Arc::new(light_sync);
 	*sync_handle.write() = Arc::downgrade(&light_sync);
 
+	// the shielded wallet is synced with the headers, its blocks are fetched on demand
+	let wallet_sync = match cmd.shielded_wallet {
+		true => {
+			let wallet = Arc::new(RwLock::new(Wallet::new_from_dir(&cmd.dirs.wallet)));
+			let fetch = ::light_helpers::EpochFetch {
+				on_demand: on_demand.clone(),
+				sync: sync_handle.clone(),
+			};
+			let wallet_sync = Arc::new(::light_helpers::LightWalletSync::start(wallet, client.clone(), wallet_db, fetch));
+			service.add_notify(wallet_sync.clone());
+			Some(wallet_sync)
+		}
+		false => None,
+	};
+
 	// spin up event loop
 	let runtime = Runtime::with_default_thread_count();
 
@@ -327,7 +373,9 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 		executor: runtime.executor(),
 		whisper_rpc: whisper_factory,
 		gas_price_percentile: cmd.gas_price_percentile,
-		poll_lifetime: cmd.poll_lifetime
+		poll_lifetime: cmd.poll_lifetime,
+		operations: Arc::new(OperationManager::new(DEFAULT_OPERATION_WORKERS)),
+		wallet_sync: wallet_sync.clone(),
 	});
 
 	let dependencies = rpc::Dependencies {
@@ -363,7 +411,7 @@ fn execute_light_impl<Cr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq
 			rpc: rpc_direct,
 			informant,
 			client,
-			keep_alive: Box::new((service, ws_server, http_server, ipc_server, runtime)),
+			keep_alive: Box::new((service, ws_server, http_server, ipc_server, runtime, wallet_sync)),
 		}
 	})
 }
@@ -416,6 +464,9 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 	// create dirs used by parity
 	cmd.dirs.create_dirs(cmd.acc_conf.unlocked_accounts.len() == 0, cmd.secretstore_conf.enabled)?;
 
+	// the shielded wallet proves transactions with the Sapling parameters, fail early if they are unusable
+	set_shielded_prover(&cmd)?;
+
 	//print out running parity environment
 	print_running_environment(&spec.data_dir, &cmd.dirs, &db_dirs);
 
@@ -500,7 +551,13 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 		),
 		Some(Arc::new(Mutex::new(sender))),
 	));
-	miner.set_author(miner::Author::External(cmd.miner_extras.author));
+	match cmd.miner_extras.shielded_author {
+		Some(ref address) => {
+			warn!("Block rewards are paid to a shielded author, the transaction fees are paid to the zero address.");
+			miner.set_author(miner::Author::Shielded(Arc::new(ShieldedCoinbase(address.clone()))));
+		},
+		None => miner.set_author(miner::Author::External(cmd.miner_extras.author)),
+	}
 	miner.set_gas_range_target(cmd.miner_extras.gas_range_target);
 	miner.set_extra_data(cmd.miner_extras.extra_data);
 
@@ -570,7 +627,6 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 		restoration_db_handler,
 		&cmd.dirs.ipc_path(),
 		miner.clone(),
-		//pt_wallet,
 		Some(Arc::new(Mutex::new(receiver))),
 	).map_err(|e| format!("Client service error: {:?}", e))?;
 
@@ -580,6 +636,17 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 
 	// take handle to client
 	let client = service.client();
+
+	// the shielded wallet is synced with the chain on a thread of its own
+	let wallet_sync = match cmd.shielded_wallet {
+		true => {
+			let wallet = Arc::new(RwLock::new(Wallet::new_from_dir(&cmd.dirs.wallet)));
+			let wallet_sync = Arc::new(WalletSync::start(wallet, client.clone()));
+			service.add_notify(wallet_sync.clone());
+			Some(wallet_sync)
+		},
+		false => None,
+	};
 	// Update miners block gas limit
 	miner.update_transaction_queue_limits(*client.best_block_header().gas_limit());
 
@@ -635,6 +702,12 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			.map_err(|e| format!("Stratum start error: {:?}", e))?;
 	}
 
+	// start internal miner
+	if let Some(threads) = cmd.internal_miner_threads {
+		internal_miner::InternalMiner::register(threads, miner.clone(), Arc::downgrade(&client))
+			.map_err(|e| format!("Internal miner start error: {}", e))?;
+	}
+
 	let mut attached_protos = Vec::new();
 
 	let whisper_factory = if cmd.whisper.enabled {
@@ -731,6 +804,8 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 		gas_price_percentile: cmd.gas_price_percentile,
 		poll_lifetime: cmd.poll_lifetime,
 		allow_missing_blocks: cmd.allow_missing_blocks,
+		operations: Arc::new(OperationManager::new(DEFAULT_OPERATION_WORKERS)),
+		wallet_sync: wallet_sync.clone(),
 	});
 
 	let dependencies = rpc::Dependencies {
@@ -764,6 +839,7 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			client: service.client(),
 			sync: Some(sync_provider.clone()),
 			net: Some(manage_network.clone()),
+			wallet_sync: wallet_sync.clone(),
 		},
 		Some(snapshot_service.clone()),
 		Some(rpc_stats.clone()),
@@ -815,7 +891,7 @@ fn execute_impl<Cr, Rr>(cmd: RunCmd, logger: Arc<RotatingLogger>, on_client_rq:
 			informant,
 			client,
 			client_service: Arc::new(service),
-			keep_alive: Box::new((watcher, updater, ws_server, http_server, ipc_server, secretstore_key_server, ipfs_server, runtime)),
+			keep_alive: Box::new((watcher, updater, ws_server, http_server, ipc_server, secretstore_key_server, ipfs_server, runtime, wallet_sync)),
 		}
 	})
 }
@@ -76,7 +76,9 @@ fn restore_using<R: SnapshotReader>(snapshot: Arc<SnapshotService>, reader: &R,
 		format!("Failed to begin restoration: {}", e)
 	})?;
 
-	let (num_state, num_blocks) = (manifest.state_hashes.len(), manifest.block_hashes.len());
+	// shielded chunks are reported together with the state chunks.
+	let num_state = manifest.state_hashes.len() + manifest.shielded_hashes.len();
+	let num_blocks = manifest.block_hashes.len();
 
 	let informant_handle = snapshot.clone();
 	::std::thread::spawn(move || {
@@ -104,6 +106,22 @@ fn restore_using<R: SnapshotReader>(snapshot: Arc<SnapshotService>, reader: &R,
  		snapshot.feed_state_chunk(state_hash, &chunk);
  	}
 
+	info!("Restoring shielded pool");
+	for &shielded_hash in &manifest.shielded_hashes {
+		if snapshot.status() == RestorationStatus::Failed {
+			return Err("Restoration failed".into());
+		}
+
+		let chunk = reader.chunk(shielded_hash)
+			.map_err(|e| format!("Encountered error while reading chunk {:?}: {}", shielded_hash, e))?;
+
+		let hash = keccak(&chunk);
+		if hash != shielded_hash {
+			return Err(format!("Mismatched chunk hash. Expected {:?}, got {:?}", shielded_hash, hash));
+		}
+		snapshot.feed_shielded_chunk(shielded_hash, &chunk);
+	}
+
 	info!("Restoring blocks");
 	for &block_hash in &manifest.block_hashes {
 		if snapshot.status() == RestorationStatus::Failed {
@@ -0,0 +1,181 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Shielded wallet seed backup and restore, and offline signing.
+
+use std::fs;
+use std::io;
+
+use ethcore_wallet::{encode_payment_address, UnsignedTransaction, Wallet};
+use ethereum_types::U256;
+use ethkey::Password;
+use helpers::{password_prompt, password_from_file};
+use rustc_hex::{FromHex, ToHex};
+use types::transaction::{Action, CONVERSION_FACTOR};
+
+#[derive(Debug, PartialEq)]
+pub enum WalletCmd {
+	NewMnemonic(NewMnemonic),
+	RestoreMnemonic(RestoreMnemonic),
+	ExportProvingKey(ExportProvingKey),
+	SignOffline(SignOffline),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NewMnemonic {
+	pub path: String,
+	pub password_file: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RestoreMnemonic {
+	pub path: String,
+	pub password_file: Option<String>,
+	pub mnemonic_file: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ExportProvingKey {
+	pub path: String,
+	pub password_file: Option<String>,
+	pub address: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SignOffline {
+	pub path: String,
+	pub password_file: Option<String>,
+	pub address: String,
+	pub transaction_file: Option<String>,
+}
+
+pub fn execute(cmd: WalletCmd) -> Result<String, String> {
+	match cmd {
+		WalletCmd::NewMnemonic(new_cmd) => new_mnemonic(new_cmd),
+		WalletCmd::RestoreMnemonic(restore_cmd) => restore_mnemonic(restore_cmd),
+		WalletCmd::ExportProvingKey(export_cmd) => export_proving_key(export_cmd),
+		WalletCmd::SignOffline(sign_cmd) => sign_offline(sign_cmd),
+	}
+}
+
+fn password(password_file: Option<String>) -> Result<Password, String> {
+	match password_file {
+		Some(file) => password_from_file(file),
+		None => password_prompt(),
+	}
+}
+
+fn open_wallet(path: &str) -> Result<Wallet, String> {
+	fs::create_dir_all(path).map_err(|e| format!("Could not open wallet directory: {}", e))?;
+	Ok(Wallet::new_from_dir(path))
+}
+
+fn new_mnemonic(cmd: NewMnemonic) -> Result<String, String> {
+	let mut wallet = open_wallet(&cmd.path)?;
+	let password = password(cmd.password_file)?;
+	let phrase = wallet.new_mnemonic(password.as_str().to_owned())
+		.map_err(|e| format!("Could not create wallet seed: {}", e))?;
+	Ok(format!("Write down the mnemonic phrase, it is the only backup of the wallet seed:\n{}", phrase))
+}
+
+fn restore_mnemonic(cmd: RestoreMnemonic) -> Result<String, String> {
+	let phrase = match cmd.mnemonic_file {
+		Some(file) => fs::read_to_string(&file)
+			.map_err(|e| format!("Could not read mnemonic file {}: {}", file, e))?,
+		None => {
+			println!("Type mnemonic phrase:");
+			let mut phrase = String::new();
+			io::stdin().read_line(&mut phrase).map_err(|e| format!("Could not read mnemonic phrase: {}", e))?;
+			phrase
+		},
+	};
+
+	let mut wallet = open_wallet(&cmd.path)?;
+	let password = password(cmd.password_file)?;
+	let addresses = wallet.restore_mnemonic(&phrase, password.as_str().to_owned())
+		.map_err(|e| format!("Could not restore wallet seed: {}", e))?;
+	Ok(format!(
+		"Restored accounts, the ones without notes are pruned once the node has scanned the chain:\n{}",
+		addresses.join("\n")
+	))
+}
+
+fn export_proving_key(cmd: ExportProvingKey) -> Result<String, String> {
+	let wallet = open_wallet(&cmd.path)?;
+	let password = password(cmd.password_file)?;
+	let key = wallet.export_proving_key(&cmd.address, password.as_str().to_owned())
+		.map_err(|e| format!("Could not export proving key: {}", e))?;
+	Ok(format!(
+		"Import the proving key into the watch-only node with origo_importViewingKey, it can't authorize spends:\n{}",
+		key
+	))
+}
+
+fn sign_offline(cmd: SignOffline) -> Result<String, String> {
+	let hex = match cmd.transaction_file {
+		Some(file) => fs::read_to_string(&file)
+			.map_err(|e| format!("Could not read transaction file {}: {}", file, e))?,
+		None => {
+			println!("Paste unsigned transaction:");
+			let mut hex = String::new();
+			io::stdin().read_line(&mut hex).map_err(|e| format!("Could not read unsigned transaction: {}", e))?;
+			hex
+		},
+	};
+	let bytes: Vec<u8> = hex.trim().trim_start_matches("0x").from_hex()
+		.map_err(|e| format!("Invalid unsigned transaction hex: {}", e))?;
+	let unsigned: UnsignedTransaction = ::rlp::decode(&bytes)
+		.map_err(|e| format!("Invalid unsigned transaction: {:?}", e))?;
+
+	let wallet = open_wallet(&cmd.path)?;
+	let password = password(cmd.password_file)?;
+	let extsk = wallet.key_store.decrypt_key_file(&cmd.address, password.as_str().to_owned())
+		.ok_or_else(|| format!("Could not unlock the spending key of {}", cmd.address))?;
+	let tx = unsigned.sign(&extsk.expsk.ask)
+		.map_err(|e| format!("Could not sign transaction: {}", e.0))?;
+
+	// The summary of what is signed, the shielded recipients are recovered with the outgoing viewing key.
+	let mut summary = Vec::new();
+	for (index, output) in unsigned.recover_outputs(&extsk.expsk.ovk).into_iter().enumerate() {
+		summary.push(match output {
+			Some((address, value, memo)) => format!(
+				"Output {}: {} to {}{}",
+				index,
+				U256::from(value) * CONVERSION_FACTOR,
+				encode_payment_address(&address),
+				match memo.to_utf8() {
+					Some(Ok(ref memo)) if !memo.is_empty() => format!(", memo {:?}", memo),
+					_ => String::new(),
+				},
+			),
+			None => format!("Output {}: not sent with the key of {}", index, cmd.address),
+		});
+	}
+	if let Action::PrivateCall(address) = tx.action {
+		summary.push(format!("Call: {} to {:?}", tx.value, address));
+	}
+	for output in tx.public_outputs() {
+		summary.push(format!("Public output: {} to {:?}", output.value, output.address));
+	}
+	summary.push(format!("Fee: at most {}", tx.gas * tx.gas_price));
+
+	let signed = tx.sign_for_private(unsigned.chain_id);
+	Ok(format!(
+		"{}\nSubmit the signed transaction with origo_submitSignedTransaction:\n0x{}",
+		summary.join("\n"),
+		::rlp::encode(&signed).to_hex()
+	))
+}
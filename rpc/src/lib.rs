@@ -72,6 +72,7 @@ extern crate ethcore_logger;
 extern crate ethcore_miner as miner;
 extern crate ethcore_network as network;
 extern crate ethcore_sync as sync;
+extern crate ethcore_wallet;
 extern crate ethereum_types;
 extern crate ethkey;
 extern crate ethstore;
@@ -116,6 +117,12 @@ extern crate fake_fetch;
 #[cfg(test)]
 extern crate ethcore_io as io;
 
+#[cfg(test)]
+extern crate sapling_crypto;
+
+#[cfg(test)]
+extern crate zcash_primitives;
+
 pub extern crate jsonrpc_ws_server as ws;
 
 mod authcodes;
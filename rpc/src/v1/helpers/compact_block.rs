@@ -0,0 +1,33 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Helpers for serving compact blocks to thin wallets.
+
+use ethcore::client::{BlockChainClient, BlockId};
+use types::compact_block::CompactBlock;
+
+/// Maximum number of compact blocks returned by one `origo_getCompactBlocks` call.
+pub const MAX_COMPACT_BLOCKS: u64 = 1000;
+
+/// The compact block of the given block, `None` if the block or the commitment tree of its parent is unknown.
+pub fn compact_block<C: BlockChainClient + ?Sized>(client: &C, id: BlockId) -> Option<CompactBlock> {
+	let block = client.block(id)?;
+	let parent_tree_size = match block.number() {
+		0 => 0,
+		_ => client.commitment_tree_size(BlockId::Hash(block.parent_hash()))?,
+	};
+	Some(CompactBlock::new(&block, parent_tree_size))
+}
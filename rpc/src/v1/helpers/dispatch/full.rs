@@ -17,12 +17,13 @@
 use std::sync::Arc;
 
 use dir::Directories;
-use ethcore::client::BlockChainClient;
+use ethcore::client::{BlockChainClient, ChainInfo};
 use ethcore::miner::{self, MinerService};
-use ethcore::{SendMany, SendManyInputs, Wallet, DUMMY_WALLET_PASS};
+use ethcore_wallet::{CoinSelection, SendMany, SendManyInputs, Wallet, DUMMY_WALLET_PASS, MAX_SPENDS_PER_TX};
 use ethereum_types::{Address, H256, U256};
 use parking_lot::Mutex;
-use types::transaction::{PendingTransaction, SignedTransaction, MAX_VALUE_ALLOWED};
+use types::ids::{BlockId, TransactionId};
+use types::transaction::{LocalizedTransaction, PendingTransaction, SignedTransaction, MAX_VALUE_ALLOWED};
 use jsonrpc_core::futures::{future, Future, IntoFuture};
 use jsonrpc_core::{BoxFuture, Result};
 use v1::helpers::{errors, nonce, FilledTransactionRequest, TransactionRequest};
@@ -37,6 +38,18 @@ use v1::helpers::errors::transaction;
 
 pub trait OrigoChainID {
 	fn get_chain_id(&self) -> Option<u64>;
+
+	/// Whether the private transactions built for the next block sign the shielded signature hash.
+	fn shielded_sighash(&self) -> bool;
+
+	/// The hash of the canonical transaction revealing the nullifier.
+	fn nullifier_transaction(&self, nullifier: &U256) -> Option<H256>;
+
+	/// The canonical transaction of the given hash, with the timestamp of its block.
+	fn timestamped_transaction(&self, hash: &H256) -> Option<(LocalizedTransaction, u64)>;
+
+	/// The number of the best block of the chain.
+	fn best_block_number(&self) -> u64;
 }
 
 #[derive(Debug)]
@@ -53,6 +66,24 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> OrigoChainI
 	fn get_chain_id(&self) -> Option<u64> {
 		self.client.signing_chain_id()
 	}
+
+	fn shielded_sighash(&self) -> bool {
+		self.client.shielded_sighash()
+	}
+
+	fn nullifier_transaction(&self, nullifier: &U256) -> Option<H256> {
+		self.client.nullifier_transaction(nullifier)
+	}
+
+	fn timestamped_transaction(&self, hash: &H256) -> Option<(LocalizedTransaction, u64)> {
+		let tx = self.client.transaction(TransactionId::Hash(*hash))?;
+		let timestamp = self.client.block_header(BlockId::Hash(tx.block_hash))?.timestamp();
+		Some((tx, timestamp))
+	}
+
+	fn best_block_number(&self) -> u64 {
+		self.client.chain_info().best_block_number
+	}
 }
 
 impl<C, M> FullDispatcher<C, M> {
@@ -141,7 +172,7 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher
 			#[cfg(test)] {
 				walelt_dir = "./";
 			}
-			let wallet = Arc::new(std::sync::RwLock::new(Wallet::new_from_file("dummy_wallet_", walelt_dir)));
+			let wallet = Arc::new(::parking_lot::RwLock::new(Wallet::new_from_file("dummy_wallet_", walelt_dir)));
 			let mut sendmany = SendMany::new(wallet.clone());
 			let mut shield_to = Vec::new();
 			for amount in shield_amounts.iter() {
@@ -158,12 +189,15 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher
 			if let Some(v) = request.value {
 				value_from_public = v;
 			}
-			let addresses = wallet.read().unwrap().list_addresses();
-			// Generate a new address to send shield transaction if wallet is empty.
+			let addresses = wallet.read().list_addresses();
+			// Derive the first account of the dummy wallet to send shield transaction if it is empty.
 			let from:String = if !addresses.is_empty() {
 				addresses[0].clone()
 			} else {
-				wallet.write().unwrap().create_new_private_address(None)
+				match wallet.write().create_new_private_address(DUMMY_WALLET_PASS.to_string()) {
+					Ok(address) => address,
+					Err(e) => return Box::new(future::err(errors::private_tx_error(format!("{}", e)))),
+				}
 			};
 
 			let chain_id = match self.client.signing_chain_id() {
@@ -180,14 +214,15 @@ impl<C: miner::BlockChainClient + BlockChainClient, M: MinerService> Dispatcher
 				shield_to,
 				to: Vec::new(),
 				min_conf: 0,
+				coin_selection: CoinSelection::default(),
+				max_spends: MAX_SPENDS_PER_TX,
 				gas_price,
 				nonce,
 				gas,
 				data: data.clone(),
 				chain_id,
+				shielded_sighash: self.client.shielded_sighash(),
 			};
-			//TODO, None("") for password?
-			//Change password to correspond funciton create_new_private_address's impl
 			match sendmany.pre_send_many(&inputs, DUMMY_WALLET_PASS.to_string()) {
 				Ok(tx) => {
 					private = tx.private;
@@ -26,7 +26,7 @@ use stats::Corpus;
 use sync::{LightSyncProvider, LightNetworkDispatcher, ManageNetwork};
 use types::basic_account::BasicAccount;
 use types::ids::BlockId;
-use types::transaction::{SignedTransaction, PendingTransaction, Error as TransactionError, PrivateTransaction};
+use types::transaction::{SignedTransaction, PendingTransaction, Error as TransactionError, PrivateTransaction, LocalizedTransaction};
 
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_core::futures::{future, Future, IntoFuture};
@@ -35,6 +35,7 @@ use v1::helpers::{errors, nonce, TransactionRequest, FilledTransactionRequest};
 use v1::types::{RichRawTransaction as RpcRichRawTransaction,};
 
 use super::{Dispatcher, Accounts, SignWith, PostSign};
+use super::full::OrigoChainID;
 
 /// Dispatcher for light clients -- fetches default gas price, next nonce, etc. from network.
 pub struct LightDispatcher<S, OD>
@@ -122,6 +123,34 @@ where
 	}
 }
 
+impl<S, OD> OrigoChainID for LightDispatcher<S, OD>
+where
+	S: LightSyncProvider + LightNetworkDispatcher + ManageNetwork + 'static,
+	OD: OnDemandRequester + 'static
+{
+	fn get_chain_id(&self) -> Option<u64> {
+		self.client.signing_chain_id()
+	}
+
+	fn shielded_sighash(&self) -> bool {
+		let next_block = self.client.chain_info().best_block_number + 1;
+		self.client.engine().machine().shielded_sighash(next_block)
+	}
+
+	// Light clients don't index the transactions of the chain.
+	fn nullifier_transaction(&self, _nullifier: &U256) -> Option<H256> {
+		None
+	}
+
+	fn timestamped_transaction(&self, _hash: &H256) -> Option<(LocalizedTransaction, u64)> {
+		None
+	}
+
+	fn best_block_number(&self) -> u64 {
+		self.client.chain_info().best_block_number
+	}
+}
+
 impl<S, OD> Clone for LightDispatcher<S, OD>
 where
 	S: LightSyncProvider + LightNetworkDispatcher + ManageNetwork + 'static,
@@ -525,38 +525,67 @@ where
 
 	/// Helper to cull the `light` transaction queue of mined transactions
 	pub fn light_cull(&self, txq: Arc<RwLock<TransactionQueue>>) -> impl Future <Item = (), Error = Error> + Send {
-		let senders = txq.read().queued_senders();
-		if senders.is_empty() {
+		let (senders, nullifiers) = {
+			let txq = txq.read();
+			(txq.queued_senders(), txq.queued_nullifiers())
+		};
+		if senders.is_empty() && nullifiers.is_empty() {
 			return Either::B(future::err(errors::internal("No pending local transactions", "")));
 		}
 
 		let sync = self.sync.clone();
 		let on_demand = self.on_demand.clone();
+		let client = self.client.clone();
 		let best_header = self.client.best_block_header();
 		let start_nonce = self.client.engine().account_start_nonce(best_header.number());
 
-		let account_request = sync.with_context(move |ctx| {
+		let cull_request = sync.with_context(move |ctx| {
 			// fetch the nonce of each sender in the queue.
 			let nonce_reqs = senders.iter()
 				.map(|&address| request::Account { header: best_header.clone().into(), address })
 				.collect::<Vec<_>>();
 
+			// and the block spending each nullifier of the private transactions queued.
+			let nullifier_reqs = nullifiers.iter()
+				.map(|&nullifier| request::Nullifier(nullifier))
+				.collect::<Vec<_>>();
+
 			// when they come in, update each sender to the new nonce.
-			on_demand.request(ctx, nonce_reqs)
+			let nonces_txq = txq.clone();
+			let nonces = on_demand.request(ctx, nonce_reqs)
 				.expect(NO_INVALID_BACK_REFS_PROOF)
 				.map(move |accs| {
-					let mut txq = txq.write();
+					let mut txq = nonces_txq.write();
 					accs.into_iter()
 						.map(|maybe_acc| maybe_acc.map_or(start_nonce, |acc| acc.nonce))
 						.zip(senders)
 						.for_each(|(nonce, addr)| {
 							txq.cull(addr, nonce);
 						});
-				})
+				});
+
+			// and drop the private transactions whose notes were spent in the canonical chain.
+			// The blocks are only checked against their own header, so they must be known canonical blocks.
+			let spends = on_demand.request(ctx, nullifier_reqs)
+				.expect(NO_INVALID_BACK_REFS_PROOF)
+				.map(move |blocks| {
+					let mut txq = txq.write();
+					blocks.into_iter()
+						.zip(nullifiers)
+						.filter(|&(ref block, _)| block.as_ref().map_or(false, |block| {
+							client.block_hash(BlockId::Number(block.number())).map_or(false, |hash| hash == block.hash())
+						}))
+						.for_each(|(_, nullifier)| {
+							txq.cull_nullifier(&nullifier);
+						});
+				});
+
+			nonces.join(spends)
+				.map(|_| ())
 				.map_err(errors::on_demand_error)
 		});
 
-		if let Some(fut) = account_request {
+		if let Some(fut) = cull_request {
 			Either::A(fut)
 		} else {
 			Either::B(future::err(errors::network_disabled()))
@@ -18,6 +18,7 @@
 pub mod errors;
 
 pub mod block_import;
+pub mod compact_block;
 pub mod deprecated;
 pub mod dispatch;
 #[cfg(any(test, feature = "accounts"))]
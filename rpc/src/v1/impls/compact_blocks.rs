@@ -0,0 +1,59 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::sync::Arc;
+
+use ethcore::client::{BlockChainClient, BlockId};
+use jsonrpc_core::Result;
+use v1::helpers::compact_block::{compact_block, MAX_COMPACT_BLOCKS};
+use v1::helpers::errors;
+use v1::traits::OrigoCompactBlocks;
+use v1::types::CompactBlock;
+
+/// Compact blocks rpc implementation.
+pub struct OrigoCompactBlocksClient<C> {
+	client: Arc<C>,
+}
+
+impl<C> OrigoCompactBlocksClient<C> {
+	/// Creates new OrigoCompactBlocksClient
+	pub fn new(client: &Arc<C>) -> Self {
+		OrigoCompactBlocksClient {
+			client: client.clone(),
+		}
+	}
+}
+
+impl<C: BlockChainClient + 'static> OrigoCompactBlocks for OrigoCompactBlocksClient<C> {
+	fn get_compact_blocks(&self, from: u64, to: u64) -> Result<Vec<CompactBlock>> {
+		if from > to {
+			return Err(errors::invalid_params("to", "Expected a block number not below the first one."));
+		}
+		if to - from >= MAX_COMPACT_BLOCKS {
+			return Err(errors::invalid_params("to", format!("At most {} blocks are returned at once.", MAX_COMPACT_BLOCKS)));
+		}
+
+		let to = to.min(self.client.chain_info().best_block_number);
+		let mut blocks = Vec::new();
+		for number in from..=to {
+			match compact_block(&*self.client, BlockId::Number(number)) {
+				Some(block) => blocks.push(block.into()),
+				None => return Err(errors::unknown_block()),
+			}
+		}
+		Ok(blocks)
+	}
+}
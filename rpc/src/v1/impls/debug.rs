@@ -59,6 +59,7 @@ impl<C: BlockChainClient + 'static> Debug for DebugClient<C> {
 					miner: cast(block.header.author()),
 					state_root: cast(block.header.state_root()),
 					receipts_root: cast(block.header.receipts_root()),
+					final_sapling_root: block.header.final_sapling_root().cloned(),
 					number: Some(number.into()),
 					gas_used: cast(block.header.gas_used()),
 					gas_limit: cast(block.header.gas_limit()),
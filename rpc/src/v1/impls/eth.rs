@@ -264,6 +264,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> EthClient<C, SN, S
 						state_root: view.state_root(),
 						transactions_root: view.transactions_root(),
 						receipts_root: view.receipts_root(),
+						final_sapling_root: view.final_sapling_root(),
 						number: match is_pending {
 							true => None,
 							false => Some(view.number().into()),
@@ -415,6 +416,7 @@ impl<C, SN: ?Sized, S: ?Sized, M, EM, T: StateInfo + 'static> EthClient<C, SN, S
 				difficulty: *uncle.difficulty(),
 				total_difficulty: Some(uncle.difficulty() + parent_difficulty),
 				receipts_root: *uncle.receipts_root(),
+				final_sapling_root: uncle.final_sapling_root().cloned(),
 				extra_data: uncle.extra_data().clone().into(),
 				seal_fields: uncle.seal().iter().cloned().map(Into::into).collect(),
 				uncles: vec![],
@@ -25,6 +25,7 @@ use jsonrpc_pubsub::typed::{Sink, Subscriber};
 use jsonrpc_pubsub::SubscriptionId;
 
 use v1::helpers::{errors, limit_logs, Subscribers, };
+use v1::helpers::compact_block::compact_block;
 use v1::helpers::light_fetch::LightFetch;
 use v1::metadata::Metadata;
 use v1::traits::EthPubSub;
@@ -53,6 +54,9 @@ pub struct EthPubSubClient<C> {
 	logs_subscribers: Arc<RwLock<Subscribers<(Client, EthFilter)>>>,
 	transactions_subscribers: Arc<RwLock<Subscribers<Client>>>,
 	sync_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	compact_blocks_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	// Light clients don't notify the compact blocks, they have no block bodies.
+	compact_blocks: bool,
 }
 
 impl<C> EthPubSubClient<C>
@@ -85,6 +89,7 @@ impl<C> EthPubSubClient<C> {
 		let logs_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 		let transactions_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 		let sync_subscribers = Arc::new(RwLock::new(Subscribers::default()));
+		let compact_blocks_subscribers = Arc::new(RwLock::new(Subscribers::default()));
 
 		EthPubSubClient {
 			handler: Arc::new(ChainNotificationHandler {
@@ -94,11 +99,14 @@ impl<C> EthPubSubClient<C> {
 				logs_subscribers: logs_subscribers.clone(),
 				transactions_subscribers: transactions_subscribers.clone(),
 				sync_subscribers: sync_subscribers.clone(),
+				compact_blocks_subscribers: compact_blocks_subscribers.clone(),
 			}),
 			sync_subscribers,
 			heads_subscribers,
 			logs_subscribers,
 			transactions_subscribers,
+			compact_blocks_subscribers,
+			compact_blocks: true,
 		}
 	}
 
@@ -110,6 +118,7 @@ impl<C> EthPubSubClient<C> {
 		*client.logs_subscribers.write() = Subscribers::new_test();
 		*client.transactions_subscribers.write() = Subscribers::new_test();
 		*client.sync_subscribers.write() = Subscribers::new_test();
+		*client.compact_blocks_subscribers.write() = Subscribers::new_test();
 		client
 	}
 
@@ -140,7 +149,9 @@ where
 			cache,
 			gas_price_percentile,
 		};
-		EthPubSubClient::new(Arc::new(fetch), executor)
+		let mut client = EthPubSubClient::new(Arc::new(fetch), executor);
+		client.compact_blocks = false;
+		client
 	}
 }
 
@@ -152,6 +163,7 @@ pub struct ChainNotificationHandler<C> {
 	logs_subscribers: Arc<RwLock<Subscribers<(Client, EthFilter)>>>,
 	transactions_subscribers: Arc<RwLock<Subscribers<Client>>>,
 	sync_subscribers: Arc<RwLock<Subscribers<Client>>>,
+	compact_blocks_subscribers: Arc<RwLock<Subscribers<Client>>>,
 }
 
 impl<C> ChainNotificationHandler<C> {
@@ -259,8 +271,28 @@ impl<C: LightClient> LightChainNotify for ChainNotificationHandler<C> {
 	}
 }
 
+impl<C: BlockChainClient> ChainNotificationHandler<C> {
+	fn notify_compact_blocks(&self, enacted: &[H256]) {
+		let subscribers = self.compact_blocks_subscribers.read();
+		if subscribers.is_empty() { return }
+
+		let blocks = enacted
+			.iter()
+			.filter_map(|hash| compact_block(&*self.client, BlockId::Hash(*hash)))
+			.collect::<Vec<_>>();
+		for subscriber in subscribers.values() {
+			for block in &blocks {
+				Self::notify(&self.executor, subscriber, pubsub::Result::CompactBlock(Box::new(block.clone().into())));
+			}
+		}
+	}
+}
+
 impl<C: BlockChainClient> ChainNotify for ChainNotificationHandler<C> {
 	fn new_blocks(&self, new_blocks: NewBlocks) {
+		// Thin wallets detect the retracted blocks with the parent hashes.
+		self.notify_compact_blocks(new_blocks.route.enacted());
+
 		if self.heads_subscribers.read().is_empty() && self.logs_subscribers.read().is_empty() { return }
 		const EXTRA_INFO_PROOF: &str = "Object exists in in blockchain (fetched earlier), extra_info is always available if object exists; qed";
 		let headers = new_blocks.route.route()
@@ -337,6 +369,16 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 			(pubsub::Kind::NewPendingTransactions, _) => {
 				errors::invalid_params("newPendingTransactions", "Expected no parameters.")
 			},
+			(pubsub::Kind::CompactBlocks, None) if self.compact_blocks => {
+				self.compact_blocks_subscribers.write().push(subscriber);
+				return;
+			},
+			(pubsub::Kind::CompactBlocks, None) => {
+				errors::light_unimplemented(Some("Compact blocks are only notified by full nodes.".into()))
+			},
+			(pubsub::Kind::CompactBlocks, _) => {
+				errors::invalid_params("compactBlocks", "Expected no parameters.")
+			},
 			_ => {
 				errors::unimplemented(None)
 			},
@@ -350,7 +392,8 @@ impl<C: Send + Sync + 'static> EthPubSub for EthPubSubClient<C> {
 		let res2 = self.logs_subscribers.write().remove(&id).is_some();
 		let res3 = self.transactions_subscribers.write().remove(&id).is_some();
 		let res4 = self.sync_subscribers.write().remove(&id).is_some();
+		let res5 = self.compact_blocks_subscribers.write().remove(&id).is_some();
 
-		Ok(res || res2 || res3 || res4)
+		Ok(res || res2 || res3 || res4 || res5)
 	}
 }
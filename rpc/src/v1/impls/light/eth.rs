@@ -152,6 +152,7 @@ where
 					state_root: *header.state_root(),
 					transactions_root: *header.transactions_root(),
 					receipts_root: *header.receipts_root(),
+					final_sapling_root: header.final_sapling_root().cloned(),
 					number: Some(header.number().into()),
 					gas_used: *header.gas_used(),
 					gas_limit: *header.gas_limit(),
@@ -597,6 +598,7 @@ fn extract_uncle_at_index<T: LightChainClient>(block: encoded::Block, index: Ind
 				difficulty: *uncle.difficulty(),
 				total_difficulty: None,
 				receipts_root: *uncle.receipts_root(),
+				final_sapling_root: uncle.final_sapling_root().cloned(),
 				extra_data: uncle.extra_data().clone().into(),
 				seal_fields: uncle.seal().iter().cloned().map(Into::into).collect(),
 				uncles: vec![],
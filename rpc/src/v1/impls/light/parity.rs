@@ -352,6 +352,7 @@ where
 					state_root: *header.state_root(),
 					transactions_root: *header.transactions_root(),
 					receipts_root: *header.receipts_root(),
+					final_sapling_root: header.final_sapling_root().cloned(),
 					number: Some(header.number().into()),
 					gas_used: *header.gas_used(),
 					gas_limit: *header.gas_limit(),
@@ -27,6 +27,7 @@ mod parity_accounts;
 #[cfg(any(test, feature = "accounts"))]
 mod origo;
 mod reward;
+mod compact_blocks;
 mod parity_set;
 #[cfg(any(test, feature = "accounts"))]
 mod personal;
@@ -67,3 +68,4 @@ pub use self::signing_unsafe::SigningUnsafeClient;
 pub use self::traces::TracesClient;
 pub use self::web3::Web3Client;
 pub use self::reward::OrigoRewardsClient;
+pub use self::compact_blocks::OrigoCompactBlocksClient;
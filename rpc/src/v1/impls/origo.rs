@@ -20,68 +20,350 @@ use std::convert::TryInto;
 use std::str::FromStr;
 use std::sync::Arc;
 
-use ethcore::{
-	decode_payment_address, decode_transparent_destination, encode_payment_address, SendMany,
-	SendManyInputs, Wallet,
+use ethcore_wallet::{
+	decode_payment_address, decode_transparent_destination, encode_payment_address, CoinSelection,
+	MergeToAddressInputs, OperationManager, OperationState, SendMany, SendManyInputs, SentNoteData,
+	Wallet, WalletSync, MAX_SPENDS_PER_TX,
 };
+use rlp::Rlp;
 
 use accounts::AccountProvider;
 use ethereum_types::{clean_0x, Address, H256, U256, U64};
 use jsonrpc_core::types::Error as JsonError;
 use jsonrpc_core::types::ErrorCode;
 use jsonrpc_core::Result;
-use std::sync::RwLock;
-use types::transaction::{*, MAX_VALUE_ALLOWED};
+use parking_lot::RwLock;
+use types::transaction::{*, MAX_PUBLIC_OUTPUTS, MAX_VALUE_ALLOWED};
 use v1::helpers::dispatch::Dispatcher;
 use v1::helpers::errors;
 use v1::helpers::dispatch::full::OrigoChainID;
 use v1::traits::OrigoAccountsInfo;
-use v1::types::{AmountRequest, UnspentNote};
+use v1::types::{
+	AmountRequest, Bytes, OperationStatus, ReceivedNote, RescanStatus, SentNote, ShieldedAccount,
+	ShieldedTransaction, UnspentNote, WalletSyncStatus, WalletTransaction,
+};
+
+/// Default minimum number of confirmations of the notes.
+const DEFAULT_MIN_CONF: u32 = 1;
+/// Default maximum number of confirmations of the listed notes.
+const DEFAULT_MAX_CONF: u32 = 9999999;
+/// Default maximum number of listed transactions.
+const DEFAULT_LIST_COUNT: u64 = 100;
+
+fn memo_string(memo: Option<::std::result::Result<String, ::std::str::Utf8Error>>) -> String {
+	match memo {
+		Some(Ok(memo)) => memo,
+		_ => "Invalid memo.".to_string(),
+	}
+}
+
+fn sent_note(wallet: &Wallet, note: &SentNoteData) -> SentNote {
+	let from = wallet.key_store.get_default_address(&note.ivk);
+	SentNote {
+		txid: note.hash.to_string(),
+		outindex: From::from(note.index as u64),
+		blocknumber: From::from(note.createHeight),
+		from: from.as_ref().map(encode_payment_address).unwrap_or_default(),
+		address: encode_payment_address(&note.address),
+		amount: U256::from(note.value) * CONVERSION_FACTOR,
+		memo: memo_string(note.memo.to_utf8()),
+		change: wallet.key_store.get_incoming_viewing_key(&note.address) == Some(note.ivk),
+	}
+}
+
+/// The transparent address sending to or paid by a private transaction.
+fn public_counterpart(mut tx: LocalizedTransaction) -> Option<Address> {
+	match tx.action.clone() {
+		Action::PublicToPrivate => Some(tx.sender()),
+		Action::PrivateCall(address) => Some(address),
+		_ => tx.public_outputs().first().map(|output| output.address),
+	}
+}
 
 /// Account management (personal) rpc implementation.
 pub struct OrigoAccountsClient<D: Dispatcher + OrigoChainID> {
 	accounts: Arc<AccountProvider>,
 	dispatcher: D,
 	wallet: Arc<RwLock<Wallet>>,
+	// Rescans the chain for the wallet, `None` if the wallet isn't synced with it.
+	wallet_sync: Option<Arc<WalletSync>>,
+	operations: Arc<OperationManager>,
 }
 
 impl<D: Dispatcher + OrigoChainID + 'static> OrigoAccountsClient<D> {
 	/// Creates new PersonalClient
-	pub fn new(store: &Arc<AccountProvider>, dispatcher: D, wallet: &Arc<RwLock<Wallet>>) -> Self {
+	pub fn new(
+		store: &Arc<AccountProvider>,
+		dispatcher: D,
+		wallet: &Arc<RwLock<Wallet>>,
+		wallet_sync: Option<Arc<WalletSync>>,
+		operations: &Arc<OperationManager>,
+	) -> Self {
 		OrigoAccountsClient {
 			accounts: store.clone(),
 			dispatcher,
 			wallet: wallet.clone(),
+			wallet_sync,
+			operations: operations.clone(),
 		}
 	}
 }
 
+impl<D: Dispatcher + OrigoChainID + 'static> OrigoAccountsClient<D> {
+	/// The thread syncing the wallet with the chain, which runs the rescans.
+	fn wallet_sync(&self) -> Result<&Arc<WalletSync>> {
+		self.wallet_sync
+			.as_ref()
+			.ok_or_else(|| errors::account("Could not rescan the wallet.", "The wallet isn't synced with the chain"))
+	}
+
+	/// The inputs of the transaction sending `amounts` from the address `from`.
+	fn send_many_inputs(
+		&self,
+		from: String,
+		amounts: Vec<AmountRequest>,
+		gas: Option<U256>,
+		gas_price: Option<U256>,
+		min_conf: Option<u32>,
+		coin_selection: Option<String>,
+		data: Option<Bytes>,
+	) -> Result<SendManyInputs> {
+		// Process inputs.
+		let min_conf = min_conf.unwrap_or(DEFAULT_MIN_CONF);
+		let coin_selection = match coin_selection {
+			Some(coin_selection) => CoinSelection::from_str(&coin_selection)
+				.map_err(|e| errors::invalid_params("coin_selection", e))?,
+			None => CoinSelection::default(),
+		};
+		let gas = match gas {
+			Some(gas) => gas,
+			None => U256::from(21000),
+		};
+		let gas_price = match gas_price {
+			Some(gas_price) => gas_price,
+			None => U256::from(1000000),
+		};
+		let mut shield_to = Vec::new();
+		let mut to = Vec::new();
+
+		for amount in amounts.iter() {
+			if amount.amount > MAX_VALUE_ALLOWED {
+				return Err(errors::private_tx_error(String::from("Amount sent is bigger than (2^63-1)*(10^9)")));
+			}
+			let memo = match amount.memo.clone() {
+				Some(memo) => memo,
+				None => "".to_string(),
+			};
+			if let Some(_) = decode_payment_address(&amount.address) {
+				shield_to.push((amount.address.clone(), amount.amount.clone(), memo));
+			} else if decode_transparent_destination(&amount.address) {
+				to.push((amount.address.clone(), amount.amount.clone(), memo));
+			} else {
+				return Err(errors::invalid_params("to", &amount.address));
+			}
+		}
+		// Check amounts are valid.
+		if shield_to.is_empty() && to.is_empty() {
+			return Err(errors::invalid_params("amounts",
+											  "No valid address appears in amounts."));
+		} else if to.len() > MAX_PUBLIC_OUTPUTS + 1 {
+			return Err(errors::invalid_params("amounts",
+											  format!("At most {} transparent addresses appear in amounts.", MAX_PUBLIC_OUTPUTS + 1)));
+		}
+		let data = data.map(Bytes::into_vec).unwrap_or_default();
+		if !data.is_empty() && to.is_empty() {
+			return Err(errors::invalid_params("data", "No transparent address to call."));
+		}
+		let chain_id = match self.dispatcher.get_chain_id() {
+			Some(v) => v,
+			None => 0,
+		};
+		Ok(SendManyInputs {
+			from,
+			value_from_public: U256::from(0),
+			shield_to,
+			to,
+			min_conf,
+			coin_selection,
+			max_spends: MAX_SPENDS_PER_TX,
+			gas_price,
+			nonce: U256::from(0),
+			gas,
+			data,
+			chain_id,
+			shielded_sighash: self.dispatcher.shielded_sighash(),
+		})
+	}
+}
+
 impl<D: Dispatcher + OrigoChainID + 'static > OrigoAccountsInfo for OrigoAccountsClient<D> {
 	// output: The new shielded address
 	// (e.g. zcU1Cd6zYyZCd2VJF8yKgmzjxdiiU1rgTTjEwoN1CGUWCziPkUTXUjXmX7TMqdMNsTfuiGN1jQoVN4kGxUR4sAPN4XZ7pxb)
 	fn get_new_address(&self, password: String) -> Result<String> {
-		let address = self.wallet.write().unwrap().create_new_private_address(Some(password));
-		Ok(address)
+		self.wallet.write()
+			.create_new_private_address(password)
+			.map_err(|e| errors::account("Could not create shielded address.", e))
+	}
+
+	fn get_new_diversified_address(&self, account: u32) -> Result<String> {
+		self.wallet.write()
+			.get_new_diversified_address(account)
+			.map_err(|e| errors::account("Could not create diversified address.", e))
+	}
+
+	fn list_accounts(&self) -> Result<Vec<ShieldedAccount>> {
+		Ok(self.wallet.read()
+			.list_accounts()
+			.into_iter()
+			.map(|account| ShieldedAccount {
+				index: account.index,
+				addresses: account.addresses,
+			})
+			.collect())
+	}
+
+	fn new_mnemonic(&self, password: String) -> Result<String> {
+		self.wallet.write()
+			.new_mnemonic(password)
+			.map_err(|e| errors::account("Could not create wallet seed.", e))
+	}
+
+	fn restore_mnemonic(&self, phrase: String, password: String) -> Result<Vec<String>> {
+		self.wallet.write()
+			.restore_mnemonic(&phrase, password)
+			.map_err(|e| errors::account("Could not restore wallet seed.", e))
+	}
+
+	fn export_viewing_key(&self, address: String, incoming_only: Option<bool>) -> Result<String> {
+		self.wallet.read()
+			.export_viewing_key(&address, incoming_only.unwrap_or(false))
+			.map_err(|e| errors::account("Could not export viewing key.", e))
+	}
+
+	fn import_viewing_key(&self, key: String, rescan_from: Option<u64>) -> Result<String> {
+		self.wallet.write()
+			.import_viewing_key(&key, rescan_from)
+			.map_err(|e| errors::account("Could not import viewing key.", e))
+	}
+
+	fn list_sent_notes(&self, address: Option<String>) -> Result<Vec<SentNote>> {
+		let wallet_read = self.wallet.read();
+		let ivk = match address {
+			Some(address) => {
+				let ivk = decode_payment_address(&address)
+					.and_then(|address| wallet_read.key_store.get_incoming_viewing_key(&address));
+				match ivk {
+					Some(ivk) => Some(ivk),
+					None => return Err(errors::account("Could not list sent notes.", "Unknown address")),
+				}
+			}
+			None => None,
+		};
+		Ok(wallet_read.get_sent_notes(ivk.as_ref())
+			.into_iter()
+			.map(|note| sent_note(&wallet_read, note))
+			.collect())
+	}
+
+	fn get_transaction(&self, hash: H256) -> Result<ShieldedTransaction> {
+		let wallet_read = self.wallet.read();
+		let (received, sent) = wallet_read.get_transaction_notes(&hash);
+		if received.is_empty() && sent.is_empty() {
+			return Err(errors::account("Could not get transaction.", "No shielded notes of the wallet"));
+		}
+		Ok(ShieldedTransaction {
+			txid: hash.to_string(),
+			received: received.into_iter()
+				.map(|note| ReceivedNote {
+					outindex: From::from(note.index as u64),
+					address: encode_payment_address(&note.address),
+					amount: U256::from(note.note.value) * CONVERSION_FACTOR,
+					memo: memo_string(note.memo.to_utf8()),
+					spent: note.confirmHeight.is_some(),
+				})
+				.collect(),
+			sent: sent.into_iter().map(|note| sent_note(&wallet_read, note)).collect(),
+		})
 	}
 
 
+	fn list_transactions(
+		&self,
+		address: Option<String>,
+		from_block: Option<u64>,
+		count: Option<u64>,
+	) -> Result<Vec<WalletTransaction>> {
+		let from_block = from_block.unwrap_or(0);
+		let count = count.unwrap_or(DEFAULT_LIST_COUNT);
+		let wallet_read = self.wallet.read();
+		let ivk = match address {
+			Some(address) => {
+				let ivk = decode_payment_address(&address)
+					.and_then(|address| wallet_read.key_store.get_incoming_viewing_key(&address));
+				match ivk {
+					Some(ivk) => Some(ivk),
+					None => return Err(errors::account("Could not list transactions.", "Unknown address")),
+				}
+			}
+			None => None,
+		};
+		let transactions = wallet_read.list_transactions(ivk.as_ref(), |nullifier| {
+			self.dispatcher.nullifier_transaction(nullifier)
+		});
+
+		Ok(transactions.into_iter()
+			.filter(|tx| tx.block_number >= from_block)
+			.take(count as usize)
+			.map(|tx| {
+				// The wallet is synced with the blocks after the chain, a transaction not found
+				// in its block on the chain has just been retracted.
+				let chain_tx = self.dispatcher.timestamped_transaction(&tx.hash)
+					.filter(|(chain_tx, _)| chain_tx.block_number == tx.block_number)
+					.map(|(chain_tx, timestamp)| (public_counterpart(chain_tx), timestamp));
+				let confirmed = chain_tx.is_some();
+				let public_address = chain_tx.and_then(|(address, _)| address);
+				let internal = public_address.is_none() && tx.sent.iter()
+					.all(|note| wallet_read.key_store.get_incoming_viewing_key(&note.address).is_some());
+				let direction = if tx.spent > 0 && internal {
+					"internal"
+				} else if tx.received >= tx.spent {
+					"incoming"
+				} else {
+					"outgoing"
+				};
+				WalletTransaction {
+					txid: tx.hash.to_string(),
+					blocknumber: From::from(tx.block_number),
+					timestamp: From::from(chain_tx.map_or(0, |(_, timestamp)| timestamp)),
+					confirmations: From::from(if confirmed { wallet_read.block_confirmations(tx.block_number) } else { 0 }),
+					status: if confirmed { "confirmed" } else { "orphaned" }.into(),
+					direction: direction.into(),
+					received: U256::from(tx.received) * CONVERSION_FACTOR,
+					spent: U256::from(tx.spent) * CONVERSION_FACTOR,
+					memos: tx.memos.iter().map(|memo| memo_string(memo.to_utf8())).collect(),
+					publicaddress: public_address,
+					coinbase: tx.coinbase,
+				}
+			})
+			.collect())
+	}
+
 	fn new_address_from_seed(&self, seed: H256, password: String) -> Result<String> {
-		Ok(self.wallet.write().unwrap().add_address_from_seed(
+		Ok(self.wallet.write().add_address_from_seed(
 			(*seed).try_into().expect("Length should equal."), password).0)
 	}
 
 	fn addresses(&self) -> Result<Vec<String>> {
-		Ok(self.wallet.read().unwrap().list_addresses())
+		Ok(self.wallet.read().list_addresses())
 	}
 
 	/// address: The selected address. It may be a transparent or private address.
 	/// minconf: optional, default = 1, Only include transactions confirmed at least this many times
 	/// Output: The total amount in ZEC received for this address
 	fn get_balance(&self, address: String, minconf: Option<u32>) -> Result<U256> {
-		//let result = U256::from(10);
-		//Ok(result)
-		let wallet_read = self.wallet.read().unwrap();
-		let entries = wallet_read.get_filtered_address_notes(&address);
+		let minconf = minconf.unwrap_or(DEFAULT_MIN_CONF);
+		let wallet_read = self.wallet.read();
+		let entries = wallet_read.get_filtered_address_notes(&address, minconf, u32::max_value(), false);
 		let mut balance = 0;
 		for entry in entries.iter() {
 			balance += entry.note.value
@@ -100,23 +382,29 @@ impl<D: Dispatcher + OrigoChainID + 'static > OrigoAccountsInfo for OrigoAccount
 		maxconf: Option<u32>,
 		include_watch_only: Option<bool>,
 	) -> Result<Vec<UnspentNote>> {
-		let wallet_read = self.wallet.read().unwrap();
-		let zaddrs = wallet_read.key_store.get_sapling_payment_addresses();
-		let notes = wallet_read.get_filtered_address_notes(&address);
+		let minconf = minconf.unwrap_or(DEFAULT_MIN_CONF);
+		let maxconf = maxconf.unwrap_or(DEFAULT_MAX_CONF);
+		if minconf > maxconf {
+			return Err(errors::invalid_params("maxconf", "Maximum number of confirmations is less than minimum"));
+		}
+		let include_watch_only = include_watch_only.unwrap_or(false);
+		let wallet_read = self.wallet.read();
+		let notes = wallet_read.get_filtered_address_notes(&address, minconf, maxconf, false);
 		let mut result: Vec<UnspentNote> = Vec::new();
 
 		for entry in notes.iter() {
-			let memo = match entry.memo.to_utf8() {
-				Some(Ok(memo)) => memo,
-				_ => "Invalid memo.".to_string(),
-			};
+			if !entry.spendable && !include_watch_only {
+				continue;
+			}
+			let memo = memo_string(entry.memo.to_utf8());
 			let note_desc = UnspentNote {
 				txid: entry.hash.to_string(),
 				outindex: From::from(entry.index as u64),
-				confirmations: U64::zero(),
+				confirmations: From::from(entry.confirmation as u64),
 				address: encode_payment_address(&entry.address),
 				amount: U256::from(entry.note.value) * CONVERSION_FACTOR,
-				spendable: true,
+				spendable: entry.spendable && !entry.locked,
+				locked: entry.locked,
 				memo,
 				change: false,
 				jsindex: U64::zero(),
@@ -139,8 +427,10 @@ impl<D: Dispatcher + OrigoChainID + 'static > OrigoAccountsInfo for OrigoAccount
 	/// gas_price: optional, default=1, The gas price of the transaction.
 	/// gas: optional, default=21000, The gas limit of the transaction.
 	/// min_conf: optional, default=1, Only use funds confirmed at least this many times.
+	/// coin_selection: optional, default=largest-first, The strategy choosing the spent notes.
+	/// data: optional, The data of the call to the first transparent address.
 	/// Output:
-	/// 	operationid: An operationid to pass to z_getoperationstatus to get the result of the operation.
+	/// 	operationid: An operationid to pass to origo_getOperationStatus to get the result of the operation.
 	fn send_many(
 		&self,
 		from: String,
@@ -148,75 +438,181 @@ impl<D: Dispatcher + OrigoChainID + 'static > OrigoAccountsInfo for OrigoAccount
 		password: String,
 		gas: Option<U256>,
 		gas_price: Option<U256>,
-		min_conf: Option<u32>
-	) -> Result<H256> {
-		// Process inputs.
-		let min_conf = match min_conf {
-			Some(min_conf) => min_conf,
-			None => 0,
-		};
-		let gas = match gas {
-			Some(gas) => gas,
-			None => U256::from(21000),
-		};
-		let gas_price = match gas_price {
-			Some(gas_price) => gas_price,
-			None => U256::from(1000000),
-		};
-		let mut shield_to = Vec::new();
-		let mut to = Vec::new();
-
-		for amount in amounts.iter() {
-			if amount.amount > MAX_VALUE_ALLOWED {
-				return Err(errors::private_tx_error(String::from("Amount sent is bigger than (2^63-1)*(10^9)")));
-			}
-			let memo = match amount.memo.clone() {
-				Some(memo) => memo,
-				None => "".to_string(),
-			};
-			if let Some(_) = decode_payment_address(&amount.address) {
-				shield_to.push((amount.address.clone(), amount.amount.clone(), memo));
-			} else if decode_transparent_destination(&amount.address) {
-				println!("public Address {:?}", amount.address);
-				to.push((amount.address.clone(), amount.amount.clone(), memo));
-			} else {
-				return Err(errors::invalid_params("to", &amount.address));
+		min_conf: Option<u32>,
+		coin_selection: Option<String>,
+		data: Option<Bytes>,
+	) -> Result<String> {
+		let inputs = self.send_many_inputs(from, amounts, gas, gas_price, min_conf, coin_selection, data)?;
+		let chain_id = inputs.chain_id;
+		let wallet = self.wallet.clone();
+		let dispatcher = self.dispatcher.clone();
+		Ok(self.operations.submit("origo_sendMany", move || {
+			let mut sendmany = SendMany::new(wallet.clone());
+			let tx = sendmany.pre_send_many(&inputs, password)
+				.map_err(|e| -> String { e.0.into() })?;
+			let result = dispatcher
+				.dispatch_transaction(PendingTransaction::new(tx.clone().sign_for_private(chain_id), None));
+			match result {
+				Ok(hash) => Ok(vec![hash]),
+				Err(e) => {
+					wallet.write().unlock_spent_notes(&tx);
+					Err(e.message)
+				}
 			}
+		}))
+	}
+
+	/// from: The watch-only zaddr imported with its proving key to send the funds from.
+	/// The other parameters are those of origo_sendMany, without password.
+	/// Output: The RLP of the unsigned transaction, whose notes are locked until it is mined.
+	fn create_unsigned_transaction(
+		&self,
+		from: String,
+		amounts: Vec<AmountRequest>,
+		gas: Option<U256>,
+		gas_price: Option<U256>,
+		min_conf: Option<u32>,
+		coin_selection: Option<String>,
+		data: Option<Bytes>,
+	) -> Result<Bytes> {
+		let inputs = self.send_many_inputs(from, amounts, gas, gas_price, min_conf, coin_selection, data)?;
+		let unsigned = SendMany::new(self.wallet.clone())
+			.create_unsigned(&inputs)
+			.map_err(|e| errors::private_tx_error(e.0.into()))?;
+		Ok(::rlp::encode(&unsigned).into())
+	}
+
+	/// raw: The RLP of the transaction signed by `parity wallet sign-offline`.
+	/// Output: The hash of the transaction.
+	fn submit_signed_transaction(&self, raw: Bytes) -> Result<H256> {
+		let tx: UnverifiedTransaction = Rlp::new(&raw.into_vec()).as_val().map_err(errors::rlp)?;
+		if !tx.is_private() || !tx.action.is_input_private() {
+			return Err(errors::invalid_params("raw", "Not a transaction spending shielded notes."));
 		}
-		// Check amounts are valid.
-		if shield_to.len() > 0 && to.len() > 0 {
-			return Err(errors::invalid_params("amounts",
-											  "Both transparent and private address appeared in amounts."));
-		} else if shield_to.is_empty() && to.is_empty() {
-			return Err(errors::invalid_params("amounts",
-											  "No valid address appears in amounts."));
+		if tx.chain_id() != Some(self.dispatcher.get_chain_id().unwrap_or(0)) {
+			return Err(errors::invalid_params("raw", "Transaction of another chain."));
 		}
-		let chain_id = match self.dispatcher.get_chain_id() {
-			Some(v) => v,
-			None => 0,
-		};
-		let inputs = SendManyInputs {
+		let signed = SignedTransaction::new(tx).map_err(errors::transaction)?;
+		let result = self.dispatcher.dispatch_transaction(PendingTransaction::new(signed.clone(), None));
+		if result.is_err() {
+			// The notes locked by origo_createUnsignedTransaction can be spent again.
+			self.wallet.write().unlock_spent_notes(&signed);
+		}
+		result
+	}
+
+	/// from: The zaddrs whose notes are merged.
+	/// to: The zaddr receiving the merged notes.
+	/// password: The password to unlock the from addresses.
+	/// shielded_limit: optional, default=50, The maximum number of notes spent by each transaction.
+	/// gas: optional, default=21000, The gas limit of each transaction.
+	/// gas_price: optional, default=1000000, The gas price of each transaction.
+	/// memo: optional, The memo of the merged notes.
+	/// Output: An operationid whose result is the hashes of the merging transactions.
+	fn merge_to_address(
+		&self,
+		from: Vec<String>,
+		to: String,
+		password: String,
+		shielded_limit: Option<u32>,
+		gas: Option<U256>,
+		gas_price: Option<U256>,
+		memo: Option<String>,
+	) -> Result<String> {
+		if from.is_empty() {
+			return Err(errors::invalid_params("from", "No address to merge the notes of."));
+		}
+		if let Some(address) = from.iter().find(|address| decode_payment_address(address).is_none()) {
+			return Err(errors::invalid_params("from", address));
+		}
+		if decode_payment_address(&to).is_none() {
+			return Err(errors::invalid_params("to", &to));
+		}
+		let max_spends = shielded_limit.map_or(MAX_SPENDS_PER_TX, |limit| limit as usize);
+		if max_spends < 2 {
+			return Err(errors::invalid_params("shielded_limit", "At least 2 notes are merged by a transaction."));
+		}
+		let chain_id = self.dispatcher.get_chain_id().unwrap_or(0);
+		let inputs = MergeToAddressInputs {
 			from,
-			value_from_public: U256::from(0),
-			shield_to,
 			to,
-			min_conf,
-			gas_price,
-			nonce: U256::from(0),
-			gas,
-			data: vec![],
+			memo: memo.unwrap_or_default(),
+			min_conf: DEFAULT_MIN_CONF,
+			max_spends,
+			gas_price: gas_price.unwrap_or_else(|| U256::from(1000000)),
+			gas: gas.unwrap_or_else(|| U256::from(21000)),
 			chain_id,
+			shielded_sighash: self.dispatcher.shielded_sighash(),
 		};
-		let mut sendmany = SendMany::new(self.wallet.clone());
-		match sendmany.pre_send_many(&inputs, password) {
-			Ok(tx) => {
-				let dispatcher = self.dispatcher.clone();
-				return dispatcher
-					.dispatch_transaction(PendingTransaction::new(tx.sign_for_private(chain_id), None));
-			}
-			Err(e) => {
-				return Err(errors::private_tx_error(e.0.into()));
+		let wallet = self.wallet.clone();
+		let dispatcher = self.dispatcher.clone();
+		Ok(self.operations.submit("origo_mergeToAddress", move || {
+			let mut sendmany = SendMany::new(wallet.clone());
+			let txs = sendmany.merge_to_address(&inputs, password)
+				.map_err(|e| -> String { e.0.into() })?;
+
+			let mut hashes = Vec::with_capacity(txs.len());
+			for (i, tx) in txs.iter().enumerate() {
+				let result = dispatcher.clone()
+					.dispatch_transaction(PendingTransaction::new(tx.clone().sign_for_private(chain_id), None));
+				match result {
+					Ok(hash) => hashes.push(hash),
+					Err(e) => {
+						// The notes of the transactions not sent can be merged again.
+						let mut wallet = wallet.write();
+						for tx in txs[i..].iter() {
+							wallet.unlock_spent_notes(tx);
+						}
+						return Err(e.message);
+					}
+				}
 			}
-		}
+			Ok(hashes)
+		}))
+	}
+
+	fn get_operation_status(&self, ids: Option<Vec<String>>) -> Result<Vec<OperationStatus>> {
+		Ok(self.operations
+			.statuses(&ids.unwrap_or_default())
+			.into_iter()
+			.map(Into::into)
+			.collect())
+	}
+
+	fn get_operation_result(&self, ids: Option<Vec<String>>) -> Result<Vec<OperationStatus>> {
+		Ok(self.operations
+			.take_finished(&ids.unwrap_or_default())
+			.into_iter()
+			.map(Into::into)
+			.collect())
+	}
+
+	fn list_operation_ids(&self, state: Option<String>) -> Result<Vec<String>> {
+		let state = match state {
+			Some(state) => Some(OperationState::from_str(&state)
+				.map_err(|e| errors::invalid_params("state", e))?),
+			None => None,
+		};
+		Ok(self.operations.list_ids(state))
+	}
+
+	fn get_wallet_sync_status(&self) -> Result<WalletSyncStatus> {
+		let scanned_block = self.wallet.read().last_scanned_number;
+		Ok(WalletSyncStatus::new(scanned_block, self.dispatcher.best_block_number()))
+	}
+
+	fn rescan(&self, from_block: u64, to_block: Option<u64>, addresses: Option<Vec<String>>) -> Result<RescanStatus> {
+		self.wallet_sync()?
+			.rescan(from_block, to_block, addresses)
+			.map(Into::into)
+			.map_err(|e| errors::account("Could not rescan the wallet.", e))
+	}
+
+	fn rescan_status(&self) -> Result<Option<RescanStatus>> {
+		Ok(self.wallet_sync()?.rescan_status().map(Into::into))
+	}
+
+	fn cancel_rescan(&self) -> Result<bool> {
+		Ok(self.wallet_sync()?.cancel_rescan())
 	}
 }
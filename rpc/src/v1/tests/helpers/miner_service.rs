@@ -68,6 +68,7 @@ impl Default for TestMinerService {
 			min_gas_price: RwLock::new(Some(0.into())),
 			authoring_params: RwLock::new(AuthoringParams {
 				author: Address::zero(),
+				shielded_author: None,
 				gas_range_target: (12345.into(), 54321.into()),
 				extra_data: vec![1, 2, 3, 4],
 			}),
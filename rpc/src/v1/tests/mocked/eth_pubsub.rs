@@ -221,3 +221,50 @@ fn eth_subscribe_syncing() {
 	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["syncing"], "id": 1}"#;
 	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
 }
+
+#[test]
+fn should_subscribe_to_compact_blocks() {
+	// given
+	let el = Runtime::with_thread_count(1);
+	let mut client = TestBlockChainClient::new();
+	client.add_blocks(1, EachBlockWith::Transaction);
+	let h1 = client.block_hash_delta_minus(1);
+	let genesis = client.block_hash_delta_minus(2);
+
+	let pubsub = EthPubSubClient::new_test(Arc::new(client), el.executor());
+	let handler = pubsub.handler().upgrade().unwrap();
+	let pubsub = pubsub.to_delegate();
+
+	let mut io = MetaIoHandler::default();
+	io.extend_with(pubsub);
+
+	let mut metadata = Metadata::default();
+	let (sender, receiver) = futures::sync::mpsc::channel(8);
+	metadata.session = Some(Arc::new(Session::new(sender)));
+
+	// Subscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["compactBlocks"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":"0x416d77337e24399d","id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata.clone()), Some(response.to_owned()));
+
+	// Check notifications, the public transaction of the block is left out
+	handler.new_blocks(NewBlocks::new(vec![], vec![], ChainRoute::new(vec![(h1, ChainRouteType::Enacted)]), vec![], vec![], DURATION_ZERO, false));
+	let (res, receiver) = receiver.into_future().wait().unwrap();
+	let response = format!(
+		r#"{{"jsonrpc":"2.0","method":"eth_subscription","params":{{"result":{{"number":"0x1","hash":"0x{:x}","parentHash":"0x{:x}","timestamp":"0x0","transactions":[],"commitmentTreeSize":"0x0"}},"subscription":"0x416d77337e24399d"}}}}"#,
+		h1, genesis,
+	);
+	assert_eq!(res, Some(response));
+
+	// Parameters are rejected
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_subscribe", "params": ["compactBlocks", {}], "id": 1}"#;
+	assert!(io.handle_request_sync(request, metadata.clone()).unwrap().contains("compactBlocks"));
+
+	// And unsubscribe
+	let request = r#"{"jsonrpc": "2.0", "method": "eth_unsubscribe", "params": ["0x416d77337e24399d"], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":true,"id":1}"#;
+	assert_eq!(io.handle_request_sync(request, metadata), Some(response.to_owned()));
+
+	let (res, _receiver) = receiver.into_future().wait().unwrap();
+	assert_eq!(res, None);
+}
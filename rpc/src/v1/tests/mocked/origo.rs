@@ -20,14 +20,19 @@ use std::fs;
 
 use bytes::ToPretty;
 use accounts::AccountProvider;
-use ethereum_types::{Address, H520, U256};
+use ethereum_types::{Address, H256, H520, U256};
 use ethcore::client::TestBlockChainClient;
-use ethcore::Wallet;
+use ethcore_wallet::{decode_payment_address, OperationManager, Wallet, DEFAULT_OPERATION_WORKERS};
+use ethcore_wallet::sendmany::SaplingNoteData;
 use jsonrpc_core::IoHandler;
 use parking_lot::Mutex;
-use types::transaction::{Action, Transaction, PrivateTransaction};
+use types::transaction::{Action, Transaction, PrivateTransaction, CONVERSION_FACTOR, MAX_PUBLIC_OUTPUTS};
 use parity_runtime::Runtime;
 use hash::keccak;
+use rand::{OsRng, Rand};
+use sapling_crypto::jubjub::fs::Fs;
+use zcash_primitives::JUBJUB;
+use zcash_primitives::note_encryption::Memo;
 
 use v1::{OrigoAccountsClient, OrigoAccountsInfo, Metadata, PersonalClient, Personal};
 use v1::helpers::{nonce, eip191};
@@ -45,7 +50,7 @@ struct OrigoTester {
 	io: IoHandler<Metadata>,
 	miner: Arc<TestMinerService>,
 	/// zcash wallet
-	pt_wallet: Arc<std::sync::RwLock<Wallet>>,
+	pt_wallet: Arc<::parking_lot::RwLock<Wallet>>,
 }
 
 fn blockchain_client() -> Arc<TestBlockChainClient> {
@@ -87,10 +92,11 @@ fn setup_with(c: &Config) -> OrigoTester {
 	let client = blockchain_client();
 	let miner = miner_service();
 	let reservations = Arc::new(Mutex::new(nonce::Reservations::new(runtime.executor())));
-	let pt_wallet = Arc::new(std::sync::RwLock::new(Wallet::new_from_file(c.wallet_file_prefix, c.wallet_file_path)));
+	let pt_wallet = Arc::new(::parking_lot::RwLock::new(Wallet::new_from_file(c.wallet_file_prefix, c.wallet_file_path)));
 
 	let dispatcher = FullDispatcher::new(client, miner.clone(), reservations.clone(), 50);
-	let origo = OrigoAccountsClient::new(&accounts, dispatcher, &pt_wallet);
+	let operations = Arc::new(OperationManager::new(DEFAULT_OPERATION_WORKERS));
+	let origo = OrigoAccountsClient::new(&accounts, dispatcher, &pt_wallet, None, &operations);
 
 	let client = blockchain_client();
 	let dispatcher = FullDispatcher::new(client, miner.clone(), reservations, 50);
@@ -111,8 +117,14 @@ fn setup_with(c: &Config) -> OrigoTester {
 	tester
 }
 
+// Remove the wallet files of the test, named after its prefix.
 fn cleanup_with(c: &Config) {
-	fs::remove_file(c.wallet_file_path);
+	for entry in fs::read_dir(c.wallet_file_path).unwrap() {
+		let path = entry.unwrap().path();
+		if path.file_name().unwrap().to_string_lossy().starts_with(c.wallet_file_prefix) {
+			fs::remove_file(path).unwrap();
+		}
+	}
 }
 
 #[test]
@@ -120,7 +132,7 @@ fn get_new_address() {
 	let config = Config {
 		allow_experimental_rpcs : true,
 		wallet_file_path: "./",
-		wallet_file_prefix: "test_wallet", 
+		wallet_file_prefix: "new_address_wallet",
 	};
 	let tester = setup_with(&config);
 
@@ -142,7 +154,7 @@ fn new_address_from_seed() {
 	let config = Config {
 		allow_experimental_rpcs : true,
 		wallet_file_path: "./",
-		wallet_file_prefix: "test_wallet", 
+		wallet_file_prefix: "address_from_seed_wallet",
 	};
 	let tester = setup_with(&config);
 
@@ -160,7 +172,7 @@ fn get_balance() {
 	let config = Config {
 		allow_experimental_rpcs : true,
 		wallet_file_path: "./",
-		wallet_file_prefix: "test_wallet", 
+		wallet_file_prefix: "balance_wallet",
 	};
 	let tester = setup_with(&config);
 
@@ -178,7 +190,7 @@ fn list_unspent() {
 	let config = Config {
 		allow_experimental_rpcs : true,
 		wallet_file_path: "./",
-		wallet_file_prefix: "test_wallet", 
+		wallet_file_prefix: "unspent_wallet",
 	};
 	let tester = setup_with(&config);
 
@@ -188,6 +200,326 @@ fn list_unspent() {
 	let response: SequenceResponse = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
 	assert_eq!(response.result.len(), 0);
 
+	// minconf can't be above maxconf.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listUnspent", "params": [""#.to_owned() + format!("{}", first_address).as_ref() + r#"", 10, 1], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert!(res.expect("response should not empty").contains(r#""error""#));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn get_new_diversified_address() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "hd_accounts_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewAddress", "params": ["pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let default_address = response.result;
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewDiversifiedAddress", "params": [0], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let diversified_address = response.result;
+	assert!(diversified_address != default_address);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listAccounts", "params": [], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let expected = format!(
+		r#"{{"jsonrpc":"2.0","result":[{{"index":0,"addresses":["{}","{}"]}}],"id":1}}"#,
+		default_address, diversified_address);
+	assert_eq!(res, Some(expected));
+
+	// Unknown accounts are rejected.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewDiversifiedAddress", "params": [1], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains(r#""error""#));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn export_and_import_viewing_key() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "viewing_keys_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewAddress", "params": ["pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let address = response.result;
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_exportViewingKey", "params": [""#.to_owned() + &address + r#""], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let viewing_key = response.result;
+	assert!(viewing_key.starts_with("ogoxviews1"));
+
+	// The key of a wallet address is already known.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_importViewingKey", "params": [""#.to_owned() + &viewing_key + r#"", 10], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert!(res.expect("response should not empty").contains(r#""error""#));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn list_sent_notes_and_get_transaction() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "sent_notes_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewAddress", "params": ["pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let address = response.result;
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listSentNotes", "params": [""#.to_owned() + &address + r#""], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert_eq!(res, Some(r#"{"jsonrpc":"2.0","result":[],"id":1}"#.into()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listSentNotes", "params": ["ogo1unknown"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains(r#""error""#));
+
+	// The wallet has no notes in the transaction.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getTransaction", "params": ["0x0000000000000000000000000000000000000000000000000000000000000001"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains(r#""error""#));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listTransactions", "params": [""#.to_owned() + &address + r#"", 0, 10], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert_eq!(res, Some(r#"{"jsonrpc":"2.0","result":[],"id":1}"#.into()));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listTransactions", "params": ["ogo1unknown"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains(r#""error""#));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn list_received_transaction() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "transactions_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewAddress", "params": ["pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let address = response.result;
+
+	// Two notes of 100 and 200 received by the same transaction.
+	{
+		let mut wallet = tester.pt_wallet.write();
+		let payment_address = decode_payment_address(&address).unwrap();
+		let ivk = wallet.key_store.get_incoming_viewing_key(&payment_address).unwrap();
+		for (index, value) in [100, 200].iter().enumerate() {
+			let note = payment_address.create_note(*value, Fs::rand(&mut OsRng::new().unwrap()), &JUBJUB).unwrap();
+			let nullifier = U256::from(index + 1);
+			let note_data = SaplingNoteData::new(payment_address.clone(), note, Memo::default(), 1, ivk.clone(), nullifier, H256::from(1), index);
+			wallet.nullifier_notes.insert(nullifier, note_data);
+		}
+	}
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listTransactions", "params": [""#.to_owned() + &address + r#"", 0, 10], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	let response: serde_json::Value = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let transactions = response["result"].as_array().unwrap();
+	assert_eq!(transactions.len(), 1);
+	let tx = &transactions[0];
+	assert_eq!(tx["txid"], H256::from(1).to_string());
+	assert_eq!(tx["blocknumber"], "0x1");
+	// The test client has no transaction, its block is considered retracted.
+	assert_eq!(tx["status"], "orphaned");
+	assert_eq!(tx["direction"], "incoming");
+	assert_eq!(tx["received"], to_value(U256::from(300) * CONVERSION_FACTOR).unwrap());
+	assert_eq!(tx["spent"], "0x0");
+	assert_eq!(tx["memos"].as_array().unwrap().len(), 2);
+
+	// Transactions are listed from the given block.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listTransactions", "params": [null, 2, 10], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert_eq!(res, Some(r#"{"jsonrpc":"2.0","result":[],"id":1}"#.into()));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn send_many_validates_recipients() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "send_many_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewAddress", "params": ["pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let address = response.result;
+
+	// Only a transparent recipient can be called.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_sendMany", "params": [""#.to_owned() + &address
+		+ r#"", [{"address": ""# + &address + r#"", "amount": "0x3b9aca00"}], "pass", null, null, null, null, "0x12"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert!(res.expect("response should not empty").contains("Couldn't parse parameters: data"));
+
+	// Transparent and private recipients are mixed, up to the public outputs limit.
+	let transparent = r#"{"address": "0x0000000000000000000000000000000000000005", "amount": "0x3b9aca00"}"#;
+	let amounts = vec![transparent; MAX_PUBLIC_OUTPUTS + 2].join(", ");
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_sendMany", "params": [""#.to_owned() + &address
+		+ r#"", [{"address": ""# + &address + r#"", "amount": "0x3b9aca00"}, "# + &amounts + r#"], "pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert!(res.expect("response should not empty").contains("Couldn't parse parameters: amounts"));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_sendMany", "params": [""#.to_owned() + &address
+		+ r#"", [{"address": ""# + &address + r#"", "amount": "0x3b9aca00"}, "# + transparent + r#"], "pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	assert!(response.result.starts_with("opid-"));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn offline_signing_validates_inputs() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "offline_signing_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewAddress", "params": ["pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let address = response.result;
+
+	// Only the watch-only addresses imported with their proving key are proven unsigned.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_createUnsignedTransaction", "params": [""#.to_owned() + &address
+		+ r#"", [{"address": ""# + &address + r#"", "amount": "0x3b9aca00"}]], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert!(res.expect("response should not empty").contains("No Proving Key."));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_submitSignedTransaction", "params": ["0x12"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains("Invalid RLP."));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn get_wallet_sync_status() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "wallet_sync_status_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getWalletSyncStatus", "params": [], "id": 1}"#;
+	let response = r#"{"jsonrpc":"2.0","result":{"bestBlock":"0x0","lag":"0x0","scannedBlock":"0x0"},"id":1}"#;
+	assert_eq!(tester.io.handle_request_sync(request), Some(response.to_owned()));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn rescan_needs_wallet_sync() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "rescan_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_rescan", "params": [1], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains("Could not rescan the wallet."));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_rescanStatus", "params": [], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains("The wallet isn't synced with the chain"));
+
+	cleanup_with(&config);
+}
+
+#[test]
+fn merge_to_address() {
+	let config = Config {
+		allow_experimental_rpcs : true,
+		wallet_file_path: "./",
+		wallet_file_prefix: "merge_wallet",
+	};
+	let tester = setup_with(&config);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getNewAddress", "params": ["pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let address = response.result;
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_mergeToAddress", "params": [[], ""#.to_owned() + &address + r#"", "pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert!(res.expect("response should not empty").contains("Couldn't parse parameters: from"));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_mergeToAddress", "params": [[""#.to_owned() + &address + r#""], ""# + &address + r#"", "pass", 1], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	assert!(res.expect("response should not empty").contains("Couldn't parse parameters: shielded_limit"));
+
+	// The address has no notes, the queued operation fails.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_mergeToAddress", "params": [[""#.to_owned() + &address + r#""], ""# + &address + r#"", "pass"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request.as_str());
+	let response: Response = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	let opid = response.result;
+	assert!(opid.starts_with("opid-"));
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getOperationStatus", "params": [[""#.to_owned() + &opid + r#""]], "id": 1}"#;
+	let mut finished = false;
+	for _ in 0..500 {
+		let res = tester.io.handle_request_sync(request.as_str()).expect("response should not empty");
+		if res.contains(r#""status":"failed""#) {
+			assert!(res.contains(r#""method":"origo_mergeToAddress""#));
+			assert!(res.contains("no notes to merge"));
+			finished = true;
+			break;
+		}
+		::std::thread::sleep(::std::time::Duration::from_millis(10));
+	}
+	assert!(finished);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listOperationIds", "params": ["failed"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: SequenceResponse = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	assert_eq!(response.result, vec![opid.clone()]);
+
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listOperationIds", "params": ["done"], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains("Couldn't parse parameters: state"));
+
+	// The result is removed once taken.
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_getOperationResult", "params": [], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	assert!(res.expect("response should not empty").contains(&opid));
+	let request = r#"{"jsonrpc": "2.0", "method": "origo_listOperationIds", "params": [], "id": 1}"#;
+	let res = tester.io.handle_request_sync(request);
+	let response: SequenceResponse = serde_json::from_str(res.expect("response should not empty").as_str()).unwrap();
+	assert!(response.result.is_empty());
+
 	cleanup_with(&config);
 }
 
@@ -197,7 +529,7 @@ fn origo_get_new_address() {
 	let config = Config {
 		allow_experimental_rpcs : true,
 		wallet_file_path: "./",
-		wallet_file_prefix: "test_wallet", 
+		wallet_file_prefix: "shield_wallet",
 	};
 	let tester = setup_with(&config);
 
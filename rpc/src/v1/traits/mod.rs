@@ -43,6 +43,7 @@ pub use self::parity::Parity;
 pub use self::parity_accounts::{ParityAccounts, ParityAccountsInfo};
 pub use self::origo::OrigoAccountsInfo;
 pub use self::origo::OrigoRewardsInfo;
+pub use self::origo::OrigoCompactBlocks;
 pub use self::parity_set::{ParitySet, ParitySetAccounts};
 pub use self::parity_signing::ParitySigning;
 pub use self::personal::Personal;
@@ -18,16 +18,18 @@
 use jsonrpc_core::{Result, BoxFuture};
 use jsonrpc_derive::rpc;
 use ethereum_types::{H256, U256};
-use v1::types::UnspentNote;
-use v1::types::AmountRequest;
+use v1::types::{OperationStatus, RescanStatus, ShieldedAccount, ShieldedTransaction, SentNote, UnspentNote, WalletSyncStatus, WalletTransaction};
+use v1::types::{AmountRequest, Bytes};
 use v1::types::{TotalReward, RewardInfo};
+use v1::types::CompactBlock;
 
 /// Origo-specific rpc interface.
 #[rpc]
 pub trait OrigoAccountsInfo {
-	/// Returns a new a private address using a random generated seed.
-	/// The password is used to encrypt the spending key in key store.
-	/// The spending key for this zaddr will be added to the node’s wallet.
+	/// Returns the default address of a new account derived from the wallet seed, which is created
+	/// from a new mnemonic phrase with the first account. The password is used to encrypt the seed
+	/// and the spending key in key store. The spending key for this zaddr will be added to the
+	/// node’s wallet. All the accounts of the wallet share the password given for the first one.
 	#[rpc(name = "origo_getNewAddress")]
 	fn get_new_address(&self, String) -> Result<String>;
 
@@ -38,6 +40,53 @@ pub trait OrigoAccountsInfo {
 
 	fn new_address_from_seed(&self, H256, String) -> Result<String>;
 
+	/// Returns a new diversified address of the given account.
+	/// It is received by and spent with the keys of the account.
+	#[rpc(name = "origo_getNewDiversifiedAddress")]
+	fn get_new_diversified_address(&self, u32) -> Result<String>;
+
+	/// Returns the accounts derived from the wallet seed with their addresses.
+	#[rpc(name = "origo_listAccounts")]
+	fn list_accounts(&self) -> Result<Vec<ShieldedAccount>>;
+
+	/// Returns the 24-word mnemonic phrase of the wallet seed, which is created from a new phrase
+	/// with its first account if the wallet has no seed yet. The password encrypts the seed and the
+	/// keys of the accounts derived from it.
+	#[rpc(name = "origo_newMnemonic")]
+	fn new_mnemonic(&self, String) -> Result<String>;
+
+	/// Restores the wallet seed from a mnemonic phrase and returns the derived addresses. More
+	/// accounts are derived while the last ones received notes, and the accounts after the last
+	/// one which received notes are pruned once the chain is scanned for them.
+	#[rpc(name = "origo_restoreMnemonic")]
+	fn restore_mnemonic(&self, String, String) -> Result<Vec<String>>;
+
+	/// Returns the Bech32 extended full viewing key of a wallet address,
+	/// or its incoming viewing key if the second parameter is true.
+	#[rpc(name = "origo_exportViewingKey")]
+	fn export_viewing_key(&self, String, Option<bool>) -> Result<String>;
+
+	/// Imports a Bech32 extended full viewing key or incoming viewing key and returns an address of it.
+	/// Its notes are watched but can't be spent. The chain is scanned for them
+	/// from the given block, or from the genesis.
+	#[rpc(name = "origo_importViewingKey")]
+	fn import_viewing_key(&self, String, Option<u64>) -> Result<String>;
+
+	/// Returns the shielded notes sent by the wallet accounts, with their recipients,
+	/// amounts and memos. Optionally filter to the account of the given address.
+	#[rpc(name = "origo_listSentNotes")]
+	fn list_sent_notes(&self, Option<String>) -> Result<Vec<SentNote>>;
+
+	/// Returns the shielded notes received and sent by the wallet in a transaction.
+	#[rpc(name = "origo_getTransaction")]
+	fn get_transaction(&self, H256) -> Result<ShieldedTransaction>;
+
+	/// Returns the transactions of the wallet, or of the account of the given address, ordered by
+	/// block. They are listed from the given block, at most the given number of them (default 100).
+	/// Each has the value received and spent by the wallet, the memos received and its confirmations.
+	#[rpc(name = "origo_listTransactions")]
+	fn list_transactions(&self, Option<String>, Option<u64>, Option<u64>) -> Result<Vec<WalletTransaction>>;
+
 
 	/// Returns all stored private addresses.
 	#[rpc(name = "origo_listAddresses")]
@@ -52,17 +101,75 @@ pub trait OrigoAccountsInfo {
 	/// Returns array of unspent shielded notes with between
 	/// minconf and maxconf (inclusive) confirmations.
 	/// Optionally filter to only include notes sent to specified addresses.
+	/// The notes spent by transactions not mined yet are listed as locked.
 	#[rpc(name = "origo_listUnspent")]
 	fn list_unspent(&self, String, Option<u32>, Option<u32>, Option<bool>) -> Result<Vec<UnspentNote>>;
 
 	/// Send multiple times. Amounts are decimal numbers with at most 8 digits of precision.
+	/// Transparent and private recipients can be mixed. The first transparent recipient is
+	/// called with the optional data, the next ones only receive the amounts.
 	/// Change generated from a taddr flows to a new taddr address,
 	/// while change generated from a zaddr returns to itself.
 	/// When sending coinbase UTXOs to a zaddr, change is not allowed.
 	/// The entire value of the UTXO(s) must be consumed.
 	/// Before Sapling activates, the maximum number of zaddr outputs is 54 due to transaction size limits.
+	/// The spent notes are chosen by the coin selection strategy: "largest-first" (default),
+	/// "fewest-notes" or "random".
+	/// Returns the id of the operation building and sending the transaction.
 	#[rpc(name = "origo_sendMany")]
-	fn send_many(&self, String, Vec<AmountRequest>, String, Option<U256>, Option<U256>, Option<u32>) -> Result<H256>;
+	fn send_many(&self, String, Vec<AmountRequest>, String, Option<U256>, Option<U256>, Option<u32>, Option<String>, Option<Bytes>) -> Result<String>;
+
+	/// Builds the transaction of origo_sendMany, without password, spending the notes of a
+	/// watch-only address imported with its extended proving key. Returns the RLP of the transaction
+	/// with its proofs, to be signed offline with `parity wallet sign-offline`.
+	#[rpc(name = "origo_createUnsignedTransaction")]
+	fn create_unsigned_transaction(&self, String, Vec<AmountRequest>, Option<U256>, Option<U256>, Option<u32>, Option<String>, Option<Bytes>) -> Result<Bytes>;
+
+	/// Submits the RLP of a private transaction signed offline and returns its hash.
+	#[rpc(name = "origo_submitSignedTransaction")]
+	fn submit_signed_transaction(&self, Bytes) -> Result<H256>;
+
+	/// Merges the notes of the given private addresses into notes sent to a private address, over
+	/// as many transactions as needed to spend at most the given number of notes each. Every
+	/// transaction creates one note, so many notes are merged into several: call it again once
+	/// the transactions are mined to merge those. Returns the id of the operation building and
+	/// sending the transactions.
+	#[rpc(name = "origo_mergeToAddress")]
+	fn merge_to_address(&self, Vec<String>, String, String, Option<u32>, Option<U256>, Option<U256>, Option<String>) -> Result<String>;
+
+	/// Returns the status of the given operations, or of all the operations if none is given.
+	#[rpc(name = "origo_getOperationStatus")]
+	fn get_operation_status(&self, Option<Vec<String>>) -> Result<Vec<OperationStatus>>;
+
+	/// Returns the status and result of the given finished operations, or of all the finished
+	/// operations if none is given, and removes them from memory.
+	#[rpc(name = "origo_getOperationResult")]
+	fn get_operation_result(&self, Option<Vec<String>>) -> Result<Vec<OperationStatus>>;
+
+	/// Returns the ids of the operations, optionally only of those in the given state:
+	/// "queued", "executing", "success" or "failed".
+	#[rpc(name = "origo_listOperationIds")]
+	fn list_operation_ids(&self, Option<String>) -> Result<Vec<String>>;
+
+	/// Returns the last block scanned by the wallet and how many blocks it is behind the chain.
+	#[rpc(name = "origo_getWalletSyncStatus")]
+	fn get_wallet_sync_status(&self) -> Result<WalletSyncStatus>;
+
+	/// Rescans the blocks from the given block to the given block, the last scanned block by default,
+	/// for the given addresses or all the wallet addresses. Their notes, witnesses and spends in these
+	/// blocks are rebuilt from the commitment tree of the block before the first, and replace those of
+	/// the wallet when the rescan finishes. Only one rescan runs at once, in the background, and the
+	/// new blocks aren't scanned until it is over. Returns its status.
+	#[rpc(name = "origo_rescan")]
+	fn rescan(&self, u64, Option<u64>, Option<Vec<String>>) -> Result<RescanStatus>;
+
+	/// Returns the progress of the last rescan, with the estimated seconds left, or null if none was run.
+	#[rpc(name = "origo_rescanStatus")]
+	fn rescan_status(&self) -> Result<Option<RescanStatus>>;
+
+	/// Cancels the rescan running, the notes it found are dropped. Returns whether a rescan was running.
+	#[rpc(name = "origo_cancelRescan")]
+	fn cancel_rescan(&self) -> Result<bool>;
 }
 
 /// Origo rewards rpc interface.
@@ -76,3 +183,14 @@ pub trait OrigoRewardsInfo {
 	#[rpc(name = "origo_getEraRewards")]
 	fn get_era_rewards(&self, Option<usize>) -> BoxFuture<Vec<RewardInfo>>;
 }
+
+/// Origo compact blocks rpc interface, for thin wallets to find their notes.
+#[rpc]
+pub trait OrigoCompactBlocks {
+	/// Returns the compact blocks from the first given block to the second one, at most 1000 of them,
+	/// stopping at the best block. Each private transaction only carries its nullifiers and, for each
+	/// output, the note commitment, the ephemeral key and the first 52 bytes of the note ciphertext.
+	/// Each block carries the size of the commitment tree as of its end.
+	#[rpc(name = "origo_getCompactBlocks")]
+	fn get_compact_blocks(&self, u64, u64) -> Result<Vec<CompactBlock>>;
+}
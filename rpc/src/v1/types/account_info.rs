@@ -101,6 +101,8 @@ pub struct UnspentNote {
 	/// True if note can be spent by wallet, 
 	/// false if note has zero confirmations, false if address is watchonly
 	pub spendable: bool,
+	/// True if note is spent by a transaction not mined yet
+	pub locked: bool,
 	/// shielded address
 	pub address: String,
 	/// amount of value in the note
@@ -111,4 +113,88 @@ pub struct UnspentNote {
 	pub change: bool
 }
 
+/// shielded note sent by a wallet account, recovered with its outgoing viewing key
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct SentNote {
+	/// transaction id
+	pub txid: String,
+	/// output index
+	pub outindex: U64,
+	/// number of the block including the transaction
+	pub blocknumber: U64,
+	/// default address of the sending account
+	pub from: String,
+	/// shielded address of the recipient
+	pub address: String,
+	/// amount of value in the note
+	pub amount: U256,
+	/// memo field of the note
+	pub memo: String,
+	/// true if the note was sent back to the sending account
+	pub change: bool
+}
+
+/// shielded note received by the wallet in a transaction
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ReceivedNote {
+	/// output index
+	pub outindex: U64,
+	/// shielded address
+	pub address: String,
+	/// amount of value in the note
+	pub amount: U256,
+	/// memo field of the note
+	pub memo: String,
+	/// true if the note has been spent
+	pub spent: bool
+}
+
+/// shielded notes of a transaction seen by the wallet
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ShieldedTransaction {
+	/// transaction id
+	pub txid: String,
+	/// notes received by the wallet addresses
+	pub received: Vec<ReceivedNote>,
+	/// notes sent by the wallet accounts
+	pub sent: Vec<SentNote>
+}
+
+/// transaction of the wallet history
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct WalletTransaction {
+	/// transaction id
+	pub txid: String,
+	/// number of the block including the transaction
+	pub blocknumber: U64,
+	/// timestamp of the block including the transaction
+	pub timestamp: U64,
+	/// number of confirmations
+	pub confirmations: U64,
+	/// "confirmed", or "orphaned" if its block was retracted and the wallet is not synced yet
+	pub status: String,
+	/// "incoming" or "outgoing" by the net change of the shielded balance of the wallet,
+	/// or "internal" if only the wallet addresses and the fee are paid
+	pub direction: String,
+	/// value of the notes received by the wallet
+	pub received: U256,
+	/// value of the notes of the wallet spent
+	pub spent: U256,
+	/// memo fields of the notes received
+	pub memos: Vec<String>,
+	/// transparent address sending to or paid by the transaction
+	pub publicaddress: Option<H160>,
+	/// true if the transaction pays a block reward
+	pub coinbase: bool,
+}
+
+/// shielded account derived from the wallet seed
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct ShieldedAccount {
+	/// account index in the derivation path
+	pub index: u32,
+	/// default address first, then the diversified addresses
+	pub addresses: Vec<String>,
+}
+
 
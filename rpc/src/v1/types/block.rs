@@ -63,6 +63,9 @@ pub struct Block {
 	pub transactions_root: H256,
 	/// Transactions receipts root hash
 	pub receipts_root: H256,
+	/// Sapling note commitment tree root after this block
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub final_sapling_root: Option<H256>,
 	/// Block number
 	pub number: Option<U256>,
 	/// Gas Used
@@ -110,6 +113,9 @@ pub struct Header {
 	pub transactions_root: H256,
 	/// Transactions receipts root hash
 	pub receipts_root: H256,
+	/// Sapling note commitment tree root after this block
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub final_sapling_root: Option<H256>,
 	/// Block number
 	pub number: Option<U256>,
 	/// Gas Used
@@ -148,6 +154,7 @@ impl<'a> From<&'a EthHeader> for Header {
 			state_root: h.state_root(),
 			transactions_root: h.transactions_root(),
 			receipts_root: h.receipts_root(),
+			final_sapling_root: h.final_sapling_root(),
 			number: Some(h.number().into()),
 			gas_used: h.gas_used(),
 			gas_limit: h.gas_limit(),
@@ -232,6 +239,7 @@ mod tests {
 			state_root: H256::default(),
 			transactions_root: H256::default(),
 			receipts_root: H256::default(),
+			final_sapling_root: None,
 			number: Some(U256::default()),
 			gas_used: U256::default(),
 			gas_limit: U256::default(),
@@ -270,6 +278,7 @@ mod tests {
 			state_root: H256::default(),
 			transactions_root: H256::default(),
 			receipts_root: H256::default(),
+			final_sapling_root: None,
 			number: Some(U256::default()),
 			gas_used: U256::default(),
 			gas_limit: U256::default(),
@@ -308,6 +317,7 @@ mod tests {
 			state_root: H256::default(),
 			transactions_root: H256::default(),
 			receipts_root: H256::default(),
+			final_sapling_root: None,
 			number: Some(U256::default()),
 			gas_used: U256::default(),
 			gas_limit: U256::default(),
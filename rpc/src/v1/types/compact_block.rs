@@ -0,0 +1,129 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+use ethereum_types::{H256, U64};
+use types::compact_block::{
+	CompactBlock as EthcoreCompactBlock, CompactOutput as EthcoreCompactOutput,
+	CompactTransaction as EthcoreCompactTransaction,
+};
+use v1::types::Bytes;
+
+/// Shielded output of a compact block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactOutput {
+	/// Note commitment.
+	pub cmu: H256,
+	/// Ephemeral public key.
+	pub ephemeral_key: H256,
+	/// First 52 bytes of the note ciphertext.
+	pub ciphertext: Bytes,
+}
+
+impl From<EthcoreCompactOutput> for CompactOutput {
+	fn from(output: EthcoreCompactOutput) -> Self {
+		CompactOutput {
+			cmu: output.cmu,
+			ephemeral_key: output.ephemeral_key,
+			ciphertext: output.ciphertext.into(),
+		}
+	}
+}
+
+/// Private transaction of a compact block.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactTransaction {
+	/// Index of the transaction in its block.
+	pub index: U64,
+	/// Transaction hash.
+	pub hash: H256,
+	/// Nullifiers of the notes spent.
+	pub nullifiers: Vec<H256>,
+	/// Shielded outputs, in the order of the commitment tree.
+	pub outputs: Vec<CompactOutput>,
+}
+
+impl From<EthcoreCompactTransaction> for CompactTransaction {
+	fn from(tx: EthcoreCompactTransaction) -> Self {
+		CompactTransaction {
+			index: (tx.index as u64).into(),
+			hash: tx.hash,
+			nullifiers: tx.nullifiers,
+			outputs: tx.outputs.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// Block reduced to what a thin wallet needs to find its notes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompactBlock {
+	/// Block number.
+	pub number: U64,
+	/// Block hash.
+	pub hash: H256,
+	/// Hash of the parent block.
+	pub parent_hash: H256,
+	/// Block timestamp.
+	pub timestamp: U64,
+	/// Private transactions of the block.
+	pub transactions: Vec<CompactTransaction>,
+	/// Number of note commitments in the tree as of the end of the block.
+	pub commitment_tree_size: U64,
+}
+
+impl From<EthcoreCompactBlock> for CompactBlock {
+	fn from(block: EthcoreCompactBlock) -> Self {
+		CompactBlock {
+			number: block.number.into(),
+			hash: block.hash,
+			parent_hash: block.parent_hash,
+			timestamp: block.timestamp.into(),
+			transactions: block.transactions.into_iter().map(Into::into).collect(),
+			commitment_tree_size: block.commitment_tree_size.into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use serde_json;
+	use super::*;
+
+	#[test]
+	fn should_serialize_compact_block() {
+		let block = CompactBlock::from(EthcoreCompactBlock {
+			number: 2,
+			hash: H256::from(1),
+			parent_hash: H256::from(0),
+			timestamp: 16,
+			transactions: vec![EthcoreCompactTransaction {
+				index: 0,
+				hash: H256::from(2),
+				nullifiers: vec![H256::from(3)],
+				outputs: vec![EthcoreCompactOutput {
+					cmu: H256::from(4),
+					ephemeral_key: H256::from(5),
+					ciphertext: vec![6, 7],
+				}],
+			}],
+			commitment_tree_size: 10,
+		});
+		let serialized = serde_json::to_string(&block).unwrap();
+		assert_eq!(serialized, r#"{"number":"0x2","hash":"0x0000000000000000000000000000000000000000000000000000000000000001","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","timestamp":"0x10","transactions":[{"index":"0x0","hash":"0x0000000000000000000000000000000000000000000000000000000000000002","nullifiers":["0x0000000000000000000000000000000000000000000000000000000000000003"],"outputs":[{"cmu":"0x0000000000000000000000000000000000000000000000000000000000000004","ephemeralKey":"0x0000000000000000000000000000000000000000000000000000000000000005","ciphertext":"0x0607"}]}],"commitmentTreeSize":"0xa"}"#);
+	}
+}
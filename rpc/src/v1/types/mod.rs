@@ -24,6 +24,7 @@ mod block;
 mod block_number;
 mod bytes;
 mod call_request;
+mod compact_block;
 mod amount_request;
 mod confirmations;
 mod consensus_status;
@@ -45,16 +46,19 @@ mod transaction_request;
 mod transaction_condition;
 mod work;
 mod eip191;
+mod operation;
 mod reward;
+mod wallet_sync;
 
 pub mod pubsub;
 
 pub use self::eip191::{EIP191Version, PresignedTransaction};
-pub use self::account_info::{AccountInfo, ExtAccountInfo, HwAccountInfo, EthAccount, StorageProof, RecoveredAccount, UnspentNote};
+pub use self::account_info::{AccountInfo, ExtAccountInfo, HwAccountInfo, EthAccount, StorageProof, RecoveredAccount, ShieldedAccount, UnspentNote, SentNote, ReceivedNote, ShieldedTransaction, WalletTransaction};
 pub use self::bytes::Bytes;
 pub use self::block::{RichBlock, Block, BlockTransactions, Header, RichHeader, Rich};
 pub use self::block_number::{BlockNumber, LightBlockNumber, block_number_to_id};
 pub use self::call_request::CallRequest;
+pub use self::compact_block::{CompactBlock, CompactTransaction, CompactOutput};
 pub use self::amount_request::AmountRequest;
 pub use self::confirmations::{
 	ConfirmationPayload, ConfirmationRequest, ConfirmationResponse, ConfirmationResponseWithToken,
@@ -77,11 +81,13 @@ pub use self::sync::{
 };
 pub use self::trace::{LocalizedTrace, TraceResults, TraceResultsWithTransactionHash};
 pub use self::trace_filter::TraceFilter;
-pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus};
+pub use self::transaction::{Transaction, RichRawTransaction, LocalTransactionStatus, PublicOutput};
 pub use self::transaction_request::TransactionRequest;
 pub use self::transaction_condition::TransactionCondition;
 pub use self::work::Work;
+pub use self::operation::OperationStatus;
 pub use self::reward::{TotalReward, RewardInfo};
+pub use self::wallet_sync::{RescanStatus, WalletSyncStatus};
 
 // TODO [ToDr] Refactor to a proper type Vec of enums?
 /// Expected tracing type.
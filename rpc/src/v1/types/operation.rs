@@ -0,0 +1,56 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+use ethcore_wallet::OperationStatus as EthcoreOperationStatus;
+use ethereum_types::H256;
+
+/// Status of a queued wallet operation.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationStatus {
+	/// Operation id.
+	pub id: String,
+	/// Method which queued the operation.
+	pub method: String,
+	/// One of queued, executing, success or failed.
+	pub status: String,
+	/// Unix time in seconds the operation was queued at.
+	pub creation_time: u64,
+	/// Seconds spent executing the operation, once finished.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub execution_secs: Option<f64>,
+	/// Hashes of the transactions sent by a successful operation.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub result: Option<Vec<H256>>,
+	/// Error of a failed operation.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+impl From<EthcoreOperationStatus> for OperationStatus {
+	fn from(status: EthcoreOperationStatus) -> Self {
+		OperationStatus {
+			id: status.id,
+			method: status.method,
+			status: status.state.to_string(),
+			creation_time: status.creation_time,
+			execution_secs: status.execution_time
+				.map(|time| time.as_secs() as f64 + time.subsec_millis() as f64 / 1000.0),
+			result: status.result,
+			error: status.error,
+		}
+	}
+}
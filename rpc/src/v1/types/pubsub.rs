@@ -20,7 +20,7 @@ use ethereum_types::H256;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde::de::Error;
 use serde_json::{Value, from_value};
-use v1::types::{RichHeader, Filter, Log};
+use v1::types::{RichHeader, Filter, Log, CompactBlock};
 
 /// Subscription result.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -32,7 +32,9 @@ pub enum Result {
 	/// Transaction hash
 	TransactionHash(H256),
 	/// SyncStatus
-	SyncState(PubSubSyncStatus)
+	SyncState(PubSubSyncStatus),
+	/// Compact block
+	CompactBlock(Box<CompactBlock>),
 }
 
 /// PubSbub sync status
@@ -52,6 +54,7 @@ impl Serialize for Result {
 			Result::Log(ref log) => log.serialize(serializer),
 			Result::TransactionHash(ref hash) => hash.serialize(serializer),
 			Result::SyncState(ref sync) => sync.serialize(serializer),
+			Result::CompactBlock(ref block) => block.serialize(serializer),
 		}
 	}
 }
@@ -69,6 +72,8 @@ pub enum Kind {
 	NewPendingTransactions,
 	/// Node syncing status subscription.
 	Syncing,
+	/// Compact blocks of the new canonical blocks, for thin wallets. Only sent by full nodes.
+	CompactBlocks,
 }
 
 /// Subscription kind.
@@ -113,6 +118,7 @@ mod tests {
 		assert_eq!(serde_json::from_str::<Kind>(r#""logs""#).unwrap(), Kind::Logs);
 		assert_eq!(serde_json::from_str::<Kind>(r#""newPendingTransactions""#).unwrap(), Kind::NewPendingTransactions);
 		assert_eq!(serde_json::from_str::<Kind>(r#""syncing""#).unwrap(), Kind::Syncing);
+		assert_eq!(serde_json::from_str::<Kind>(r#""compactBlocks""#).unwrap(), Kind::CompactBlocks);
 	}
 
 	#[test]
@@ -166,6 +172,7 @@ mod tests {
 				state_root: Default::default(),
 				transactions_root: Default::default(),
 				receipts_root: Default::default(),
+				final_sapling_root: None,
 				number: Some(Default::default()),
 				gas_used: Default::default(),
 				gas_limit: Default::default(),
@@ -21,7 +21,7 @@ use serde::ser::SerializeStruct;
 use ethcore::{contract_address, CreateContractAddress};
 use ethereum_types::{H160, H256, H512, U64, U256};
 use miner;
-use types::transaction::{LocalizedTransaction, Action, PendingTransaction, SignedTransaction};
+use types::transaction::{self, LocalizedTransaction, Action, PendingTransaction, SignedTransaction};
 use v1::types::{Bytes, TransactionCondition};
 
 /// Transaction
@@ -72,6 +72,27 @@ pub struct Transaction {
 	pub is_private: bool,
 	/// Balancing value.
 	pub balancing_value: Option<i64>,
+	/// Public value paid by a private transaction besides `value`.
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub public_outputs: Vec<PublicOutput>,
+}
+
+/// Public output of a private transaction.
+#[derive(Debug, Default, Clone, PartialEq, Serialize)]
+pub struct PublicOutput {
+	/// Recipient
+	pub to: H160,
+	/// Transfered value
+	pub value: U256,
+}
+
+impl<'a> From<&'a transaction::PublicOutput> for PublicOutput {
+	fn from(output: &'a transaction::PublicOutput) -> Self {
+		PublicOutput {
+			to: output.address,
+			value: output.value,
+		}
+	}
 }
 
 /// Local Transaction Status
@@ -214,6 +235,7 @@ impl Transaction {
 				None => None,
 				Some(_) => Some(t.private.clone().unwrap().balancing_value),
 			},
+			public_outputs: t.public_outputs().iter().map(Into::into).collect(),
 		}
 	}
 
@@ -255,6 +277,7 @@ impl Transaction {
 				None => None,
 				Some(_) => Some(t.private.clone().unwrap().balancing_value),
 			},
+			public_outputs: t.public_outputs().iter().map(Into::into).collect(),
 		}
 	}
 
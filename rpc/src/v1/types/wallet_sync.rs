@@ -0,0 +1,92 @@
+// Copyright 2018-2020 Origo Foundation.
+// This file is part of Origo Network.
+
+// Origo Network is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Origo Network is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Origo Network.  If not, see <http://www.gnu.org/licenses/>.
+
+use std::time::Duration;
+
+use ethcore_wallet::RescanStatus as EthcoreRescanStatus;
+use ethereum_types::U64;
+
+/// How far the shielded wallet has scanned the chain.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalletSyncStatus {
+	/// Number of the last block scanned by the wallet.
+	pub scanned_block: U64,
+	/// Number of the best block of the chain.
+	pub best_block: U64,
+	/// Number of blocks the wallet is behind the chain.
+	pub lag: U64,
+}
+
+impl WalletSyncStatus {
+	/// The status of a wallet which scanned the chain up to `scanned_block`.
+	pub fn new(scanned_block: u64, best_block: u64) -> Self {
+		WalletSyncStatus {
+			scanned_block: scanned_block.into(),
+			best_block: best_block.into(),
+			lag: best_block.saturating_sub(scanned_block).into(),
+		}
+	}
+}
+
+fn duration_secs(duration: Duration) -> f64 {
+	duration.as_secs() as f64 + duration.subsec_millis() as f64 / 1000.0
+}
+
+/// Progress of a rescan of the chain by the shielded wallet.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RescanStatus {
+	/// Number of the first block rescanned.
+	pub from_block: U64,
+	/// Number of the last block rescanned for notes.
+	pub to_block: U64,
+	/// Number of the last block the witnesses of the notes found are updated to.
+	pub end_block: U64,
+	/// Number of the last block processed so far.
+	pub scanned_block: U64,
+	/// Addresses rescanned for.
+	pub addresses: Vec<String>,
+	/// One of queued, running, finished, cancelled or failed.
+	pub status: String,
+	/// Percentage of the blocks processed.
+	pub progress: f64,
+	/// Seconds spent rescanning so far.
+	pub elapsed_secs: f64,
+	/// Estimated seconds left, while running.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub eta_secs: Option<f64>,
+	/// Error of a failed rescan.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+}
+
+impl From<EthcoreRescanStatus> for RescanStatus {
+	fn from(status: EthcoreRescanStatus) -> Self {
+		RescanStatus {
+			from_block: status.from_block.into(),
+			to_block: status.to_block.into(),
+			end_block: status.end_block.into(),
+			scanned_block: status.scanned_block.into(),
+			progress: (status.progress() * 10000.0).round() / 100.0,
+			elapsed_secs: duration_secs(status.elapsed),
+			eta_secs: status.eta().map(duration_secs),
+			addresses: status.addresses,
+			status: status.state.to_string(),
+			error: status.error,
+		}
+	}
+}
@@ -69,6 +69,8 @@ pub struct Directories {
 	pub secretstore: String,
 	/// wallet dir
 	pub wallet: String,
+	/// Dir of the Sapling proving parameters
+	pub sapling_params: String,
 }
 
 impl Default for Directories {
@@ -83,6 +85,7 @@ impl Default for Directories {
 			signer: replace_home(&data_dir, "$BASE/signer"),
 			secretstore: replace_home(&data_dir, "$BASE/secretstore"),
 			wallet: replace_home(&data_dir, "$BASE/wallet"),
+			sapling_params: replace_home(&data_dir, "$BASE/sapling-params"),
 		}
 	}
 }
@@ -357,6 +360,7 @@ mod tests {
 			),
 			keys: replace_home(&data_dir, "$BASE/keys"),
 			wallet: replace_home(&data_dir, "$BASE/wallet"),
+			sapling_params: replace_home(&data_dir, "$BASE/sapling-params"),
 			signer: replace_home(&data_dir, "$BASE/signer"),
 			secretstore: replace_home(&data_dir, "$BASE/secretstore"),
 		};